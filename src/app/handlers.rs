@@ -1,6 +1,6 @@
 use eframe::egui;
 
-use crate::{app_settings::AppSettings, layout::Theme};
+use crate::layout::Theme;
 
 impl crate::MyApp {
     pub fn handle_zoom_shortcuts(&mut self, ctx: &egui::Context) {
@@ -107,6 +107,10 @@ impl crate::MyApp {
                     self.open_snapshot_aggregates_tab();
                     self.db_status = "Opened Snapshot Aggregates tab".to_string();
                 }
+                AggregatesAction::Authors => {
+                    self.open_authors_tab();
+                    self.db_status = "Opened Authors tab".to_string();
+                }
             }
         }
 
@@ -116,6 +120,66 @@ impl crate::MyApp {
                     self.open_scan_snapshot_tab();
                     self.db_status = "Opened Scan Snapshot tab".to_string();
                 }
+                UseCasesAction::AuditDependencies => {
+                    self.open_audit_tab();
+                    self.db_status = "Opened Dependency Audit tab".to_string();
+                }
+                UseCasesAction::SearchContents => {
+                    self.open_search_tab();
+                    self.db_status = "Opened Search tab".to_string();
+                }
+                UseCasesAction::RegexSearch => {
+                    self.open_regex_search_tab();
+                    self.db_status = "Opened Regex Search tab".to_string();
+                }
+                UseCasesAction::CompareSnapshots => {
+                    self.open_compare_tab();
+                    self.db_status = "Opened Compare Snapshots tab".to_string();
+                }
+                UseCasesAction::Hotspots => {
+                    self.open_hotspots_tab();
+                    self.db_status = "Opened Hotspots tab".to_string();
+                }
+                UseCasesAction::Ownership => {
+                    self.open_ownership_tab();
+                    self.db_status = "Opened Ownership tab".to_string();
+                }
+                UseCasesAction::Complexity => {
+                    self.open_complexity_tab();
+                    self.db_status = "Opened Complexity tab".to_string();
+                }
+                UseCasesAction::Markers => {
+                    self.open_markers_tab();
+                    self.db_status = "Opened Markers tab".to_string();
+                }
+                UseCasesAction::Safety => {
+                    self.open_safety_tab();
+                    self.db_status = "Opened Safety tab".to_string();
+                }
+                UseCasesAction::Coverage => {
+                    self.open_coverage_tab();
+                    self.db_status = "Opened Coverage tab".to_string();
+                }
+                UseCasesAction::ApiSurface => {
+                    self.open_api_surface_tab();
+                    self.db_status = "Opened API Surface tab".to_string();
+                }
+                UseCasesAction::DocCoverage => {
+                    self.open_doc_coverage_tab();
+                    self.db_status = "Opened Doc Coverage tab".to_string();
+                }
+                UseCasesAction::Languages => {
+                    self.open_languages_tab();
+                    self.db_status = "Opened Languages tab".to_string();
+                }
+                UseCasesAction::Dependencies => {
+                    self.open_dependencies_tab();
+                    self.db_status = "Opened Dependencies tab".to_string();
+                }
+                UseCasesAction::ProjectDashboard => {
+                    self.open_dashboard_tab();
+                    self.db_status = "Opened Project Dashboard tab".to_string();
+                }
             }
         }
 
@@ -159,12 +223,7 @@ impl crate::MyApp {
         ctx.set_zoom_factor(zoom);
 
         // Save all current settings
-        let navbar_width_frac = self.settings_form.get_navbar_width_frac();
-        let app_settings = AppSettings {
-            theme,
-            zoom,
-            navbar_width_frac,
-        };
+        let app_settings = self.settings_form.to_app_settings();
         if app_settings.save_to_db(&self.db_connection).is_ok() {
             self.db_status = format!(
                 "Settings saved: Theme={:?}, Zoom={:.0}%",
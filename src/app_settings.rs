@@ -6,6 +6,12 @@ pub struct AppSettings {
     pub theme: Theme,
     pub zoom: f32,
     pub navbar_width_frac: f32,
+    /// Local checkout of a RustSec advisory database (empty = not configured)
+    pub advisory_db_path: String,
+    /// Comma-separated SPDX identifiers accepted by the dependency audit
+    pub audit_allowed_licenses: String,
+    /// Comma-separated advisory IDs that must fail the dependency audit
+    pub audit_denied_advisories: String,
 }
 
 impl Default for AppSettings {
@@ -14,6 +20,9 @@ impl Default for AppSettings {
             theme: Theme::Dark,
             zoom: 1.0,
             navbar_width_frac: 0.20,
+            advisory_db_path: String::new(),
+            audit_allowed_licenses: String::new(),
+            audit_denied_advisories: String::new(),
         }
     }
 }
@@ -58,8 +67,18 @@ impl AppSettings {
             }
         }
 
+        // Load dependency audit settings
+        if let Ok(path) = Self::get_value(conn, "advisory_db_path") {
+            settings.advisory_db_path = path;
+        }
+        if let Ok(licenses) = Self::get_value(conn, "audit_allowed_licenses") {
+            settings.audit_allowed_licenses = licenses;
+        }
+        if let Ok(advisories) = Self::get_value(conn, "audit_denied_advisories") {
+            settings.audit_denied_advisories = advisories;
+        }
 
-        println!(
+        eprintln!(
             "Settings loaded from database: Theme={:?}, Zoom={:.0}%",
             settings.theme,
             settings.zoom * 100.0
@@ -80,9 +99,22 @@ impl AppSettings {
         Self::set_value(conn, "zoom", &self.zoom.to_string())?;
 
         // Save navbar width (fraction)
-        Self::set_value(conn, "navbar_width_frac", &self.navbar_width_frac.to_string())?;
+        Self::set_value(
+            conn,
+            "navbar_width_frac",
+            &self.navbar_width_frac.to_string(),
+        )?;
+
+        // Save dependency audit settings
+        Self::set_value(conn, "advisory_db_path", &self.advisory_db_path)?;
+        Self::set_value(conn, "audit_allowed_licenses", &self.audit_allowed_licenses)?;
+        Self::set_value(
+            conn,
+            "audit_denied_advisories",
+            &self.audit_denied_advisories,
+        )?;
 
-        println!(
+        eprintln!(
             "Settings saved to database: Theme={:?}, Zoom={:.0}%, NavbarWidth={:.0}%",
             self.theme,
             self.zoom * 100.0,
@@ -91,6 +123,16 @@ impl AppSettings {
         Ok(())
     }
 
+    /// Allowed license identifiers parsed from `audit_allowed_licenses`
+    pub fn allowed_licenses(&self) -> Vec<String> {
+        split_list(&self.audit_allowed_licenses)
+    }
+
+    /// Denied advisory IDs parsed from `audit_denied_advisories`
+    pub fn denied_advisories(&self) -> Vec<String> {
+        split_list(&self.audit_denied_advisories)
+    }
+
     /// Get a single setting value
     fn get_value(conn: &Connection, key: &str) -> SqlResult<String> {
         conn.query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| {
@@ -128,3 +170,13 @@ impl AppSettings {
         Ok(settings)
     }
 }
+
+/// Split a comma/whitespace separated setting into trimmed, non-empty entries
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
@@ -0,0 +1,47 @@
+use rusqlite::Connection;
+
+use super::resolve_snapshot_id;
+use crate::app_settings::AppSettings;
+use crate::usecases::s502_audit_dependencies::run_audit;
+
+/// `navigator audit`: exit code 1 when vulnerabilities or policy violations are found
+pub fn run(conn: &Connection, args: &[String]) -> Result<i32, String> {
+    let snapshot_id = resolve_snapshot_id(conn, args)?;
+    let settings = AppSettings::load_from_db(conn).unwrap_or_default();
+    let report = run_audit(conn, snapshot_id, &settings)?;
+
+    println!(
+        "Audited {} packages against {} advisories",
+        report.packages.len(),
+        report.advisories_loaded
+    );
+
+    for pkg in report.packages.iter().filter(|p| !p.advisories.is_empty()) {
+        for hit in &pkg.advisories {
+            let kind = hit.informational.as_deref().unwrap_or("vulnerability");
+            println!(
+                "  {} {} {} [{}] {} (patched: {})",
+                hit.id, pkg.name, pkg.version, kind, hit.title, hit.patched
+            );
+        }
+    }
+
+    for w in &report.warnings {
+        println!("warning: {}", w);
+    }
+    for v in &report.violations {
+        println!("policy violation: {}", v);
+    }
+
+    if report.passed() {
+        println!("Audit passed");
+        Ok(0)
+    } else {
+        println!(
+            "Audit failed: {} vulnerable packages, {} policy violations",
+            report.vulnerable_count,
+            report.violations.len()
+        );
+        Ok(1)
+    }
+}
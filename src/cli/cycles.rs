@@ -0,0 +1,58 @@
+use rusqlite::Connection;
+
+use super::{arg_value, resolve_snapshot_id};
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::usecases::s518_dependencies::cycles::{all_cycles, find_cycles};
+use crate::usecases::s518_dependencies::{GraphLevel, GraphOptions, ModuleGraph};
+
+/// `navigator cycles`: exit code 1 when the dependency graph has a cycle
+pub fn run(conn: &Connection, args: &[String]) -> Result<i32, String> {
+    let snapshot_id = resolve_snapshot_id(conn, args)?;
+    let snapshot = snapshot_repo::get_by_id(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load snapshot: {}", e))?;
+    let exclude_tests = !args.iter().any(|a| a == "--include-tests");
+    let graph = ModuleGraph::load(conn, snapshot_id)?;
+    let cycles = match arg_value(args, "--level") {
+        Some(key) => {
+            let level = GraphLevel::parse(key).ok_or_else(|| {
+                format!(
+                    "Unknown level '{}', expected module, aggregate or crate",
+                    key
+                )
+            })?;
+            find_cycles(
+                &graph,
+                &GraphOptions {
+                    level,
+                    exclude_tests,
+                    ..Default::default()
+                },
+            )
+        }
+        None => all_cycles(&graph, exclude_tests),
+    };
+
+    println!(
+        "Snapshot '{}': {} dependency cycles",
+        snapshot.name,
+        cycles.len()
+    );
+    for cycle in &cycles {
+        println!("{}", cycle.describe());
+        for (i, edge) in cycle.edges.iter().enumerate() {
+            let marker = if i == cycle.cut { "  (cut)" } else { "" };
+            println!("  {} → {}{}", edge.from, edge.to, marker);
+            for u in &edge.uses {
+                println!("    {}:{}  use {}", u.path, u.line, u.use_path);
+            }
+        }
+    }
+
+    if cycles.is_empty() {
+        println!("Cycle check passed");
+        Ok(0)
+    } else {
+        println!("Cycle check failed");
+        Ok(1)
+    }
+}
@@ -0,0 +1,90 @@
+use rusqlite::Connection;
+
+use super::{arg_value, resolve_snapshot_id};
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::usecases::s513_doc_coverage::rollup::{coverage_drop, doc_rows, doc_summary, DocLevel};
+
+fn percent(coverage: Option<f64>) -> String {
+    coverage
+        .map(|c| format!("{:.1}%", c * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// `navigator docs`: exit code 1 when documentation coverage dropped since the previous snapshot
+pub fn run(conn: &Connection, args: &[String]) -> Result<i32, String> {
+    let snapshot_id = resolve_snapshot_id(conn, args)?;
+    let snapshot = snapshot_repo::get_by_id(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load snapshot: {}", e))?;
+    let max_drop = arg_value(args, "--max-drop")
+        .map(|v| {
+            v.parse::<f64>()
+                .map_err(|_| format!("Invalid value '{}' for --max-drop", v))
+        })
+        .transpose()?
+        .unwrap_or(0.0);
+
+    let summary = doc_summary(conn, snapshot_id)?;
+    println!(
+        "Snapshot '{}': {} of {} public items documented ({})",
+        snapshot.name,
+        summary.documented,
+        summary.items,
+        percent(summary.coverage())
+    );
+    for row in doc_rows(conn, snapshot_id, DocLevel::Crates)? {
+        println!(
+            "  {:<24} {:>5} / {:<5} {:>7}",
+            row.name,
+            row.documented,
+            row.items,
+            percent(row.coverage())
+        );
+    }
+    println!(
+        "Modules without //! header: {} of {}",
+        summary.modules_without_header.len(),
+        summary.modules
+    );
+    if !summary.crates_without_readme.is_empty() {
+        println!(
+            "Crates without README: {}",
+            summary.crates_without_readme.join(", ")
+        );
+    }
+
+    let baseline = match arg_value(args, "--baseline") {
+        Some(id) => {
+            let id = id
+                .parse::<i64>()
+                .map_err(|_| format!("Invalid snapshot id '{}'", id))?;
+            Some(
+                snapshot_repo::get_by_id(conn, id)
+                    .map_err(|e| format!("Failed to load baseline snapshot: {}", e))?,
+            )
+        }
+        None => snapshot_repo::previous_for_project(conn, snapshot.project_id, snapshot.id)
+            .map_err(|e| format!("Failed to load snapshots: {}", e))?,
+    };
+    let Some(baseline) = baseline else {
+        println!("No previous snapshot to compare with");
+        return Ok(0);
+    };
+    let previous = doc_summary(conn, baseline.id)?.coverage();
+    println!(
+        "Previous snapshot '{}': {}",
+        baseline.name,
+        percent(previous)
+    );
+
+    match coverage_drop(previous, summary.coverage(), max_drop) {
+        Some(failure) => {
+            println!("{}", failure);
+            println!("Doc coverage check failed");
+            Ok(1)
+        }
+        None => {
+            println!("Doc coverage check passed");
+            Ok(0)
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use rusqlite::Connection;
+use std::io;
+use std::path::Path;
+
+use super::{arg_value, resolve_snapshot_id};
+use crate::usecases::s515_export::collect::collect_snapshot;
+use crate::usecases::s515_export::format::ExportFormat;
+use crate::usecases::s515_export::{export_snapshot, write_export};
+
+/// `navigator export`: writes to `--output`, or to stdout for JSON formats
+pub fn run(conn: &Connection, args: &[String]) -> Result<i32, String> {
+    let snapshot_id = resolve_snapshot_id(conn, args)?;
+    let key = arg_value(args, "--format").unwrap_or("json");
+    let format = ExportFormat::parse(key).ok_or_else(|| {
+        let known: Vec<&str> = ExportFormat::ALL.iter().map(|f| f.key()).collect();
+        format!(
+            "Unknown format '{}', expected one of: {}",
+            key,
+            known.join(", ")
+        )
+    })?;
+
+    match arg_value(args, "--output") {
+        Some(path) => {
+            let rows = export_snapshot(conn, snapshot_id, format, Path::new(path))?;
+            eprintln!(
+                "Exported snapshot {} ({} rows) to {}",
+                snapshot_id, rows, path
+            );
+        }
+        None if format == ExportFormat::Csv => {
+            return Err("CSV export needs --output <directory>".to_string())
+        }
+        None => {
+            let export = collect_snapshot(conn, snapshot_id)?;
+            write_export(&export, format, &mut io::stdout().lock())?;
+        }
+    }
+    Ok(0)
+}
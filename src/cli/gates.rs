@@ -0,0 +1,37 @@
+use rusqlite::Connection;
+
+use super::resolve_snapshot_id;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n020_gate_result::ui::result_line;
+use crate::usecases::s519_quality_gates::record_gates;
+
+/// `navigator gates`: evaluate the project's quality gate, exit code 1 when a rule fails
+pub fn run(conn: &Connection, args: &[String]) -> Result<i32, String> {
+    let snapshot_id = resolve_snapshot_id(conn, args)?;
+    let snapshot = snapshot_repo::get_by_id(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load snapshot: {}", e))?;
+    let results = record_gates(conn, snapshot_id)?;
+
+    if results.is_empty() {
+        println!(
+            "Snapshot '{}': no quality gates defined for the project",
+            snapshot.name
+        );
+        return Ok(0);
+    }
+    println!(
+        "Snapshot '{}': {} quality gate rules",
+        snapshot.name,
+        results.len()
+    );
+    for result in &results {
+        println!("  {}", result_line(result));
+    }
+    if results.iter().all(|r| r.passed) {
+        println!("Quality gates passed");
+        Ok(0)
+    } else {
+        println!("Quality gates failed");
+        Ok(1)
+    }
+}
@@ -0,0 +1,53 @@
+use rusqlite::Connection;
+use std::path::Path;
+
+use super::{arg_value, resolve_snapshot_id};
+use crate::usecases::s518_dependencies::render::{self, GraphFormat};
+use crate::usecases::s518_dependencies::{write_graph, GraphLevel, GraphOptions, ModuleGraph};
+
+/// `navigator graph`: the module dependency graph, to `--output` or stdout
+pub fn run(conn: &Connection, args: &[String]) -> Result<i32, String> {
+    let snapshot_id = resolve_snapshot_id(conn, args)?;
+    let key = arg_value(args, "--format").unwrap_or("dot");
+    let format = GraphFormat::parse(key).ok_or_else(|| {
+        let known: Vec<&str> = GraphFormat::ALL.iter().map(|f| f.key()).collect();
+        format!(
+            "Unknown format '{}', expected one of: {}",
+            key,
+            known.join(", ")
+        )
+    })?;
+    let level = match arg_value(args, "--level") {
+        Some(key) => GraphLevel::parse(key).ok_or_else(|| {
+            format!(
+                "Unknown level '{}', expected module, aggregate or crate",
+                key
+            )
+        })?,
+        None => GraphLevel::Module,
+    };
+    let options = GraphOptions {
+        level,
+        layer: arg_value(args, "--layer").map(str::to_string),
+        exclude_tests: args.iter().any(|a| a == "--exclude-tests"),
+        highlight_cycles: args.iter().any(|a| a == "--highlight-cycles"),
+    };
+
+    match arg_value(args, "--output") {
+        Some(path) => {
+            write_graph(conn, snapshot_id, &options, format, Path::new(path))?;
+            eprintln!(
+                "Wrote dependency graph of snapshot {} to {}",
+                snapshot_id, path
+            );
+        }
+        None => print!(
+            "{}",
+            render::render(
+                &ModuleGraph::load(conn, snapshot_id)?.view(&options),
+                format
+            )
+        ),
+    }
+    Ok(0)
+}
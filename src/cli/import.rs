@@ -0,0 +1,27 @@
+use rusqlite::Connection;
+use std::path::Path;
+
+use super::arg_value;
+use crate::usecases::s516_import::import::{import_snapshot, OnConflict};
+use crate::usecases::s516_import::read_export;
+
+/// `navigator import <file>`: recreate a snapshot from a JSON export
+pub fn run(conn: &Connection, args: &[String]) -> Result<i32, String> {
+    let path = args
+        .first()
+        .filter(|a| !a.starts_with("--"))
+        .ok_or("Missing export file: navigator import <file.json>")?;
+    let key = arg_value(args, "--on-conflict").unwrap_or("rename");
+    let on_conflict = OnConflict::parse(key).ok_or_else(|| {
+        format!(
+            "Unknown --on-conflict '{}', expected rename, skip or overwrite",
+            key
+        )
+    })?;
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+
+    let doc = read_export(Path::new(path))?;
+    let summary = import_snapshot(conn, &doc, on_conflict, dry_run)?;
+    println!("{}", summary.describe());
+    Ok(0)
+}
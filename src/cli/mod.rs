@@ -0,0 +1,130 @@
+mod audit;
+mod cycles;
+mod docs;
+mod export;
+mod gates;
+mod graph;
+mod import;
+mod report;
+mod safety;
+mod scan;
+
+use rusqlite::Connection;
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::shared::db;
+
+const USAGE: &str = "\
+Usage: navigator <command> [options]
+
+Commands:
+  scan    Create a snapshot of the project named after the current time, scan it and compute
+          git churn, fail if a quality gate fails
+          --project <id>
+          [--name <name>]       (snapshot name instead of the current time)
+  audit   Audit lockfile packages against the local advisory database
+          --snapshot <id> | --project <id>   (latest snapshot of the project)
+  safety  Count unsafe code and panic sites, fail above the project thresholds
+          --snapshot <id> | --project <id>
+          [--max-unsafe <n>] [--max-panics <n>]   (override the project thresholds)
+  docs    Report documentation coverage, fail when it dropped since the previous snapshot
+          --snapshot <id> | --project <id>
+          [--baseline <id>]     (compare with this snapshot instead of the previous one)
+          [--max-drop <points>] (allowed drop in percentage points, default 0)
+  export  Export a snapshot with its files and metrics (schema: docs/export-schema.md)
+          --snapshot <id> | --project <id>
+          [--format json|json-flat|jsonl|csv]   (default json, the nested file tree)
+          [--output <path>]     (file, or directory for csv; JSON formats default to stdout)
+  import  Recreate a project snapshot from a json or json-flat export
+          <file>
+          [--on-conflict rename|skip|overwrite]   (snapshot name taken, default rename)
+          [--dry-run]           (report what would be imported, write nothing)
+  report  Build a self-contained report of a snapshot
+          --snapshot <id> | --project <id>
+          [--format html|markdown]   (default html; markdown is a PR summary against a base)
+          [--base <id>]         (markdown: compare with this snapshot instead of the baseline
+                                 or the previous one)
+          [--output <path>]     (default stdout)
+  graph   Export the module dependency graph
+          --snapshot <id> | --project <id>
+          [--format dot|mermaid|graphml]   (default dot)
+          [--level module|aggregate|crate]   (default module)
+          [--layer <layer>]     (only modules of this crate layer)
+          [--exclude-tests]     (ignore `use` in test code)
+          [--highlight-cycles]  (mark nodes and edges on cycles)
+          [--output <path>]     (default stdout)
+  cycles  List dependency cycles with their `use` lines and the edge to cut, fail if any
+          --snapshot <id> | --project <id>
+          [--level module|aggregate|crate]   (default every level)
+          [--include-tests]     (also follow `use` in test code)
+  gates   Evaluate the project's quality gates and record the results, fail if any rule fails
+          --snapshot <id> | --project <id>
+
+Without a command the graphical interface is started.";
+
+/// Run a headless command; returns the process exit code
+pub fn run(args: &[String]) -> i32 {
+    let command = args[0].as_str();
+    if matches!(command, "help" | "--help" | "-h") {
+        println!("{}", USAGE);
+        return 0;
+    }
+
+    let conn = match db::open_or_create(db::DB_PATH) {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("Error: failed to open database: {}", e);
+            return 2;
+        }
+    };
+
+    let rest = &args[1..];
+    let result = match command {
+        "scan" => scan::run(&conn, rest),
+        "audit" => audit::run(&conn, rest),
+        "safety" => safety::run(&conn, rest),
+        "docs" => docs::run(&conn, rest),
+        "export" => export::run(&conn, rest),
+        "import" => import::run(&conn, rest),
+        "report" => report::run(&conn, rest),
+        "graph" => graph::run(&conn, rest),
+        "cycles" => cycles::run(&conn, rest),
+        "gates" => gates::run(&conn, rest),
+        other => Err(format!("Unknown command '{}'\n\n{}", other, USAGE)),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            2
+        }
+    }
+}
+
+/// Value following `--name` in the argument list
+pub fn arg_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(|s| s.as_str())
+}
+
+/// Resolve `--snapshot <id>` or the latest snapshot of `--project <id>`
+pub fn resolve_snapshot_id(conn: &Connection, args: &[String]) -> Result<i64, String> {
+    if let Some(id) = arg_value(args, "--snapshot") {
+        return id
+            .parse::<i64>()
+            .map_err(|_| format!("Invalid snapshot id '{}'", id));
+    }
+    if let Some(id) = arg_value(args, "--project") {
+        let project_id = id
+            .parse::<i64>()
+            .map_err(|_| format!("Invalid project id '{}'", id))?;
+        return snapshot_repo::latest_for_project(conn, project_id)
+            .map_err(|e| format!("Failed to load snapshots: {}", e))?
+            .map(|s| s.id)
+            .ok_or_else(|| format!("Project {} has no snapshots", project_id));
+    }
+    Err("Specify --snapshot <id> or --project <id>".to_string())
+}
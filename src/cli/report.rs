@@ -0,0 +1,65 @@
+use rusqlite::Connection;
+use std::path::Path;
+
+use super::{arg_value, resolve_snapshot_id};
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::usecases::s517_report::markdown::{self, PrReport};
+use crate::usecases::s517_report::{html, write_html_report, write_markdown_report, ReportData};
+
+/// `--base <id>`, or the project baseline, or the snapshot before `snapshot_id` in its project
+fn resolve_base_id(conn: &Connection, args: &[String], snapshot_id: i64) -> Result<i64, String> {
+    if let Some(id) = arg_value(args, "--base") {
+        return id
+            .parse::<i64>()
+            .map_err(|_| format!("Invalid snapshot id '{}'", id));
+    }
+    let snapshot = snapshot_repo::get_by_id(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load snapshot {}: {}", snapshot_id, e))?;
+    snapshot_repo::comparison_base(conn, &snapshot)
+        .map_err(|e| format!("Failed to load snapshots: {}", e))?
+        .map(|s| s.id)
+        .ok_or_else(|| {
+            format!(
+                "Snapshot {} has no baseline or previous snapshot, specify --base <id>",
+                snapshot_id
+            )
+        })
+}
+
+/// `navigator report`: writes to `--output`, or to stdout
+pub fn run(conn: &Connection, args: &[String]) -> Result<i32, String> {
+    let snapshot_id = resolve_snapshot_id(conn, args)?;
+    let output = arg_value(args, "--output");
+    match arg_value(args, "--format").unwrap_or("html") {
+        "html" => match output {
+            Some(path) => {
+                write_html_report(conn, snapshot_id, Path::new(path))?;
+                eprintln!("Wrote report of snapshot {} to {}", snapshot_id, path);
+            }
+            None => print!("{}", html::render(&ReportData::load(conn, snapshot_id)?)),
+        },
+        "markdown" => {
+            let base_id = resolve_base_id(conn, args, snapshot_id)?;
+            match output {
+                Some(path) => {
+                    write_markdown_report(conn, base_id, snapshot_id, Path::new(path))?;
+                    eprintln!(
+                        "Wrote report of snapshot {} against {} to {}",
+                        snapshot_id, base_id, path
+                    );
+                }
+                None => print!(
+                    "{}",
+                    markdown::render(&PrReport::load(conn, base_id, snapshot_id)?)
+                ),
+            }
+        }
+        other => {
+            return Err(format!(
+                "Unknown report format '{}', expected html or markdown",
+                other
+            ))
+        }
+    }
+    Ok(0)
+}
@@ -0,0 +1,82 @@
+use rusqlite::Connection;
+
+use super::{arg_value, resolve_snapshot_id};
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::usecases::s510_safety::inventory::{
+    effective_thresholds, safety_rows, threshold_violations, SafetyLevel,
+};
+
+/// Number of files listed in the report
+const TOP_FILES: usize = 10;
+
+fn parse_limit(args: &[String], name: &str) -> Result<Option<i64>, String> {
+    arg_value(args, name)
+        .map(|v| {
+            v.parse::<i64>()
+                .map_err(|_| format!("Invalid value '{}' for {}", v, name))
+        })
+        .transpose()
+}
+
+/// `navigator safety`: exit code 1 when the project's unsafe/panic thresholds are exceeded
+pub fn run(conn: &Connection, args: &[String]) -> Result<i32, String> {
+    let snapshot_id = resolve_snapshot_id(conn, args)?;
+    let snapshot = snapshot_repo::get_by_id(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load snapshot: {}", e))?;
+    let project = project_repo::get_by_id(conn, snapshot.project_id)
+        .map_err(|e| format!("Failed to load project: {}", e))?;
+    let (max_unsafe, max_panics) = effective_thresholds(
+        &project,
+        parse_limit(args, "--max-unsafe")?,
+        parse_limit(args, "--max-panics")?,
+    );
+
+    let layers = safety_rows(conn, snapshot_id, SafetyLevel::CrateLayers)?;
+    let totals = (
+        layers.iter().map(|r| r.unsafe_total()).sum::<i64>(),
+        layers.iter().map(|r| r.panic_total()).sum::<i64>(),
+    );
+
+    println!(
+        "Snapshot '{}': {} unsafe sites, {} panic sites",
+        snapshot.name, totals.0, totals.1
+    );
+    for row in &layers {
+        println!(
+            "  {:<24} unsafe {:>5}  panics {:>6}",
+            row.name,
+            row.unsafe_total(),
+            row.panic_total()
+        );
+    }
+
+    let files = safety_rows(conn, snapshot_id, SafetyLevel::Files)?;
+    if !files.is_empty() {
+        println!("Top files:");
+        for row in files.iter().take(TOP_FILES) {
+            println!(
+                "  {:>5} {:>6}  {}",
+                row.unsafe_total(),
+                row.panic_total(),
+                row.name
+            );
+        }
+    }
+
+    if max_unsafe.is_none() && max_panics.is_none() {
+        println!("No thresholds set");
+        return Ok(0);
+    }
+    let violations = threshold_violations(max_unsafe, max_panics, totals);
+    if violations.is_empty() {
+        println!("Safety check passed");
+        Ok(0)
+    } else {
+        for v in &violations {
+            println!("threshold violation: {}", v);
+        }
+        println!("Safety check failed");
+        Ok(1)
+    }
+}
@@ -0,0 +1,24 @@
+use rusqlite::Connection;
+
+use super::arg_value;
+use crate::domain::n020_gate_result::ui::result_line;
+use crate::usecases::s521_new_scan::new_snapshot_and_scan;
+
+/// `navigator scan`: create a snapshot of the project and scan it, exit code 1 when a quality gate fails
+pub fn run(conn: &Connection, args: &[String]) -> Result<i32, String> {
+    let id = arg_value(args, "--project").ok_or("Specify --project <id>")?;
+    let project_id = id
+        .parse::<i64>()
+        .map_err(|_| format!("Invalid project id '{}'", id))?;
+    let result = new_snapshot_and_scan(conn, project_id, arg_value(args, "--name"), |_| {})?;
+
+    println!("{} (id {})", result.describe(), result.snapshot_id);
+    for gate in &result.scan.gates {
+        println!("  {}", result_line(gate));
+    }
+    Ok(if result.scan.gates.iter().all(|r| r.passed) {
+        0
+    } else {
+        1
+    })
+}
@@ -4,4 +4,36 @@ pub mod n002_snapshot;
 
 pub mod n003_snapshot_file;
 
-pub mod n004_snapshot_aggregate;
\ No newline at end of file
+pub mod n004_snapshot_aggregate;
+
+pub mod n005_snapshot_package;
+
+pub mod n006_file_content;
+
+pub mod n007_file_churn;
+
+pub mod n008_file_ownership;
+
+pub mod n009_author;
+
+pub mod n010_function_metric;
+
+pub mod n011_debt_marker;
+
+pub mod n012_safety_site;
+
+pub mod n013_file_tests;
+
+pub mod n014_file_coverage;
+
+pub mod n015_public_item;
+
+pub mod n016_module_doc;
+
+pub mod n017_file_language;
+
+pub mod n018_module_dependency;
+
+pub mod n019_quality_gate;
+
+pub mod n020_gate_result;
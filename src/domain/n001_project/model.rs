@@ -4,11 +4,32 @@ pub struct Project {
     pub root_path: String,
     pub name: String,
     pub description: Option<String>,
+    /// Most unsafe blocks/fns/impls the CLI accepts (None = unchecked)
+    pub max_unsafe_sites: Option<i64>,
+    /// Most unwrap/expect/panic!/index sites the CLI accepts (None = unchecked)
+    pub max_panic_sites: Option<i64>,
+    /// Snapshot the list views compare against (None = not pinned)
+    pub baseline_snapshot_id: Option<i64>,
 }
 
 impl Project {
-    pub fn new(id: i64, root_path: String, name: String, description: Option<String>) -> Self {
-        Self { id, root_path, name, description }
+    pub fn new(
+        id: i64,
+        root_path: String,
+        name: String,
+        description: Option<String>,
+        max_unsafe_sites: Option<i64>,
+        max_panic_sites: Option<i64>,
+        baseline_snapshot_id: Option<i64>,
+    ) -> Self {
+        Self {
+            id,
+            root_path,
+            name,
+            description,
+            max_unsafe_sites,
+            max_panic_sites,
+            baseline_snapshot_id,
+        }
     }
 }
-
@@ -1,6 +1,7 @@
 use rusqlite::{params, Connection, Result as SqlResult};
 
 use super::model::Project;
+use crate::shared::db::ensure_column;
 
 pub fn init_table(conn: &Connection) -> SqlResult<()> {
     conn.execute(
@@ -8,14 +9,27 @@ pub fn init_table(conn: &Connection) -> SqlResult<()> {
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             root_path TEXT NOT NULL,
             name TEXT NOT NULL,
-            description TEXT
+            description TEXT,
+            max_unsafe_sites INTEGER,
+            max_panic_sites INTEGER,
+            baseline_snapshot_id INTEGER
         )",
         [],
     )?;
+    // Databases created before safety thresholds existed
+    ensure_column(conn, "n001_project", "max_unsafe_sites", "INTEGER")?;
+    ensure_column(conn, "n001_project", "max_panic_sites", "INTEGER")?;
+    // Databases created before baselines existed
+    ensure_column(conn, "n001_project", "baseline_snapshot_id", "INTEGER")?;
     Ok(())
 }
 
-pub fn create(conn: &Connection, name: &str, root_path: &str, description: Option<&str>) -> SqlResult<i64> {
+pub fn create(
+    conn: &Connection,
+    name: &str,
+    root_path: &str,
+    description: Option<&str>,
+) -> SqlResult<i64> {
     conn.execute(
         "INSERT INTO n001_project (name, root_path, description) VALUES (?1, ?2, ?3)",
         params![name, root_path, description],
@@ -25,7 +39,7 @@ pub fn create(conn: &Connection, name: &str, root_path: &str, description: Optio
 
 pub fn list_all(conn: &Connection) -> SqlResult<Vec<Project>> {
     let mut stmt = conn.prepare(
-        "SELECT id, root_path, name, description FROM n001_project ORDER BY id DESC",
+        "SELECT id, root_path, name, description, max_unsafe_sites, max_panic_sites, baseline_snapshot_id FROM n001_project ORDER BY id DESC",
     )?;
     let rows = stmt.query_map([], |row| {
         Ok(Project::new(
@@ -33,6 +47,9 @@ pub fn list_all(conn: &Connection) -> SqlResult<Vec<Project>> {
             row.get::<_, String>(1)?,
             row.get::<_, String>(2)?,
             row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<i64>>(4)?,
+            row.get::<_, Option<i64>>(5)?,
+            row.get::<_, Option<i64>>(6)?,
         ))
     })?;
 
@@ -45,7 +62,7 @@ pub fn list_all(conn: &Connection) -> SqlResult<Vec<Project>> {
 
 pub fn get_by_id(conn: &Connection, id: i64) -> SqlResult<Project> {
     conn.query_row(
-        "SELECT id, root_path, name, description FROM n001_project WHERE id = ?1",
+        "SELECT id, root_path, name, description, max_unsafe_sites, max_panic_sites, baseline_snapshot_id FROM n001_project WHERE id = ?1",
         [id],
         |row| {
             Ok(Project::new(
@@ -53,15 +70,58 @@ pub fn get_by_id(conn: &Connection, id: i64) -> SqlResult<Project> {
                 row.get::<_, String>(1)?,
                 row.get::<_, String>(2)?,
                 row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<i64>>(4)?,
+                row.get::<_, Option<i64>>(5)?,
+                row.get::<_, Option<i64>>(6)?,
             ))
         },
     )
 }
 
+/// Update editable fields; thresholds and the baseline are only written by their setters
 pub fn update(conn: &Connection, project: &Project) -> SqlResult<()> {
     conn.execute(
         "UPDATE n001_project SET name = ?1, root_path = ?2, description = ?3 WHERE id = ?4",
-        params![project.name, project.root_path, project.description, project.id],
+        params![
+            project.name,
+            project.root_path,
+            project.description,
+            project.id
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn set_safety_thresholds(
+    conn: &Connection,
+    id: i64,
+    max_unsafe_sites: Option<i64>,
+    max_panic_sites: Option<i64>,
+) -> SqlResult<()> {
+    conn.execute(
+        "UPDATE n001_project SET max_unsafe_sites = ?1, max_panic_sites = ?2 WHERE id = ?3",
+        params![max_unsafe_sites, max_panic_sites, id],
+    )?;
+    Ok(())
+}
+
+pub fn set_baseline(
+    conn: &Connection,
+    id: i64,
+    baseline_snapshot_id: Option<i64>,
+) -> SqlResult<()> {
+    conn.execute(
+        "UPDATE n001_project SET baseline_snapshot_id = ?1 WHERE id = ?2",
+        params![baseline_snapshot_id, id],
+    )?;
+    Ok(())
+}
+
+/// Unpin `snapshot_id` from every project that uses it as the baseline
+pub fn clear_baseline(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "UPDATE n001_project SET baseline_snapshot_id = NULL WHERE baseline_snapshot_id = ?1",
+        [snapshot_id],
     )?;
     Ok(())
 }
@@ -70,4 +130,3 @@ pub fn delete(conn: &Connection, id: i64) -> SqlResult<()> {
     conn.execute("DELETE FROM n001_project WHERE id = ?1", [id])?;
     Ok(())
 }
-
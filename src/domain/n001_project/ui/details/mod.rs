@@ -1,11 +1,14 @@
 use eframe::egui;
 use rfd::FileDialog;
 
+use crate::domain::n019_quality_gate::ui::{gates_form, GatesFormState};
+
 #[derive(Default, Clone)]
 pub struct ProjectDetailsState {
     pub name: String,
     pub root_path: String,
     pub description: String,
+    pub gates: GatesFormState,
 }
 
 impl ProjectDetailsState {
@@ -14,6 +17,7 @@ impl ProjectDetailsState {
             name: name.to_string(),
             root_path: root_path.to_string(),
             description: description.unwrap_or("").to_string(),
+            gates: GatesFormState::default(),
         }
     }
 }
@@ -28,7 +32,10 @@ pub fn details_form(ui: &mut egui::Ui, state: &mut ProjectDetailsState) {
         ui.text_edit_singleline(&mut state.root_path);
         ui.add_space(6.0);
         if ui.button("Browse...").clicked() {
-            if let Some(path) = FileDialog::new().set_directory(&state.root_path).pick_folder() {
+            if let Some(path) = FileDialog::new()
+                .set_directory(&state.root_path)
+                .pick_folder()
+            {
                 if let Some(s) = path.to_str() {
                     state.root_path = s.to_string();
                 } else {
@@ -41,5 +48,6 @@ pub fn details_form(ui: &mut egui::Ui, state: &mut ProjectDetailsState) {
         ui.label("Description:");
         ui.text_edit_singleline(&mut state.description);
     });
+    ui.add_space(6.0);
+    gates_form(ui, &mut state.gates);
 }
-
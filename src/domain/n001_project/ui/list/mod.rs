@@ -1,11 +1,14 @@
 use eframe::egui;
+use egui::{Color32, RichText, Stroke, TextStyle, Vec2};
 use egui_extras::{Column, TableBuilder};
-use egui::{Color32, Stroke, Vec2, RichText, TextStyle};
 use rusqlite::Connection;
 
 use crate::domain::n001_project::model::Project;
 use crate::domain::n001_project::repository as repo;
 use crate::domain::n001_project::ui::details::{details_form, ProjectDetailsState};
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n019_quality_gate::repository as gate_repo;
+use crate::domain::n019_quality_gate::ui::GatesFormState;
 
 #[derive(Default)]
 pub struct ProjectsListState {
@@ -17,11 +20,28 @@ pub struct ProjectsListState {
     pub create_details: ProjectDetailsState,
     pub edit_details: ProjectDetailsState,
     pub view_details: ProjectDetailsState,
+    /// Name of the viewed project's baseline snapshot
+    pub view_baseline: Option<String>,
 
     pub status: String,
 }
 
-pub fn ui_projects_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ProjectsListState) {
+/// What a project row asks of the other tabs
+pub enum ProjectAction {
+    /// Open the project dashboard
+    Dashboard(i64),
+    /// Create a snapshot, scan it and open the dashboard
+    Scan(i64),
+}
+
+/// Renders the Projects tab
+pub fn ui_projects_list(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut ProjectsListState,
+) -> Option<ProjectAction> {
+    let mut action = None;
+
     // Top buttons only (no headings)
     ui.horizontal(|ui| {
         if ui.button("Add").clicked() {
@@ -40,7 +60,7 @@ pub fn ui_projects_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Projec
                 egui::Color32::LIGHT_RED,
                 format!("Error loading projects: {}", e),
             );
-            return;
+            return None;
         }
     };
 
@@ -106,6 +126,16 @@ pub fn ui_projects_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Projec
                     });
                     row.col(|ui| {
                         ui.horizontal(|ui| {
+                            if ui.button("Dashboard").clicked() {
+                                action = Some(ProjectAction::Dashboard(id));
+                            }
+                            if ui
+                                .button("Scan")
+                                .on_hover_text("Create a snapshot named after the current time and scan the project root")
+                                .clicked()
+                            {
+                                action = Some(ProjectAction::Scan(id));
+                            }
                             if ui.button("View").clicked() {
                                 match repo::get_by_id(conn, id) {
                                     Ok(p) => {
@@ -114,6 +144,13 @@ pub fn ui_projects_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Projec
                                             &p.root_path,
                                             p.description.as_deref(),
                                         );
+                                        state.view_details.gates = GatesFormState::from_gates(
+                                            &gate_repo::list_by_project(conn, id).unwrap_or_default(),
+                                        );
+                                        state.view_baseline = p
+                                            .baseline_snapshot_id
+                                            .and_then(|b| snapshot_repo::get_by_id(conn, b).ok())
+                                            .map(|b| b.name);
                                         state.show_view = true;
                                     }
                                     Err(e) => state.status = format!("Error loading project: {}", e),
@@ -128,6 +165,9 @@ pub fn ui_projects_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Projec
                                             &p.root_path,
                                             p.description.as_deref(),
                                         );
+                                        state.edit_details.gates = GatesFormState::from_gates(
+                                            &gate_repo::list_by_project(conn, id).unwrap_or_default(),
+                                        );
                                         state.show_edit = true;
                                     }
                                     Err(e) => state.status = format!("Error loading project: {}", e),
@@ -164,35 +204,41 @@ pub fn ui_projects_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Projec
             .id(egui::Id::new("n001_add_project"))
             .open(&mut open)
             .show(ui.ctx(), |ui| {
-            details_form(ui, &mut state.create_details);
-            ui.add_space(6.0);
-            ui.horizontal(|ui| {
-                if ui.button("Save").clicked() {
-                    let name = state.create_details.name.trim();
-                    let path = state.create_details.root_path.trim();
-                    let desc_opt = if state.create_details.description.trim().is_empty() {
-                        None
-                    } else {
-                        Some(state.create_details.description.trim())
-                    };
-                    if name.is_empty() || path.is_empty() {
-                        state.status = "Name and path required".to_string();
-                    } else {
-                        match repo::create(conn, name, path, desc_opt) {
-                            Ok(id) => {
-                                state.status = format!("Project created (ID={})", id);
-                                state.show_create = false;
-                                state.create_details = ProjectDetailsState::default();
+                details_form(ui, &mut state.create_details);
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        let name = state.create_details.name.trim();
+                        let path = state.create_details.root_path.trim();
+                        let desc_opt = if state.create_details.description.trim().is_empty() {
+                            None
+                        } else {
+                            Some(state.create_details.description.trim())
+                        };
+                        let gates = state.create_details.gates.rules();
+                        if name.is_empty() || path.is_empty() {
+                            state.status = "Name and path required".to_string();
+                        } else if let Err(e) = &gates {
+                            state.status = e.clone();
+                        } else {
+                            let gates = gates.unwrap_or_default();
+                            match repo::create(conn, name, path, desc_opt).and_then(|id| {
+                                gate_repo::set_for_project(conn, id, &gates).map(|_| id)
+                            }) {
+                                Ok(id) => {
+                                    state.status = format!("Project created (ID={})", id);
+                                    state.show_create = false;
+                                    state.create_details = ProjectDetailsState::default();
+                                }
+                                Err(e) => state.status = format!("Error creating: {}", e),
                             }
-                            Err(e) => state.status = format!("Error creating: {}", e),
                         }
                     }
-                }
-                if ui.button("Cancel").clicked() {
-                    state.show_create = false;
-                }
+                    if ui.button("Cancel").clicked() {
+                        state.show_create = false;
+                    }
+                });
             });
-        });
         if !open {
             state.show_create = false;
         }
@@ -205,14 +251,22 @@ pub fn ui_projects_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Projec
             .id(egui::Id::new("n001_view_project"))
             .open(&mut open)
             .show(ui.ctx(), |ui| {
-            ui.label(format!("Name: {}", state.view_details.name));
-            ui.label(format!("Root Path: {}", state.view_details.root_path));
-            ui.label(format!("Description: {}", state.view_details.description));
-            ui.add_space(6.0);
-            if ui.button("Close").clicked() {
-                state.show_view = false;
-            }
-        });
+                ui.label(format!("Name: {}", state.view_details.name));
+                ui.label(format!("Root Path: {}", state.view_details.root_path));
+                ui.label(format!("Description: {}", state.view_details.description));
+                ui.label(format!(
+                    "Quality gates: {}",
+                    state.view_details.gates.summary()
+                ));
+                ui.label(format!(
+                    "Baseline: {}",
+                    state.view_baseline.as_deref().unwrap_or("none")
+                ));
+                ui.add_space(6.0);
+                if ui.button("Close").clicked() {
+                    state.show_view = false;
+                }
+            });
         if !open {
             state.show_view = false;
         }
@@ -225,42 +279,59 @@ pub fn ui_projects_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Projec
             .id(egui::Id::new("n001_edit_project"))
             .open(&mut open)
             .show(ui.ctx(), |ui| {
-            details_form(ui, &mut state.edit_details);
-            ui.add_space(6.0);
-            ui.horizontal(|ui| {
-                if ui.button("Save").clicked() {
-                    if let Some(id) = state.editing_id {
-                        let name = state.edit_details.name.trim().to_string();
-                        let path = state.edit_details.root_path.trim().to_string();
-                        let desc = if state.edit_details.description.trim().is_empty() {
-                            None
-                        } else {
-                            Some(state.edit_details.description.trim().to_string())
-                        };
-                        if name.is_empty() || path.is_empty() {
-                            state.status = "Name and path required".to_string();
-                        } else {
-                            let updated = Project { id, name, root_path: path, description: desc };
-                            match repo::update(conn, &updated) {
-                                Ok(_) => {
-                                    state.status = "Project updated".to_string();
-                                    state.show_edit = false;
-                                    state.editing_id = None;
+                details_form(ui, &mut state.edit_details);
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        if let Some(id) = state.editing_id {
+                            let name = state.edit_details.name.trim().to_string();
+                            let path = state.edit_details.root_path.trim().to_string();
+                            let desc = if state.edit_details.description.trim().is_empty() {
+                                None
+                            } else {
+                                Some(state.edit_details.description.trim().to_string())
+                            };
+                            let gates = state.edit_details.gates.rules();
+                            if name.is_empty() || path.is_empty() {
+                                state.status = "Name and path required".to_string();
+                            } else if let Err(e) = &gates {
+                                state.status = e.clone();
+                            } else {
+                                let gates = gates.unwrap_or_default();
+                                let updated = Project {
+                                    id,
+                                    name,
+                                    root_path: path,
+                                    description: desc,
+                                    // Not editable here; repo::update leaves the stored values untouched
+                                    max_unsafe_sites: None,
+                                    max_panic_sites: None,
+                                    baseline_snapshot_id: None,
+                                };
+                                match repo::update(conn, &updated)
+                                    .and_then(|_| gate_repo::set_for_project(conn, id, &gates))
+                                {
+                                    Ok(_) => {
+                                        state.status = "Project updated".to_string();
+                                        state.show_edit = false;
+                                        state.editing_id = None;
+                                    }
+                                    Err(e) => state.status = format!("Error updating: {}", e),
                                 }
-                                Err(e) => state.status = format!("Error updating: {}", e),
                             }
                         }
                     }
-                }
-                if ui.button("Cancel").clicked() {
-                    state.show_edit = false;
-                    state.editing_id = None;
-                }
+                    if ui.button("Cancel").clicked() {
+                        state.show_edit = false;
+                        state.editing_id = None;
+                    }
+                });
             });
-        });
         if !open {
             state.show_edit = false;
             state.editing_id = None;
         }
     }
+
+    action
 }
@@ -9,6 +9,8 @@ pub struct Snapshot {
     pub files_size_bytes: i64,
     pub loc_count: i64,
     pub scanned_at: String,
+    /// HEAD commit of the project repository at scan time (None outside git)
+    pub git_revision: Option<String>,
 }
 
 impl Snapshot {
@@ -23,6 +25,7 @@ impl Snapshot {
         files_size_bytes: i64,
         loc_count: i64,
         scanned_at: String,
+        git_revision: Option<String>,
     ) -> Self {
         Self {
             id,
@@ -34,6 +37,7 @@ impl Snapshot {
             files_size_bytes,
             loc_count,
             scanned_at,
+            git_revision,
         }
     }
 }
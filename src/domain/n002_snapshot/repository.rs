@@ -1,5 +1,7 @@
-use rusqlite::{Connection, Result as SqlResult};
 use rusqlite::params;
+use rusqlite::{Connection, OptionalExtension, Result as SqlResult};
+
+use crate::shared::db::ensure_column;
 
 use super::model::Snapshot;
 
@@ -14,16 +16,19 @@ pub fn init_table(conn: &Connection) -> SqlResult<()> {
             dirs_count INTEGER NOT NULL,
             files_size_bytes INTEGER NOT NULL,
             loc_count INTEGER NOT NULL,
-            scanned_at TEXT NOT NULL
+            scanned_at TEXT NOT NULL,
+            git_revision TEXT
         )",
         [],
     )?;
+    // Databases created before git revisions were recorded
+    ensure_column(conn, "n002_snapshot", "git_revision", "TEXT")?;
     Ok(())
 }
 
 pub fn list_all(conn: &Connection) -> SqlResult<Vec<Snapshot>> {
     let mut stmt = conn.prepare(
-        "SELECT id, name, project_id, comment, files_count, dirs_count, files_size_bytes, loc_count, scanned_at
+        "SELECT id, name, project_id, comment, files_count, dirs_count, files_size_bytes, loc_count, scanned_at, git_revision
          FROM n002_snapshot ORDER BY id DESC",
     )?;
     let rows = stmt.query_map([], |row| {
@@ -37,12 +42,10 @@ pub fn list_all(conn: &Connection) -> SqlResult<Vec<Snapshot>> {
             row.get::<_, i64>(6)?,
             row.get::<_, i64>(7)?,
             row.get::<_, String>(8)?,
+            row.get::<_, Option<String>>(9)?,
         ))
     })?;
-
-    let mut out = Vec::new();
-    for r in rows { out.push(r?); }
-    Ok(out)
+    rows.collect()
 }
 
 pub fn create(
@@ -66,7 +69,7 @@ pub fn create(
 
 pub fn get_by_id(conn: &Connection, id: i64) -> SqlResult<Snapshot> {
     conn.query_row(
-        "SELECT id, name, project_id, comment, files_count, dirs_count, files_size_bytes, loc_count, scanned_at
+        "SELECT id, name, project_id, comment, files_count, dirs_count, files_size_bytes, loc_count, scanned_at, git_revision
          FROM n002_snapshot WHERE id = ?1",
         [id],
         |row| {
@@ -80,11 +83,13 @@ pub fn get_by_id(conn: &Connection, id: i64) -> SqlResult<Snapshot> {
                 row.get::<_, i64>(6)?,
                 row.get::<_, i64>(7)?,
                 row.get::<_, String>(8)?,
+                row.get::<_, Option<String>>(9)?,
             ))
         },
     )
 }
 
+/// Update editable fields; `git_revision` is only written by `set_git_revision`
 pub fn update(conn: &Connection, snap: &Snapshot) -> SqlResult<()> {
     conn.execute(
         "UPDATE n002_snapshot
@@ -105,7 +110,77 @@ pub fn update(conn: &Connection, snap: &Snapshot) -> SqlResult<()> {
     Ok(())
 }
 
+pub fn set_git_revision(conn: &Connection, id: i64, revision: Option<&str>) -> SqlResult<()> {
+    conn.execute(
+        "UPDATE n002_snapshot SET git_revision = ?1 WHERE id = ?2",
+        params![revision, id],
+    )?;
+    Ok(())
+}
+
 pub fn delete(conn: &Connection, id: i64) -> SqlResult<()> {
     conn.execute("DELETE FROM n002_snapshot WHERE id = ?1", [id])?;
     Ok(())
 }
+
+pub fn list_by_project(conn: &Connection, project_id: i64) -> SqlResult<Vec<Snapshot>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, project_id, comment, files_count, dirs_count, files_size_bytes, loc_count, scanned_at, git_revision
+         FROM n002_snapshot WHERE project_id = ?1 ORDER BY id DESC",
+    )?;
+    let rows = stmt.query_map([project_id], |row| {
+        Ok(Snapshot::new(
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, i64>(4)?,
+            row.get::<_, i64>(5)?,
+            row.get::<_, i64>(6)?,
+            row.get::<_, i64>(7)?,
+            row.get::<_, String>(8)?,
+            row.get::<_, Option<String>>(9)?,
+        ))
+    })?;
+    rows.collect()
+}
+
+/// Most recently created snapshot of a project, if any
+pub fn latest_for_project(conn: &Connection, project_id: i64) -> SqlResult<Option<Snapshot>> {
+    Ok(list_by_project(conn, project_id)?.into_iter().next())
+}
+
+/// Snapshot of the same project created just before `snapshot_id`, if any
+pub fn previous_for_project(
+    conn: &Connection,
+    project_id: i64,
+    snapshot_id: i64,
+) -> SqlResult<Option<Snapshot>> {
+    Ok(list_by_project(conn, project_id)?
+        .into_iter()
+        .find(|s| s.id < snapshot_id))
+}
+
+/// Baseline pinned on the snapshot's project, None when unpinned or the snapshot is the baseline itself
+pub fn baseline_for_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Option<Snapshot>> {
+    let baseline_id: Option<i64> = conn
+        .query_row(
+            "SELECT p.baseline_snapshot_id FROM n001_project p JOIN n002_snapshot s ON s.project_id = p.id WHERE s.id = ?1",
+            [snapshot_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten();
+    match baseline_id {
+        Some(id) if id != snapshot_id => get_by_id(conn, id).optional(),
+        _ => Ok(None),
+    }
+}
+
+/// Snapshot to compare with: the project baseline if pinned, else the previous snapshot
+pub fn comparison_base(conn: &Connection, snapshot: &Snapshot) -> SqlResult<Option<Snapshot>> {
+    match baseline_for_snapshot(conn, snapshot.id)? {
+        Some(baseline) => Ok(Some(baseline)),
+        None => previous_for_project(conn, snapshot.project_id, snapshot.id),
+    }
+}
@@ -0,0 +1,70 @@
+use eframe::egui;
+use rusqlite::Connection;
+
+use crate::domain::n002_snapshot::model::Snapshot;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+
+/// "Compare with baseline" checkbox; returns the project baseline while it is ticked
+pub fn ui_baseline_toggle(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    snapshot_id: i64,
+    enabled: &mut bool,
+) -> Option<Snapshot> {
+    match snapshot_repo::baseline_for_snapshot(conn, snapshot_id) {
+        Ok(Some(baseline)) => {
+            ui.checkbox(
+                enabled,
+                format!("Compare with baseline '{}'", baseline.name),
+            );
+            if *enabled {
+                Some(baseline)
+            } else {
+                None
+            }
+        }
+        Ok(None) => {
+            ui.label(egui::RichText::new("No baseline to compare with").weak())
+                .on_hover_text(
+                    "Pin a baseline from the snapshot context menu in the Snapshots tab",
+                );
+            None
+        }
+        Err(e) => {
+            ui.colored_label(
+                egui::Color32::LIGHT_RED,
+                format!("Error loading baseline: {}", e),
+            );
+            None
+        }
+    }
+}
+
+/// `▲ 12` in red for growth, `▼ 3` in green for shrinkage, nothing when unchanged
+pub fn delta_label(ui: &mut egui::Ui, delta: i64, format: fn(i64) -> String) {
+    if delta > 0 {
+        ui.colored_label(egui::Color32::LIGHT_RED, format!("▲ {}", format(delta)));
+    } else if delta < 0 {
+        ui.colored_label(egui::Color32::LIGHT_GREEN, format!("▼ {}", format(-delta)));
+    }
+}
+
+/// Marker for a row missing from the baseline
+pub fn new_marker(ui: &mut egui::Ui) {
+    ui.label(
+        egui::RichText::new("new")
+            .small()
+            .color(egui::Color32::LIGHT_BLUE),
+    )
+    .on_hover_text("Not in the baseline");
+}
+
+/// Marker for a baseline row missing from the current snapshot
+pub fn removed_marker(ui: &mut egui::Ui) {
+    ui.label(
+        egui::RichText::new("removed")
+            .small()
+            .color(egui::Color32::GRAY),
+    )
+    .on_hover_text("Only in the baseline");
+}
@@ -1,15 +1,25 @@
 use std::collections::HashMap;
 
 use eframe::egui;
+use egui::{Color32, RichText, Stroke, TextStyle, Vec2};
 use egui_extras::{Column, TableBuilder};
-use egui::{Color32, RichText, TextStyle, Vec2, Stroke};
 use rusqlite::Connection;
 
-use crate::domain::n002_snapshot::repository as repo;
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n001_project::ui::picker::ProjectPickerState;
 use crate::domain::n002_snapshot::model::Snapshot;
+use crate::domain::n002_snapshot::repository as repo;
 use crate::domain::n002_snapshot::ui::details::{details_form, SnapshotDetailsState};
-use crate::domain::n001_project::ui::picker::ProjectPickerState;
-use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n017_file_language::model::{share, LanguageStats};
+use crate::domain::n017_file_language::repository as language_repo;
+use crate::domain::n017_file_language::ui::{legend_entry, share_bar};
+use crate::domain::n020_gate_result::model::GateResult;
+use crate::domain::n020_gate_result::repository as gate_result_repo;
+use crate::domain::n020_gate_result::ui::{gate_badge, result_line};
+use crate::usecases::s515_export::export_menu;
+use crate::usecases::s516_import::{ui_import_window, ImportState};
+use crate::usecases::s517_report::report_menu;
+use crate::usecases::s522_delete_snapshot::delete_snapshot;
 
 #[derive(Default)]
 pub struct SnapshotsListState {
@@ -21,6 +31,12 @@ pub struct SnapshotsListState {
     pub create_details: SnapshotDetailsState,
     pub edit_details: SnapshotDetailsState,
     pub view_details: SnapshotDetailsState,
+    /// Language breakdown of the viewed snapshot
+    pub view_languages: Vec<LanguageStats>,
+    /// Quality gate results of the viewed snapshot
+    pub view_gates: Vec<GateResult>,
+    /// Import from a JSON export
+    pub import: ImportState,
 
     // Picker for selecting a project
     pub project_picker: ProjectPickerState,
@@ -35,6 +51,9 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
             state.show_create = true;
             state.create_details = SnapshotDetailsState::default();
         }
+        if ui.button("Import...").clicked() {
+            state.import.open();
+        }
     });
 
     ui.add_space(6.0);
@@ -52,10 +71,23 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
     };
 
     // Load projects map for name lookup
-    let project_names: HashMap<i64, String> = match project_repo::list_all(conn) {
-        Ok(list) => list.into_iter().map(|p| (p.id, p.name)).collect(),
-        Err(_) => HashMap::new(),
-    };
+    let projects = project_repo::list_all(conn).unwrap_or_default();
+    let project_names: HashMap<i64, String> =
+        projects.iter().map(|p| (p.id, p.name.clone())).collect();
+    // Pinned baseline snapshot per project
+    let baselines: HashMap<i64, i64> = projects
+        .iter()
+        .filter_map(|p| Some((p.id, p.baseline_snapshot_id?)))
+        .collect();
+
+    // Quality gate results per snapshot, for the status badges
+    let mut gate_results: HashMap<i64, Vec<GateResult>> = HashMap::new();
+    for result in gate_result_repo::list_all(conn).unwrap_or_default() {
+        gate_results
+            .entry(result.snapshot_id)
+            .or_default()
+            .push(result);
+    }
 
     // Ensure table uses full available width
     ui.set_width(ui.available_width());
@@ -88,10 +120,20 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
         .column(Column::remainder()) // LOC
         .column(Column::remainder()) // Scanned At
         .column(Column::remainder()) // Comment
-        .column(Column::auto())      // Actions
+        .column(Column::auto()) // Gates
+        .column(Column::auto()) // Actions
         .header(24.0, |mut header| {
             for title in [
-                "Name", "Project", "Files", "Dirs", "Size(bytes)", "LOC", "Scanned At", "Comment", "Actions",
+                "Name",
+                "Project",
+                "Files",
+                "Dirs",
+                "Size(bytes)",
+                "LOC",
+                "Scanned At",
+                "Comment",
+                "Gates",
+                "Actions",
             ] {
                 header.col(|ui| {
                     let rect = ui.max_rect();
@@ -103,20 +145,76 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
         .body(|mut body| {
             for s in snapshots.iter() {
                 body.row(26.0, |mut row| {
-                    row.col(|ui| { ui.label(RichText::new(&s.name).size(row_size)); });
+                    let is_baseline = baselines.get(&s.project_id) == Some(&s.id);
+                    row.col(|ui| {
+                        let response = ui.add(
+                            egui::Label::new(RichText::new(&s.name).size(row_size))
+                                .sense(egui::Sense::click()),
+                        );
+                        response
+                            .on_hover_text(
+                                "Right-click to export, build a report or pin as baseline",
+                            )
+                            .context_menu(|ui| {
+                                if let Some(status) = export_menu(ui, conn, s.id, &s.name) {
+                                    state.status = status;
+                                }
+                                ui.separator();
+                                if let Some(status) = report_menu(ui, conn, s.id, &s.name) {
+                                    state.status = status;
+                                }
+                                ui.separator();
+                                if let Some(status) = baseline_menu(ui, conn, s, is_baseline) {
+                                    state.status = status;
+                                }
+                            });
+                        if is_baseline {
+                            ui.label(
+                                RichText::new("★ baseline")
+                                    .size(row_size)
+                                    .color(Color32::GOLD),
+                            )
+                            .on_hover_text("Project baseline: list views show deltas against it");
+                        }
+                    });
                     row.col(|ui| {
                         let pname = project_names
                             .get(&s.project_id)
                             .map(|s| s.as_str())
                             .unwrap_or("<unknown>");
-                        ui.label(RichText::new(format!("{} (ID: {})", pname, s.project_id)).size(row_size));
+                        ui.label(
+                            RichText::new(format!("{} (ID: {})", pname, s.project_id))
+                                .size(row_size),
+                        );
+                    });
+                    row.col(|ui| {
+                        ui.label(RichText::new(format!("{}", s.files_count)).size(row_size));
+                    });
+                    row.col(|ui| {
+                        ui.label(RichText::new(format!("{}", s.dirs_count)).size(row_size));
+                    });
+                    row.col(|ui| {
+                        ui.label(RichText::new(format!("{}", s.files_size_bytes)).size(row_size));
+                    });
+                    row.col(|ui| {
+                        ui.label(RichText::new(format!("{}", s.loc_count)).size(row_size));
+                    });
+                    row.col(|ui| {
+                        ui.label(RichText::new(&s.scanned_at).size(row_size));
+                    });
+                    row.col(|ui| {
+                        ui.label(RichText::new(s.comment.as_deref().unwrap_or("")).size(row_size));
+                    });
+                    row.col(|ui| {
+                        gate_badge(
+                            ui,
+                            gate_results
+                                .get(&s.id)
+                                .map(Vec::as_slice)
+                                .unwrap_or_default(),
+                            row_size,
+                        );
                     });
-                    row.col(|ui| { ui.label(RichText::new(format!("{}", s.files_count)).size(row_size)); });
-                    row.col(|ui| { ui.label(RichText::new(format!("{}", s.dirs_count)).size(row_size)); });
-                    row.col(|ui| { ui.label(RichText::new(format!("{}", s.files_size_bytes)).size(row_size)); });
-                    row.col(|ui| { ui.label(RichText::new(format!("{}", s.loc_count)).size(row_size)); });
-                    row.col(|ui| { ui.label(RichText::new(&s.scanned_at).size(row_size)); });
-                    row.col(|ui| { ui.label(RichText::new(s.comment.as_deref().unwrap_or("")).size(row_size)); });
                     row.col(|ui| {
                         ui.horizontal(|ui| {
                             if ui.button("View").clicked() {
@@ -133,9 +231,16 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
                                             snap.loc_count,
                                             &snap.scanned_at,
                                         );
+                                        state.view_languages =
+                                            language_repo::breakdown_by_snapshot(conn, id)
+                                                .unwrap_or_default();
+                                        state.view_gates =
+                                            gate_results.get(&id).cloned().unwrap_or_default();
                                         state.show_view = true;
                                     }
-                                    Err(e) => state.status = format!("Error loading snapshot: {}", e),
+                                    Err(e) => {
+                                        state.status = format!("Error loading snapshot: {}", e)
+                                    }
                                 }
                             }
                             if ui.button("Edit").clicked() {
@@ -155,13 +260,17 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
                                         );
                                         state.show_edit = true;
                                     }
-                                    Err(e) => state.status = format!("Error loading snapshot: {}", e),
+                                    Err(e) => {
+                                        state.status = format!("Error loading snapshot: {}", e)
+                                    }
                                 }
                             }
                             if ui.button("Delete").clicked() {
                                 let id = s.id;
-                                match repo::delete(conn, id) {
-                                    Ok(_) => { state.status = format!("Deleted snapshot {}", id); }
+                                match delete_snapshot(conn, id) {
+                                    Ok(_) => {
+                                        state.status = format!("Deleted snapshot {}", id);
+                                    }
                                     Err(e) => state.status = format!("Error deleting: {}", e),
                                 }
                             }
@@ -170,11 +279,15 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
                 });
                 // horizontal separator line
                 body.row(2.0, |mut row| {
-                    for _i in 0..9 {
+                    for _i in 0..10 {
                         row.col(|ui| {
                             let rect = ui.max_rect();
                             let y = rect.bottom();
-                            ui.painter().hline(rect.x_range(), y, Stroke::new(1.0, Color32::from_gray(60)));
+                            ui.painter().hline(
+                                rect.x_range(),
+                                y,
+                                Stroke::new(1.0, Color32::from_gray(60)),
+                            );
                             ui.allocate_space(Vec2::new(0.0, 0.0));
                         });
                     }
@@ -182,6 +295,10 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
             }
         });
 
+    if let Some(status) = ui_import_window(ui.ctx(), conn, &mut state.import) {
+        state.status = status;
+    }
+
     // Create snapshot window
     if state.show_create {
         let mut open = true;
@@ -189,39 +306,52 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
             .id(egui::Id::new("n002_add_snapshot"))
             .open(&mut open)
             .show(ui.ctx(), |ui| {
-            details_form(ui, conn, &mut state.project_picker, &mut state.create_details);
-            ui.add_space(6.0);
-            ui.horizontal(|ui| {
-                if ui.button("Save").clicked() {
-                    let d = &state.create_details;
-                    if d.name.trim().is_empty() || d.project_id <= 0 {
-                        state.status = "Name and project required".to_string();
-                    } else {
-                        let comment_opt = if d.comment.trim().is_empty() { None } else { Some(d.comment.trim()) };
-                        match repo::create(
-                            conn,
-                            d.name.trim(),
-                            d.project_id,
-                            comment_opt,
-                            d.files_count,
-                            d.dirs_count,
-                            d.files_size_bytes,
-                            d.loc_count,
-                            d.scanned_at.trim(),
-                        ) {
-                            Ok(id) => {
-                                state.status = format!("Snapshot created (ID={})", id);
-                                state.show_create = false;
-                                state.create_details = SnapshotDetailsState::default();
+                details_form(
+                    ui,
+                    conn,
+                    &mut state.project_picker,
+                    &mut state.create_details,
+                );
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        let d = &state.create_details;
+                        if d.name.trim().is_empty() || d.project_id <= 0 {
+                            state.status = "Name and project required".to_string();
+                        } else {
+                            let comment_opt = if d.comment.trim().is_empty() {
+                                None
+                            } else {
+                                Some(d.comment.trim())
+                            };
+                            match repo::create(
+                                conn,
+                                d.name.trim(),
+                                d.project_id,
+                                comment_opt,
+                                d.files_count,
+                                d.dirs_count,
+                                d.files_size_bytes,
+                                d.loc_count,
+                                d.scanned_at.trim(),
+                            ) {
+                                Ok(id) => {
+                                    state.status = format!("Snapshot created (ID={})", id);
+                                    state.show_create = false;
+                                    state.create_details = SnapshotDetailsState::default();
+                                }
+                                Err(e) => state.status = format!("Error creating: {}", e),
                             }
-                            Err(e) => state.status = format!("Error creating: {}", e),
                         }
                     }
-                }
-                if ui.button("Cancel").clicked() { state.show_create = false; }
+                    if ui.button("Cancel").clicked() {
+                        state.show_create = false;
+                    }
+                });
             });
-        });
-        if !open { state.show_create = false; }
+        if !open {
+            state.show_create = false;
+        }
     }
 
     // View snapshot window
@@ -231,22 +361,61 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
             .id(egui::Id::new("n002_view_snapshot"))
             .open(&mut open)
             .show(ui.ctx(), |ui| {
-            let pname = project_names
-                .get(&state.view_details.project_id)
-                .map(|s| s.as_str())
-                .unwrap_or("<unknown>");
-            ui.label(format!("Name: {}", state.view_details.name));
-            ui.label(format!("Project: {} (ID: {})", pname, state.view_details.project_id));
-            ui.label(format!("Comment: {}", state.view_details.comment));
-            ui.label(format!("Files: {}", state.view_details.files_count));
-            ui.label(format!("Dirs: {}", state.view_details.dirs_count));
-            ui.label(format!("Size(bytes): {}", state.view_details.files_size_bytes));
-            ui.label(format!("LOC: {}", state.view_details.loc_count));
-            ui.label(format!("Scanned At: {}", state.view_details.scanned_at));
-            ui.add_space(6.0);
-            if ui.button("Close").clicked() { state.show_view = false; }
-        });
-        if !open { state.show_view = false; }
+                let pname = project_names
+                    .get(&state.view_details.project_id)
+                    .map(|s| s.as_str())
+                    .unwrap_or("<unknown>");
+                ui.label(format!("Name: {}", state.view_details.name));
+                ui.label(format!(
+                    "Project: {} (ID: {})",
+                    pname, state.view_details.project_id
+                ));
+                ui.label(format!("Comment: {}", state.view_details.comment));
+                ui.label(format!("Files: {}", state.view_details.files_count));
+                ui.label(format!("Dirs: {}", state.view_details.dirs_count));
+                ui.label(format!(
+                    "Size(bytes): {}",
+                    state.view_details.files_size_bytes
+                ));
+                ui.label(format!("LOC: {}", state.view_details.loc_count));
+                ui.label(format!("Scanned At: {}", state.view_details.scanned_at));
+                if !state.view_languages.is_empty() {
+                    ui.add_space(6.0);
+                    ui.strong("Languages");
+                    share_bar(ui, &state.view_languages, 300.0);
+                    let total_loc: i64 = state.view_languages.iter().map(|l| l.loc).sum();
+                    egui::Grid::new("n002_view_languages")
+                        .num_columns(4)
+                        .show(ui, |ui| {
+                            for l in &state.view_languages {
+                                ui.horizontal(|ui| legend_entry(ui, &l.language));
+                                ui.label(format!("{} files", l.files));
+                                ui.label(format!("{} LOC", l.loc));
+                                ui.label(format!("{:.1}%", share(l.loc, total_loc) * 100.0));
+                                ui.end_row();
+                            }
+                        });
+                }
+                if !state.view_gates.is_empty() {
+                    ui.add_space(6.0);
+                    ui.strong("Quality gates");
+                    for result in &state.view_gates {
+                        let color = if result.passed {
+                            egui::Color32::LIGHT_GREEN
+                        } else {
+                            egui::Color32::LIGHT_RED
+                        };
+                        ui.colored_label(color, result_line(result));
+                    }
+                }
+                ui.add_space(6.0);
+                if ui.button("Close").clicked() {
+                    state.show_view = false;
+                }
+            });
+        if !open {
+            state.show_view = false;
+        }
     }
 
     // Edit snapshot window
@@ -256,41 +425,53 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
             .id(egui::Id::new("n002_edit_snapshot"))
             .open(&mut open)
             .show(ui.ctx(), |ui| {
-            details_form(ui, conn, &mut state.project_picker, &mut state.edit_details);
-            ui.add_space(6.0);
-            ui.horizontal(|ui| {
-                if ui.button("Save").clicked() {
-                    if let Some(id) = state.editing_id {
-                        let d = &state.edit_details;
-                        if d.name.trim().is_empty() || d.project_id <= 0 {
-                            state.status = "Name and project required".to_string();
-                        } else {
-                            let updated = Snapshot {
-                                id,
-                                name: d.name.trim().to_string(),
-                                project_id: d.project_id,
-                                comment: if d.comment.trim().is_empty() { None } else { Some(d.comment.trim().to_string()) },
-                                files_count: d.files_count,
-                                dirs_count: d.dirs_count,
-                                files_size_bytes: d.files_size_bytes,
-                                loc_count: d.loc_count,
-                                scanned_at: d.scanned_at.trim().to_string(),
-                            };
-                            match repo::update(conn, &updated) {
-                                Ok(_) => {
-                                    state.status = "Snapshot updated".to_string();
-                                    state.show_edit = false;
-                                    state.editing_id = None;
+                details_form(ui, conn, &mut state.project_picker, &mut state.edit_details);
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        if let Some(id) = state.editing_id {
+                            let d = &state.edit_details;
+                            if d.name.trim().is_empty() || d.project_id <= 0 {
+                                state.status = "Name and project required".to_string();
+                            } else {
+                                let updated = Snapshot {
+                                    id,
+                                    name: d.name.trim().to_string(),
+                                    project_id: d.project_id,
+                                    comment: if d.comment.trim().is_empty() {
+                                        None
+                                    } else {
+                                        Some(d.comment.trim().to_string())
+                                    },
+                                    files_count: d.files_count,
+                                    dirs_count: d.dirs_count,
+                                    files_size_bytes: d.files_size_bytes,
+                                    loc_count: d.loc_count,
+                                    scanned_at: d.scanned_at.trim().to_string(),
+                                    // Not editable; repo::update leaves the stored value untouched
+                                    git_revision: None,
+                                };
+                                match repo::update(conn, &updated) {
+                                    Ok(_) => {
+                                        state.status = "Snapshot updated".to_string();
+                                        state.show_edit = false;
+                                        state.editing_id = None;
+                                    }
+                                    Err(e) => state.status = format!("Error updating: {}", e),
                                 }
-                                Err(e) => state.status = format!("Error updating: {}", e),
                             }
                         }
                     }
-                }
-                if ui.button("Cancel").clicked() { state.show_edit = false; state.editing_id = None; }
+                    if ui.button("Cancel").clicked() {
+                        state.show_edit = false;
+                        state.editing_id = None;
+                    }
+                });
             });
-        });
-        if !open { state.show_edit = false; state.editing_id = None; }
+        if !open {
+            state.show_edit = false;
+            state.editing_id = None;
+        }
     }
 
     if !state.status.is_empty() {
@@ -298,3 +479,28 @@ pub fn ui_snapshots_list(ui: &mut egui::Ui, conn: &Connection, state: &mut Snaps
         ui.colored_label(egui::Color32::LIGHT_RED, &state.status);
     }
 }
+
+/// Context-menu entry pinning the snapshot as its project's baseline, or unpinning it
+fn baseline_menu(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    snapshot: &Snapshot,
+    is_baseline: bool,
+) -> Option<String> {
+    let (label, baseline_id) = if is_baseline {
+        ("Clear project baseline", None)
+    } else {
+        ("Set as project baseline", Some(snapshot.id))
+    };
+    if !ui.button(label).clicked() {
+        return None;
+    }
+    ui.close_menu();
+    Some(
+        match project_repo::set_baseline(conn, snapshot.project_id, baseline_id) {
+            Ok(()) if is_baseline => "Project baseline cleared".to_string(),
+            Ok(()) => format!("Baseline set to '{}'", snapshot.name),
+            Err(e) => format!("Error setting baseline: {}", e),
+        },
+    )
+}
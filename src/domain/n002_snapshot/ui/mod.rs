@@ -3,3 +3,4 @@ pub mod list;
 
 pub mod picker;
 
+pub mod baseline;
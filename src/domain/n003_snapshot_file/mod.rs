@@ -1,4 +1,4 @@
 pub mod model;
 pub mod repository;
+pub mod source;
 pub mod ui;
-
@@ -77,6 +77,15 @@ pub fn create(
     Ok(conn.last_insert_rowid())
 }
 
+/// Largest row id of a snapshot's files; a rescan re-inserts them with larger ids
+pub fn max_id(conn: &Connection, snapshot_id: i64) -> SqlResult<Option<i64>> {
+    conn.query_row(
+        "SELECT MAX(id) FROM n003_snapshot_file WHERE snapshot_id = ?1",
+        [snapshot_id],
+        |row| row.get(0),
+    )
+}
+
 pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<SnapshotFile>> {
     let mut stmt = conn.prepare(
         "SELECT id, snapshot_id, parent_id, name, path, size_bytes, is_directory,
@@ -147,3 +156,30 @@ pub fn sum_file_sizes(conn: &Connection, snapshot_id: i64) -> SqlResult<i64> {
 
     Ok(total_size)
 }
+
+pub fn get_by_id(conn: &Connection, id: i64) -> SqlResult<SnapshotFile> {
+    conn.query_row(
+        "SELECT id, snapshot_id, parent_id, name, path, size_bytes, is_directory,
+         file_extension, crate_layer, artifact_type, artifact_id, artifact_name, role
+         FROM n003_snapshot_file
+         WHERE id = ?1",
+        [id],
+        |row| {
+            Ok(SnapshotFile::new(
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, Option<i64>>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, i64>(5)?,
+                row.get::<_, i64>(6)? != 0,
+                row.get::<_, Option<String>>(7)?,
+                row.get::<_, Option<String>>(8)?,
+                row.get::<_, Option<String>>(9)?,
+                row.get::<_, Option<String>>(10)?,
+                row.get::<_, Option<String>>(11)?,
+                row.get::<_, Option<String>>(12)?,
+            ))
+        },
+    )
+}
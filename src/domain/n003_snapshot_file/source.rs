@@ -0,0 +1,89 @@
+use rusqlite::Connection;
+use std::path::Path;
+
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n003_snapshot_file::model::SnapshotFile;
+use crate::domain::n006_file_content::repository as content_repo;
+use crate::shared::git;
+
+/// Where the bytes of a snapshot file were read from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContentOrigin {
+    Stored,
+    GitBlob(String),
+    WorkingTree,
+}
+
+impl ContentOrigin {
+    pub fn label(&self) -> String {
+        match self {
+            ContentOrigin::Stored => "stored content".to_string(),
+            ContentOrigin::GitBlob(rev) => {
+                format!("git {}", rev.chars().take(8).collect::<String>())
+            }
+            ContentOrigin::WorkingTree => "working tree".to_string(),
+        }
+    }
+}
+
+pub struct LoadedContent {
+    pub bytes: Vec<u8>,
+    pub origin: ContentOrigin,
+    /// Why a preferred source was skipped, if any
+    pub note: Option<String>,
+}
+
+/// Content of a file as it was in its snapshot.
+///
+/// Order: stored content, then the git blob at the snapshot revision, then
+/// (only if `allow_working_tree`) the current project working tree.
+pub fn load_file_bytes(
+    conn: &Connection,
+    file: &SnapshotFile,
+    allow_working_tree: bool,
+) -> Result<LoadedContent, String> {
+    if let Some(text) = content_repo::get_content(conn, file.id)
+        .map_err(|e| format!("Error loading content: {}", e))?
+    {
+        return Ok(LoadedContent {
+            bytes: text.into_bytes(),
+            origin: ContentOrigin::Stored,
+            note: None,
+        });
+    }
+
+    let snapshot = snapshot_repo::get_by_id(conn, file.snapshot_id)
+        .map_err(|e| format!("Error loading snapshot: {}", e))?;
+    let root = project_repo::get_by_id(conn, snapshot.project_id)
+        .map_err(|e| format!("Error loading project: {}", e))?
+        .root_path;
+
+    let mut note = None;
+    if let Some(revision) = &snapshot.git_revision {
+        match git::show_blob(Path::new(&root), revision, &file.path) {
+            Ok(bytes) => {
+                return Ok(LoadedContent {
+                    bytes,
+                    origin: ContentOrigin::GitBlob(revision.clone()),
+                    note: None,
+                })
+            }
+            Err(e) => note = Some(format!("Blob not available: {}", e)),
+        }
+    }
+
+    if !allow_working_tree {
+        return Err(
+            note.unwrap_or_else(|| "No stored content or git revision for this file".to_string())
+        );
+    }
+
+    let bytes = std::fs::read(Path::new(&root).join(&file.path))
+        .map_err(|e| format!("Cannot read file: {}", e))?;
+    Ok(LoadedContent {
+        bytes,
+        origin: ContentOrigin::WorkingTree,
+        note,
+    })
+}
@@ -1,16 +1,25 @@
 use eframe::egui;
 use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
 
 use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::baseline::{
+    delta_label, new_marker, removed_marker, ui_baseline_toggle,
+};
 use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
 use crate::domain::n003_snapshot_file::model::SnapshotFile;
 use crate::domain::n003_snapshot_file::repository as repo;
+use crate::domain::n014_file_coverage::repository as coverage_repo;
+use crate::domain::n014_file_coverage::ui::coverage_label;
+use crate::domain::n017_file_language::repository as language_repo;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum SortColumn {
     Name,
     Path,
     Size,
+    Loc,
+    Coverage,
     Extension,
     CrateLayer,
     ArtifactType,
@@ -32,6 +41,12 @@ pub struct ListState {
     pub snapshot_picker: SnapshotPickerState,
     pub status: String,
     pub filter: String,
+    /// Row highlighted in the table (last file opened in the preview)
+    pub selected_file_id: Option<i64>,
+    /// Show deltas against the project baseline
+    pub compare_baseline: bool,
+    /// Comparison with the baseline, kept until either snapshot changes or is rescanned
+    baseline: Option<(BaselineKey, BaselineFiles)>,
     sort_column: Option<SortColumn>,
     sort_direction: SortDirection,
 }
@@ -42,13 +57,87 @@ impl Default for SortDirection {
     }
 }
 
+/// (snapshot id, largest file row id) of the current snapshot and of the baseline.
+/// A rescan re-inserts the rows with new ids and so invalidates the comparison.
+type BaselineKey = ((i64, Option<i64>), (i64, Option<i64>));
+
+/// Size and LOC change of a file since the baseline
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FileDelta {
+    size: i64,
+    /// None unless both snapshots counted the file's lines
+    loc: Option<i64>,
+}
+
+/// Current files compared with the pinned baseline by path
+#[derive(Debug, Default)]
+struct BaselineFiles {
+    /// Change by path; current files missing from the map are new
+    deltas: HashMap<String, FileDelta>,
+    /// Baseline files missing from the current snapshot, sorted by path
+    removed: Vec<SnapshotFile>,
+}
+
+/// Match current files to baseline files by path; `*_loc` map file ids to non-blank lines
+fn compare_files(
+    current: &[SnapshotFile],
+    current_loc: &HashMap<i64, i64>,
+    baseline: &[SnapshotFile],
+    baseline_loc: &HashMap<i64, i64>,
+) -> BaselineFiles {
+    let by_path: HashMap<&str, &SnapshotFile> =
+        baseline.iter().map(|f| (f.path.as_str(), f)).collect();
+    let deltas = current
+        .iter()
+        .filter_map(|file| {
+            let base = by_path.get(file.path.as_str())?;
+            let loc = current_loc
+                .get(&file.id)
+                .zip(baseline_loc.get(&base.id))
+                .map(|(a, b)| a - b);
+            Some((
+                file.path.clone(),
+                FileDelta {
+                    size: file.size_bytes - base.size_bytes,
+                    loc,
+                },
+            ))
+        })
+        .collect();
+    let current_paths: HashSet<&str> = current.iter().map(|f| f.path.as_str()).collect();
+    let mut removed: Vec<SnapshotFile> = baseline
+        .iter()
+        .filter(|f| !current_paths.contains(f.path.as_str()))
+        .cloned()
+        .collect();
+    removed.sort_by(|a, b| a.path.cmp(&b.path));
+    BaselineFiles { deltas, removed }
+}
+
+fn load_baseline(
+    conn: &Connection,
+    baseline_id: i64,
+    current: &[SnapshotFile],
+    current_loc: &HashMap<i64, i64>,
+) -> rusqlite::Result<BaselineFiles> {
+    let files = repo::list_by_snapshot(conn, baseline_id)?;
+    let loc = language_repo::list_by_snapshot(conn, baseline_id)?
+        .into_iter()
+        .map(|l| (l.file_id, l.loc))
+        .collect();
+    Ok(compare_files(current, current_loc, &files, &loc))
+}
+
 impl ListState {
     pub fn open_picker(&mut self) {
         self.snapshot_picker.open();
     }
 }
 
-pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
+/// Renders the Snapshot Files tab. Returns the file id when a file row is clicked.
+pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) -> Option<i64> {
+    let mut opened = None;
+
     // Snapshot picker window
     if let Some(selected_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
         state.snapshot_id = Some(selected_id);
@@ -60,6 +149,7 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
     }
 
     // Selection controls
+    let mut baseline_snapshot = None;
     ui.horizontal(|ui| {
         ui.label("Selected Snapshot:");
         if state.snapshot_id.is_some() {
@@ -71,6 +161,12 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
         if ui.button("Select...").clicked() {
             state.open_picker();
         }
+
+        if let Some(snapshot_id) = state.snapshot_id {
+            ui.separator();
+            baseline_snapshot =
+                ui_baseline_toggle(ui, conn, snapshot_id, &mut state.compare_baseline);
+        }
     });
 
     ui.add_space(8.0);
@@ -90,35 +186,59 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
     if let Some(snapshot_id) = state.snapshot_id {
         match repo::list_by_snapshot(conn, snapshot_id) {
             Ok(mut files) => {
+                // Line rate per file id (empty without imported coverage)
+                let coverage: HashMap<i64, f64> =
+                    coverage_repo::list_by_snapshot(conn, snapshot_id)
+                        .map(|rows| {
+                            rows.into_iter()
+                                .filter_map(|c| Some((c.file_id, c.line_rate()?)))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                // Non-blank lines per file id
+                let loc: HashMap<i64, i64> = language_repo::list_by_snapshot(conn, snapshot_id)
+                    .map(|rows| rows.into_iter().map(|l| (l.file_id, l.loc)).collect())
+                    .unwrap_or_default();
+                // Baseline comparison, recomputed only when a snapshot changes or is rescanned
+                let key = baseline_snapshot.map(|b| {
+                    let current = files.iter().map(|f| f.id).max();
+                    (
+                        (snapshot_id, current),
+                        (b.id, repo::max_id(conn, b.id).ok().flatten()),
+                    )
+                });
+                let stale =
+                    key.filter(|k| state.baseline.as_ref().map(|(cached, _)| cached) != Some(k));
+                if let Some(key @ (_, (baseline_id, _))) = stale {
+                    state.baseline = match load_baseline(conn, baseline_id, &files, &loc) {
+                        Ok(baseline) => Some((key, baseline)),
+                        Err(e) => {
+                            ui.colored_label(
+                                egui::Color32::LIGHT_RED,
+                                format!("Error loading baseline: {}", e),
+                            );
+                            None
+                        }
+                    };
+                }
+                // Taken for the frame: the table borrows the state mutably
+                let cached = state.baseline.take();
+                let baseline = cached.as_ref().filter(|_| key.is_some()).map(|(_, b)| b);
+
                 if files.is_empty() {
                     ui.label(egui::RichText::new("No files found. Run scan to populate.").weak());
                 } else {
                     // Apply filter
-                    if !state.filter.is_empty() {
-                        let filter_lower = state.filter.to_lowercase();
-                        files.retain(|f| {
-                            f.name.to_lowercase().contains(&filter_lower)
-                                || f.path.to_lowercase().contains(&filter_lower)
-                                || f.file_extension
-                                    .as_ref()
-                                    .map_or(false, |e| e.to_lowercase().contains(&filter_lower))
-                                || f.crate_layer
-                                    .as_ref()
-                                    .map_or(false, |c| c.to_lowercase().contains(&filter_lower))
-                                || f.artifact_type
-                                    .as_ref()
-                                    .map_or(false, |a| a.to_lowercase().contains(&filter_lower))
-                                || f.artifact_id
-                                    .as_ref()
-                                    .map_or(false, |a| a.to_lowercase().contains(&filter_lower))
-                                || f.artifact_name
-                                    .as_ref()
-                                    .map_or(false, |a| a.to_lowercase().contains(&filter_lower))
-                                || f.role
-                                    .as_ref()
-                                    .map_or(false, |r| r.to_lowercase().contains(&filter_lower))
-                        });
-                    }
+                    let filter_lower = state.filter.to_lowercase();
+                    files.retain(|f| matches_filter(f, &filter_lower));
+                    let removed: Vec<&SnapshotFile> = baseline
+                        .map(|b| {
+                            b.removed
+                                .iter()
+                                .filter(|f| matches_filter(f, &filter_lower))
+                                .collect()
+                        })
+                        .unwrap_or_default();
 
                     // Apply sorting
                     if let Some(sort_col) = state.sort_column {
@@ -127,6 +247,14 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
                                 SortColumn::Name => a.name.cmp(&b.name),
                                 SortColumn::Path => a.path.cmp(&b.path),
                                 SortColumn::Size => a.size_bytes.cmp(&b.size_bytes),
+                                SortColumn::Loc => loc
+                                    .get(&a.id)
+                                    .unwrap_or(&-1)
+                                    .cmp(loc.get(&b.id).unwrap_or(&-1)),
+                                SortColumn::Coverage => coverage
+                                    .get(&a.id)
+                                    .unwrap_or(&-1.0)
+                                    .total_cmp(coverage.get(&b.id).unwrap_or(&-1.0)),
                                 SortColumn::Extension => a
                                     .file_extension
                                     .as_ref()
@@ -183,15 +311,21 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
                         files.len(),
                         files.len()
                     ));
+                    if baseline.is_some() {
+                        ui.label(format!("Removed since baseline: {}", removed.len()));
+                    }
                     ui.add_space(6.0);
 
                     // Table with scrolling
                     egui::ScrollArea::vertical()
                         .auto_shrink([false; 2])
                         .show(ui, |ui| {
-                            render_table(ui, &files, state);
+                            opened = render_table(
+                                ui, &files, &coverage, &loc, baseline, &removed, state,
+                            );
                         });
                 }
+                state.baseline = cached;
             }
             Err(e) => {
                 ui.colored_label(
@@ -208,19 +342,34 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
         ui.add_space(12.0);
         ui.colored_label(egui::Color32::LIGHT_BLUE, &state.status);
     }
+
+    opened
 }
 
-fn render_table(ui: &mut egui::Ui, files: &[SnapshotFile], state: &mut ListState) {
+fn render_table(
+    ui: &mut egui::Ui,
+    files: &[SnapshotFile],
+    coverage: &HashMap<i64, f64>,
+    loc: &HashMap<i64, i64>,
+    baseline: Option<&BaselineFiles>,
+    removed: &[&SnapshotFile],
+    state: &mut ListState,
+) -> Option<i64> {
     use egui_extras::{Column, TableBuilder};
 
+    let mut opened = None;
+
     TableBuilder::new(ui)
         .striped(true)
+        .sense(egui::Sense::click())
         .resizable(true)
         .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
         .column(Column::auto().at_least(40.0)) // Icon
         .column(Column::auto().at_least(150.0)) // Name
         .column(Column::auto().at_least(250.0)) // Path
         .column(Column::auto().at_least(80.0)) // Size
+        .column(Column::auto().at_least(60.0)) // LOC
+        .column(Column::auto().at_least(70.0)) // Coverage
         .column(Column::auto().at_least(80.0)) // Extension
         .column(Column::auto().at_least(100.0)) // Crate Layer
         .column(Column::auto().at_least(100.0)) // Artifact Type
@@ -248,6 +397,18 @@ fn render_table(ui: &mut egui::Ui, files: &[SnapshotFile], state: &mut ListState
                 }
             });
 
+            header.col(|ui| {
+                if sortable_header(ui, "LOC", state, SortColumn::Loc) {
+                    toggle_sort(state, SortColumn::Loc);
+                }
+            });
+
+            header.col(|ui| {
+                if sortable_header(ui, "Coverage", state, SortColumn::Coverage) {
+                    toggle_sort(state, SortColumn::Coverage);
+                }
+            });
+
             header.col(|ui| {
                 if sortable_header(ui, "Ext", state, SortColumn::Extension) {
                     toggle_sort(state, SortColumn::Extension);
@@ -286,7 +447,10 @@ fn render_table(ui: &mut egui::Ui, files: &[SnapshotFile], state: &mut ListState
         })
         .body(|mut body| {
             for file in files {
+                let delta = baseline.and_then(|b| b.deltas.get(&file.path));
                 body.row(18.0, |mut row| {
+                    row.set_selected(state.selected_file_id == Some(file.id));
+
                     // Icon
                     row.col(|ui| {
                         let icon = if file.is_directory { "📁" } else { "📄" };
@@ -296,6 +460,9 @@ fn render_table(ui: &mut egui::Ui, files: &[SnapshotFile], state: &mut ListState
                     // Name
                     row.col(|ui| {
                         ui.label(&file.name);
+                        if baseline.is_some() && delta.is_none() {
+                            new_marker(ui);
+                        }
                     });
 
                     // Path
@@ -309,6 +476,26 @@ fn render_table(ui: &mut egui::Ui, files: &[SnapshotFile], state: &mut ListState
                             ui.label("");
                         } else {
                             ui.label(format_size(file.size_bytes));
+                            if let Some(delta) = delta {
+                                delta_label(ui, delta.size, format_size);
+                            }
+                        }
+                    });
+
+                    // LOC
+                    row.col(|ui| {
+                        if let Some(&lines) = loc.get(&file.id) {
+                            ui.label(lines.to_string());
+                            if let Some(lines) = delta.and_then(|d| d.loc) {
+                                delta_label(ui, lines, |n| n.to_string());
+                            }
+                        }
+                    });
+
+                    // Coverage
+                    row.col(|ui| {
+                        if !file.is_directory {
+                            coverage_label(ui, coverage.get(&file.id).copied());
                         }
                     });
 
@@ -341,9 +528,42 @@ fn render_table(ui: &mut egui::Ui, files: &[SnapshotFile], state: &mut ListState
                     row.col(|ui| {
                         ui.label(file.role.as_deref().unwrap_or(""));
                     });
+
+                    if row.response().clicked() && !file.is_directory {
+                        state.selected_file_id = Some(file.id);
+                        opened = Some(file.id);
+                    }
+                });
+            }
+
+            // Baseline files gone from this snapshot, greyed out after the current ones
+            for file in removed {
+                body.row(18.0, |mut row| {
+                    row.col(|ui| {
+                        ui.label(
+                            egui::RichText::new(if file.is_directory { "📁" } else { "📄" }).weak(),
+                        );
+                    });
+                    row.col(|ui| {
+                        ui.label(egui::RichText::new(&file.name).weak());
+                        removed_marker(ui);
+                    });
+                    row.col(|ui| {
+                        ui.label(egui::RichText::new(&file.path).weak());
+                    });
+                    row.col(|ui| {
+                        if !file.is_directory {
+                            ui.label(egui::RichText::new(format_size(file.size_bytes)).weak());
+                        }
+                    });
+                    for _ in 0..8 {
+                        row.col(|_ui| {});
+                    }
                 });
             }
         });
+
+    opened
 }
 
 fn sortable_header(ui: &mut egui::Ui, text: &str, state: &ListState, column: SortColumn) -> bool {
@@ -387,3 +607,79 @@ fn format_size(bytes: i64) -> String {
         format!("{} B", bytes)
     }
 }
+
+/// Case-insensitive match of the lowercased filter against name, path and classification
+fn matches_filter(f: &SnapshotFile, filter_lower: &str) -> bool {
+    filter_lower.is_empty()
+        || f.name.to_lowercase().contains(filter_lower)
+        || f.path.to_lowercase().contains(filter_lower)
+        || [
+            &f.file_extension,
+            &f.crate_layer,
+            &f.artifact_type,
+            &f.artifact_id,
+            &f.artifact_name,
+            &f.role,
+        ]
+        .into_iter()
+        .any(|v| {
+            v.as_ref()
+                .is_some_and(|v| v.to_lowercase().contains(filter_lower))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(id: i64, path: &str, size: i64) -> SnapshotFile {
+        let name = path.rsplit('/').next().unwrap_or(path).to_string();
+        SnapshotFile::new(
+            id,
+            0,
+            None,
+            name,
+            path.into(),
+            size,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn compares_files_by_path() {
+        let baseline = [
+            file(1, "src/lib.rs", 100),
+            file(2, "src/old.rs", 10),
+            file(3, "README.md", 5),
+        ];
+        let current = [
+            file(11, "src/lib.rs", 120),
+            file(12, "src/new.rs", 30),
+            file(13, "README.md", 5),
+        ];
+        let baseline_loc = HashMap::from([(1, 40), (3, 2)]);
+        let current_loc = HashMap::from([(11, 35), (12, 9)]);
+
+        let compared = compare_files(&current, &current_loc, &baseline, &baseline_loc);
+        assert_eq!(
+            compared.deltas["src/lib.rs"],
+            FileDelta {
+                size: 20,
+                loc: Some(-5)
+            }
+        );
+        assert_eq!(
+            compared.deltas["README.md"],
+            FileDelta { size: 0, loc: None }
+        );
+        assert!(!compared.deltas.contains_key("src/new.rs"));
+        let removed: Vec<&str> = compared.removed.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(removed, ["src/old.rs"]);
+    }
+}
@@ -0,0 +1,318 @@
+use eframe::egui;
+use egui::text::LayoutJob;
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+use crate::domain::n003_snapshot_file::repository as repo;
+use crate::domain::n003_snapshot_file::source::load_file_bytes;
+use crate::domain::n014_file_coverage::repository as coverage_repo;
+use crate::shared::highlight::{append_line, line_states, HighlightState, Language};
+use crate::shared::text::is_binary;
+
+/// Lines rendered per page for text files
+const PAGE_LINES: usize = 5000;
+/// Bytes per row and rows per page in the hex view
+const HEX_ROW_BYTES: usize = 16;
+const PAGE_HEX_ROWS: usize = 4096;
+/// Files above this size are truncated when read from disk or git
+const MAX_PREVIEW_BYTES: usize = 32 * 1024 * 1024;
+
+#[derive(Default)]
+pub enum PreviewContent {
+    #[default]
+    Empty,
+    Text(Vec<String>),
+    Binary(Vec<u8>),
+}
+
+#[derive(Default)]
+pub struct FilePreviewState {
+    pub file_id: Option<i64>,
+    pub path: String,
+    pub source: String,
+    pub content: PreviewContent,
+    pub language: Option<Language>,
+    pub truncated: bool,
+    /// 1-based line to highlight and scroll to
+    pub target_line: Option<usize>,
+    pub page: usize,
+    scroll_pending: bool,
+    /// Highlighting state at the start of each line of the current page
+    page_states: Vec<HighlightState>,
+    /// line -> execution count from imported coverage (empty without coverage)
+    pub line_hits: HashMap<usize, u64>,
+    pub status: String,
+}
+
+impl FilePreviewState {
+    /// Load file content (see `load_file_bytes` for the source order) and focus a line
+    pub fn open(&mut self, conn: &Connection, file_id: i64, line: Option<usize>) {
+        self.file_id = Some(file_id);
+        self.target_line = line;
+        self.scroll_pending = line.is_some();
+        self.content = PreviewContent::Empty;
+        self.truncated = false;
+        self.status.clear();
+
+        let file = match repo::get_by_id(conn, file_id) {
+            Ok(f) => f,
+            Err(e) => {
+                self.status = format!("Error loading file: {}", e);
+                return;
+            }
+        };
+        self.path = file.path.clone();
+        self.line_hits = coverage_repo::get_by_file(conn, file_id)
+            .ok()
+            .flatten()
+            .map(|c| c.hits_by_line())
+            .unwrap_or_default();
+        self.language = Some(Language::from_path(&file.path));
+
+        match load_file_bytes(conn, &file, true) {
+            Ok(loaded) => {
+                self.source = loaded.origin.label();
+                if let Some(note) = loaded.note {
+                    self.status = format!("{}, showing working tree", note);
+                }
+                self.set_bytes(loaded.bytes);
+            }
+            Err(e) => self.status = e,
+        }
+    }
+
+    fn set_bytes(&mut self, mut bytes: Vec<u8>) {
+        if bytes.len() > MAX_PREVIEW_BYTES {
+            bytes.truncate(MAX_PREVIEW_BYTES);
+            self.truncated = true;
+        }
+        if is_binary(&bytes) {
+            self.content = PreviewContent::Binary(bytes);
+            self.set_page(0);
+            return;
+        }
+        match String::from_utf8(bytes) {
+            Ok(text) => self.set_text(&text),
+            // A cut inside a multi-byte char or non-UTF-8 text: show lossily
+            Err(e) => {
+                let text = String::from_utf8_lossy(e.as_bytes()).into_owned();
+                self.set_text(&text);
+            }
+        }
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.content = PreviewContent::Text(text.lines().map(|l| l.to_string()).collect());
+        let page = self
+            .target_line
+            .map(|l| l.saturating_sub(1) / PAGE_LINES)
+            .unwrap_or(0);
+        self.set_page(page);
+    }
+
+    fn page_count(&self) -> usize {
+        let (len, per_page) = match &self.content {
+            PreviewContent::Empty => (0, 1),
+            PreviewContent::Text(lines) => (lines.len(), PAGE_LINES),
+            PreviewContent::Binary(bytes) => (bytes.len().div_ceil(HEX_ROW_BYTES), PAGE_HEX_ROWS),
+        };
+        len.div_ceil(per_page).max(1)
+    }
+
+    fn set_page(&mut self, page: usize) {
+        self.page = page.min(self.page_count() - 1);
+        self.page_states.clear();
+        if let PreviewContent::Text(lines) = &self.content {
+            let start = (self.page * PAGE_LINES).min(lines.len());
+            let end = (start + PAGE_LINES).min(lines.len());
+            // States are computed per page; constructs spanning a page boundary start fresh
+            self.page_states =
+                line_states(self.language.unwrap_or(Language::Plain), &lines[start..end]);
+        }
+    }
+}
+
+pub fn ui_preview(ui: &mut egui::Ui, state: &mut FilePreviewState) {
+    if state.file_id.is_none() {
+        ui.label(
+            egui::RichText::new("Open a file from Snapshot Files or search results to preview it")
+                .weak(),
+        );
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(&state.path).strong());
+        if !state.source.is_empty() {
+            ui.label(egui::RichText::new(format!("({})", state.source)).weak());
+        }
+        match &state.content {
+            PreviewContent::Text(lines) => {
+                ui.label(format!("{} lines", lines.len()));
+            }
+            PreviewContent::Binary(bytes) => {
+                ui.label(format!("binary, {} bytes", bytes.len()));
+            }
+            PreviewContent::Empty => {}
+        }
+        if state.truncated {
+            ui.colored_label(egui::Color32::YELLOW, "truncated");
+        }
+        if !state.line_hits.is_empty() {
+            let hit = state.line_hits.values().filter(|h| **h > 0).count();
+            ui.label(
+                egui::RichText::new(format!("coverage {}/{} lines", hit, state.line_hits.len()))
+                    .weak(),
+            );
+        }
+    });
+
+    let pages = state.page_count();
+    if pages > 1 {
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(state.page > 0, egui::Button::new("◀ Prev"))
+                .clicked()
+            {
+                state.set_page(state.page - 1);
+            }
+            ui.label(format!("Page {} of {}", state.page + 1, pages));
+            if ui
+                .add_enabled(state.page + 1 < pages, egui::Button::new("Next ▶"))
+                .clicked()
+            {
+                state.set_page(state.page + 1);
+            }
+        });
+    }
+
+    if !state.status.is_empty() {
+        ui.colored_label(egui::Color32::LIGHT_RED, &state.status);
+    }
+
+    ui.add_space(6.0);
+
+    match &state.content {
+        PreviewContent::Text(_) => ui_text(ui, state),
+        PreviewContent::Binary(_) => ui_hex(ui, state),
+        PreviewContent::Empty => {}
+    }
+}
+
+fn ui_text(ui: &mut egui::Ui, state: &mut FilePreviewState) {
+    let PreviewContent::Text(lines) = &state.content else {
+        return;
+    };
+    let start = (state.page * PAGE_LINES).min(lines.len());
+    let page_lines = &lines[start..(start + PAGE_LINES).min(lines.len())];
+    let lang = state.language.unwrap_or(Language::Plain);
+
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    let digits = lines.len().max(1).to_string().len();
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let gutter = egui::text::TextFormat {
+        font_id,
+        color: ui.visuals().weak_text_color(),
+        ..Default::default()
+    };
+
+    let mut scroll = egui::ScrollArea::both().auto_shrink([false; 2]);
+    if state.scroll_pending {
+        if let Some(line) = state.target_line {
+            let spacing = ui.spacing().item_spacing.y;
+            let row_in_page = line.saturating_sub(1).saturating_sub(start);
+            let offset = (row_in_page as f32 - 5.0).max(0.0) * (row_height + spacing);
+            scroll = scroll.vertical_scroll_offset(offset);
+        }
+        state.scroll_pending = false;
+    }
+
+    scroll.show_rows(ui, row_height, page_lines.len(), |ui, range| {
+        for idx in range {
+            let line_no = start + idx + 1;
+            let background = if state.target_line == Some(line_no) {
+                egui::Color32::from_rgba_unmultiplied(255, 200, 0, 40)
+            } else {
+                egui::Color32::TRANSPARENT
+            };
+            let mut hl_state = state.page_states.get(idx).copied().unwrap_or_default();
+            let mut job = LayoutJob::default();
+            job.append(
+                &format!("{:>width$}", line_no, width = digits),
+                0.0,
+                gutter.clone(),
+            );
+            // Coverage marker: green executed, red instrumented but never run
+            let mut marker = gutter.clone();
+            let marker_text = match state.line_hits.get(&line_no) {
+                Some(0) => {
+                    marker.color = egui::Color32::LIGHT_RED;
+                    "▌ "
+                }
+                Some(_) => {
+                    marker.color = egui::Color32::LIGHT_GREEN;
+                    "▌ "
+                }
+                None => "  ",
+            };
+            job.append(marker_text, 0.0, marker);
+            append_line(
+                &mut job,
+                ui,
+                lang,
+                &page_lines[idx],
+                &mut hl_state,
+                background,
+            );
+            ui.add(egui::Label::new(job).extend());
+        }
+    });
+}
+
+fn ui_hex(ui: &mut egui::Ui, state: &FilePreviewState) {
+    let PreviewContent::Binary(bytes) = &state.content else {
+        return;
+    };
+    let total_rows = bytes.len().div_ceil(HEX_ROW_BYTES);
+    let first_row = (state.page * PAGE_HEX_ROWS).min(total_rows);
+    let rows = (total_rows - first_row).min(PAGE_HEX_ROWS);
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+
+    egui::ScrollArea::both().auto_shrink([false; 2]).show_rows(
+        ui,
+        row_height,
+        rows,
+        |ui, range| {
+            for row in range {
+                let offset = (first_row + row) * HEX_ROW_BYTES;
+                let chunk = &bytes[offset..(offset + HEX_ROW_BYTES).min(bytes.len())];
+                ui.label(egui::RichText::new(hex_row(offset, chunk)).monospace());
+            }
+        },
+    );
+}
+
+/// `offset  hex bytes  |ascii|` line of a classic hex dump
+fn hex_row(offset: usize, chunk: &[u8]) -> String {
+    let mut hex = String::with_capacity(HEX_ROW_BYTES * 3 + 1);
+    for i in 0..HEX_ROW_BYTES {
+        match chunk.get(i) {
+            Some(b) => hex.push_str(&format!("{:02x} ", b)),
+            None => hex.push_str("   "),
+        }
+        if i == 7 {
+            hex.push(' ');
+        }
+    }
+    let ascii: String = chunk
+        .iter()
+        .map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            }
+        })
+        .collect();
+    format!("{:08x}  {} |{}|", offset, hex, ascii)
+}
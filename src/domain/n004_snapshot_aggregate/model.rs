@@ -8,6 +8,14 @@ pub struct SnapshotAggregate {
     pub description: Option<String>,
     pub file_count: i64,
     pub total_size_bytes: i64,
+    /// Test functions attributed to the aggregate
+    pub test_fns: i64,
+    /// Non-blank lines of test / production Rust code
+    pub test_loc: i64,
+    pub code_loc: i64,
+    /// Public items that need docs / have them
+    pub doc_items: i64,
+    pub doc_documented: i64,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -22,6 +30,11 @@ impl SnapshotAggregate {
         description: Option<String>,
         file_count: i64,
         total_size_bytes: i64,
+        test_fns: i64,
+        test_loc: i64,
+        code_loc: i64,
+        doc_items: i64,
+        doc_documented: i64,
         created_at: String,
         updated_at: String,
     ) -> Self {
@@ -33,8 +46,28 @@ impl SnapshotAggregate {
             description,
             file_count,
             total_size_bytes,
+            test_fns,
+            test_loc,
+            code_loc,
+            doc_items,
+            doc_documented,
             created_at,
             updated_at,
         }
     }
+
+    /// Test LOC per production LOC, None without production code
+    pub fn test_ratio(&self) -> Option<f64> {
+        (self.code_loc > 0).then(|| self.test_loc as f64 / self.code_loc as f64)
+    }
+
+    /// Share of documented public items, None without public items
+    pub fn doc_coverage(&self) -> Option<f64> {
+        (self.doc_items > 0).then(|| self.doc_documented as f64 / self.doc_items as f64)
+    }
+}
+
+/// Aggregate code of a file's artifact id ("n001_" -> "n001")
+pub fn code_for_artifact(artifact_id: &str) -> String {
+    artifact_id.trim_end_matches('_').to_string()
 }
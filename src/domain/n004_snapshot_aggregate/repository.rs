@@ -1,6 +1,7 @@
 use rusqlite::{params, Connection, Result as SqlResult};
 
 use super::model::SnapshotAggregate;
+use crate::shared::db::ensure_column;
 
 pub fn init_table(conn: &Connection) -> SqlResult<()> {
     conn.execute(
@@ -26,6 +27,14 @@ pub fn init_table(conn: &Connection) -> SqlResult<()> {
         [],
     )?;
 
+    // Test inventory columns (added later, hence the migration)
+    ensure_column(conn, "n004_snapshot_aggregate", "test_fns", "INTEGER NOT NULL DEFAULT 0")?;
+    ensure_column(conn, "n004_snapshot_aggregate", "test_loc", "INTEGER NOT NULL DEFAULT 0")?;
+    ensure_column(conn, "n004_snapshot_aggregate", "code_loc", "INTEGER NOT NULL DEFAULT 0")?;
+    // Documentation coverage columns
+    ensure_column(conn, "n004_snapshot_aggregate", "doc_items", "INTEGER NOT NULL DEFAULT 0")?;
+    ensure_column(conn, "n004_snapshot_aggregate", "doc_documented", "INTEGER NOT NULL DEFAULT 0")?;
+
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_snapshot_aggregate_code 
          ON n004_snapshot_aggregate(code)",
@@ -83,6 +92,22 @@ pub fn update(
     Ok(())
 }
 
+pub fn set_test_stats(conn: &Connection, id: i64, test_fns: i64, test_loc: i64, code_loc: i64) -> SqlResult<()> {
+    conn.execute(
+        "UPDATE n004_snapshot_aggregate SET test_fns = ?1, test_loc = ?2, code_loc = ?3 WHERE id = ?4",
+        params![test_fns, test_loc, code_loc, id],
+    )?;
+    Ok(())
+}
+
+pub fn set_doc_stats(conn: &Connection, id: i64, doc_items: i64, doc_documented: i64) -> SqlResult<()> {
+    conn.execute(
+        "UPDATE n004_snapshot_aggregate SET doc_items = ?1, doc_documented = ?2 WHERE id = ?3",
+        params![doc_items, doc_documented, id],
+    )?;
+    Ok(())
+}
+
 pub fn delete(conn: &Connection, id: i64) -> SqlResult<()> {
     conn.execute(
         "DELETE FROM n004_snapshot_aggregate WHERE id = ?1",
@@ -101,7 +126,7 @@ pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()>
 
 pub fn get_by_id(conn: &Connection, id: i64) -> SqlResult<Option<SnapshotAggregate>> {
     let mut stmt = conn.prepare(
-        "SELECT id, snapshot_id, code, name, description, file_count, total_size_bytes, created_at, updated_at
+        "SELECT id, snapshot_id, code, name, description, file_count, total_size_bytes, test_fns, test_loc, code_loc, doc_items, doc_documented, created_at, updated_at
          FROM n004_snapshot_aggregate
          WHERE id = ?1",
     )?;
@@ -115,8 +140,13 @@ pub fn get_by_id(conn: &Connection, id: i64) -> SqlResult<Option<SnapshotAggrega
             row.get::<_, Option<String>>(4)?,
             row.get::<_, i64>(5)?,
             row.get::<_, i64>(6)?,
-            row.get::<_, String>(7)?,
-            row.get::<_, String>(8)?,
+            row.get::<_, i64>(7)?,
+            row.get::<_, i64>(8)?,
+            row.get::<_, i64>(9)?,
+            row.get::<_, i64>(10)?,
+            row.get::<_, i64>(11)?,
+            row.get::<_, String>(12)?,
+            row.get::<_, String>(13)?,
         ))
     })?;
 
@@ -126,9 +156,18 @@ pub fn get_by_id(conn: &Connection, id: i64) -> SqlResult<Option<SnapshotAggrega
     }
 }
 
+/// Largest row id of a snapshot's aggregates; a rescan re-inserts them with larger ids
+pub fn max_id(conn: &Connection, snapshot_id: i64) -> SqlResult<Option<i64>> {
+    conn.query_row(
+        "SELECT MAX(id) FROM n004_snapshot_aggregate WHERE snapshot_id = ?1",
+        [snapshot_id],
+        |row| row.get(0),
+    )
+}
+
 pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<SnapshotAggregate>> {
     let mut stmt = conn.prepare(
-        "SELECT id, snapshot_id, code, name, description, file_count, total_size_bytes, created_at, updated_at
+        "SELECT id, snapshot_id, code, name, description, file_count, total_size_bytes, test_fns, test_loc, code_loc, doc_items, doc_documented, created_at, updated_at
          FROM n004_snapshot_aggregate
          WHERE snapshot_id = ?1
          ORDER BY code",
@@ -143,8 +182,13 @@ pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<Sn
             row.get::<_, Option<String>>(4)?,
             row.get::<_, i64>(5)?,
             row.get::<_, i64>(6)?,
-            row.get::<_, String>(7)?,
-            row.get::<_, String>(8)?,
+            row.get::<_, i64>(7)?,
+            row.get::<_, i64>(8)?,
+            row.get::<_, i64>(9)?,
+            row.get::<_, i64>(10)?,
+            row.get::<_, i64>(11)?,
+            row.get::<_, String>(12)?,
+            row.get::<_, String>(13)?,
         ))
     })?;
 
@@ -1,10 +1,15 @@
 use eframe::egui;
 use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
 
 use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::baseline::{
+    delta_label, new_marker, removed_marker, ui_baseline_toggle,
+};
 use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
 use crate::domain::n004_snapshot_aggregate::model::SnapshotAggregate;
 use crate::domain::n004_snapshot_aggregate::repository as repo;
+use crate::domain::n014_file_coverage::ui::coverage_label;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum SortColumn {
@@ -13,6 +18,11 @@ enum SortColumn {
     Description,
     FileCount,
     TotalSize,
+    Tests,
+    TestLoc,
+    CodeLoc,
+    TestRatio,
+    DocCoverage,
     CreatedAt,
     UpdatedAt,
 }
@@ -30,6 +40,12 @@ pub struct ListState {
     pub snapshot_picker: SnapshotPickerState,
     pub status: String,
     pub filter: String,
+    /// Show only aggregates with production code and no tests
+    pub untested_only: bool,
+    /// Show deltas against the project baseline
+    pub compare_baseline: bool,
+    /// Comparison with the baseline, kept until either snapshot changes or is rescanned
+    baseline: Option<(BaselineKey, BaselineAggregates)>,
     sort_column: Option<SortColumn>,
     sort_direction: SortDirection,
 }
@@ -40,6 +56,57 @@ impl Default for SortDirection {
     }
 }
 
+/// (snapshot id, largest aggregate row id) of the current snapshot and of the baseline.
+/// A rescan re-inserts the rows with new ids and so invalidates the comparison.
+type BaselineKey = ((i64, Option<i64>), (i64, Option<i64>));
+
+/// Change of an aggregate since the baseline
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AggregateDelta {
+    file_count: i64,
+    total_size_bytes: i64,
+    test_loc: i64,
+    code_loc: i64,
+}
+
+/// Current aggregates compared with the pinned baseline by code
+#[derive(Debug, Default)]
+struct BaselineAggregates {
+    /// Change by code; current aggregates missing from the map are new
+    deltas: HashMap<String, AggregateDelta>,
+    /// Baseline aggregates missing from the current snapshot, sorted by code
+    removed: Vec<SnapshotAggregate>,
+}
+
+fn compare_aggregates(
+    current: &[SnapshotAggregate],
+    baseline: &[SnapshotAggregate],
+) -> BaselineAggregates {
+    let by_code: HashMap<&str, &SnapshotAggregate> =
+        baseline.iter().map(|a| (a.code.as_str(), a)).collect();
+    let deltas = current
+        .iter()
+        .filter_map(|a| {
+            let base = by_code.get(a.code.as_str())?;
+            let delta = AggregateDelta {
+                file_count: a.file_count - base.file_count,
+                total_size_bytes: a.total_size_bytes - base.total_size_bytes,
+                test_loc: a.test_loc - base.test_loc,
+                code_loc: a.code_loc - base.code_loc,
+            };
+            Some((a.code.clone(), delta))
+        })
+        .collect();
+    let current_codes: HashSet<&str> = current.iter().map(|a| a.code.as_str()).collect();
+    let mut removed: Vec<SnapshotAggregate> = baseline
+        .iter()
+        .filter(|a| !current_codes.contains(a.code.as_str()))
+        .cloned()
+        .collect();
+    removed.sort_by(|a, b| a.code.cmp(&b.code));
+    BaselineAggregates { deltas, removed }
+}
+
 impl ListState {
     pub fn open_picker(&mut self) {
         self.snapshot_picker.open();
@@ -58,6 +125,7 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
     }
 
     // Selection controls
+    let mut baseline_snapshot = None;
     ui.horizontal(|ui| {
         ui.label("Selected Snapshot:");
         if state.snapshot_id.is_some() {
@@ -69,6 +137,12 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
         if ui.button("Select...").clicked() {
             state.open_picker();
         }
+
+        if let Some(snapshot_id) = state.snapshot_id {
+            ui.separator();
+            baseline_snapshot =
+                ui_baseline_toggle(ui, conn, snapshot_id, &mut state.compare_baseline);
+        }
     });
 
     ui.add_space(8.0);
@@ -80,6 +154,7 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
         if ui.button("Clear").clicked() {
             state.filter.clear();
         }
+        ui.checkbox(&mut state.untested_only, "Without tests only");
     });
 
     ui.add_space(8.0);
@@ -88,21 +163,56 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
     if let Some(snapshot_id) = state.snapshot_id {
         match repo::list_by_snapshot(conn, snapshot_id) {
             Ok(mut aggregates) => {
+                // Baseline comparison, recomputed only when a snapshot changes or is rescanned
+                let key = baseline_snapshot.map(|b| {
+                    let current = aggregates.iter().map(|a| a.id).max();
+                    (
+                        (snapshot_id, current),
+                        (b.id, repo::max_id(conn, b.id).ok().flatten()),
+                    )
+                });
+                let stale =
+                    key.filter(|k| state.baseline.as_ref().map(|(cached, _)| cached) != Some(k));
+                if let Some(key @ (_, (baseline_id, _))) = stale {
+                    state.baseline = match repo::list_by_snapshot(conn, baseline_id) {
+                        Ok(rows) => Some((key, compare_aggregates(&aggregates, &rows))),
+                        Err(e) => {
+                            ui.colored_label(
+                                egui::Color32::LIGHT_RED,
+                                format!("Error loading baseline: {}", e),
+                            );
+                            None
+                        }
+                    };
+                }
+                // Taken for the frame: the table borrows the state mutably
+                let cached = state.baseline.take();
+                let baseline = cached.as_ref().filter(|_| key.is_some()).map(|(_, b)| b);
+
                 if aggregates.is_empty() {
-                    ui.label(egui::RichText::new("No aggregates found. Create some to populate.").weak());
+                    ui.label(
+                        egui::RichText::new("No aggregates found. Create some to populate.").weak(),
+                    );
                 } else {
                     // Apply filter
-                    if !state.filter.is_empty() {
-                        let filter_lower = state.filter.to_lowercase();
-                        aggregates.retain(|a| {
-                            a.code.to_lowercase().contains(&filter_lower)
-                                || a.name.to_lowercase().contains(&filter_lower)
-                                || a.description
-                                    .as_ref()
-                                    .map_or(false, |d| d.to_lowercase().contains(&filter_lower))
-                        });
+                    let filter_lower = state.filter.to_lowercase();
+                    aggregates.retain(|a| matches_filter(a, &filter_lower));
+
+                    let untested = aggregates.iter().filter(|a| is_untested(a)).count();
+                    if state.untested_only {
+                        aggregates.retain(is_untested);
                     }
 
+                    let removed: Vec<&SnapshotAggregate> = baseline
+                        .map(|b| {
+                            b.removed
+                                .iter()
+                                .filter(|a| matches_filter(a, &filter_lower))
+                                .filter(|a| !state.untested_only || is_untested(a))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+
                     // Apply sorting
                     if let Some(sort_col) = state.sort_column {
                         aggregates.sort_by(|a, b| {
@@ -114,11 +224,22 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
                                     .as_ref()
                                     .map(|s| s.as_str())
                                     .unwrap_or("")
-                                    .cmp(
-                                        b.description.as_ref().map(|s| s.as_str()).unwrap_or(""),
-                                    ),
+                                    .cmp(b.description.as_ref().map(|s| s.as_str()).unwrap_or("")),
                                 SortColumn::FileCount => a.file_count.cmp(&b.file_count),
-                                SortColumn::TotalSize => a.total_size_bytes.cmp(&b.total_size_bytes),
+                                SortColumn::TotalSize => {
+                                    a.total_size_bytes.cmp(&b.total_size_bytes)
+                                }
+                                SortColumn::Tests => a.test_fns.cmp(&b.test_fns),
+                                SortColumn::TestLoc => a.test_loc.cmp(&b.test_loc),
+                                SortColumn::CodeLoc => a.code_loc.cmp(&b.code_loc),
+                                SortColumn::TestRatio => a
+                                    .test_ratio()
+                                    .unwrap_or(0.0)
+                                    .total_cmp(&b.test_ratio().unwrap_or(0.0)),
+                                SortColumn::DocCoverage => a
+                                    .doc_coverage()
+                                    .unwrap_or(0.0)
+                                    .total_cmp(&b.doc_coverage().unwrap_or(0.0)),
                                 SortColumn::CreatedAt => a.created_at.cmp(&b.created_at),
                                 SortColumn::UpdatedAt => a.updated_at.cmp(&b.updated_at),
                             };
@@ -131,28 +252,37 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
                     }
 
                     // Show statistics
-                    if let Ok((aggregate_count, total_files, total_size)) = repo::get_stats(conn, snapshot_id) {
+                    if let Ok((aggregate_count, total_files, total_size)) =
+                        repo::get_stats(conn, snapshot_id)
+                    {
                         ui.horizontal(|ui| {
                             ui.label(format!("Aggregates: {}", aggregate_count));
                             ui.label(format!("Total Files: {}", total_files));
                             ui.label(format!("Total Size: {}", format_size(total_size)));
+                            if untested > 0 {
+                                ui.colored_label(
+                                    egui::Color32::LIGHT_RED,
+                                    format!("Without tests: {}", untested),
+                                );
+                            }
                         });
                         ui.add_space(6.0);
                     }
 
-                    ui.label(format!(
-                        "Displaying {} aggregates",
-                        aggregates.len()
-                    ));
+                    ui.label(format!("Displaying {} aggregates", aggregates.len()));
+                    if baseline.is_some() {
+                        ui.label(format!("Removed since baseline: {}", removed.len()));
+                    }
                     ui.add_space(6.0);
 
                     // Table with scrolling
                     egui::ScrollArea::vertical()
                         .auto_shrink([false; 2])
                         .show(ui, |ui| {
-                            render_table(ui, &aggregates, state);
+                            render_table(ui, &aggregates, baseline, &removed, state);
                         });
                 }
+                state.baseline = cached;
             }
             Err(e) => {
                 ui.colored_label(
@@ -171,7 +301,13 @@ pub fn ui_list(ui: &mut egui::Ui, conn: &Connection, state: &mut ListState) {
     }
 }
 
-fn render_table(ui: &mut egui::Ui, aggregates: &[SnapshotAggregate], state: &mut ListState) {
+fn render_table(
+    ui: &mut egui::Ui,
+    aggregates: &[SnapshotAggregate],
+    baseline: Option<&BaselineAggregates>,
+    removed: &[&SnapshotAggregate],
+    state: &mut ListState,
+) {
     use egui_extras::{Column, TableBuilder};
 
     TableBuilder::new(ui)
@@ -183,6 +319,11 @@ fn render_table(ui: &mut egui::Ui, aggregates: &[SnapshotAggregate], state: &mut
         .column(Column::auto().at_least(300.0)) // Description
         .column(Column::auto().at_least(80.0)) // File Count
         .column(Column::auto().at_least(100.0)) // Total Size
+        .column(Column::auto().at_least(60.0)) // Tests
+        .column(Column::auto().at_least(80.0)) // Test LOC
+        .column(Column::auto().at_least(80.0)) // Code LOC
+        .column(Column::auto().at_least(70.0)) // Test ratio
+        .column(Column::auto().at_least(60.0)) // Doc coverage
         .column(Column::auto().at_least(150.0)) // Created At
         .column(Column::auto().at_least(150.0)) // Updated At
         .header(20.0, |mut header| {
@@ -216,6 +357,36 @@ fn render_table(ui: &mut egui::Ui, aggregates: &[SnapshotAggregate], state: &mut
                 }
             });
 
+            header.col(|ui| {
+                if sortable_header(ui, "Tests", state, SortColumn::Tests) {
+                    toggle_sort(state, SortColumn::Tests);
+                }
+            });
+
+            header.col(|ui| {
+                if sortable_header(ui, "Test LOC", state, SortColumn::TestLoc) {
+                    toggle_sort(state, SortColumn::TestLoc);
+                }
+            });
+
+            header.col(|ui| {
+                if sortable_header(ui, "Code LOC", state, SortColumn::CodeLoc) {
+                    toggle_sort(state, SortColumn::CodeLoc);
+                }
+            });
+
+            header.col(|ui| {
+                if sortable_header(ui, "Test/Code", state, SortColumn::TestRatio) {
+                    toggle_sort(state, SortColumn::TestRatio);
+                }
+            });
+
+            header.col(|ui| {
+                if sortable_header(ui, "Docs", state, SortColumn::DocCoverage) {
+                    toggle_sort(state, SortColumn::DocCoverage);
+                }
+            });
+
             header.col(|ui| {
                 if sortable_header(ui, "Created", state, SortColumn::CreatedAt) {
                     toggle_sort(state, SortColumn::CreatedAt);
@@ -230,10 +401,14 @@ fn render_table(ui: &mut egui::Ui, aggregates: &[SnapshotAggregate], state: &mut
         })
         .body(|mut body| {
             for aggregate in aggregates {
+                let delta = baseline.and_then(|b| b.deltas.get(&aggregate.code));
                 body.row(18.0, |mut row| {
                     // Code
                     row.col(|ui| {
                         ui.label(&aggregate.code);
+                        if baseline.is_some() && delta.is_none() {
+                            new_marker(ui);
+                        }
                     });
 
                     // Name
@@ -249,13 +424,55 @@ fn render_table(ui: &mut egui::Ui, aggregates: &[SnapshotAggregate], state: &mut
                     // File Count
                     row.col(|ui| {
                         ui.label(format!("{}", aggregate.file_count));
+                        if let Some(delta) = delta {
+                            delta_label(ui, delta.file_count, |n| n.to_string());
+                        }
                     });
 
                     // Total Size
                     row.col(|ui| {
                         ui.label(format_size(aggregate.total_size_bytes));
+                        if let Some(delta) = delta {
+                            delta_label(ui, delta.total_size_bytes, format_size);
+                        }
+                    });
+
+                    // Tests
+                    row.col(|ui| {
+                        if is_untested(aggregate) {
+                            ui.colored_label(egui::Color32::LIGHT_RED, "0");
+                        } else {
+                            ui.label(format!("{}", aggregate.test_fns));
+                        }
+                    });
+
+                    // Test LOC
+                    row.col(|ui| {
+                        ui.label(format!("{}", aggregate.test_loc));
+                        if let Some(delta) = delta {
+                            delta_label(ui, delta.test_loc, |n| n.to_string());
+                        }
+                    });
+
+                    // Code LOC
+                    row.col(|ui| {
+                        ui.label(format!("{}", aggregate.code_loc));
+                        if let Some(delta) = delta {
+                            delta_label(ui, delta.code_loc, |n| n.to_string());
+                        }
                     });
 
+                    // Test ratio
+                    row.col(|ui| {
+                        match aggregate.test_ratio() {
+                            Some(ratio) => ui.label(format!("{:.2}", ratio)),
+                            None => ui.label(egui::RichText::new("-").weak()),
+                        };
+                    });
+
+                    // Doc coverage
+                    row.col(|ui| coverage_label(ui, aggregate.doc_coverage()));
+
                     // Created At
                     row.col(|ui| {
                         ui.label(format_timestamp(&aggregate.created_at));
@@ -267,9 +484,55 @@ fn render_table(ui: &mut egui::Ui, aggregates: &[SnapshotAggregate], state: &mut
                     });
                 });
             }
+
+            // Baseline aggregates gone from this snapshot, greyed out after the current ones
+            for aggregate in removed {
+                body.row(18.0, |mut row| {
+                    row.col(|ui| {
+                        ui.label(egui::RichText::new(&aggregate.code).weak());
+                        removed_marker(ui);
+                    });
+                    row.col(|ui| {
+                        ui.label(egui::RichText::new(&aggregate.name).weak());
+                    });
+                    row.col(|ui| {
+                        ui.label(
+                            egui::RichText::new(aggregate.description.as_deref().unwrap_or(""))
+                                .weak(),
+                        );
+                    });
+                    row.col(|ui| {
+                        ui.label(egui::RichText::new(aggregate.file_count.to_string()).weak());
+                    });
+                    row.col(|ui| {
+                        ui.label(
+                            egui::RichText::new(format_size(aggregate.total_size_bytes)).weak(),
+                        );
+                    });
+                    for _ in 0..7 {
+                        row.col(|_ui| {});
+                    }
+                });
+            }
         });
 }
 
+/// Case-insensitive match of the lowercased filter against code, name and description
+fn matches_filter(aggregate: &SnapshotAggregate, filter_lower: &str) -> bool {
+    filter_lower.is_empty()
+        || aggregate.code.to_lowercase().contains(filter_lower)
+        || aggregate.name.to_lowercase().contains(filter_lower)
+        || aggregate
+            .description
+            .as_ref()
+            .is_some_and(|d| d.to_lowercase().contains(filter_lower))
+}
+
+/// Aggregate with Rust production code but no test functions
+fn is_untested(aggregate: &SnapshotAggregate) -> bool {
+    aggregate.code_loc > 0 && aggregate.test_fns == 0
+}
+
 fn sortable_header(ui: &mut egui::Ui, text: &str, state: &ListState, column: SortColumn) -> bool {
     let is_sorted = state.sort_column == Some(column);
     let arrow = if is_sorted {
@@ -320,3 +583,47 @@ fn format_timestamp(timestamp: &str) -> String {
         timestamp.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aggregate(id: i64, code: &str, files: i64, code_loc: i64) -> SnapshotAggregate {
+        let size = files * 100;
+        let (name, stamp) = (code.to_string(), String::new());
+        SnapshotAggregate::new(
+            id,
+            0,
+            code.into(),
+            name,
+            None,
+            files,
+            size,
+            0,
+            0,
+            code_loc,
+            0,
+            0,
+            stamp.clone(),
+            stamp,
+        )
+    }
+
+    #[test]
+    fn compares_aggregates_by_code() {
+        let baseline = [aggregate(1, "a001", 3, 50), aggregate(2, "a002", 1, 10)];
+        let current = [aggregate(11, "a001", 4, 45), aggregate(12, "a003", 2, 20)];
+
+        let compared = compare_aggregates(&current, &baseline);
+        let expected = AggregateDelta {
+            file_count: 1,
+            total_size_bytes: 100,
+            test_loc: 0,
+            code_loc: -5,
+        };
+        assert_eq!(compared.deltas["a001"], expected);
+        assert!(!compared.deltas.contains_key("a003"));
+        let removed: Vec<&str> = compared.removed.iter().map(|a| a.code.as_str()).collect();
+        assert_eq!(removed, ["a002"]);
+    }
+}
@@ -0,0 +1,33 @@
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct SnapshotPackage {
+    pub id: i64,
+    pub snapshot_id: i64,
+    pub name: String,
+    pub version: String,
+    pub source: Option<String>,
+    pub checksum: Option<String>,
+    pub license: Option<String>,
+}
+
+impl SnapshotPackage {
+    pub fn new(
+        id: i64,
+        snapshot_id: i64,
+        name: String,
+        version: String,
+        source: Option<String>,
+        checksum: Option<String>,
+        license: Option<String>,
+    ) -> Self {
+        Self {
+            id,
+            snapshot_id,
+            name,
+            version,
+            source,
+            checksum,
+            license,
+        }
+    }
+}
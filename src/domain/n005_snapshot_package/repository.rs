@@ -0,0 +1,79 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::SnapshotPackage;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n005_snapshot_package (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            snapshot_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            version TEXT NOT NULL,
+            source TEXT,
+            checksum TEXT,
+            license TEXT,
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_snapshot_package_snapshot_id
+         ON n005_snapshot_package(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn create(
+    conn: &Connection,
+    snapshot_id: i64,
+    name: &str,
+    version: &str,
+    source: Option<&str>,
+    checksum: Option<&str>,
+    license: Option<&str>,
+) -> SqlResult<i64> {
+    conn.execute(
+        "INSERT INTO n005_snapshot_package (snapshot_id, name, version, source, checksum, license)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![snapshot_id, name, version, source, checksum, license],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<SnapshotPackage>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, snapshot_id, name, version, source, checksum, license
+         FROM n005_snapshot_package
+         WHERE snapshot_id = ?1
+         ORDER BY name, version",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(SnapshotPackage::new(
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, Option<String>>(4)?,
+            row.get::<_, Option<String>>(5)?,
+            row.get::<_, Option<String>>(6)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n005_snapshot_package WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,14 @@
+/// Ranked full-text hit within a snapshot file
+#[derive(Debug, Clone)]
+pub struct ContentSearchHit {
+    pub file_id: i64,
+    pub snapshot_id: i64,
+    pub path: String,
+    /// Snippet with matches wrapped in `HIGHLIGHT_START` / `HIGHLIGHT_END`
+    pub snippet: String,
+    /// bm25 rank (lower is better)
+    pub rank: f64,
+}
+
+pub const HIGHLIGHT_START: char = '\u{1}';
+pub const HIGHLIGHT_END: char = '\u{2}';
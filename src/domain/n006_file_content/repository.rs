@@ -0,0 +1,212 @@
+use rusqlite::{params, Connection, OptionalExtension, Result as SqlResult};
+
+use super::model::{ContentSearchHit, HIGHLIGHT_START};
+
+/// Text of snapshot files keyed by `n003_snapshot_file.id`, indexed by snapshot, plus an
+/// external-content FTS5 index over `content` (same rowid) kept in sync by triggers
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    // Databases created before: a single FTS5 table with UNINDEXED snapshot_id and path
+    let legacy: Option<String> = conn
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'n006_file_content'",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let migrate = legacy.is_some_and(|sql| sql.to_lowercase().contains("using fts5"));
+    let tx = conn.unchecked_transaction()?;
+    if migrate {
+        conn.execute(
+            "ALTER TABLE n006_file_content RENAME TO n006_file_content_legacy",
+            [],
+        )?;
+    }
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS n006_file_content (
+            file_id INTEGER PRIMARY KEY,
+            snapshot_id INTEGER NOT NULL,
+            path TEXT NOT NULL,
+            content TEXT NOT NULL,
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_file_content_snapshot_id
+            ON n006_file_content(snapshot_id);
+        CREATE VIRTUAL TABLE IF NOT EXISTS n006_file_content_fts USING fts5(
+            content,
+            content = 'n006_file_content',
+            content_rowid = 'file_id',
+            tokenize = 'unicode61',
+            prefix = '2 3'
+        );
+        CREATE TRIGGER IF NOT EXISTS n006_file_content_ai AFTER INSERT ON n006_file_content BEGIN
+            INSERT INTO n006_file_content_fts (rowid, content) VALUES (new.file_id, new.content);
+        END;
+        CREATE TRIGGER IF NOT EXISTS n006_file_content_ad AFTER DELETE ON n006_file_content BEGIN
+            INSERT INTO n006_file_content_fts (n006_file_content_fts, rowid, content)
+                VALUES ('delete', old.file_id, old.content);
+        END;
+        CREATE TRIGGER IF NOT EXISTS n006_file_content_au AFTER UPDATE ON n006_file_content BEGIN
+            INSERT INTO n006_file_content_fts (n006_file_content_fts, rowid, content)
+                VALUES ('delete', old.file_id, old.content);
+            INSERT INTO n006_file_content_fts (rowid, content) VALUES (new.file_id, new.content);
+        END;",
+    )?;
+
+    if migrate {
+        // Rows left behind by deleted snapshots are dropped while copying
+        conn.execute_batch(
+            "INSERT INTO n006_file_content (file_id, snapshot_id, path, content)
+                 SELECT rowid, snapshot_id, path, content FROM n006_file_content_legacy
+                 WHERE snapshot_id IN (SELECT id FROM n002_snapshot);
+             DROP TABLE n006_file_content_legacy;",
+        )?;
+    }
+    tx.commit()
+}
+
+pub fn create(
+    conn: &Connection,
+    snapshot_id: i64,
+    file_id: i64,
+    path: &str,
+    content: &str,
+) -> SqlResult<()> {
+    conn.execute(
+        "INSERT INTO n006_file_content (file_id, snapshot_id, path, content) VALUES (?1, ?2, ?3, ?4)",
+        params![file_id, snapshot_id, path, content],
+    )?;
+    Ok(())
+}
+
+pub fn get_content(conn: &Connection, file_id: i64) -> SqlResult<Option<String>> {
+    conn.query_row(
+        "SELECT content FROM n006_file_content WHERE file_id = ?1",
+        [file_id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Ids of the snapshot's files whose text content is stored
+pub fn list_file_ids(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<i64>> {
+    let mut stmt = conn.prepare("SELECT file_id FROM n006_file_content WHERE snapshot_id = ?1")?;
+    let ids = stmt.query_map([snapshot_id], |row| row.get(0))?;
+    ids.collect()
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n006_file_content WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
+
+/// Ranked search using FTS5 query syntax; `snapshot_id = None` searches all snapshots
+pub fn search(
+    conn: &Connection,
+    snapshot_id: Option<i64>,
+    fts_query: &str,
+    limit: i64,
+) -> SqlResult<Vec<ContentSearchHit>> {
+    let mut stmt = conn.prepare(
+        "SELECT c.file_id, c.snapshot_id, c.path,
+                snippet(n006_file_content_fts, 0, char(1), char(2), '…', 16),
+                bm25(n006_file_content_fts)
+         FROM n006_file_content_fts
+         JOIN n006_file_content c ON c.file_id = n006_file_content_fts.rowid
+         WHERE n006_file_content_fts MATCH ?1
+           AND (?2 IS NULL OR c.snapshot_id = ?2)
+         ORDER BY bm25(n006_file_content_fts)
+         LIMIT ?3",
+    )?;
+
+    let rows = stmt.query_map(params![fts_query, snapshot_id, limit], |row| {
+        Ok(ContentSearchHit {
+            file_id: row.get::<_, i64>(0)?,
+            snapshot_id: row.get::<_, i64>(1)?,
+            path: row.get::<_, String>(2)?,
+            snippet: row.get::<_, String>(3)?,
+            rank: row.get::<_, f64>(4)?,
+        })
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+/// 1-based line of the first match of `fts_query` in the file, if any
+pub fn first_match_line(
+    conn: &Connection,
+    file_id: i64,
+    fts_query: &str,
+) -> SqlResult<Option<usize>> {
+    let highlighted: Option<String> = conn
+        .query_row(
+            "SELECT highlight(n006_file_content_fts, 0, char(1), char(2))
+             FROM n006_file_content_fts
+             WHERE n006_file_content_fts MATCH ?1 AND rowid = ?2",
+            params![fts_query, file_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    Ok(highlighted.and_then(|text| {
+        text.find(HIGHLIGHT_START)
+            .map(|pos| text[..pos].matches('\n').count() + 1)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hits(conn: &Connection, snapshot_id: Option<i64>, query: &str) -> Vec<(i64, String)> {
+        search(conn, snapshot_id, query, 10)
+            .unwrap()
+            .into_iter()
+            .map(|h| (h.file_id, h.path))
+            .collect()
+    }
+
+    #[test]
+    fn migrates_and_keeps_the_index_in_sync() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::shared::db::init_database(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO n001_project (id, name, root_path) VALUES (1, 'demo', '/demo');
+             INSERT INTO n002_snapshot (id, name, project_id, files_count, dirs_count, files_size_bytes,
+                                        loc_count, scanned_at)
+                 VALUES (1, 'a', 1, 1, 0, 0, 0, ''), (2, 'b', 1, 1, 0, 0, 0, '');
+             DROP TABLE n006_file_content_fts;
+             DROP TABLE n006_file_content;
+             CREATE VIRTUAL TABLE n006_file_content USING fts5(snapshot_id UNINDEXED, path UNINDEXED, content);
+             INSERT INTO n006_file_content (rowid, snapshot_id, path, content)
+                 VALUES (1, 1, 'a.rs', 'fn parse_config()'), (7, 99, 'gone.rs', 'parse_config');",
+        )
+        .unwrap();
+        init_table(&conn).unwrap();
+        init_table(&conn).unwrap();
+        create(&conn, 2, 2, "b.rs", "let config = parse_config();\nconfig").unwrap();
+
+        assert_eq!(
+            get_content(&conn, 1).unwrap().as_deref(),
+            Some("fn parse_config()")
+        );
+        assert_eq!(get_content(&conn, 7).unwrap(), None);
+        assert_eq!(hits(&conn, None, "parse_config").len(), 2);
+        assert_eq!(
+            hits(&conn, Some(2), "parse_config"),
+            [(2, "b.rs".to_string())]
+        );
+        assert_eq!(first_match_line(&conn, 2, "config").unwrap(), Some(1));
+
+        delete_by_snapshot(&conn, 1).unwrap();
+        assert_eq!(hits(&conn, None, "parse_config"), [(2, "b.rs".to_string())]);
+        assert_eq!(list_file_ids(&conn, 1).unwrap(), Vec::<i64>::new());
+    }
+}
@@ -0,0 +1,49 @@
+/// Git history metrics of one snapshot file
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct FileChurn {
+    /// n003_snapshot_file id
+    pub file_id: i64,
+    pub snapshot_id: i64,
+    /// Commits touching the file within the window
+    pub commits: i64,
+    /// Distinct author emails within the window
+    pub authors: i64,
+    pub lines_added: i64,
+    pub lines_removed: i64,
+    /// Date of the latest commit touching the file (any time), RFC 3339
+    pub last_modified: Option<String>,
+    /// Lines of code of the file at scan time
+    pub loc: i64,
+    pub window_days: i64,
+    pub computed_at: String,
+}
+
+impl FileChurn {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        file_id: i64,
+        snapshot_id: i64,
+        commits: i64,
+        authors: i64,
+        lines_added: i64,
+        lines_removed: i64,
+        last_modified: Option<String>,
+        loc: i64,
+        window_days: i64,
+        computed_at: String,
+    ) -> Self {
+        Self {
+            file_id,
+            snapshot_id,
+            commits,
+            authors,
+            lines_added,
+            lines_removed,
+            last_modified,
+            loc,
+            window_days,
+            computed_at,
+        }
+    }
+}
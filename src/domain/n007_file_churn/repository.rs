@@ -0,0 +1,90 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::FileChurn;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n007_file_churn (
+            file_id INTEGER PRIMARY KEY,
+            snapshot_id INTEGER NOT NULL,
+            commits INTEGER NOT NULL DEFAULT 0,
+            authors INTEGER NOT NULL DEFAULT 0,
+            lines_added INTEGER NOT NULL DEFAULT 0,
+            lines_removed INTEGER NOT NULL DEFAULT 0,
+            last_modified TEXT,
+            loc INTEGER NOT NULL DEFAULT 0,
+            window_days INTEGER NOT NULL,
+            computed_at TEXT NOT NULL,
+            FOREIGN KEY (file_id) REFERENCES n003_snapshot_file(id),
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_file_churn_snapshot_id
+         ON n007_file_churn(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Insert or replace the metrics of a file
+pub fn upsert(conn: &Connection, churn: &FileChurn) -> SqlResult<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO n007_file_churn
+            (file_id, snapshot_id, commits, authors, lines_added, lines_removed, last_modified, loc, window_days, computed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            churn.file_id,
+            churn.snapshot_id,
+            churn.commits,
+            churn.authors,
+            churn.lines_added,
+            churn.lines_removed,
+            churn.last_modified,
+            churn.loc,
+            churn.window_days,
+            churn.computed_at
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<FileChurn>> {
+    let mut stmt = conn.prepare(
+        "SELECT file_id, snapshot_id, commits, authors, lines_added, lines_removed, last_modified, loc, window_days, computed_at
+         FROM n007_file_churn
+         WHERE snapshot_id = ?1",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(FileChurn::new(
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, i64>(3)?,
+            row.get::<_, i64>(4)?,
+            row.get::<_, i64>(5)?,
+            row.get::<_, Option<String>>(6)?,
+            row.get::<_, i64>(7)?,
+            row.get::<_, i64>(8)?,
+            row.get::<_, String>(9)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n007_file_churn WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
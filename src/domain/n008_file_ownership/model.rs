@@ -0,0 +1,32 @@
+/// Lines of a snapshot file last touched by one author (from git blame)
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct FileOwnership {
+    pub id: i64,
+    pub snapshot_id: i64,
+    /// n003_snapshot_file id
+    pub file_id: i64,
+    pub author_email: String,
+    pub author_name: String,
+    pub lines: i64,
+}
+
+impl FileOwnership {
+    pub fn new(
+        id: i64,
+        snapshot_id: i64,
+        file_id: i64,
+        author_email: String,
+        author_name: String,
+        lines: i64,
+    ) -> Self {
+        Self {
+            id,
+            snapshot_id,
+            file_id,
+            author_email,
+            author_name,
+            lines,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::FileOwnership;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n008_file_ownership (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            snapshot_id INTEGER NOT NULL,
+            file_id INTEGER NOT NULL,
+            author_email TEXT NOT NULL,
+            author_name TEXT NOT NULL,
+            lines INTEGER NOT NULL,
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id),
+            FOREIGN KEY (file_id) REFERENCES n003_snapshot_file(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_file_ownership_snapshot_id
+         ON n008_file_ownership(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn create(
+    conn: &Connection,
+    snapshot_id: i64,
+    file_id: i64,
+    author_email: &str,
+    author_name: &str,
+    lines: i64,
+) -> SqlResult<i64> {
+    conn.execute(
+        "INSERT INTO n008_file_ownership (snapshot_id, file_id, author_email, author_name, lines)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![snapshot_id, file_id, author_email, author_name, lines],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<FileOwnership>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, snapshot_id, file_id, author_email, author_name, lines
+         FROM n008_file_ownership
+         WHERE snapshot_id = ?1",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(FileOwnership::new(
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, i64>(5)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n008_file_ownership WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,40 @@
+/// A person, with every email/name git may know them by
+#[derive(Debug, Clone)]
+pub struct Author {
+    pub id: i64,
+    pub name: String,
+    /// Comma-separated emails or names used in commits
+    pub aliases: String,
+    /// No longer on the team
+    pub has_left: bool,
+    pub comment: Option<String>,
+}
+
+impl Author {
+    pub fn new(
+        id: i64,
+        name: String,
+        aliases: String,
+        has_left: bool,
+        comment: Option<String>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            aliases,
+            has_left,
+            comment,
+        }
+    }
+
+    /// True if the email or name of a commit author belongs to this person
+    pub fn matches(&self, email: &str, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+            || self
+                .aliases
+                .split(',')
+                .map(|a| a.trim())
+                .filter(|a| !a.is_empty())
+                .any(|a| a.eq_ignore_ascii_case(email) || a.eq_ignore_ascii_case(name))
+    }
+}
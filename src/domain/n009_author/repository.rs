@@ -0,0 +1,70 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::Author;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n009_author (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            aliases TEXT NOT NULL DEFAULT '',
+            has_left INTEGER NOT NULL DEFAULT 0,
+            comment TEXT
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+pub fn list_all(conn: &Connection) -> SqlResult<Vec<Author>> {
+    let mut stmt =
+        conn.prepare("SELECT id, name, aliases, has_left, comment FROM n009_author ORDER BY name")?;
+    let rows = stmt.query_map([], |row| {
+        Ok(Author::new(
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, bool>(3)?,
+            row.get::<_, Option<String>>(4)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn create(
+    conn: &Connection,
+    name: &str,
+    aliases: &str,
+    has_left: bool,
+    comment: Option<&str>,
+) -> SqlResult<i64> {
+    conn.execute(
+        "INSERT INTO n009_author (name, aliases, has_left, comment) VALUES (?1, ?2, ?3, ?4)",
+        params![name, aliases, has_left, comment],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn update(conn: &Connection, author: &Author) -> SqlResult<()> {
+    conn.execute(
+        "UPDATE n009_author SET name = ?1, aliases = ?2, has_left = ?3, comment = ?4 WHERE id = ?5",
+        params![
+            author.name,
+            author.aliases,
+            author.has_left,
+            author.comment,
+            author.id
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn delete(conn: &Connection, id: i64) -> SqlResult<()> {
+    conn.execute("DELETE FROM n009_author WHERE id = ?1", [id])?;
+    Ok(())
+}
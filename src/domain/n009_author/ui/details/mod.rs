@@ -0,0 +1,37 @@
+use eframe::egui;
+
+#[derive(Default, Clone)]
+pub struct AuthorDetailsState {
+    pub name: String,
+    pub aliases: String,
+    pub has_left: bool,
+    pub comment: String,
+}
+
+impl AuthorDetailsState {
+    pub fn from_values(name: &str, aliases: &str, has_left: bool, comment: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            aliases: aliases.to_string(),
+            has_left,
+            comment: comment.unwrap_or("").to_string(),
+        }
+    }
+}
+
+pub fn details_form(ui: &mut egui::Ui, state: &mut AuthorDetailsState) {
+    ui.horizontal(|ui| {
+        ui.label("Name:");
+        ui.text_edit_singleline(&mut state.name);
+    });
+    ui.horizontal(|ui| {
+        ui.label("Aliases:");
+        ui.text_edit_singleline(&mut state.aliases)
+            .on_hover_text("Comma-separated emails or names used in commits");
+    });
+    ui.checkbox(&mut state.has_left, "Has left the team");
+    ui.horizontal(|ui| {
+        ui.label("Comment:");
+        ui.text_edit_singleline(&mut state.comment);
+    });
+}
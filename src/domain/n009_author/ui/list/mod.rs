@@ -0,0 +1,198 @@
+use eframe::egui;
+use egui::RichText;
+use egui_extras::{Column, TableBuilder};
+use rusqlite::Connection;
+
+use crate::domain::n009_author::model::Author;
+use crate::domain::n009_author::repository as repo;
+use crate::domain::n009_author::ui::details::{details_form, AuthorDetailsState};
+
+#[derive(Default)]
+pub struct AuthorsListState {
+    pub show_create: bool,
+    pub show_edit: bool,
+    pub editing_id: Option<i64>,
+    pub create_details: AuthorDetailsState,
+    pub edit_details: AuthorDetailsState,
+    pub status: String,
+}
+
+pub fn ui_authors_list(ui: &mut egui::Ui, conn: &Connection, state: &mut AuthorsListState) {
+    ui.horizontal(|ui| {
+        if ui.button("Add").clicked() {
+            state.show_create = true;
+            state.create_details = AuthorDetailsState::default();
+        }
+    });
+
+    ui.add_space(6.0);
+
+    let authors: Vec<Author> = match repo::list_all(conn) {
+        Ok(list) => list,
+        Err(e) => {
+            ui.colored_label(
+                egui::Color32::LIGHT_RED,
+                format!("Error loading authors: {}", e),
+            );
+            return;
+        }
+    };
+
+    if authors.is_empty() {
+        ui.label(
+            RichText::new("No authors yet. Add people to map commit emails and mark who has left.")
+                .weak(),
+        );
+    }
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .column(Column::auto().at_least(150.0)) // Name
+        .column(Column::remainder()) // Aliases
+        .column(Column::auto().at_least(60.0)) // Status
+        .column(Column::remainder()) // Comment
+        .column(Column::auto()) // Actions
+        .header(24.0, |mut header| {
+            for title in ["Name", "Aliases", "Status", "Comment", "Actions"] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|mut body| {
+            for a in authors.iter() {
+                let id = a.id;
+                body.row(24.0, |mut row| {
+                    row.col(|ui| {
+                        ui.label(&a.name);
+                    });
+                    row.col(|ui| {
+                        ui.label(&a.aliases);
+                    });
+                    row.col(|ui| {
+                        if a.has_left {
+                            ui.colored_label(egui::Color32::LIGHT_RED, "left");
+                        } else {
+                            ui.label("active");
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.label(a.comment.as_deref().unwrap_or(""));
+                    });
+                    row.col(|ui| {
+                        ui.horizontal(|ui| {
+                            if ui.button("Edit").clicked() {
+                                state.editing_id = Some(id);
+                                state.edit_details = AuthorDetailsState::from_values(
+                                    &a.name,
+                                    &a.aliases,
+                                    a.has_left,
+                                    a.comment.as_deref(),
+                                );
+                                state.show_edit = true;
+                            }
+                            if ui.button("Delete").clicked() {
+                                match repo::delete(conn, id) {
+                                    Ok(_) => state.status = format!("Deleted author ID {}", id),
+                                    Err(e) => state.status = format!("Error deleting: {}", e),
+                                }
+                            }
+                        });
+                    });
+                });
+            }
+        });
+
+    // Create author window
+    if state.show_create {
+        let mut open = true;
+        egui::Window::new("Add Author")
+            .id(egui::Id::new("n009_add_author"))
+            .open(&mut open)
+            .show(ui.ctx(), |ui| {
+                details_form(ui, &mut state.create_details);
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        let d = &state.create_details;
+                        let comment = Some(d.comment.trim()).filter(|c| !c.is_empty());
+                        if d.name.trim().is_empty() {
+                            state.status = "Name required".to_string();
+                        } else {
+                            match repo::create(
+                                conn,
+                                d.name.trim(),
+                                d.aliases.trim(),
+                                d.has_left,
+                                comment,
+                            ) {
+                                Ok(id) => {
+                                    state.status = format!("Author created (ID={})", id);
+                                    state.show_create = false;
+                                }
+                                Err(e) => state.status = format!("Error creating: {}", e),
+                            }
+                        }
+                    }
+                    if ui.button("Cancel").clicked() {
+                        state.show_create = false;
+                    }
+                });
+            });
+        if !open {
+            state.show_create = false;
+        }
+    }
+
+    // Edit author window
+    if state.show_edit {
+        let mut open = true;
+        egui::Window::new("Edit Author")
+            .id(egui::Id::new("n009_edit_author"))
+            .open(&mut open)
+            .show(ui.ctx(), |ui| {
+                details_form(ui, &mut state.edit_details);
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        if let Some(id) = state.editing_id {
+                            let d = &state.edit_details;
+                            if d.name.trim().is_empty() {
+                                state.status = "Name required".to_string();
+                            } else {
+                                let updated = Author::new(
+                                    id,
+                                    d.name.trim().to_string(),
+                                    d.aliases.trim().to_string(),
+                                    d.has_left,
+                                    Some(d.comment.trim().to_string()).filter(|c| !c.is_empty()),
+                                );
+                                match repo::update(conn, &updated) {
+                                    Ok(_) => {
+                                        state.status = "Author updated".to_string();
+                                        state.show_edit = false;
+                                        state.editing_id = None;
+                                    }
+                                    Err(e) => state.status = format!("Error updating: {}", e),
+                                }
+                            }
+                        }
+                    }
+                    if ui.button("Cancel").clicked() {
+                        state.show_edit = false;
+                        state.editing_id = None;
+                    }
+                });
+            });
+        if !open {
+            state.show_edit = false;
+            state.editing_id = None;
+        }
+    }
+
+    if !state.status.is_empty() {
+        ui.add_space(12.0);
+        ui.colored_label(egui::Color32::LIGHT_BLUE, &state.status);
+    }
+}
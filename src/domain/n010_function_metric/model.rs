@@ -0,0 +1,49 @@
+/// Complexity metrics of one Rust function of a snapshot file
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct FunctionMetric {
+    pub id: i64,
+    pub snapshot_id: i64,
+    /// n003_snapshot_file id
+    pub file_id: i64,
+    /// `Type::name` for methods
+    pub name: String,
+    /// 1-based line of the `fn` keyword
+    pub line: i64,
+    /// Lines from `fn` to the closing brace
+    pub length: i64,
+    pub params: i64,
+    pub cyclomatic: i64,
+    pub cognitive: i64,
+    /// Deepest nesting of control-flow blocks
+    pub nesting: i64,
+}
+
+impl FunctionMetric {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: i64,
+        snapshot_id: i64,
+        file_id: i64,
+        name: String,
+        line: i64,
+        length: i64,
+        params: i64,
+        cyclomatic: i64,
+        cognitive: i64,
+        nesting: i64,
+    ) -> Self {
+        Self {
+            id,
+            snapshot_id,
+            file_id,
+            name,
+            line,
+            length,
+            params,
+            cyclomatic,
+            cognitive,
+            nesting,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::FunctionMetric;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n010_function_metric (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            snapshot_id INTEGER NOT NULL,
+            file_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            line INTEGER NOT NULL,
+            length INTEGER NOT NULL,
+            params INTEGER NOT NULL,
+            cyclomatic INTEGER NOT NULL,
+            cognitive INTEGER NOT NULL,
+            nesting INTEGER NOT NULL,
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id),
+            FOREIGN KEY (file_id) REFERENCES n003_snapshot_file(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_function_metric_snapshot_id
+         ON n010_function_metric(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Insert a metric row (`metric.id` is ignored)
+pub fn create(conn: &Connection, metric: &FunctionMetric) -> SqlResult<i64> {
+    conn.execute(
+        "INSERT INTO n010_function_metric
+            (snapshot_id, file_id, name, line, length, params, cyclomatic, cognitive, nesting)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            metric.snapshot_id,
+            metric.file_id,
+            metric.name,
+            metric.line,
+            metric.length,
+            metric.params,
+            metric.cyclomatic,
+            metric.cognitive,
+            metric.nesting
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<FunctionMetric>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, snapshot_id, file_id, name, line, length, params, cyclomatic, cognitive, nesting
+         FROM n010_function_metric
+         WHERE snapshot_id = ?1",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(FunctionMetric::new(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+            row.get(6)?,
+            row.get(7)?,
+            row.get(8)?,
+            row.get(9)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n010_function_metric WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
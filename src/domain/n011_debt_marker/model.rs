@@ -0,0 +1,47 @@
+/// Marker kinds collected during scans, in display order
+pub const MARKER_KINDS: [&str; 6] = ["TODO", "FIXME", "HACK", "XXX", "todo!", "unimplemented!"];
+
+/// A TODO/FIXME/HACK/XXX comment or `todo!`/`unimplemented!` call in a snapshot file
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct DebtMarker {
+    pub id: i64,
+    pub snapshot_id: i64,
+    /// n003_snapshot_file id
+    pub file_id: i64,
+    /// 1-based line
+    pub line: i64,
+    /// One of [`MARKER_KINDS`]
+    pub kind: String,
+    /// The trimmed source line
+    pub text: String,
+    /// Git author of the line, if the project is a git repository
+    pub author: Option<String>,
+    /// Author date of the line, UTC RFC 3339
+    pub authored_at: Option<String>,
+}
+
+impl DebtMarker {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: i64,
+        snapshot_id: i64,
+        file_id: i64,
+        line: i64,
+        kind: String,
+        text: String,
+        author: Option<String>,
+        authored_at: Option<String>,
+    ) -> Self {
+        Self {
+            id,
+            snapshot_id,
+            file_id,
+            line,
+            kind,
+            text,
+            author,
+            authored_at,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::DebtMarker;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n011_debt_marker (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            snapshot_id INTEGER NOT NULL,
+            file_id INTEGER NOT NULL,
+            line INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            text TEXT NOT NULL,
+            author TEXT,
+            authored_at TEXT,
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id),
+            FOREIGN KEY (file_id) REFERENCES n003_snapshot_file(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_debt_marker_snapshot_id
+         ON n011_debt_marker(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Insert a marker (`marker.id` is ignored)
+pub fn create(conn: &Connection, marker: &DebtMarker) -> SqlResult<i64> {
+    conn.execute(
+        "INSERT INTO n011_debt_marker (snapshot_id, file_id, line, kind, text, author, authored_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            marker.snapshot_id,
+            marker.file_id,
+            marker.line,
+            marker.kind,
+            marker.text,
+            marker.author,
+            marker.authored_at
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<DebtMarker>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, snapshot_id, file_id, line, kind, text, author, authored_at
+         FROM n011_debt_marker
+         WHERE snapshot_id = ?1
+         ORDER BY file_id, line",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(DebtMarker::new(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+            row.get(6)?,
+            row.get(7)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n011_debt_marker WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
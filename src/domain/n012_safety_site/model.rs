@@ -0,0 +1,35 @@
+/// An unsafe block/fn/impl or a potential panic site in a Rust file of a snapshot
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct SafetySite {
+    pub id: i64,
+    pub snapshot_id: i64,
+    /// n003_snapshot_file id
+    pub file_id: i64,
+    /// `SiteKind::as_str` value, e.g. `unsafe block`, `expect`
+    pub kind: String,
+    /// 1-based line
+    pub line: i64,
+    /// Enclosing function (`Type::name`), None at item level
+    pub function: Option<String>,
+}
+
+impl SafetySite {
+    pub fn new(
+        id: i64,
+        snapshot_id: i64,
+        file_id: i64,
+        kind: String,
+        line: i64,
+        function: Option<String>,
+    ) -> Self {
+        Self {
+            id,
+            snapshot_id,
+            file_id,
+            kind,
+            line,
+            function,
+        }
+    }
+}
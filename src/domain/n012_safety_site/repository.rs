@@ -0,0 +1,77 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::SafetySite;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n012_safety_site (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            snapshot_id INTEGER NOT NULL,
+            file_id INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            line INTEGER NOT NULL,
+            function TEXT,
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id),
+            FOREIGN KEY (file_id) REFERENCES n003_snapshot_file(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_safety_site_snapshot_id
+         ON n012_safety_site(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn create(
+    conn: &Connection,
+    snapshot_id: i64,
+    file_id: i64,
+    kind: &str,
+    line: i64,
+    function: Option<&str>,
+) -> SqlResult<i64> {
+    conn.execute(
+        "INSERT INTO n012_safety_site (snapshot_id, file_id, kind, line, function)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![snapshot_id, file_id, kind, line, function],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<SafetySite>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, snapshot_id, file_id, kind, line, function
+         FROM n012_safety_site
+         WHERE snapshot_id = ?1
+         ORDER BY file_id, line",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(SafetySite::new(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n012_safety_site WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
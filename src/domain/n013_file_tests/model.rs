@@ -0,0 +1,36 @@
+/// Test inventory of one Rust file of a snapshot
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct FileTests {
+    /// n003_snapshot_file id
+    pub file_id: i64,
+    pub snapshot_id: i64,
+    /// `#[test]` / `#[bench]` functions
+    pub test_fns: i64,
+    /// Non-blank lines of test code (whole file under `tests/` / `benches/`)
+    pub test_loc: i64,
+    /// Non-blank lines of production code
+    pub code_loc: i64,
+    /// Artifact id the tests are attributed to (`n001_`), None when unknown
+    pub covers_artifact: Option<String>,
+}
+
+impl FileTests {
+    pub fn new(
+        file_id: i64,
+        snapshot_id: i64,
+        test_fns: i64,
+        test_loc: i64,
+        code_loc: i64,
+        covers_artifact: Option<String>,
+    ) -> Self {
+        Self {
+            file_id,
+            snapshot_id,
+            test_fns,
+            test_loc,
+            code_loc,
+            covers_artifact,
+        }
+    }
+}
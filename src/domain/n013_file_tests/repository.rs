@@ -0,0 +1,76 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::FileTests;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n013_file_tests (
+            file_id INTEGER PRIMARY KEY,
+            snapshot_id INTEGER NOT NULL,
+            test_fns INTEGER NOT NULL DEFAULT 0,
+            test_loc INTEGER NOT NULL DEFAULT 0,
+            code_loc INTEGER NOT NULL DEFAULT 0,
+            covers_artifact TEXT,
+            FOREIGN KEY (file_id) REFERENCES n003_snapshot_file(id),
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_file_tests_snapshot_id
+         ON n013_file_tests(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn create(conn: &Connection, tests: &FileTests) -> SqlResult<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO n013_file_tests (file_id, snapshot_id, test_fns, test_loc, code_loc, covers_artifact)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            tests.file_id,
+            tests.snapshot_id,
+            tests.test_fns,
+            tests.test_loc,
+            tests.code_loc,
+            tests.covers_artifact
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<FileTests>> {
+    let mut stmt = conn.prepare(
+        "SELECT file_id, snapshot_id, test_fns, test_loc, code_loc, covers_artifact
+         FROM n013_file_tests
+         WHERE snapshot_id = ?1",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(FileTests::new(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n013_file_tests WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// Line rate at or above which coverage counts as good, and below which it is poor
+pub const GOOD_RATE: f64 = 0.8;
+pub const POOR_RATE: f64 = 0.5;
+
+/// Imported line and branch coverage of one snapshot file
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct FileCoverage {
+    /// n003_snapshot_file id
+    pub file_id: i64,
+    pub snapshot_id: i64,
+    /// Instrumented lines and those executed at least once
+    pub lines_found: i64,
+    pub lines_hit: i64,
+    pub branches_found: i64,
+    pub branches_hit: i64,
+    /// `line:hits` pairs separated by commas, for the preview gutter
+    pub line_hits: String,
+    /// Report file the data came from
+    pub report_path: String,
+    pub imported_at: String,
+}
+
+impl FileCoverage {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        file_id: i64,
+        snapshot_id: i64,
+        lines_found: i64,
+        lines_hit: i64,
+        branches_found: i64,
+        branches_hit: i64,
+        line_hits: String,
+        report_path: String,
+        imported_at: String,
+    ) -> Self {
+        Self {
+            file_id,
+            snapshot_id,
+            lines_found,
+            lines_hit,
+            branches_found,
+            branches_hit,
+            line_hits,
+            report_path,
+            imported_at,
+        }
+    }
+
+    pub fn line_rate(&self) -> Option<f64> {
+        rate(self.lines_hit, self.lines_found)
+    }
+
+    pub fn branch_rate(&self) -> Option<f64> {
+        rate(self.branches_hit, self.branches_found)
+    }
+
+    /// line -> execution count
+    pub fn hits_by_line(&self) -> HashMap<usize, u64> {
+        self.line_hits
+            .split(',')
+            .filter_map(|pair| {
+                let (line, hits) = pair.split_once(':')?;
+                Some((line.parse().ok()?, hits.parse().ok()?))
+            })
+            .collect()
+    }
+}
+
+/// Covered share in 0..=1, None when nothing is instrumented
+pub fn rate(hit: i64, found: i64) -> Option<f64> {
+    (found > 0).then(|| hit as f64 / found as f64)
+}
+
+pub fn encode_line_hits(lines: &BTreeMap<usize, u64>) -> String {
+    lines
+        .iter()
+        .map(|(line, hits)| format!("{}:{}", line, hits))
+        .collect::<Vec<_>>()
+        .join(",")
+}
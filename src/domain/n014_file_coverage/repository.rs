@@ -0,0 +1,100 @@
+use rusqlite::{params, Connection, OptionalExtension, Result as SqlResult};
+
+use super::model::FileCoverage;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n014_file_coverage (
+            file_id INTEGER PRIMARY KEY,
+            snapshot_id INTEGER NOT NULL,
+            lines_found INTEGER NOT NULL DEFAULT 0,
+            lines_hit INTEGER NOT NULL DEFAULT 0,
+            branches_found INTEGER NOT NULL DEFAULT 0,
+            branches_hit INTEGER NOT NULL DEFAULT 0,
+            line_hits TEXT NOT NULL DEFAULT '',
+            report_path TEXT NOT NULL,
+            imported_at TEXT NOT NULL,
+            FOREIGN KEY (file_id) REFERENCES n003_snapshot_file(id),
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_file_coverage_snapshot_id
+         ON n014_file_coverage(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Insert or replace the coverage of a file
+pub fn upsert(conn: &Connection, coverage: &FileCoverage) -> SqlResult<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO n014_file_coverage
+            (file_id, snapshot_id, lines_found, lines_hit, branches_found, branches_hit, line_hits, report_path, imported_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            coverage.file_id,
+            coverage.snapshot_id,
+            coverage.lines_found,
+            coverage.lines_hit,
+            coverage.branches_found,
+            coverage.branches_hit,
+            coverage.line_hits,
+            coverage.report_path,
+            coverage.imported_at
+        ],
+    )?;
+    Ok(())
+}
+
+fn from_row(row: &rusqlite::Row) -> SqlResult<FileCoverage> {
+    Ok(FileCoverage::new(
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        row.get(3)?,
+        row.get(4)?,
+        row.get(5)?,
+        row.get(6)?,
+        row.get(7)?,
+        row.get(8)?,
+    ))
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<FileCoverage>> {
+    let mut stmt = conn.prepare(
+        "SELECT file_id, snapshot_id, lines_found, lines_hit, branches_found, branches_hit, line_hits, report_path, imported_at
+         FROM n014_file_coverage
+         WHERE snapshot_id = ?1",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], from_row)?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn get_by_file(conn: &Connection, file_id: i64) -> SqlResult<Option<FileCoverage>> {
+    conn.query_row(
+        "SELECT file_id, snapshot_id, lines_found, lines_hit, branches_found, branches_hit, line_hits, report_path, imported_at
+         FROM n014_file_coverage
+         WHERE file_id = ?1",
+        [file_id],
+        from_row,
+    )
+    .optional()
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n014_file_coverage WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
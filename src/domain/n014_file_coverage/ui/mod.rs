@@ -0,0 +1,26 @@
+use eframe::egui;
+
+use super::model::{GOOD_RATE, POOR_RATE};
+
+/// Green / yellow / red by covered share
+pub fn coverage_color(rate: f64) -> egui::Color32 {
+    if rate >= GOOD_RATE {
+        egui::Color32::LIGHT_GREEN
+    } else if rate >= POOR_RATE {
+        egui::Color32::YELLOW
+    } else {
+        egui::Color32::LIGHT_RED
+    }
+}
+
+/// `85.0%` in the coverage colour, a weak dash without data
+pub fn coverage_label(ui: &mut egui::Ui, rate: Option<f64>) {
+    match rate {
+        Some(rate) => {
+            ui.colored_label(coverage_color(rate), format!("{:.1}%", rate * 100.0));
+        }
+        None => {
+            ui.label(egui::RichText::new("-").weak());
+        }
+    }
+}
@@ -0,0 +1,47 @@
+/// A public item of a crate in a snapshot (the crate's API surface)
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct PublicItem {
+    pub id: i64,
+    pub snapshot_id: i64,
+    /// n003_snapshot_file id of the declaring file
+    pub file_id: i64,
+    /// `[package] name` of the crate
+    pub crate_name: String,
+    /// `fn`, `method`, `struct`, `field`, `enum`, `variant`, `trait`, `impl`, `type`, `const`, `static`, `use`
+    pub kind: String,
+    /// Path from the crate root: `orders::Order::new`
+    pub path: String,
+    pub signature: String,
+    /// 1-based line
+    pub line: i64,
+    /// Has a doc comment or `#[doc]` attribute
+    pub documented: bool,
+}
+
+impl PublicItem {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: i64,
+        snapshot_id: i64,
+        file_id: i64,
+        crate_name: String,
+        kind: String,
+        path: String,
+        signature: String,
+        line: i64,
+        documented: bool,
+    ) -> Self {
+        Self {
+            id,
+            snapshot_id,
+            file_id,
+            crate_name,
+            kind,
+            path,
+            signature,
+            line,
+            documented,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::PublicItem;
+use crate::shared::db::ensure_column;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n015_public_item (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            snapshot_id INTEGER NOT NULL,
+            file_id INTEGER NOT NULL,
+            crate_name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            path TEXT NOT NULL,
+            signature TEXT NOT NULL,
+            line INTEGER NOT NULL,
+            documented INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id),
+            FOREIGN KEY (file_id) REFERENCES n003_snapshot_file(id)
+        )",
+        [],
+    )?;
+
+    // Documentation coverage (added later, hence the migration)
+    ensure_column(
+        conn,
+        "n015_public_item",
+        "documented",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_public_item_snapshot_id
+         ON n015_public_item(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn create(conn: &Connection, item: &PublicItem) -> SqlResult<i64> {
+    conn.execute(
+        "INSERT INTO n015_public_item (snapshot_id, file_id, crate_name, kind, path, signature, line, documented)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            item.snapshot_id,
+            item.file_id,
+            item.crate_name,
+            item.kind,
+            item.path,
+            item.signature,
+            item.line,
+            item.documented
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<PublicItem>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, snapshot_id, file_id, crate_name, kind, path, signature, line, documented
+         FROM n015_public_item
+         WHERE snapshot_id = ?1
+         ORDER BY crate_name, path",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(PublicItem::new(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+            row.get(6)?,
+            row.get(7)?,
+            row.get(8)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n015_public_item WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,41 @@
+/// A module file of a crate's `src/` tree in a snapshot
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct ModuleDoc {
+    /// n003_snapshot_file id
+    pub file_id: i64,
+    pub snapshot_id: i64,
+    /// `[package] name` of the crate
+    pub crate_name: String,
+    /// Module path from the crate root, empty for `lib.rs` / `main.rs`
+    pub module_path: String,
+    /// Opens with a `//!` header (or `#![doc]`)
+    pub has_doc: bool,
+}
+
+impl ModuleDoc {
+    pub fn new(
+        file_id: i64,
+        snapshot_id: i64,
+        crate_name: String,
+        module_path: String,
+        has_doc: bool,
+    ) -> Self {
+        Self {
+            file_id,
+            snapshot_id,
+            crate_name,
+            module_path,
+            has_doc,
+        }
+    }
+
+    /// `crate::module` (the crate name alone for the crate root)
+    pub fn display_path(&self) -> String {
+        if self.module_path.is_empty() {
+            self.crate_name.clone()
+        } else {
+            format!("{}::{}", self.crate_name, self.module_path)
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::ModuleDoc;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n016_module_doc (
+            file_id INTEGER PRIMARY KEY,
+            snapshot_id INTEGER NOT NULL,
+            crate_name TEXT NOT NULL,
+            module_path TEXT NOT NULL,
+            has_doc INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (file_id) REFERENCES n003_snapshot_file(id),
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_module_doc_snapshot_id
+         ON n016_module_doc(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn create(conn: &Connection, module: &ModuleDoc) -> SqlResult<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO n016_module_doc (file_id, snapshot_id, crate_name, module_path, has_doc)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            module.file_id,
+            module.snapshot_id,
+            module.crate_name,
+            module.module_path,
+            module.has_doc
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<ModuleDoc>> {
+    let mut stmt = conn.prepare(
+        "SELECT file_id, snapshot_id, crate_name, module_path, has_doc
+         FROM n016_module_doc
+         WHERE snapshot_id = ?1
+         ORDER BY crate_name, module_path",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(ModuleDoc::new(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n016_module_doc WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+/// Detected language of one file of a snapshot
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct FileLanguage {
+    /// n003_snapshot_file id
+    pub file_id: i64,
+    pub snapshot_id: i64,
+    pub language: String,
+    /// Non-blank lines, 0 for files without stored text
+    pub loc: i64,
+}
+
+impl FileLanguage {
+    pub fn new(file_id: i64, snapshot_id: i64, language: String, loc: i64) -> Self {
+        Self {
+            file_id,
+            snapshot_id,
+            language,
+            loc,
+        }
+    }
+}
+
+/// Totals of one language in a snapshot
+#[derive(Debug, Clone, Default)]
+pub struct LanguageStats {
+    pub language: String,
+    pub files: i64,
+    pub bytes: i64,
+    pub loc: i64,
+}
+
+/// Share of `part` in `total`, 0 for an empty total
+pub fn share(part: i64, total: i64) -> f64 {
+    if total > 0 {
+        part as f64 / total as f64
+    } else {
+        0.0
+    }
+}
+
+/// Chart colours, shared by the app and the HTML report
+pub const PALETTE: [(u8, u8, u8); 10] = [
+    (222, 165, 132),
+    (86, 156, 214),
+    (106, 180, 110),
+    (220, 200, 100),
+    (190, 120, 200),
+    (90, 190, 190),
+    (230, 120, 120),
+    (160, 160, 240),
+    (180, 140, 90),
+    (150, 150, 150),
+];
+
+/// Stable colour of a language, the same in every chart
+pub fn language_rgb(language: &str) -> (u8, u8, u8) {
+    let hash = language
+        .bytes()
+        .fold(0usize, |h, b| h.wrapping_mul(31).wrapping_add(b as usize));
+    PALETTE[hash % PALETTE.len()]
+}
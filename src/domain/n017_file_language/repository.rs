@@ -0,0 +1,98 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::{FileLanguage, LanguageStats};
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n017_file_language (
+            file_id INTEGER PRIMARY KEY,
+            snapshot_id INTEGER NOT NULL,
+            language TEXT NOT NULL,
+            loc INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (file_id) REFERENCES n003_snapshot_file(id),
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_file_language_snapshot_id
+         ON n017_file_language(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn create(conn: &Connection, language: &FileLanguage) -> SqlResult<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO n017_file_language (file_id, snapshot_id, language, loc)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![
+            language.file_id,
+            language.snapshot_id,
+            language.language,
+            language.loc
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<FileLanguage>> {
+    let mut stmt = conn.prepare(
+        "SELECT file_id, snapshot_id, language, loc
+         FROM n017_file_language
+         WHERE snapshot_id = ?1
+         ORDER BY file_id",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(FileLanguage::new(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+/// Files, bytes and LOC per language of a snapshot, most LOC first
+pub fn breakdown_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<LanguageStats>> {
+    let mut stmt = conn.prepare(
+        "SELECT l.language, COUNT(*), COALESCE(SUM(f.size_bytes), 0), COALESCE(SUM(l.loc), 0)
+         FROM n017_file_language l
+         JOIN n003_snapshot_file f ON f.id = l.file_id
+         WHERE l.snapshot_id = ?1
+         GROUP BY l.language
+         ORDER BY SUM(l.loc) DESC, SUM(f.size_bytes) DESC",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(LanguageStats {
+            language: row.get(0)?,
+            files: row.get(1)?,
+            bytes: row.get(2)?,
+            loc: row.get(3)?,
+        })
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n017_file_language WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use eframe::egui;
+
+use super::model::{language_rgb, share, LanguageStats};
+
+/// Stable colour of a language, the same in every chart
+pub fn language_color(language: &str) -> egui::Color32 {
+    let (r, g, b) = language_rgb(language);
+    egui::Color32::from_rgb(r, g, b)
+}
+
+/// Horizontal bar split by each language's LOC share; hovering shows the numbers
+pub fn share_bar(ui: &mut egui::Ui, stats: &[LanguageStats], width: f32) {
+    let total: i64 = stats.iter().map(|s| s.loc).sum();
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(width, 14.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let mut x = rect.left();
+    for s in stats {
+        let w = share(s.loc, total) as f32 * rect.width();
+        if w <= 0.0 {
+            continue;
+        }
+        let segment =
+            egui::Rect::from_min_size(egui::pos2(x, rect.top()), egui::vec2(w, rect.height()));
+        painter.rect_filled(segment, 0.0, language_color(&s.language));
+        x += w;
+    }
+
+    response.on_hover_ui(|ui| {
+        for s in stats.iter().filter(|s| s.loc > 0) {
+            ui.label(format!(
+                "{}: {} LOC ({:.1}%)",
+                s.language,
+                s.loc,
+                share(s.loc, total) * 100.0
+            ));
+        }
+    });
+}
+
+/// Colour swatch followed by the language name
+pub fn legend_entry(ui: &mut egui::Ui, language: &str) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), egui::Sense::hover());
+    ui.painter()
+        .rect_filled(rect, 2.0, language_color(language));
+    ui.label(language);
+}
@@ -0,0 +1,39 @@
+/// A `use` declaration of one crate module that resolves to another module file of the snapshot
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct ModuleDependency {
+    pub id: i64,
+    pub snapshot_id: i64,
+    /// n003_snapshot_file id of the importing module
+    pub file_id: i64,
+    /// n003_snapshot_file id of the imported module
+    pub target_file_id: i64,
+    /// 1-based line of the `use` declaration
+    pub line: i64,
+    /// Imported path as written, groups expanded (`crate::domain::n001_project::repository`)
+    pub use_path: String,
+    /// Inside `#[test]` / `#[cfg(test)]` code
+    pub in_test: bool,
+}
+
+impl ModuleDependency {
+    pub fn new(
+        id: i64,
+        snapshot_id: i64,
+        file_id: i64,
+        target_file_id: i64,
+        line: i64,
+        use_path: String,
+        in_test: bool,
+    ) -> Self {
+        Self {
+            id,
+            snapshot_id,
+            file_id,
+            target_file_id,
+            line,
+            use_path,
+            in_test,
+        }
+    }
+}
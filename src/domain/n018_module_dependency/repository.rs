@@ -0,0 +1,80 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::ModuleDependency;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n018_module_dependency (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            snapshot_id INTEGER NOT NULL,
+            file_id INTEGER NOT NULL,
+            target_file_id INTEGER NOT NULL,
+            line INTEGER NOT NULL,
+            use_path TEXT NOT NULL,
+            in_test INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id),
+            FOREIGN KEY (file_id) REFERENCES n003_snapshot_file(id),
+            FOREIGN KEY (target_file_id) REFERENCES n003_snapshot_file(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_module_dependency_snapshot_id
+         ON n018_module_dependency(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn create(conn: &Connection, dependency: &ModuleDependency) -> SqlResult<i64> {
+    conn.execute(
+        "INSERT INTO n018_module_dependency (snapshot_id, file_id, target_file_id, line, use_path, in_test)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            dependency.snapshot_id,
+            dependency.file_id,
+            dependency.target_file_id,
+            dependency.line,
+            dependency.use_path,
+            dependency.in_test
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<ModuleDependency>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, snapshot_id, file_id, target_file_id, line, use_path, in_test
+         FROM n018_module_dependency
+         WHERE snapshot_id = ?1
+         ORDER BY file_id, line, id",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(ModuleDependency::new(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+            row.get(6)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n018_module_dependency WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
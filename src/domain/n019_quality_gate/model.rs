@@ -0,0 +1,99 @@
+/// A check a snapshot of the project must pass
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GateRule {
+    /// No file above the threshold LOC
+    MaxFileLoc,
+    /// No TODO/FIXME/... markers beyond those of the baseline (or previous) snapshot
+    NoNewTodos,
+    /// Documented public items at least the threshold percent
+    MinDocCoverage,
+    /// No unsafe blocks, fns or impls in domain modules
+    NoUnsafeInDomain,
+    /// No dependency that breaks an architecture rule
+    NoArchitectureViolations,
+    /// No dependency cycle at any level
+    NoDependencyCycles,
+}
+
+impl GateRule {
+    pub const ALL: [GateRule; 6] = [
+        GateRule::MaxFileLoc,
+        GateRule::NoNewTodos,
+        GateRule::MinDocCoverage,
+        GateRule::NoUnsafeInDomain,
+        GateRule::NoArchitectureViolations,
+        GateRule::NoDependencyCycles,
+    ];
+
+    /// Stored and CLI name
+    pub fn key(self) -> &'static str {
+        match self {
+            GateRule::MaxFileLoc => "max_file_loc",
+            GateRule::NoNewTodos => "no_new_todos",
+            GateRule::MinDocCoverage => "min_doc_coverage",
+            GateRule::NoUnsafeInDomain => "no_unsafe_in_domain",
+            GateRule::NoArchitectureViolations => "no_architecture_violations",
+            GateRule::NoDependencyCycles => "no_dependency_cycles",
+        }
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GateRule::MaxFileLoc => "Max file LOC",
+            GateRule::NoNewTodos => "No new TODOs",
+            GateRule::MinDocCoverage => "Min doc coverage, %",
+            GateRule::NoUnsafeInDomain => "No unsafe in domain",
+            GateRule::NoArchitectureViolations => "No architecture violations",
+            GateRule::NoDependencyCycles => "No dependency cycles",
+        }
+    }
+
+    /// Threshold offered for a new gate; None for rules without one
+    pub fn default_threshold(self) -> Option<i64> {
+        match self {
+            GateRule::MaxFileLoc => Some(800),
+            GateRule::MinDocCoverage => Some(60),
+            _ => None,
+        }
+    }
+
+    pub fn has_threshold(self) -> bool {
+        self.default_threshold().is_some()
+    }
+
+    /// `max file LOC 800`, `doc coverage ≥ 60%`, `no new TODOs`
+    pub fn describe(self, threshold: Option<i64>) -> String {
+        match (self, threshold) {
+            (GateRule::MaxFileLoc, Some(n)) => format!("max file LOC {}", n),
+            (GateRule::MinDocCoverage, Some(n)) => format!("doc coverage ≥ {}%", n),
+            _ => self.label().to_lowercase(),
+        }
+    }
+}
+
+/// One enabled rule of a project's quality gate
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct QualityGate {
+    pub id: i64,
+    pub project_id: i64,
+    /// `GateRule::key` value
+    pub rule: String,
+    /// Limit of rules that have one
+    pub threshold: Option<i64>,
+}
+
+impl QualityGate {
+    pub fn new(id: i64, project_id: i64, rule: String, threshold: Option<i64>) -> Self {
+        Self {
+            id,
+            project_id,
+            rule,
+            threshold,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::QualityGate;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n019_quality_gate (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            project_id INTEGER NOT NULL,
+            rule TEXT NOT NULL,
+            threshold INTEGER,
+            FOREIGN KEY (project_id) REFERENCES n001_project(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_quality_gate_project_id
+         ON n019_quality_gate(project_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn list_by_project(conn: &Connection, project_id: i64) -> SqlResult<Vec<QualityGate>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, project_id, rule, threshold
+         FROM n019_quality_gate
+         WHERE project_id = ?1
+         ORDER BY id",
+    )?;
+
+    let rows = stmt.query_map([project_id], |row| {
+        Ok(QualityGate::new(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+/// Replace the project's gate with `rules` (rule key, threshold)
+pub fn set_for_project(
+    conn: &Connection,
+    project_id: i64,
+    rules: &[(&str, Option<i64>)],
+) -> SqlResult<()> {
+    delete_by_project(conn, project_id)?;
+    for (rule, threshold) in rules {
+        conn.execute(
+            "INSERT INTO n019_quality_gate (project_id, rule, threshold) VALUES (?1, ?2, ?3)",
+            params![project_id, rule, threshold],
+        )?;
+    }
+    Ok(())
+}
+
+pub fn delete_by_project(conn: &Connection, project_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n019_quality_gate WHERE project_id = ?1",
+        [project_id],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use eframe::egui;
+
+use super::model::{GateRule, QualityGate};
+
+/// One rule of the gate editor
+#[derive(Clone)]
+pub struct GateRow {
+    pub rule: GateRule,
+    pub enabled: bool,
+    pub threshold: String,
+}
+
+/// Editable quality gate of a project: every rule, enabled or not
+#[derive(Clone)]
+pub struct GatesFormState {
+    pub rows: Vec<GateRow>,
+}
+
+impl Default for GatesFormState {
+    fn default() -> Self {
+        Self {
+            rows: GateRule::ALL
+                .into_iter()
+                .map(|rule| GateRow {
+                    rule,
+                    enabled: false,
+                    threshold: rule
+                        .default_threshold()
+                        .map(|t| t.to_string())
+                        .unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl GatesFormState {
+    pub fn from_gates(gates: &[QualityGate]) -> Self {
+        let mut state = Self::default();
+        for gate in gates {
+            if let Some(row) = state.rows.iter_mut().find(|r| r.rule.key() == gate.rule) {
+                row.enabled = true;
+                if let Some(t) = gate.threshold {
+                    row.threshold = t.to_string();
+                }
+            }
+        }
+        state
+    }
+
+    /// Enabled rules as (rule key, threshold) for `repository::set_for_project`
+    pub fn rules(&self) -> Result<Vec<(&'static str, Option<i64>)>, String> {
+        self.rows
+            .iter()
+            .filter(|r| r.enabled)
+            .map(|r| {
+                if !r.rule.has_threshold() {
+                    return Ok((r.rule.key(), None));
+                }
+                match r.threshold.trim().parse::<i64>() {
+                    Ok(t) if t >= 0 => Ok((r.rule.key(), Some(t))),
+                    _ => Err(format!("{}: enter a non-negative number", r.rule.label())),
+                }
+            })
+            .collect()
+    }
+
+    /// Enabled rules as text, for the read-only project view
+    pub fn summary(&self) -> String {
+        let enabled: Vec<String> = self
+            .rows
+            .iter()
+            .filter(|r| r.enabled)
+            .map(|r| r.rule.describe(r.threshold.trim().parse().ok()))
+            .collect();
+        if enabled.is_empty() {
+            "none".to_string()
+        } else {
+            enabled.join(", ")
+        }
+    }
+}
+
+pub fn gates_form(ui: &mut egui::Ui, state: &mut GatesFormState) {
+    ui.label(egui::RichText::new("Quality gates").strong());
+    egui::Grid::new("n019_quality_gates")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for row in &mut state.rows {
+                ui.checkbox(&mut row.enabled, row.rule.label());
+                if row.rule.has_threshold() {
+                    ui.add_enabled(
+                        row.enabled,
+                        egui::TextEdit::singleline(&mut row.threshold).desired_width(60.0),
+                    );
+                }
+                ui.end_row();
+            }
+        });
+}
@@ -0,0 +1,34 @@
+/// Outcome of one quality gate rule for a snapshot, recorded at scan time
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct GateResult {
+    pub id: i64,
+    pub snapshot_id: i64,
+    /// `GateRule::key` value
+    pub rule: String,
+    /// Threshold the rule was evaluated with
+    pub threshold: Option<i64>,
+    pub passed: bool,
+    /// What was measured, e.g. `largest file 950 LOC: src/main.rs`
+    pub detail: String,
+}
+
+impl GateResult {
+    pub fn new(
+        id: i64,
+        snapshot_id: i64,
+        rule: String,
+        threshold: Option<i64>,
+        passed: bool,
+        detail: String,
+    ) -> Self {
+        Self {
+            id,
+            snapshot_id,
+            rule,
+            threshold,
+            passed,
+            detail,
+        }
+    }
+}
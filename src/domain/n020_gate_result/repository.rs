@@ -0,0 +1,101 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+
+use super::model::GateResult;
+
+pub fn init_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS n020_gate_result (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            snapshot_id INTEGER NOT NULL,
+            rule TEXT NOT NULL,
+            threshold INTEGER,
+            passed INTEGER NOT NULL,
+            detail TEXT NOT NULL,
+            FOREIGN KEY (snapshot_id) REFERENCES n002_snapshot(id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_gate_result_snapshot_id
+         ON n020_gate_result(snapshot_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn create(conn: &Connection, result: &GateResult) -> SqlResult<i64> {
+    conn.execute(
+        "INSERT INTO n020_gate_result (snapshot_id, rule, threshold, passed, detail)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            result.snapshot_id,
+            result.rule,
+            result.threshold,
+            result.passed,
+            result.detail
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn list_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<Vec<GateResult>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, snapshot_id, rule, threshold, passed, detail
+         FROM n020_gate_result
+         WHERE snapshot_id = ?1
+         ORDER BY id",
+    )?;
+
+    let rows = stmt.query_map([snapshot_id], |row| {
+        Ok(GateResult::new(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+/// Results of every snapshot, for the status badges of the snapshot list
+pub fn list_all(conn: &Connection) -> SqlResult<Vec<GateResult>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, snapshot_id, rule, threshold, passed, detail
+         FROM n020_gate_result
+         ORDER BY snapshot_id, id",
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok(GateResult::new(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+pub fn delete_by_snapshot(conn: &Connection, snapshot_id: i64) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM n020_gate_result WHERE snapshot_id = ?1",
+        [snapshot_id],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use eframe::egui;
+
+use super::model::GateResult;
+use crate::domain::n019_quality_gate::model::GateRule;
+
+/// `max file LOC 800: largest file 650 LOC: src/main.rs`
+pub fn result_line(result: &GateResult) -> String {
+    let rule = match GateRule::parse(&result.rule) {
+        Some(rule) => rule.describe(result.threshold),
+        None => result.rule.clone(),
+    };
+    format!(
+        "{} {}: {}",
+        if result.passed { "✔" } else { "✖" },
+        rule,
+        result.detail
+    )
+}
+
+/// Pass/fail badge of a snapshot's quality gate; hovering lists every rule
+pub fn gate_badge(ui: &mut egui::Ui, results: &[GateResult], size: f32) {
+    if results.is_empty() {
+        ui.label(egui::RichText::new("—").size(size).weak());
+        return;
+    }
+    let failed = results.iter().filter(|r| !r.passed).count();
+    let (text, color) = if failed == 0 {
+        ("✔ Passed".to_string(), egui::Color32::LIGHT_GREEN)
+    } else {
+        (format!("✖ {} failed", failed), egui::Color32::LIGHT_RED)
+    };
+    ui.label(egui::RichText::new(text).size(size).color(color))
+        .on_hover_ui(|ui| {
+            for result in results {
+                ui.label(result_line(result));
+            }
+        });
+}
@@ -53,11 +53,27 @@ pub enum AggregatesAction {
     Snapshots,
     SnapshotFiles,
     SnapshotAggregates,
+    Authors,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum UseCasesAction {
     ScanSnapshot,
+    AuditDependencies,
+    SearchContents,
+    RegexSearch,
+    CompareSnapshots,
+    Hotspots,
+    Ownership,
+    Complexity,
+    Markers,
+    Safety,
+    Coverage,
+    ApiSurface,
+    DocCoverage,
+    Languages,
+    Dependencies,
+    ProjectDashboard,
 }
 
 impl Default for MenuBar {
@@ -165,6 +181,10 @@ impl MenuBar {
                 self.aggregates_action = Some(AggregatesAction::SnapshotAggregates);
                 ui.close_menu();
             }
+            if ui.button("Authors").clicked() {
+                self.aggregates_action = Some(AggregatesAction::Authors);
+                ui.close_menu();
+            }
         });
     }
 
@@ -174,6 +194,66 @@ impl MenuBar {
                 self.usecases_action = Some(UseCasesAction::ScanSnapshot);
                 ui.close_menu();
             }
+            if ui.button("Dependency Audit").clicked() {
+                self.usecases_action = Some(UseCasesAction::AuditDependencies);
+                ui.close_menu();
+            }
+            if ui.button("Search").clicked() {
+                self.usecases_action = Some(UseCasesAction::SearchContents);
+                ui.close_menu();
+            }
+            if ui.button("Regex Search").clicked() {
+                self.usecases_action = Some(UseCasesAction::RegexSearch);
+                ui.close_menu();
+            }
+            if ui.button("Compare Snapshots").clicked() {
+                self.usecases_action = Some(UseCasesAction::CompareSnapshots);
+                ui.close_menu();
+            }
+            if ui.button("Churn & Hotspots").clicked() {
+                self.usecases_action = Some(UseCasesAction::Hotspots);
+                ui.close_menu();
+            }
+            if ui.button("Ownership").clicked() {
+                self.usecases_action = Some(UseCasesAction::Ownership);
+                ui.close_menu();
+            }
+            if ui.button("Complexity").clicked() {
+                self.usecases_action = Some(UseCasesAction::Complexity);
+                ui.close_menu();
+            }
+            if ui.button("Debt Markers").clicked() {
+                self.usecases_action = Some(UseCasesAction::Markers);
+                ui.close_menu();
+            }
+            if ui.button("Unsafe & Panics").clicked() {
+                self.usecases_action = Some(UseCasesAction::Safety);
+                ui.close_menu();
+            }
+            if ui.button("Coverage").clicked() {
+                self.usecases_action = Some(UseCasesAction::Coverage);
+                ui.close_menu();
+            }
+            if ui.button("API Surface").clicked() {
+                self.usecases_action = Some(UseCasesAction::ApiSurface);
+                ui.close_menu();
+            }
+            if ui.button("Doc Coverage").clicked() {
+                self.usecases_action = Some(UseCasesAction::DocCoverage);
+                ui.close_menu();
+            }
+            if ui.button("Languages").clicked() {
+                self.usecases_action = Some(UseCasesAction::Languages);
+                ui.close_menu();
+            }
+            if ui.button("Dependencies").clicked() {
+                self.usecases_action = Some(UseCasesAction::Dependencies);
+                ui.close_menu();
+            }
+            if ui.button("Project Dashboard").clicked() {
+                self.usecases_action = Some(UseCasesAction::ProjectDashboard);
+                ui.close_menu();
+            }
         });
     }
 
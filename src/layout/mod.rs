@@ -6,6 +6,6 @@ pub mod side_panel;
 
 pub use central_panel::CentralPanel;
 pub use menu_bar::MenuBar;
-pub use panels::AppTab;
+pub use panels::{AppTab, TabStates};
 pub use settings::{SettingsForm, Theme};
 pub use side_panel::SidePanel;
@@ -2,11 +2,30 @@ use eframe::egui;
 use egui_dock::TabViewer;
 use rusqlite::Connection;
 
-use crate::domain::n001_project::ui::list::{ui_projects_list, ProjectsListState};
+use crate::domain::n001_project::ui::list::{ui_projects_list, ProjectAction, ProjectsListState};
 use crate::domain::n002_snapshot::ui::list::{ui_snapshots_list, SnapshotsListState};
 use crate::domain::n003_snapshot_file::ui::list::{ui_list, ListState};
-use crate::domain::n004_snapshot_aggregate::ui::list::{ui_list as ui_aggregates_list, ListState as AggregatesListState};
+use crate::domain::n003_snapshot_file::ui::preview::{ui_preview, FilePreviewState};
+use crate::domain::n004_snapshot_aggregate::ui::list::{
+    ui_list as ui_aggregates_list, ListState as AggregatesListState,
+};
+use crate::domain::n009_author::ui::list::{ui_authors_list, AuthorsListState};
 use crate::usecases::s501_create_snapshot::{ui_scan_snapshot, ScanSnapshotState};
+use crate::usecases::s502_audit_dependencies::{ui_audit, AuditState};
+use crate::usecases::s503_search_contents::{ui_search, SearchState};
+use crate::usecases::s504_regex_search::{ui_regex_search, RegexSearchState};
+use crate::usecases::s505_compare_snapshots::{ui_compare, ui_diff, CompareState, DiffViewState};
+use crate::usecases::s506_git_churn::{ui_churn, ChurnState};
+use crate::usecases::s507_ownership::{ui_ownership, OwnershipState};
+use crate::usecases::s508_complexity::{ui_complexity, ComplexityState};
+use crate::usecases::s509_markers::{ui_markers, MarkersState};
+use crate::usecases::s510_safety::{ui_safety, SafetyState};
+use crate::usecases::s511_coverage::{ui_coverage, CoverageState};
+use crate::usecases::s512_api_surface::{ui_api_surface, ApiSurfaceState};
+use crate::usecases::s513_doc_coverage::{ui_doc_coverage, DocCoverageState};
+use crate::usecases::s514_languages::{ui_languages, LanguagesState};
+use crate::usecases::s518_dependencies::{ui_dependencies, DependenciesState};
+use crate::usecases::s520_project_dashboard::{ui_dashboard, DashboardAction, DashboardState};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum AppTab {
@@ -14,7 +33,25 @@ pub enum AppTab {
     Snapshots,
     SnapshotFiles,
     SnapshotAggregates,
+    Authors,
+    FilePreview,
     ScanSnapshot,
+    AuditDependencies,
+    SearchContents,
+    RegexSearch,
+    CompareSnapshots,
+    FileDiff,
+    Hotspots,
+    Ownership,
+    Complexity,
+    Markers,
+    Safety,
+    Coverage,
+    ApiSurface,
+    DocCoverage,
+    Languages,
+    Dependencies,
+    ProjectDashboard,
 }
 
 impl AppTab {
@@ -24,18 +61,64 @@ impl AppTab {
             AppTab::Snapshots => "Snapshots",
             AppTab::SnapshotFiles => "Snapshot Files",
             AppTab::SnapshotAggregates => "Snapshot Aggregates",
+            AppTab::Authors => "Authors",
+            AppTab::FilePreview => "File Preview",
             AppTab::ScanSnapshot => "Scan Snapshot",
+            AppTab::AuditDependencies => "Dependency Audit",
+            AppTab::SearchContents => "Search",
+            AppTab::RegexSearch => "Regex Search",
+            AppTab::CompareSnapshots => "Compare Snapshots",
+            AppTab::FileDiff => "File Diff",
+            AppTab::Hotspots => "Hotspots",
+            AppTab::Ownership => "Ownership",
+            AppTab::Complexity => "Complexity",
+            AppTab::Markers => "Markers",
+            AppTab::Safety => "Safety",
+            AppTab::Coverage => "Coverage",
+            AppTab::ApiSurface => "API Surface",
+            AppTab::DocCoverage => "Doc Coverage",
+            AppTab::Languages => "Languages",
+            AppTab::Dependencies => "Dependencies",
+            AppTab::ProjectDashboard => "Project Dashboard",
         }
     }
 }
 
+/// UI state of every tab, shared by the navigation and content dock areas
+#[derive(Default)]
+pub struct TabStates {
+    // Domain UI states
+    pub projects: ProjectsListState,
+    pub snapshots: SnapshotsListState,
+    pub snapshot_files: ListState,
+    pub snapshot_aggregates: AggregatesListState,
+    pub authors: AuthorsListState,
+    pub file_preview: FilePreviewState,
+    // Usecase UI states
+    pub scan_snapshot: ScanSnapshotState,
+    pub audit: AuditState,
+    pub search: SearchState,
+    pub regex_search: RegexSearchState,
+    pub compare: CompareState,
+    pub file_diff: DiffViewState,
+    pub churn: ChurnState,
+    pub ownership: OwnershipState,
+    pub complexity: ComplexityState,
+    pub markers: MarkersState,
+    pub safety: SafetyState,
+    pub coverage: CoverageState,
+    pub api_surface: ApiSurfaceState,
+    pub doc_coverage: DocCoverageState,
+    pub languages: LanguagesState,
+    pub dependencies: DependenciesState,
+    pub dashboard: DashboardState,
+    /// Tab another tab asked to open/focus (handled after the dock is drawn)
+    pub pending_tab: Option<AppTab>,
+}
+
 pub struct DualTabViewer<'a> {
     pub db_connection: &'a Connection,
-    pub projects_state: &'a mut ProjectsListState,
-    pub snapshots_state: &'a mut SnapshotsListState,
-    pub snapshot_files_state: &'a mut ListState,
-    pub snapshot_aggregates_state: &'a mut AggregatesListState,
-    pub scan_snapshot_state: &'a mut ScanSnapshotState,
+    pub states: &'a mut TabStates,
 }
 
 impl<'a> TabViewer for DualTabViewer<'a> {
@@ -49,22 +132,190 @@ impl<'a> TabViewer for DualTabViewer<'a> {
         match tab {
             AppTab::Projects => {
                 ui.heading("Projects");
-                ui_projects_list(ui, self.db_connection, self.projects_state);
+                match ui_projects_list(ui, self.db_connection, &mut self.states.projects) {
+                    Some(ProjectAction::Dashboard(project_id)) => {
+                        self.states.dashboard.open(self.db_connection, project_id);
+                        self.states.pending_tab = Some(AppTab::ProjectDashboard);
+                    }
+                    Some(ProjectAction::Scan(project_id)) => {
+                        self.states
+                            .dashboard
+                            .new_scan(self.db_connection, project_id);
+                        self.states.pending_tab = Some(AppTab::ProjectDashboard);
+                    }
+                    None => {}
+                }
             }
             AppTab::Snapshots => {
                 ui.heading("Snapshots");
-                ui_snapshots_list(ui, self.db_connection, self.snapshots_state);
+                ui_snapshots_list(ui, self.db_connection, &mut self.states.snapshots);
             }
             AppTab::SnapshotFiles => {
                 ui.heading("Snapshot Files");
-                ui_list(ui, self.db_connection, self.snapshot_files_state);
+                if let Some(file_id) =
+                    ui_list(ui, self.db_connection, &mut self.states.snapshot_files)
+                {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, None);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
             }
             AppTab::SnapshotAggregates => {
                 ui.heading("Snapshot Aggregates");
-                ui_aggregates_list(ui, self.db_connection, self.snapshot_aggregates_state);
+                ui_aggregates_list(ui, self.db_connection, &mut self.states.snapshot_aggregates);
+            }
+            AppTab::Authors => {
+                ui.heading("Authors");
+                ui_authors_list(ui, self.db_connection, &mut self.states.authors);
+            }
+            AppTab::FilePreview => {
+                ui_preview(ui, &mut self.states.file_preview);
             }
             AppTab::ScanSnapshot => {
-                ui_scan_snapshot(ui, self.db_connection, self.scan_snapshot_state);
+                ui_scan_snapshot(ui, self.db_connection, &mut self.states.scan_snapshot);
+            }
+            AppTab::AuditDependencies => {
+                ui_audit(ui, self.db_connection, &mut self.states.audit);
+            }
+            AppTab::SearchContents => {
+                if let Some((file_id, line)) =
+                    ui_search(ui, self.db_connection, &mut self.states.search)
+                {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, line);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
+            }
+            AppTab::RegexSearch => {
+                if let Some((file_id, line)) =
+                    ui_regex_search(ui, self.db_connection, &mut self.states.regex_search)
+                {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, line);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
+            }
+            AppTab::CompareSnapshots => {
+                if let Some((base_file_id, target_file_id)) =
+                    ui_compare(ui, self.db_connection, &mut self.states.compare)
+                {
+                    self.states
+                        .file_diff
+                        .open(self.db_connection, base_file_id, target_file_id);
+                    self.states.pending_tab = Some(AppTab::FileDiff);
+                }
+            }
+            AppTab::FileDiff => {
+                ui_diff(ui, &mut self.states.file_diff);
+            }
+            AppTab::Hotspots => {
+                if let Some(file_id) = ui_churn(ui, self.db_connection, &mut self.states.churn) {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, None);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
+            }
+            AppTab::Ownership => {
+                if let Some(file_id) =
+                    ui_ownership(ui, self.db_connection, &mut self.states.ownership)
+                {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, None);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
+            }
+            AppTab::Complexity => {
+                if let Some((file_id, line)) =
+                    ui_complexity(ui, self.db_connection, &mut self.states.complexity)
+                {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, line);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
+            }
+            AppTab::Markers => {
+                if let Some((file_id, line)) =
+                    ui_markers(ui, self.db_connection, &mut self.states.markers)
+                {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, line);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
+            }
+            AppTab::Safety => {
+                if let Some((file_id, line)) =
+                    ui_safety(ui, self.db_connection, &mut self.states.safety)
+                {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, line);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
+            }
+            AppTab::Coverage => {
+                if let Some((file_id, line)) =
+                    ui_coverage(ui, self.db_connection, &mut self.states.coverage)
+                {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, line);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
+            }
+            AppTab::ApiSurface => {
+                if let Some((file_id, line)) =
+                    ui_api_surface(ui, self.db_connection, &mut self.states.api_surface)
+                {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, line);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
+            }
+            AppTab::DocCoverage => {
+                if let Some((file_id, line)) =
+                    ui_doc_coverage(ui, self.db_connection, &mut self.states.doc_coverage)
+                {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, line);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
+            }
+            AppTab::Languages => {
+                ui_languages(ui, self.db_connection, &mut self.states.languages);
+            }
+            AppTab::Dependencies => {
+                if let Some((file_id, line)) =
+                    ui_dependencies(ui, self.db_connection, &mut self.states.dependencies)
+                {
+                    self.states
+                        .file_preview
+                        .open(self.db_connection, file_id, line);
+                    self.states.pending_tab = Some(AppTab::FilePreview);
+                }
+            }
+            AppTab::ProjectDashboard => {
+                match ui_dashboard(ui, self.db_connection, &mut self.states.dashboard) {
+                    Some(DashboardAction::Compare { base, target }) => {
+                        self.states.compare.open(self.db_connection, base, target);
+                        self.states.pending_tab = Some(AppTab::CompareSnapshots);
+                    }
+                    Some(DashboardAction::OpenFile(file_id)) => {
+                        self.states
+                            .file_preview
+                            .open(self.db_connection, file_id, None);
+                        self.states.pending_tab = Some(AppTab::FilePreview);
+                    }
+                    None => {}
+                }
             }
         }
     }
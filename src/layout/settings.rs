@@ -12,10 +12,16 @@ pub struct SettingsForm {
     temp_theme: Theme,
     temp_zoom: f32,
     temp_navbar_width_frac: f32,
+    temp_advisory_db_path: String,
+    temp_audit_allowed_licenses: String,
+    temp_audit_denied_advisories: String,
     // Applied values
     pub current_theme: Theme,
     pub current_zoom: f32,
     pub current_navbar_width_frac: f32,
+    pub current_advisory_db_path: String,
+    pub current_audit_allowed_licenses: String,
+    pub current_audit_denied_advisories: String,
 }
 
 impl Default for SettingsForm {
@@ -31,9 +37,15 @@ impl SettingsForm {
             temp_theme: Theme::Dark,
             temp_zoom: 1.0,
             temp_navbar_width_frac: 0.20,
+            temp_advisory_db_path: String::new(),
+            temp_audit_allowed_licenses: String::new(),
+            temp_audit_denied_advisories: String::new(),
             current_theme: Theme::Dark,
             current_zoom: 1.0,
             current_navbar_width_frac: 0.20,
+            current_advisory_db_path: String::new(),
+            current_audit_allowed_licenses: String::new(),
+            current_audit_denied_advisories: String::new(),
         }
     }
 
@@ -43,9 +55,15 @@ impl SettingsForm {
             temp_theme: settings.theme,
             temp_zoom: settings.zoom,
             temp_navbar_width_frac: settings.navbar_width_frac,
+            temp_advisory_db_path: settings.advisory_db_path.clone(),
+            temp_audit_allowed_licenses: settings.audit_allowed_licenses.clone(),
+            temp_audit_denied_advisories: settings.audit_denied_advisories.clone(),
             current_theme: settings.theme,
             current_zoom: settings.zoom,
             current_navbar_width_frac: settings.navbar_width_frac,
+            current_advisory_db_path: settings.advisory_db_path.clone(),
+            current_audit_allowed_licenses: settings.audit_allowed_licenses.clone(),
+            current_audit_denied_advisories: settings.audit_denied_advisories.clone(),
         }
     }
 
@@ -137,6 +155,41 @@ impl SettingsForm {
 
                 ui.add_space(20.0);
 
+                // Dependency audit
+                ui.group(|ui| {
+                    ui.label("Dependency Audit:");
+                    ui.add_space(5.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Advisory DB:");
+                        ui.text_edit_singleline(&mut self.temp_advisory_db_path);
+                        if ui.button("Browse...").clicked() {
+                            if let Some(path) = rfd::FileDialog::new()
+                                .set_directory(&self.temp_advisory_db_path)
+                                .pick_folder()
+                            {
+                                self.temp_advisory_db_path = path.to_string_lossy().to_string();
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Allowed licenses:");
+                        ui.text_edit_singleline(&mut self.temp_audit_allowed_licenses);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Denied advisories:");
+                        ui.text_edit_singleline(&mut self.temp_audit_denied_advisories);
+                    });
+                    ui.label(
+                        egui::RichText::new(
+                            "Comma-separated, e.g. MIT, Apache-2.0 / RUSTSEC-2020-0071",
+                        )
+                        .weak(),
+                    );
+                });
+
+                ui.add_space(20.0);
+
                 // Action buttons
                 ui.separator();
                 ui.add_space(10.0);
@@ -146,6 +199,11 @@ impl SettingsForm {
                         self.current_theme = self.temp_theme;
                         self.current_zoom = self.temp_zoom;
                         self.current_navbar_width_frac = self.temp_navbar_width_frac;
+                        self.current_advisory_db_path = self.temp_advisory_db_path.clone();
+                        self.current_audit_allowed_licenses =
+                            self.temp_audit_allowed_licenses.clone();
+                        self.current_audit_denied_advisories =
+                            self.temp_audit_denied_advisories.clone();
                         settings_changed = true;
                         should_close = true;
                     }
@@ -157,6 +215,11 @@ impl SettingsForm {
                         self.temp_theme = self.current_theme;
                         self.temp_zoom = self.current_zoom;
                         self.temp_navbar_width_frac = self.current_navbar_width_frac;
+                        self.temp_advisory_db_path = self.current_advisory_db_path.clone();
+                        self.temp_audit_allowed_licenses =
+                            self.current_audit_allowed_licenses.clone();
+                        self.temp_audit_denied_advisories =
+                            self.current_audit_denied_advisories.clone();
                         should_close = true;
                     }
                 });
@@ -179,13 +242,26 @@ impl SettingsForm {
     pub fn get_zoom(&self) -> f32 {
         self.current_zoom
     }
+
+    /// Build the full settings record from applied values
+    pub fn to_app_settings(&self) -> crate::app_settings::AppSettings {
+        crate::app_settings::AppSettings {
+            theme: self.current_theme,
+            zoom: self.current_zoom,
+            navbar_width_frac: self.current_navbar_width_frac,
+            advisory_db_path: self.current_advisory_db_path.clone(),
+            audit_allowed_licenses: self.current_audit_allowed_licenses.clone(),
+            audit_denied_advisories: self.current_audit_denied_advisories.clone(),
+        }
+    }
 }
 
 impl SettingsForm {
-    pub fn get_navbar_width_frac(&self) -> f32 { self.current_navbar_width_frac }
+    pub fn get_navbar_width_frac(&self) -> f32 {
+        self.current_navbar_width_frac
+    }
     pub fn set_current_navbar_width_frac(&mut self, frac: f32) {
         self.current_navbar_width_frac = frac.clamp(0.10, 0.50);
         self.temp_navbar_width_frac = self.current_navbar_width_frac;
     }
 }
-
@@ -2,12 +2,7 @@ use eframe::egui;
 use egui_dock::{DockArea, DockState, Style};
 use rusqlite::Connection;
 
-use super::panels::{AppTab, DualTabViewer};
-use crate::domain::n001_project::ui::list::ProjectsListState;
-use crate::domain::n002_snapshot::ui::list::SnapshotsListState;
-use crate::domain::n003_snapshot_file::ui::list::ListState;
-use crate::domain::n004_snapshot_aggregate::ui::list::ListState as AggregatesListState;
-use crate::usecases::s501_create_snapshot::ScanSnapshotState;
+use super::panels::{AppTab, DualTabViewer, TabStates};
 
 pub struct SidePanel;
 
@@ -18,11 +13,7 @@ impl SidePanel {
         ctx: &egui::Context,
         dock_state: &mut DockState<AppTab>,
         db_connection: &Connection,
-        projects_state: &mut ProjectsListState,
-        snapshots_state: &mut SnapshotsListState,
-        snapshot_files_state: &mut ListState,
-        snapshot_aggregates_state: &mut AggregatesListState,
-        scan_snapshot_state: &mut ScanSnapshotState,
+        states: &mut TabStates,
         screen_width: f32,
         stored_width_fraction: f32,
         last_width: f32,
@@ -62,11 +53,7 @@ impl SidePanel {
                     ui,
                     &mut DualTabViewer {
                         db_connection,
-                        projects_state,
-                        snapshots_state,
-                        snapshot_files_state,
-                        snapshot_aggregates_state,
-                        scan_snapshot_state,
+                        states,
                     },
                 );
         });
@@ -5,20 +5,22 @@ use std::time::{Duration, Instant};
 
 mod app;
 mod app_settings;
+mod cli;
 mod domain;
 mod layout;
 mod shared;
 mod usecases;
 
 use app_settings::AppSettings;
-use domain::n001_project::ui::list::ProjectsListState;
-use domain::n002_snapshot::ui::list::SnapshotsListState;
-use domain::n003_snapshot_file::ui::list::ListState;
-use domain::n004_snapshot_aggregate::ui::list::ListState as AggregatesListState;
-use layout::{AppTab, CentralPanel, MenuBar, SettingsForm, SidePanel};
-use usecases::s501_create_snapshot::ScanSnapshotState;
+use layout::{AppTab, CentralPanel, MenuBar, SettingsForm, SidePanel, TabStates};
 
 fn main() -> eframe::Result<()> {
+    // Headless CLI mode when a command is given
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1200.0, 800.0])
@@ -41,13 +43,8 @@ struct MyApp {
     db_connection: Connection,
     db_items: Vec<(i32, String)>,
     db_status: String,
-    // Domain UI states
-    projects_state: ProjectsListState,
-    snapshots_state: SnapshotsListState,
-    snapshot_files_state: ListState,
-    snapshot_aggregates_state: AggregatesListState,
-    // Usecase UI states
-    scan_snapshot_state: ScanSnapshotState,
+    // Domain and usecase UI states
+    tab_states: TabStates,
     // Menu & settings
     menu_bar: MenuBar,
     settings_form: SettingsForm,
@@ -74,9 +71,62 @@ impl MyApp {
     fn open_snapshot_aggregates_tab(&mut self) {
         self.open_or_focus(AppTab::SnapshotAggregates);
     }
+    fn open_authors_tab(&mut self) {
+        self.open_or_focus(AppTab::Authors);
+    }
     fn open_scan_snapshot_tab(&mut self) {
         self.open_or_focus(AppTab::ScanSnapshot);
     }
+    fn open_audit_tab(&mut self) {
+        self.open_or_focus(AppTab::AuditDependencies);
+    }
+    fn open_search_tab(&mut self) {
+        self.open_or_focus(AppTab::SearchContents);
+    }
+    fn open_regex_search_tab(&mut self) {
+        self.open_or_focus(AppTab::RegexSearch);
+    }
+    fn open_compare_tab(&mut self) {
+        self.open_or_focus(AppTab::CompareSnapshots);
+    }
+    fn open_hotspots_tab(&mut self) {
+        self.open_or_focus(AppTab::Hotspots);
+    }
+    fn open_ownership_tab(&mut self) {
+        self.open_or_focus(AppTab::Ownership);
+    }
+    fn open_complexity_tab(&mut self) {
+        self.open_or_focus(AppTab::Complexity);
+    }
+    fn open_markers_tab(&mut self) {
+        self.open_or_focus(AppTab::Markers);
+    }
+    fn open_safety_tab(&mut self) {
+        self.open_or_focus(AppTab::Safety);
+    }
+    fn open_coverage_tab(&mut self) {
+        self.open_or_focus(AppTab::Coverage);
+    }
+
+    fn open_api_surface_tab(&mut self) {
+        self.open_or_focus(AppTab::ApiSurface);
+    }
+
+    fn open_doc_coverage_tab(&mut self) {
+        self.open_or_focus(AppTab::DocCoverage);
+    }
+
+    fn open_languages_tab(&mut self) {
+        self.open_or_focus(AppTab::Languages);
+    }
+
+    fn open_dependencies_tab(&mut self) {
+        self.open_or_focus(AppTab::Dependencies);
+    }
+
+    fn open_dashboard_tab(&mut self) {
+        self.open_or_focus(AppTab::ProjectDashboard);
+    }
 
     fn open_or_focus(&mut self, tab: AppTab) {
         let ds = &mut self.dock_content;
@@ -111,11 +161,7 @@ impl MyApp {
             menu_bar: MenuBar::new(),
             settings_form: SettingsForm::new_with_settings(&saved_settings),
             first_frame: true,
-            projects_state: ProjectsListState::default(),
-            snapshots_state: SnapshotsListState::default(),
-            snapshot_files_state: ListState::default(),
-            snapshot_aggregates_state: AggregatesListState::default(),
-            scan_snapshot_state: ScanSnapshotState::default(),
+            tab_states: TabStates::default(),
             saved_navbar_width_frac: saved_settings.navbar_width_frac,
             pending_nav_frac: None,
             nav_save_deadline: None,
@@ -178,11 +224,7 @@ impl eframe::App for MyApp {
                 ctx,
                 &mut self.dock_nav,
                 &self.db_connection,
-                &mut self.projects_state,
-                &mut self.snapshots_state,
-                &mut self.snapshot_files_state,
-                &mut self.snapshot_aggregates_state,
-                &mut self.scan_snapshot_state,
+                &mut self.tab_states,
                 screen_w,
                 stored_frac,
                 self.last_nav_w,
@@ -210,12 +252,9 @@ impl eframe::App for MyApp {
         if let (Some(target_frac), Some(deadline)) = (self.pending_nav_frac, self.nav_save_deadline)
         {
             if Instant::now() >= deadline {
-                let theme = self.settings_form.get_theme();
-                let zoom = self.settings_form.get_zoom();
                 let app_settings = AppSettings {
-                    theme,
-                    zoom,
                     navbar_width_frac: target_frac,
+                    ..self.settings_form.to_app_settings()
                 };
                 if app_settings.save_to_db(&self.db_connection).is_ok() {
                     self.saved_navbar_width_frac = target_frac;
@@ -230,11 +269,12 @@ impl eframe::App for MyApp {
             ctx,
             &mut self.dock_content,
             &self.db_connection,
-            &mut self.projects_state,
-            &mut self.snapshots_state,
-            &mut self.snapshot_files_state,
-            &mut self.snapshot_aggregates_state,
-            &mut self.scan_snapshot_state,
+            &mut self.tab_states,
         );
+
+        // 8. Tabs requested by other tabs (e.g. search hit → preview)
+        if let Some(tab) = self.tab_states.pending_tab.take() {
+            self.open_or_focus(tab);
+        }
     }
 }
@@ -0,0 +1,75 @@
+/// Dependency rules between artifact types (`domain`, `usecase`, `shared`) and crate layers
+/// (`backend`, `frontend`, `contracts`): (from, forbidden to, rule)
+const TYPE_RULES: [(&str, &str, &str); 3] = [
+    ("shared", "domain", "shared must not depend on domain"),
+    ("shared", "usecase", "shared must not depend on use cases"),
+    ("domain", "usecase", "domain must not depend on use cases"),
+];
+const LAYER_RULES: [(&str, &str, &str); 4] = [
+    (
+        "contracts",
+        "backend",
+        "contracts must not depend on backend",
+    ),
+    (
+        "contracts",
+        "frontend",
+        "contracts must not depend on frontend",
+    ),
+    ("frontend", "backend", "frontend must not depend on backend"),
+    ("backend", "frontend", "backend must not depend on frontend"),
+];
+
+/// Position of a file: its crate layer and artifact type, as classified by the scanner
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Placement<'a> {
+    pub layer: Option<&'a str>,
+    pub artifact_type: Option<&'a str>,
+}
+
+/// The rule a dependency from `from` on `to` breaks, if any
+pub fn violated_rule(from: Placement, to: Placement) -> Option<&'static str> {
+    let find = |rules: &[(&str, &str, &'static str)], a: Option<&str>, b: Option<&str>| {
+        let (a, b) = (a?, b?);
+        rules
+            .iter()
+            .find(|(f, t, _)| *f == a && *t == b)
+            .map(|(_, _, rule)| *rule)
+    };
+    find(&TYPE_RULES, from.artifact_type, to.artifact_type)
+        .or_else(|| find(&LAYER_RULES, from.layer, to.layer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_types_then_layers() {
+        let at = |layer, artifact_type| Placement {
+            layer,
+            artifact_type,
+        };
+        let domain = at(Some("backend"), Some("domain"));
+        assert_eq!(
+            violated_rule(domain, at(Some("backend"), Some("usecase"))),
+            Some("domain must not depend on use cases")
+        );
+        assert_eq!(
+            violated_rule(at(Some("backend"), Some("usecase")), domain),
+            None
+        );
+        assert_eq!(
+            violated_rule(at(Some("contracts"), None), domain),
+            Some("contracts must not depend on backend")
+        );
+        assert_eq!(
+            violated_rule(
+                at(Some("frontend"), Some("usecase")),
+                at(Some("contracts"), Some("shared"))
+            ),
+            None
+        );
+        assert_eq!(violated_rule(at(None, None), domain), None);
+    }
+}
@@ -0,0 +1,225 @@
+use super::rust_syntax::{code_tokens, functions, tokenize, FnItem, Token, TokenKind};
+
+/// Size and complexity of one Rust function
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionMetrics {
+    /// `Type::name` for methods
+    pub name: String,
+    /// 1-based line of the `fn` keyword
+    pub line: usize,
+    /// Lines from `fn` to the closing brace
+    pub length: usize,
+    pub params: usize,
+    /// 1 + decision points (`if`, loops, match arms beyond the first, `&&`, `||`)
+    pub cyclomatic: usize,
+    /// Cognitive complexity: branches weighted by nesting, mixed logical operators, labeled jumps, recursion
+    pub cognitive: usize,
+    /// Deepest nesting of control-flow blocks
+    pub nesting: usize,
+}
+
+/// Metrics of every function with a body in a Rust source file
+pub fn analyze_rust(src: &str) -> Vec<FunctionMetrics> {
+    let tokens = code_tokens(&tokenize(src));
+    let items = functions(&tokens);
+
+    items
+        .iter()
+        .map(|f| {
+            // Nested functions are measured on their own
+            let nested: Vec<(usize, usize)> = items
+                .iter()
+                .filter(|n| n.fn_idx > f.body_open && n.body_close < f.body_close)
+                .map(|n| (n.fn_idx, n.body_close))
+                .collect();
+            let (cyclomatic, cognitive, nesting) = measure_body(&tokens, f, &nested);
+            let line = tokens[f.fn_idx].line;
+            FunctionMetrics {
+                name: f.qualified_name.clone(),
+                line,
+                length: tokens[f.body_close].line - line + 1,
+                params: f.params,
+                cyclomatic,
+                cognitive,
+                nesting,
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Block {
+    Plain,
+    Control,
+    Closure,
+}
+
+/// True if `t` ends an operand, so a following `&&`/`||` is a binary operator
+/// (and not a double reference or an empty closure)
+fn ends_operand(t: &Token) -> bool {
+    match t.kind {
+        TokenKind::Literal => true,
+        TokenKind::Ident => !matches!(
+            t.text.as_str(),
+            "return" | "move" | "in" | "if" | "while" | "match" | "else" | "let" | "mut" | "break"
+        ),
+        TokenKind::Punct => matches!(t.text.as_str(), ")" | "]" | "?"),
+        _ => false,
+    }
+}
+
+/// (cyclomatic, cognitive, max nesting) of a function body
+fn measure_body(tokens: &[Token], f: &FnItem, nested: &[(usize, usize)]) -> (usize, usize, usize) {
+    let mut cyclomatic = 1usize;
+    let mut cognitive = 0usize;
+    let mut max_nesting = 0;
+    let mut blocks: Vec<Block> = Vec::new();
+    // A control keyword was seen; its block is the next `{`
+    let mut pending = false;
+    let mut else_if = false;
+    let mut last_logical: Option<&str> = None;
+    let mut matches = 0usize;
+    let mut arms = 0usize;
+
+    let mut i = f.body_open + 1;
+    while i < f.body_close {
+        if let Some(&(_, end)) = nested.iter().find(|(start, _)| *start == i) {
+            i = end + 1;
+            continue;
+        }
+        let t = &tokens[i];
+        let prev = &tokens[i - 1];
+        let next = tokens.get(i + 1);
+        let nesting = blocks.iter().filter(|b| **b != Block::Plain).count();
+
+        match (t.kind, t.text.as_str()) {
+            (TokenKind::Ident, "if") => {
+                cyclomatic += 1;
+                cognitive += if else_if { 1 } else { 1 + nesting };
+                else_if = false;
+                pending = true;
+            }
+            (TokenKind::Ident, "else") => {
+                if next.is_some_and(|n| n.is_ident("if")) {
+                    else_if = true;
+                } else {
+                    cognitive += 1;
+                    pending = true;
+                }
+            }
+            (TokenKind::Ident, "match") => {
+                matches += 1;
+                cognitive += 1 + nesting;
+                pending = true;
+            }
+            (TokenKind::Ident, "for") | (TokenKind::Ident, "while") => {
+                cyclomatic += 1;
+                cognitive += 1 + nesting;
+                pending = true;
+            }
+            (TokenKind::Ident, "loop") => {
+                cognitive += 1 + nesting;
+                pending = true;
+            }
+            (TokenKind::Ident, "break") | (TokenKind::Ident, "continue")
+                if next.is_some_and(|n| n.kind == TokenKind::Lifetime) =>
+            {
+                // Labeled jump
+                cognitive += 1;
+            }
+            (TokenKind::Ident, name) if name == f.name && next.is_some_and(|n| n.is_punct("(")) => {
+                // Recursion
+                cognitive += 1;
+            }
+            (TokenKind::Punct, "=>") => {
+                arms += 1;
+                // A match guard `if` does not open a block
+                pending = false;
+            }
+            (TokenKind::Punct, op @ ("&&" | "||")) if ends_operand(prev) => {
+                cyclomatic += 1;
+                if last_logical != Some(op) {
+                    cognitive += 1;
+                }
+                last_logical = Some(op);
+            }
+            (TokenKind::Punct, "{") => {
+                let block = if prev.is_punct("|") || prev.is_punct("||") {
+                    Block::Closure
+                } else if pending {
+                    pending = false;
+                    Block::Control
+                } else {
+                    Block::Plain
+                };
+                blocks.push(block);
+                max_nesting =
+                    max_nesting.max(blocks.iter().filter(|b| **b == Block::Control).count());
+                last_logical = None;
+            }
+            (TokenKind::Punct, "}") => {
+                blocks.pop();
+                last_logical = None;
+            }
+            (TokenKind::Punct, ";") | (TokenKind::Punct, ",") => {
+                last_logical = None;
+                if t.text == ";" {
+                    pending = false;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    // n arms of a match add n - 1 paths
+    cyclomatic += arms.saturating_sub(matches);
+    (cyclomatic, cognitive, max_nesting)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_branches_and_nesting() {
+        let src = r#"
+struct S;
+impl S {
+    fn check(&self, items: &[u8], limit: u8) -> u8 {
+        let mut n = 0;
+        for x in items {
+            if *x > limit && *x < 200 {
+                n += 1;
+            } else if *x == 0 || *x == 1 {
+                continue;
+            } else {
+                match x {
+                    2 => n += 2,
+                    3 => n += 3,
+                    _ => {}
+                }
+            }
+        }
+        items.iter().filter(|&&x| x > 0).count() as u8 + n
+    }
+}
+fn fact(n: u64) -> u64 { if n == 0 { 1 } else { n * fact(n - 1) } }
+"#;
+        let metrics = analyze_rust(src);
+        assert_eq!(metrics.len(), 2);
+
+        let check = &metrics[0];
+        assert_eq!(check.name, "S::check");
+        assert_eq!((check.line, check.length, check.params), (4, 17, 2));
+        // 1 + for + if + && + else-if + || + (3 arms - 1)
+        assert_eq!(check.cyclomatic, 8);
+        // for 1, if 2, && 1, else if 1, || 1, else 1, match 3
+        assert_eq!(check.cognitive, 10);
+        assert_eq!(check.nesting, 3);
+
+        let fact = &metrics[1];
+        // if 1, else 1, recursion 1
+        assert_eq!((fact.cyclomatic, fact.cognitive, fact.nesting), (2, 3, 1));
+    }
+}
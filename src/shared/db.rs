@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::time::Duration;
 
 use rusqlite::{Connection, Result as SqlResult};
 
@@ -7,26 +8,47 @@ use crate::domain::n001_project::repository as project_repo;
 use crate::domain::n002_snapshot::repository as snapshot_repo;
 use crate::domain::n003_snapshot_file::repository as snapshot_file_repo;
 use crate::domain::n004_snapshot_aggregate::repository as snapshot_aggregate_repo;
+use crate::domain::n005_snapshot_package::repository as snapshot_package_repo;
+use crate::domain::n006_file_content::repository as file_content_repo;
+use crate::domain::n007_file_churn::repository as file_churn_repo;
+use crate::domain::n008_file_ownership::repository as file_ownership_repo;
+use crate::domain::n009_author::repository as author_repo;
+use crate::domain::n010_function_metric::repository as function_metric_repo;
+use crate::domain::n011_debt_marker::repository as debt_marker_repo;
+use crate::domain::n012_safety_site::repository as safety_site_repo;
+use crate::domain::n013_file_tests::repository as file_tests_repo;
+use crate::domain::n014_file_coverage::repository as file_coverage_repo;
+use crate::domain::n015_public_item::repository as public_item_repo;
+use crate::domain::n016_module_doc::repository as module_doc_repo;
+use crate::domain::n017_file_language::repository as file_language_repo;
+use crate::domain::n018_module_dependency::repository as module_dependency_repo;
+use crate::domain::n019_quality_gate::repository as quality_gate_repo;
+use crate::domain::n020_gate_result::repository as gate_result_repo;
 
 pub const DB_PATH: &str = "navigator.db";
 
+/// How long a connection waits for a write lock held by a background worker
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Open database by path, creating it (and initializing tables) if missing.
+/// Status lines go to stderr so that CLI output on stdout stays machine-readable.
 pub fn open_or_create(db_path: &str) -> SqlResult<Connection> {
     let db_exists = Path::new(db_path).exists();
 
     if db_exists {
-        println!("Database found: {}", db_path);
+        eprintln!("Database found: {}", db_path);
     } else {
-        println!("Database not found. Creating new database: {}", db_path);
+        eprintln!("Database not found. Creating new database: {}", db_path);
     }
 
     let conn = Connection::open(db_path)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
 
     // Ensure all tables exist (idempotent)
     init_database(&conn)?;
 
     if !db_exists {
-        println!("Database created successfully with all tables");
+        eprintln!("Database created successfully with all tables");
     }
 
     Ok(conn)
@@ -42,32 +64,115 @@ pub fn init_database(conn: &Connection) -> SqlResult<()> {
         )",
         [],
     )?;
-    println!("  ✓ Table 'items' initialized");
+    eprintln!("  ✓ Table 'items' initialized");
 
     // App settings table
     AppSettings::init_table(conn)?;
-    println!("  ✓ Table 'settings' initialized");
+    eprintln!("  ✓ Table 'settings' initialized");
 
     // Domain: n001_project aggregate table
     project_repo::init_table(conn)?;
-    println!("  ✓ Table 'n001_project' initialized");
+    eprintln!("  ✓ Table 'n001_project' initialized");
 
     // Domain: n002_snapshot aggregate table
     snapshot_repo::init_table(conn)?;
-    println!("  ✓ Table 'n002_snapshot' initialized");
+    eprintln!("  ✓ Table 'n002_snapshot' initialized");
 
     // Domain: n003_snapshot_file aggregate table
     snapshot_file_repo::init_table(conn)?;
-    println!("  ✓ Table 'n003_snapshot_file' initialized");
+    eprintln!("  ✓ Table 'n003_snapshot_file' initialized");
 
     // Domain: n004_snapshot_aggregate aggregate table
     snapshot_aggregate_repo::init_table(conn)?;
-    println!("  ✓ Table 'n004_snapshot_aggregate' initialized");
+    eprintln!("  ✓ Table 'n004_snapshot_aggregate' initialized");
 
-    Ok(())
-}
+    // Domain: n005_snapshot_package aggregate table
+    snapshot_package_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n005_snapshot_package' initialized");
+
+    // Domain: n006_file_content file text + full-text index (FTS5)
+    file_content_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n006_file_content' initialized");
+
+    // Domain: n007_file_churn git history metrics
+    file_churn_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n007_file_churn' initialized");
+
+    // Domain: n008_file_ownership blame line counts
+    file_ownership_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n008_file_ownership' initialized");
+
+    // Domain: n009_author people and their commit aliases
+    author_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n009_author' initialized");
+
+    // Domain: n010_function_metric Rust function complexity
+    function_metric_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n010_function_metric' initialized");
+
+    // Domain: n011_debt_marker TODO/FIXME/... occurrences
+    debt_marker_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n011_debt_marker' initialized");
+
+    // Domain: n012_safety_site unsafe code and panic sites
+    safety_site_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n012_safety_site' initialized");
+
+    // Domain: n013_file_tests test functions and test/production LOC
+    file_tests_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n013_file_tests' initialized");
 
+    // Domain: n014_file_coverage imported lcov/Cobertura coverage
+    file_coverage_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n014_file_coverage' initialized");
 
+    // Domain: n015_public_item public API surface per crate
+    public_item_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n015_public_item' initialized");
 
+    // Domain: n016_module_doc `//!` headers of crate modules
+    module_doc_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n016_module_doc' initialized");
 
+    // Domain: n017_file_language detected language and LOC per file
+    file_language_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n017_file_language' initialized");
 
+    // Domain: n018_module_dependency resolved `use` declarations between modules
+    module_dependency_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n018_module_dependency' initialized");
+
+    // Domain: n019_quality_gate rules every snapshot of a project must pass
+    quality_gate_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n019_quality_gate' initialized");
+
+    // Domain: n020_gate_result pass/fail of every gate rule per snapshot
+    gate_result_repo::init_table(conn)?;
+    eprintln!("  ✓ Table 'n020_gate_result' initialized");
+
+    Ok(())
+}
+
+/// Add a column to an existing table if it is missing (lightweight migration).
+pub fn ensure_column(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> SqlResult<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let names = stmt.query_map([], |row| row.get::<_, String>(1))?;
+    let mut exists = false;
+    for name in names {
+        if name? == column {
+            exists = true;
+        }
+    }
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+    Ok(())
+}
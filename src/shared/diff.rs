@@ -0,0 +1,209 @@
+/// One step of an edit script turning `a` into `b`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    /// `a[i] == b[j]`
+    Equal(usize, usize),
+    /// `a[i]` removed
+    Delete(usize),
+    /// `b[j]` inserted
+    Insert(usize),
+}
+
+/// Shortest edit script between two sequences (Myers' O(ND) algorithm)
+pub fn diff<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    // Common prefix and suffix don't need the expensive search
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let mut edits: Vec<Edit> = (0..prefix).map(|i| Edit::Equal(i, i)).collect();
+    let mid_a = &a[prefix..a.len() - suffix];
+    let mid_b = &b[prefix..b.len() - suffix];
+    for edit in myers(mid_a, mid_b) {
+        edits.push(match edit {
+            Edit::Equal(i, j) => Edit::Equal(i + prefix, j + prefix),
+            Edit::Delete(i) => Edit::Delete(i + prefix),
+            Edit::Insert(j) => Edit::Insert(j + prefix),
+        });
+    }
+    for k in 0..suffix {
+        edits.push(Edit::Equal(a.len() - suffix + k, b.len() - suffix + k));
+    }
+    edits
+}
+
+/// Edit distance beyond which the middle block is replaced as a whole;
+/// bounds the O(D²) memory of the backtracking trace
+const MAX_EDIT_DISTANCE: isize = 2000;
+
+/// Every element of `a` deleted, then every element of `b` inserted
+fn replace_all(a_len: usize, b_len: usize) -> Vec<Edit> {
+    (0..a_len)
+        .map(Edit::Delete)
+        .chain((0..b_len).map(Edit::Insert))
+        .collect()
+}
+
+fn myers<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    if n == 0 || m == 0 {
+        return replace_all(a.len(), b.len());
+    }
+
+    let max = n + m;
+    let offset = max + 1;
+    let mut v = vec![0isize; 2 * max as usize + 3];
+    // Diagonals -d-1..=d+1 of `v` before each round, for backtracking
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    let mut found = false;
+
+    'search: for d in 0..=max.min(MAX_EDIT_DISTANCE) {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        let mut k = -d;
+        while k <= d {
+            let idx = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                found = true;
+                break 'search;
+            }
+            k += 2;
+        }
+    }
+    if !found {
+        return replace_all(a.len(), b.len());
+    }
+
+    // Walk the trace backwards from (n, m)
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal(x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                y -= 1;
+                edits.push(Edit::Insert(y as usize));
+            } else {
+                x -= 1;
+                edits.push(Edit::Delete(x as usize));
+            }
+        }
+    }
+    edits.reverse();
+    edits
+}
+
+/// Byte ranges of a string
+pub type Ranges = Vec<(usize, usize)>;
+
+/// Byte ranges of `old` and `new` that differ, at character granularity
+pub fn intraline_ranges(old: &str, new: &str) -> (Ranges, Ranges) {
+    let a: Vec<(usize, char)> = old.char_indices().collect();
+    let b: Vec<(usize, char)> = new.char_indices().collect();
+    let a_chars: Vec<char> = a.iter().map(|(_, c)| *c).collect();
+    let b_chars: Vec<char> = b.iter().map(|(_, c)| *c).collect();
+
+    let mut old_ranges: Ranges = Vec::new();
+    let mut new_ranges: Ranges = Vec::new();
+    let extend = |ranges: &mut Ranges, start: usize, end: usize| match ranges.last_mut() {
+        Some(last) if last.1 == start => last.1 = end,
+        _ => ranges.push((start, end)),
+    };
+    for edit in diff(&a_chars, &b_chars) {
+        match edit {
+            Edit::Delete(i) => extend(&mut old_ranges, a[i].0, a[i].0 + a[i].1.len_utf8()),
+            Edit::Insert(j) => extend(&mut new_ranges, b[j].0, b[j].0 + b[j].1.len_utf8()),
+            Edit::Equal(..) => {}
+        }
+    }
+    (old_ranges, new_ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(a: &[&str], b: &[&str], edits: &[Edit]) -> Vec<String> {
+        edits
+            .iter()
+            .filter_map(|e| match e {
+                Edit::Equal(i, _) => Some(a[*i].to_string()),
+                Edit::Insert(j) => Some(b[*j].to_string()),
+                Edit::Delete(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn edit_script_rebuilds_target() {
+        let a = ["a", "b", "c", "a", "b", "b", "a"];
+        let b = ["c", "b", "a", "b", "a", "c"];
+        let edits = diff(&a, &b);
+        assert_eq!(apply(&a, &b, &edits), b);
+        let changes = edits
+            .iter()
+            .filter(|e| !matches!(e, Edit::Equal(..)))
+            .count();
+        assert_eq!(changes, 5);
+    }
+
+    #[test]
+    fn empty_and_identical_inputs() {
+        let empty: [&str; 0] = [];
+        assert_eq!(diff(&empty, &["x"]), vec![Edit::Insert(0)]);
+        assert_eq!(diff(&["x"], &empty), vec![Edit::Delete(0)]);
+        assert_eq!(
+            diff(&["x", "y"], &["x", "y"]),
+            vec![Edit::Equal(0, 0), Edit::Equal(1, 1)]
+        );
+    }
+
+    #[test]
+    fn large_distance_replaces_block() {
+        let a: Vec<String> = (0..3000).map(|i| format!("a{}", i)).collect();
+        let b: Vec<String> = (0..3000).map(|i| format!("b{}", i)).collect();
+        let a: Vec<&str> = a.iter().map(String::as_str).collect();
+        let mut b: Vec<&str> = b.iter().map(String::as_str).collect();
+        b[0] = a[0];
+        let edits = diff(&a, &b);
+        assert_eq!(apply(&a, &b, &edits), b);
+        assert_eq!(edits[0], Edit::Equal(0, 0));
+        assert_eq!(edits.len(), 1 + 2 * 2999);
+    }
+
+    #[test]
+    fn intraline_marks_changed_word() {
+        let (old, new) = intraline_ranges("let x = 1;", "let y = 1;");
+        assert_eq!(old, vec![(4, 5)]);
+        assert_eq!(new, vec![(4, 5)]);
+    }
+}
@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// True if the directory is the root of a git working tree
+pub fn is_repository(root: &Path) -> bool {
+    root.join(".git").exists()
+}
+
+/// Run `git -C <root> <args>` and return stdout
+pub fn run(root: &Path, args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or(""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+/// Commit hash of HEAD, if the root is a git repository
+pub fn head_revision(root: &Path) -> Option<String> {
+    if !is_repository(root) {
+        return None;
+    }
+    run(root, &["rev-parse", "HEAD"])
+        .ok()
+        .map(|out| String::from_utf8_lossy(&out).trim().to_string())
+        .filter(|rev| !rev.is_empty())
+}
+
+/// Committer date of `revision`
+pub fn commit_date(root: &Path, revision: &str) -> Result<DateTime<Utc>, String> {
+    let out = run(root, &["show", "-s", "--format=%cI", revision])?;
+    let date = String::from_utf8_lossy(&out).trim().to_string();
+    DateTime::parse_from_rfc3339(&date)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|e| format!("Unexpected commit date '{}': {}", date, e))
+}
+
+/// Raw blob content of `path` at `revision`
+pub fn show_blob(root: &Path, revision: &str, path: &str) -> Result<Vec<u8>, String> {
+    let spec = format!("{}:{}", revision, path.replace('\\', "/"));
+    run(root, &["show", &spec])
+}
+
+/// Blob hash of every file tracked at `revision`, keyed by path
+pub fn blob_ids(root: &Path, revision: &str) -> Result<HashMap<String, String>, String> {
+    let out = run(root, &["ls-tree", "-r", "-z", revision])?;
+    let mut ids = HashMap::new();
+    // Entries: "<mode> <type> <hash>\t<path>\0"
+    for entry in out.split(|b| *b == 0).filter(|e| !e.is_empty()) {
+        let entry = String::from_utf8_lossy(entry);
+        let Some((meta, path)) = entry.split_once('\t') else {
+            continue;
+        };
+        let mut parts = meta.split_whitespace();
+        if let (Some(_mode), Some("blob"), Some(hash)) = (parts.next(), parts.next(), parts.next())
+        {
+            ids.insert(path.to_string(), hash.to_string());
+        }
+    }
+    Ok(ids)
+}
+
+/// Author name and author time (unix seconds) of the given 1-based lines of a
+/// working-tree file, keyed by line. Uncommitted lines are reported by git as
+/// "Not Committed Yet".
+pub fn blame_lines(
+    root: &Path,
+    path: &str,
+    lines: &[usize],
+) -> Result<HashMap<usize, (String, i64)>, String> {
+    let path = path.replace('\\', "/");
+    let ranges: Vec<String> = lines.iter().map(|l| format!("-L{},{}", l, l)).collect();
+    let mut args = vec!["blame", "--line-porcelain"];
+    args.extend(ranges.iter().map(|r| r.as_str()));
+    args.push("--");
+    args.push(&path);
+    let out = run(root, &args)?;
+    Ok(parse_line_porcelain(&String::from_utf8_lossy(&out)))
+}
+
+/// Per-line author from `git blame --line-porcelain` output
+fn parse_line_porcelain(output: &str) -> HashMap<usize, (String, i64)> {
+    let mut result = HashMap::new();
+    let (mut line_no, mut name, mut time) = (0, String::new(), 0);
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            // Content line closes the entry
+            result.insert(line_no, (name.clone(), time));
+        } else if let Some(rest) = line.strip_prefix("author ") {
+            name = rest.to_string();
+        } else if let Some(rest) = line.strip_prefix("author-time ") {
+            time = rest.trim().parse().unwrap_or(0);
+        } else {
+            // Entry header: "<hash> <orig line> <final line> [<group size>]"
+            let parts: Vec<&str> = line.split(' ').collect();
+            if parts.len() >= 3
+                && parts[0].len() >= 40
+                && parts[0].chars().all(|c| c.is_ascii_hexdigit())
+            {
+                line_no = parts[2].parse().unwrap_or(0);
+            }
+        }
+    }
+    result
+}
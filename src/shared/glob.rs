@@ -0,0 +1,69 @@
+/// Match a relative path against a glob pattern.
+/// `*` and `?` stay within one path segment, `**` spans segments.
+/// Patterns without `/` are matched against the file name only.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let path = path.replace('\\', "/");
+    let pattern = pattern.trim().trim_start_matches("./");
+    if pattern.contains('/') {
+        match_from(pattern.as_bytes(), path.as_bytes())
+    } else {
+        let name = path.rsplit('/').next().unwrap_or(&path);
+        match_from(pattern.as_bytes(), name.as_bytes())
+    }
+}
+
+/// Parse a comma-separated pattern list and check if any pattern matches
+pub fn matches_any(patterns: &str, path: &str) -> bool {
+    patterns
+        .split(',')
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .any(|p| glob_match(p, path))
+}
+
+fn match_from(p: &[u8], t: &[u8]) -> bool {
+    match p.first() {
+        None => t.is_empty(),
+        Some(b'*') if p.get(1) == Some(&b'*') => {
+            let rest = &p[2..];
+            // "**/" may also match zero directories
+            if rest.first() == Some(&b'/') && match_from(&rest[1..], t) {
+                return true;
+            }
+            (0..=t.len()).any(|i| match_from(rest, &t[i..]))
+        }
+        Some(b'*') => {
+            let rest = &p[1..];
+            for i in 0..=t.len() {
+                if match_from(rest, &t[i..]) {
+                    return true;
+                }
+                if i < t.len() && t[i] == b'/' {
+                    break;
+                }
+            }
+            false
+        }
+        Some(b'?') => !t.is_empty() && t[0] != b'/' && match_from(&p[1..], &t[1..]),
+        Some(c) => !t.is_empty() && t[0] == *c && match_from(&p[1..], &t[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_patterns() {
+        assert!(glob_match("*.rs", "src/domain/model.rs"));
+        assert!(!glob_match("*.rs", "Cargo.toml"));
+        assert!(glob_match("src/*.rs", "src/main.rs"));
+        assert!(!glob_match("src/*.rs", "src/layout/mod.rs"));
+        assert!(glob_match("src/**/*.rs", "src/layout/mod.rs"));
+        assert!(glob_match("src/**/*.rs", "src/main.rs"));
+        assert!(glob_match("**/ui/**", "crates/frontend/ui/list/mod.rs"));
+        assert!(glob_match("mod?.rs", "mod1.rs"));
+        assert!(matches_any("*.toml, *.md", "README.md"));
+        assert!(!matches_any("", "README.md"));
+    }
+}
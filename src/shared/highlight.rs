@@ -0,0 +1,574 @@
+use eframe::egui;
+use egui::text::{LayoutJob, TextFormat};
+use egui::Color32;
+
+/// Languages with syntax highlighting support
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Toml,
+    Markdown,
+    Json,
+    Sql,
+    JsTs,
+    Plain,
+}
+
+impl Language {
+    pub fn from_path(path: &str) -> Self {
+        let ext = path
+            .rsplit_once('.')
+            .map(|(_, e)| e.to_ascii_lowercase())
+            .unwrap_or_default();
+        match ext.as_str() {
+            "rs" => Language::Rust,
+            "toml" => Language::Toml,
+            "md" | "markdown" => Language::Markdown,
+            "json" => Language::Json,
+            "sql" => Language::Sql,
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => Language::JsTs,
+            _ => Language::Plain,
+        }
+    }
+
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+                "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match",
+                "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct",
+                "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+            ],
+            Language::JsTs => &[
+                "abstract",
+                "as",
+                "async",
+                "await",
+                "break",
+                "case",
+                "catch",
+                "class",
+                "const",
+                "continue",
+                "default",
+                "delete",
+                "do",
+                "else",
+                "enum",
+                "export",
+                "extends",
+                "false",
+                "finally",
+                "for",
+                "from",
+                "function",
+                "if",
+                "implements",
+                "import",
+                "in",
+                "instanceof",
+                "interface",
+                "let",
+                "new",
+                "null",
+                "private",
+                "protected",
+                "public",
+                "readonly",
+                "return",
+                "static",
+                "super",
+                "switch",
+                "this",
+                "throw",
+                "true",
+                "try",
+                "type",
+                "typeof",
+                "undefined",
+                "var",
+                "void",
+                "while",
+                "yield",
+            ],
+            Language::Sql => &[
+                "add",
+                "all",
+                "alter",
+                "and",
+                "as",
+                "asc",
+                "autoincrement",
+                "begin",
+                "between",
+                "by",
+                "case",
+                "commit",
+                "create",
+                "default",
+                "delete",
+                "desc",
+                "distinct",
+                "drop",
+                "else",
+                "end",
+                "exists",
+                "foreign",
+                "from",
+                "group",
+                "having",
+                "if",
+                "in",
+                "index",
+                "inner",
+                "insert",
+                "integer",
+                "into",
+                "is",
+                "join",
+                "key",
+                "left",
+                "like",
+                "limit",
+                "not",
+                "null",
+                "offset",
+                "on",
+                "or",
+                "order",
+                "outer",
+                "primary",
+                "references",
+                "select",
+                "set",
+                "table",
+                "text",
+                "then",
+                "union",
+                "unique",
+                "update",
+                "values",
+                "view",
+                "when",
+                "where",
+                "with",
+            ],
+            Language::Toml | Language::Json => &["true", "false", "null"],
+            Language::Markdown | Language::Plain => &[],
+        }
+    }
+
+    fn line_comment(self) -> Option<&'static str> {
+        match self {
+            Language::Rust | Language::JsTs => Some("//"),
+            Language::Sql => Some("--"),
+            Language::Toml => Some("#"),
+            _ => None,
+        }
+    }
+
+    fn has_block_comments(self) -> bool {
+        matches!(self, Language::Rust | Language::JsTs | Language::Sql)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Text,
+    Keyword,
+    String,
+    Comment,
+    Number,
+    Type,
+    Macro,
+    Attribute,
+    Key,
+    Heading,
+    Code,
+}
+
+/// Multi-line context carried from one line to the next
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HighlightState {
+    in_block_comment: bool,
+    in_code_fence: bool,
+}
+
+/// Split a line into `(start, end, kind)` byte ranges covering the whole line
+pub fn tokenize_line(
+    lang: Language,
+    line: &str,
+    state: &mut HighlightState,
+) -> Vec<(usize, usize, TokenKind)> {
+    match lang {
+        Language::Plain => vec![(0, line.len(), TokenKind::Text)],
+        Language::Markdown => tokenize_markdown(line, state),
+        _ => tokenize_code(lang, line, state),
+    }
+}
+
+fn tokenize_markdown(line: &str, state: &mut HighlightState) -> Vec<(usize, usize, TokenKind)> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("```") {
+        state.in_code_fence = !state.in_code_fence;
+        return vec![(0, line.len(), TokenKind::Code)];
+    }
+    if state.in_code_fence {
+        return vec![(0, line.len(), TokenKind::Code)];
+    }
+    if trimmed.starts_with('#') {
+        return vec![(0, line.len(), TokenKind::Heading)];
+    }
+
+    // Inline `code` spans
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = line[pos..].find('`').map(|i| pos + i) {
+        let Some(end) = line[start + 1..].find('`').map(|i| start + 1 + i + 1) else {
+            break;
+        };
+        if start > pos {
+            tokens.push((pos, start, TokenKind::Text));
+        }
+        tokens.push((start, end, TokenKind::Code));
+        pos = end;
+    }
+    if pos < line.len() {
+        tokens.push((pos, line.len(), TokenKind::Text));
+    }
+    tokens
+}
+
+fn tokenize_code(
+    lang: Language,
+    line: &str,
+    state: &mut HighlightState,
+) -> Vec<(usize, usize, TokenKind)> {
+    let bytes = line.as_bytes();
+    let mut tokens: Vec<(usize, usize, TokenKind)> = Vec::new();
+    let mut i = 0;
+    if state.in_block_comment {
+        match line.find("*/") {
+            Some(end) => {
+                state.in_block_comment = false;
+                push_token(&mut tokens, 0, end + 2, TokenKind::Comment);
+                i = end + 2;
+            }
+            None => return vec![(0, line.len(), TokenKind::Comment)],
+        }
+    }
+
+    // TOML table headers: `[section]` / `[[array]]`
+    if lang == Language::Toml && line.trim_start().starts_with('[') {
+        let end = line.find('#').unwrap_or(line.len());
+        push_token(&mut tokens, 0, end, TokenKind::Heading);
+        push_token(&mut tokens, end, line.len(), TokenKind::Comment);
+        return tokens;
+    }
+
+    let keywords = lang.keywords();
+    let mut at_line_start = true;
+    while i < bytes.len() {
+        let rest = &line[i..];
+        let c = bytes[i];
+
+        if let Some(marker) = lang.line_comment() {
+            if rest.starts_with(marker) {
+                push_token(&mut tokens, i, line.len(), TokenKind::Comment);
+                break;
+            }
+        }
+        if lang.has_block_comments() && rest.starts_with("/*") {
+            match rest[2..].find("*/") {
+                Some(end) => {
+                    push_token(&mut tokens, i, i + 2 + end + 2, TokenKind::Comment);
+                    i += 2 + end + 2;
+                    continue;
+                }
+                None => {
+                    state.in_block_comment = true;
+                    push_token(&mut tokens, i, line.len(), TokenKind::Comment);
+                    break;
+                }
+            }
+        }
+        if lang == Language::Rust
+            && c == b'#'
+            && matches!(bytes.get(i + 1), Some(b'[') | Some(b'!'))
+        {
+            let end = rest.find(']').map(|e| i + e + 1).unwrap_or(line.len());
+            push_token(&mut tokens, i, end, TokenKind::Attribute);
+            i = end;
+            continue;
+        }
+
+        let is_quote = c == b'"'
+            || (c == b'\''
+                && lang != Language::Json
+                && !(lang == Language::Rust && is_lifetime(rest)))
+            || (c == b'`' && lang == Language::JsTs);
+        if is_quote {
+            let end = string_end(bytes, i, c, lang != Language::Sql);
+            let key = (lang == Language::Json && line[end..].trim_start().starts_with(':'))
+                || (lang == Language::Toml
+                    && at_line_start
+                    && line[end..].trim_start().starts_with('='));
+            push_token(
+                &mut tokens,
+                i,
+                end,
+                if key {
+                    TokenKind::Key
+                } else {
+                    TokenKind::String
+                },
+            );
+            i = end;
+            at_line_start = false;
+            continue;
+        }
+
+        if c.is_ascii_digit() {
+            let end = scan_while(bytes, i, |b| {
+                b.is_ascii_alphanumeric() || b == b'_' || b == b'.'
+            });
+            push_token(&mut tokens, i, end, TokenKind::Number);
+            i = end;
+            at_line_start = false;
+            continue;
+        }
+
+        if c.is_ascii_alphabetic() || c == b'_' {
+            let end = scan_while(bytes, i, |b| {
+                b.is_ascii_alphanumeric() || b == b'_' || (lang == Language::Toml && b == b'-')
+            });
+            let word = &line[i..end];
+            let kind = if lang == Language::Toml
+                && at_line_start
+                && line[end..].trim_start().starts_with(['=', '.'])
+            {
+                TokenKind::Key
+            } else if is_keyword(lang, keywords, word) {
+                TokenKind::Keyword
+            } else if lang == Language::Rust && bytes.get(end) == Some(&b'!') {
+                TokenKind::Macro
+            } else if matches!(lang, Language::Rust | Language::JsTs)
+                && word.starts_with(|ch: char| ch.is_ascii_uppercase())
+            {
+                TokenKind::Type
+            } else {
+                TokenKind::Text
+            };
+            let end = if kind == TokenKind::Macro {
+                end + 1
+            } else {
+                end
+            };
+            push_token(&mut tokens, i, end, kind);
+            i = end;
+            at_line_start = at_line_start && kind == TokenKind::Key;
+            continue;
+        }
+
+        // Any other character (whitespace, punctuation, non-ASCII) is plain text
+        let len = rest.chars().next().map(|ch| ch.len_utf8()).unwrap_or(1);
+        if !c.is_ascii_whitespace() && c != b'.' {
+            at_line_start = false;
+        }
+        push_token(&mut tokens, i, i + len, TokenKind::Text);
+        i += len;
+    }
+    tokens
+}
+
+fn push_token(
+    tokens: &mut Vec<(usize, usize, TokenKind)>,
+    start: usize,
+    end: usize,
+    kind: TokenKind,
+) {
+    if start >= end {
+        return;
+    }
+    // Merge adjacent plain text so the layout job stays small
+    if let Some(last) = tokens.last_mut() {
+        if kind == TokenKind::Text && last.2 == kind && last.1 == start {
+            last.1 = end;
+            return;
+        }
+    }
+    tokens.push((start, end, kind));
+}
+
+fn is_keyword(lang: Language, keywords: &[&str], word: &str) -> bool {
+    if lang == Language::Sql {
+        keywords.iter().any(|k| k.eq_ignore_ascii_case(word))
+    } else {
+        keywords.contains(&word)
+    }
+}
+
+/// `'a` lifetimes vs `'a'` char literals
+fn is_lifetime(rest: &str) -> bool {
+    let mut chars = rest.chars().skip(1);
+    !matches!(
+        (chars.next(), chars.next()),
+        (Some('\\'), _) | (Some(_), Some('\''))
+    )
+}
+
+/// Byte index just past the closing quote (or end of line when unterminated)
+fn string_end(bytes: &[u8], start: usize, quote: u8, escapes: bool) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        if escapes && bytes[i] == b'\\' {
+            i += 2;
+            continue;
+        }
+        if bytes[i] == quote {
+            return i + 1;
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+fn scan_while(bytes: &[u8], start: usize, pred: impl Fn(u8) -> bool) -> usize {
+    let mut i = start;
+    while i < bytes.len() && pred(bytes[i]) {
+        i += 1;
+    }
+    i
+}
+
+fn token_color(kind: TokenKind, dark: bool) -> Option<Color32> {
+    let (dark_color, light_color) = match kind {
+        TokenKind::Text => return None,
+        TokenKind::Keyword => (
+            Color32::from_rgb(198, 120, 221),
+            Color32::from_rgb(160, 30, 170),
+        ),
+        TokenKind::String => (
+            Color32::from_rgb(152, 195, 121),
+            Color32::from_rgb(40, 130, 40),
+        ),
+        TokenKind::Comment => (
+            Color32::from_rgb(110, 120, 130),
+            Color32::from_rgb(120, 120, 120),
+        ),
+        TokenKind::Number => (
+            Color32::from_rgb(209, 154, 102),
+            Color32::from_rgb(170, 90, 0),
+        ),
+        TokenKind::Type => (
+            Color32::from_rgb(229, 192, 123),
+            Color32::from_rgb(150, 110, 0),
+        ),
+        TokenKind::Macro => (
+            Color32::from_rgb(86, 182, 194),
+            Color32::from_rgb(0, 120, 140),
+        ),
+        TokenKind::Attribute => (
+            Color32::from_rgb(150, 150, 200),
+            Color32::from_rgb(90, 90, 160),
+        ),
+        TokenKind::Key => (
+            Color32::from_rgb(224, 108, 117),
+            Color32::from_rgb(180, 40, 50),
+        ),
+        TokenKind::Heading => (
+            Color32::from_rgb(97, 175, 239),
+            Color32::from_rgb(20, 90, 180),
+        ),
+        TokenKind::Code => (
+            Color32::from_rgb(152, 195, 121),
+            Color32::from_rgb(40, 130, 40),
+        ),
+    };
+    Some(if dark { dark_color } else { light_color })
+}
+
+/// Append a highlighted line to a layout job using the current visuals
+pub fn append_line(
+    job: &mut LayoutJob,
+    ui: &egui::Ui,
+    lang: Language,
+    line: &str,
+    state: &mut HighlightState,
+    background: Color32,
+) {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let dark = ui.visuals().dark_mode;
+    let text_color = ui.visuals().text_color();
+    for (start, end, kind) in tokenize_line(lang, line, state) {
+        let format = TextFormat {
+            font_id: font_id.clone(),
+            color: token_color(kind, dark).unwrap_or(text_color),
+            background,
+            italics: kind == TokenKind::Comment,
+            ..Default::default()
+        };
+        job.append(&line[start..end], 0.0, format);
+    }
+}
+
+/// Highlighting state at the start of every line (index i = state before line i)
+pub fn line_states(lang: Language, lines: &[String]) -> Vec<HighlightState> {
+    let mut state = HighlightState::default();
+    let mut out = Vec::with_capacity(lines.len());
+    for line in lines {
+        out.push(state);
+        tokenize_line(lang, line, &mut state);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(lang: Language, line: &str) -> Vec<(String, TokenKind)> {
+        let mut state = HighlightState::default();
+        tokenize_line(lang, line, &mut state)
+            .into_iter()
+            .filter(|t| t.2 != TokenKind::Text)
+            .map(|(s, e, k)| (line[s..e].to_string(), k))
+            .collect()
+    }
+
+    #[test]
+    fn rust_tokens() {
+        let tokens = kinds(
+            Language::Rust,
+            "pub fn f<'a>(x: &'a str) -> Option<u8> { println!(\"{}\", 42); } // done",
+        );
+        assert!(tokens.contains(&("pub".to_string(), TokenKind::Keyword)));
+        assert!(tokens.contains(&("Option".to_string(), TokenKind::Type)));
+        assert!(tokens.contains(&("println!".to_string(), TokenKind::Macro)));
+        assert!(tokens.contains(&("\"{}\"".to_string(), TokenKind::String)));
+        assert!(tokens.contains(&("42".to_string(), TokenKind::Number)));
+        assert!(tokens.contains(&("// done".to_string(), TokenKind::Comment)));
+    }
+
+    #[test]
+    fn block_comment_spans_lines() {
+        let mut state = HighlightState::default();
+        tokenize_line(Language::Rust, "let x = 1; /* start", &mut state);
+        let tokens = tokenize_line(Language::Rust, "still */ let", &mut state);
+        assert_eq!(tokens[0], (0, 8, TokenKind::Comment));
+        assert_eq!(tokens.last().unwrap().2, TokenKind::Keyword);
+    }
+
+    #[test]
+    fn toml_and_json_keys() {
+        let toml = kinds(Language::Toml, "name = \"navigator\" # comment");
+        assert_eq!(toml[0], ("name".to_string(), TokenKind::Key));
+        assert_eq!(toml[1].1, TokenKind::String);
+        assert_eq!(toml[2].1, TokenKind::Comment);
+
+        let json = kinds(Language::Json, "  \"id\": \"x\", \"ok\": true");
+        assert_eq!(json[0], ("\"id\"".to_string(), TokenKind::Key));
+        assert_eq!(json[1], ("\"x\"".to_string(), TokenKind::String));
+        assert_eq!(json[3], ("true".to_string(), TokenKind::Keyword));
+    }
+}
@@ -0,0 +1,178 @@
+/// Well-known file names without a telling extension
+const FILE_NAMES: [(&str, &str); 14] = [
+    ("Dockerfile", "Dockerfile"),
+    ("Containerfile", "Dockerfile"),
+    ("Makefile", "Makefile"),
+    ("GNUmakefile", "Makefile"),
+    ("makefile", "Makefile"),
+    ("CMakeLists.txt", "CMake"),
+    ("Justfile", "Just"),
+    ("justfile", "Just"),
+    ("Cargo.lock", "TOML"),
+    ("Gemfile", "Ruby"),
+    ("Rakefile", "Ruby"),
+    ("Jenkinsfile", "Groovy"),
+    ("Vagrantfile", "Ruby"),
+    ("BUILD.bazel", "Starlark"),
+];
+
+/// Lower-case extension -> language
+const EXTENSIONS: [(&str, &str); 58] = [
+    ("rs", "Rust"),
+    ("toml", "TOML"),
+    ("md", "Markdown"),
+    ("markdown", "Markdown"),
+    ("json", "JSON"),
+    ("yaml", "YAML"),
+    ("yml", "YAML"),
+    ("xml", "XML"),
+    ("html", "HTML"),
+    ("htm", "HTML"),
+    ("css", "CSS"),
+    ("scss", "SCSS"),
+    ("js", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("cjs", "JavaScript"),
+    ("jsx", "JavaScript"),
+    ("ts", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("py", "Python"),
+    ("rb", "Ruby"),
+    ("go", "Go"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("scala", "Scala"),
+    ("swift", "Swift"),
+    ("c", "C"),
+    ("h", "C"),
+    ("cc", "C++"),
+    ("cpp", "C++"),
+    ("cxx", "C++"),
+    ("hpp", "C++"),
+    ("hh", "C++"),
+    ("cs", "C#"),
+    ("php", "PHP"),
+    ("lua", "Lua"),
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("zsh", "Shell"),
+    ("fish", "Shell"),
+    ("ps1", "PowerShell"),
+    ("bat", "Batch"),
+    ("cmd", "Batch"),
+    ("sql", "SQL"),
+    ("proto", "Protocol Buffers"),
+    ("graphql", "GraphQL"),
+    ("dockerfile", "Dockerfile"),
+    ("mk", "Makefile"),
+    ("cmake", "CMake"),
+    ("nix", "Nix"),
+    ("wgsl", "WGSL"),
+    ("glsl", "GLSL"),
+    ("svg", "SVG"),
+    ("ini", "INI"),
+    ("cfg", "INI"),
+    ("csv", "CSV"),
+    ("txt", "Text"),
+    ("rst", "reStructuredText"),
+];
+
+/// Interpreter named by a shebang -> language
+const INTERPRETERS: [(&str, &str); 12] = [
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("zsh", "Shell"),
+    ("dash", "Shell"),
+    ("fish", "Shell"),
+    ("python", "Python"),
+    ("ruby", "Ruby"),
+    ("node", "JavaScript"),
+    ("deno", "TypeScript"),
+    ("perl", "Perl"),
+    ("php", "PHP"),
+    ("lua", "Lua"),
+];
+
+/// Language of a file by its name, extension, or (for extension-less scripts) the shebang
+/// of its first line. None when unknown.
+pub fn detect_language(name: &str, first_line: Option<&str>) -> Option<&'static str> {
+    if let Some((_, language)) = FILE_NAMES.iter().find(|(n, _)| *n == name) {
+        return Some(language);
+    }
+    // Dockerfile.dev, Makefile.common
+    if let Some(language) = name
+        .split_once('.')
+        .and_then(|(stem, _)| {
+            FILE_NAMES
+                .iter()
+                .find(|(n, _)| *n == stem && !n.contains('.'))
+        })
+        .map(|(_, language)| *language)
+    {
+        return Some(language);
+    }
+
+    if let Some(dot) = name.rfind('.').filter(|&d| d > 0) {
+        let ext = name[dot + 1..].to_lowercase();
+        if let Some((_, language)) = EXTENSIONS.iter().find(|(e, _)| *e == ext) {
+            return Some(language);
+        }
+    }
+
+    shebang_language(first_line?)
+}
+
+/// `#!/bin/bash`, `#!/usr/bin/env python3 -u` -> language of the interpreter
+fn shebang_language(line: &str) -> Option<&'static str> {
+    let command = line.strip_prefix("#!")?.trim();
+    let mut words = command.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        // Skip `env -S` style options
+        program = words.find(|w| !w.starts_with('-'))?;
+    }
+    // python3.12 -> python
+    let base = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    INTERPRETERS
+        .iter()
+        .find(|(i, _)| *i == base)
+        .map(|(_, language)| *language)
+}
+
+/// Non-blank lines
+pub fn count_loc(text: &str) -> i64 {
+    text.lines().filter(|l| !l.trim().is_empty()).count() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_languages() {
+        assert_eq!(detect_language("main.rs", None), Some("Rust"));
+        assert_eq!(detect_language("App.TSX", None), Some("TypeScript"));
+        assert_eq!(detect_language("Dockerfile", None), Some("Dockerfile"));
+        assert_eq!(detect_language("Dockerfile.dev", None), Some("Dockerfile"));
+        assert_eq!(
+            detect_language("Makefile", Some("all: build")),
+            Some("Makefile")
+        );
+        assert_eq!(
+            detect_language("deploy", Some("#!/usr/bin/env python3")),
+            Some("Python")
+        );
+        assert_eq!(
+            detect_language("run", Some("#!/bin/bash -e")),
+            Some("Shell")
+        );
+        assert_eq!(
+            detect_language("tool", Some("#!/usr/bin/env -S node --harmony")),
+            Some("JavaScript")
+        );
+        assert_eq!(detect_language(".gitignore", None), None);
+        assert_eq!(detect_language("LICENSE", Some("MIT License")), None);
+        assert_eq!(count_loc("a\n\n  \nb\n"), 2);
+    }
+}
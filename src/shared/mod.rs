@@ -1,2 +1,14 @@
+pub mod architecture;
+pub mod complexity;
 pub mod db;
-
+pub mod diff;
+pub mod git;
+pub mod glob;
+pub mod highlight;
+pub mod language;
+pub mod rust_api;
+pub mod rust_imports;
+pub mod rust_syntax;
+pub mod safety;
+pub mod test_stats;
+pub mod text;
@@ -0,0 +1,553 @@
+use super::rust_syntax::{matching_close, test_ranges, tokenize, Token, TokenKind};
+
+/// A public item of a Rust source file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiItem {
+    /// `fn`, `method`, `struct`, `field`, `enum`, `variant`, `trait`, `impl`, `type`, `const`, `static`, `use`
+    pub kind: &'static str,
+    /// Path relative to the file's module: `name`, `Type::method`, `mod::Type`
+    pub path: String,
+    /// Declaration without body or value, whitespace normalized
+    pub signature: String,
+    /// 1-based line
+    pub line: usize,
+    /// Preceded by a `///` / `/** */` comment or a `#[doc]` attribute
+    pub documented: bool,
+}
+
+/// Comment-free tokens of a file with what the scanner needs to know about them
+struct Source<'a> {
+    tokens: &'a [Token],
+    /// Whether an outer doc comment directly precedes the token
+    docs: &'a [bool],
+    tests: &'a [(usize, usize)],
+}
+
+impl Source<'_> {
+    /// Whether the item whose attributes start at `from` and declaration at `to` is documented
+    fn documented(&self, from: usize, to: usize) -> bool {
+        (from..=to).any(|k| self.docs[k])
+            || (from + 1..to)
+                .any(|k| self.tokens[k].is_ident("doc") && self.tokens[k - 1].is_punct("["))
+    }
+}
+
+#[derive(Clone)]
+enum Scope {
+    Module,
+    /// Inherent impl of the named type
+    Impl(String),
+    Trait(String),
+}
+
+/// Public items declared in a Rust file. Only `pub` (not `pub(crate)` etc.) counts; whether the
+/// enclosing module is itself reachable from the crate root is not checked. Test items are skipped.
+pub fn public_items(src: &str) -> Vec<ApiItem> {
+    let mut tokens = Vec::new();
+    let mut docs = Vec::new();
+    let mut doc = false;
+    for t in tokenize(src) {
+        if t.kind == TokenKind::Comment {
+            doc |= is_outer_doc(&t.text);
+        } else {
+            tokens.push(t);
+            docs.push(std::mem::take(&mut doc));
+        }
+    }
+    let tests = test_ranges(&tokens);
+    let source = Source {
+        tokens: &tokens,
+        docs: &docs,
+        tests: &tests,
+    };
+    let mut out = Vec::new();
+    scan(&source, 0, tokens.len(), &Scope::Module, "", &mut out);
+    out
+}
+
+/// `///` or `/** */`, but not `////` or `/***` (plain comments)
+fn is_outer_doc(comment: &str) -> bool {
+    (comment.starts_with("///") && !comment.starts_with("////"))
+        || (comment.starts_with("/**") && !comment.starts_with("/***") && comment != "/**/")
+}
+
+/// Whether the file opens with an inner doc comment (`//!`, `/*! */`) or `#![doc = ...]`,
+/// i.e. documents the module it defines
+pub fn has_module_doc(src: &str) -> bool {
+    let tokens = tokenize(src);
+    let mut i = 0;
+    while i < tokens.len() {
+        let t = &tokens[i];
+        if t.kind == TokenKind::Comment {
+            if t.text.starts_with("//!") || t.text.starts_with("/*!") {
+                return true;
+            }
+            i += 1;
+        } else if t.is_punct("#")
+            && tokens.get(i + 1).is_some_and(|n| n.is_punct("!"))
+            && tokens.get(i + 2).is_some_and(|n| n.is_punct("["))
+        {
+            if tokens.get(i + 3).is_some_and(|n| n.is_ident("doc")) {
+                return true;
+            }
+            i = matching_close(&tokens, i + 2) + 1;
+        } else {
+            return false;
+        }
+    }
+    false
+}
+
+/// Whether missing docs on an item of this kind count against documentation coverage.
+/// Trait impls and re-exports are documented where they are defined.
+pub fn needs_doc(kind: &str) -> bool {
+    !matches!(kind, "impl" | "use")
+}
+
+/// Module path of a file relative to its crate's `src/` directory (`a/b.rs`, `a/b/mod.rs` -> `a::b`)
+pub fn module_path(path_in_src: &str) -> String {
+    let path = path_in_src.replace('\\', "/");
+    let path = path.trim_end_matches(".rs");
+    let mut parts: Vec<&str> = path.split('/').collect();
+    if matches!(parts.last(), Some(&"mod") | Some(&"lib") | Some(&"main")) {
+        parts.pop();
+    }
+    parts.join("::")
+}
+
+/// Tokens joined with Rust-like spacing
+pub fn render(tokens: &[Token]) -> String {
+    const NO_SPACE_BEFORE: [&str; 8] = [",", ";", ")", "]", ">", "::", ".", "?"];
+    const NO_SPACE_AFTER: [&str; 7] = ["(", "[", "<", "::", "&", "#", "!"];
+    let mut out = String::new();
+    for (i, t) in tokens.iter().enumerate() {
+        if i > 0 {
+            let prev = &tokens[i - 1];
+            let glued = (t.kind == TokenKind::Punct && NO_SPACE_BEFORE.contains(&t.text.as_str()))
+                || (prev.kind == TokenKind::Punct && NO_SPACE_AFTER.contains(&prev.text.as_str()))
+                // `f(`, `Vec<`, `m!`, `x:`
+                || ((t.is_punct("(") || t.is_punct("<") || t.is_punct("!") || t.is_punct(":"))
+                    && (prev.kind == TokenKind::Ident || prev.is_punct(">")));
+            if !glued {
+                out.push(' ');
+            }
+        }
+        out.push_str(&t.text);
+    }
+    out
+}
+
+/// Index of the `;` ending the item at `start`, or of the `{` opening its body.
+/// `value` items (const/static/type/use) may contain braces before their `;`.
+fn item_end(tokens: &[Token], start: usize, end: usize, value: bool) -> usize {
+    let mut depth = 0i32;
+    let mut k = start;
+    while k < end {
+        let t = &tokens[k];
+        if t.is_punct("(") || t.is_punct("[") || t.is_punct("<") || (value && t.is_punct("{")) {
+            depth += 1;
+        } else if t.is_punct(")")
+            || t.is_punct("]")
+            || t.is_punct(">")
+            || (value && t.is_punct("}"))
+        {
+            depth -= 1;
+        } else if depth <= 0 && (t.is_punct(";") || (!value && t.is_punct("{"))) {
+            return k;
+        }
+        k += 1;
+    }
+    end.saturating_sub(1)
+}
+
+fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}::{}", prefix, name)
+    }
+}
+
+/// Comma-separated entries of a `{ ... }` body at depth 0: (attributes start, entry start, end)
+fn body_entries(tokens: &[Token], open: usize, close: usize) -> Vec<(usize, usize, usize)> {
+    let mut entries = Vec::new();
+    let mut depth = 0i32;
+    let mut start = open + 1;
+    for k in open + 1..=close {
+        let t = &tokens[k];
+        if k == close || (depth == 0 && t.is_punct(",")) {
+            let mut s = start;
+            while s + 1 < k && tokens[s].is_punct("#") && tokens[s + 1].is_punct("[") {
+                s = matching_close(tokens, s + 1) + 1;
+            }
+            if s < k {
+                entries.push((start, s, k));
+            }
+            start = k + 1;
+        } else if t.is_punct("(") || t.is_punct("[") || t.is_punct("{") || t.is_punct("<") {
+            depth += 1;
+        } else if t.is_punct(")") || t.is_punct("]") || t.is_punct("}") || t.is_punct(">") {
+            depth -= 1;
+        }
+    }
+    entries
+}
+
+fn scan(
+    src: &Source,
+    start: usize,
+    end: usize,
+    scope: &Scope,
+    prefix: &str,
+    out: &mut Vec<ApiItem>,
+) {
+    let tokens = src.tokens;
+    let mut attrs_start = None;
+    let mut i = start;
+    while i < end {
+        if let Some(&(_, test_end)) = src.tests.iter().find(|(s, e)| *s <= i && i <= *e) {
+            i = test_end + 1;
+            attrs_start = None;
+            continue;
+        }
+        // Attributes (outer and inner)
+        if tokens[i].is_punct("#") {
+            let inner = tokens.get(i + 1).is_some_and(|t| t.is_punct("!"));
+            let open = if inner { i + 2 } else { i + 1 };
+            if tokens.get(open).is_some_and(|t| t.is_punct("[")) {
+                if !inner {
+                    attrs_start.get_or_insert(i);
+                }
+                i = matching_close(tokens, open) + 1;
+                continue;
+            }
+        }
+
+        let item_start = i;
+        let documented = src.documented(attrs_start.take().unwrap_or(item_start), item_start);
+        let mut j = i;
+        let public = match scope {
+            Scope::Trait(_) => true,
+            _ if tokens[j].is_ident("pub") => {
+                if tokens.get(j + 1).is_some_and(|t| t.is_punct("(")) {
+                    j = matching_close(tokens, j + 1) + 1;
+                    false
+                } else {
+                    j += 1;
+                    true
+                }
+            }
+            _ => false,
+        };
+        while j < end {
+            let t = &tokens[j];
+            let qualifier = match t.text.as_str() {
+                "unsafe" | "async" | "default" | "extern" | "auto" => t.kind == TokenKind::Ident,
+                "const" => tokens.get(j + 1).is_some_and(|n| {
+                    n.is_ident("fn")
+                        || n.is_ident("unsafe")
+                        || n.is_ident("async")
+                        || n.is_ident("extern")
+                }),
+                _ => t.kind == TokenKind::Literal && j > 0 && tokens[j - 1].is_ident("extern"),
+            };
+            if !qualifier {
+                break;
+            }
+            j += 1;
+        }
+        if j >= end {
+            break;
+        }
+
+        let kw = tokens[j].text.as_str();
+        let value = matches!(kw, "const" | "static" | "type" | "use");
+        let stop = item_end(tokens, j, end, value);
+        let has_body = tokens[stop].is_punct("{");
+        let body_close = if has_body {
+            matching_close(tokens, stop)
+        } else {
+            stop
+        };
+        let name = tokens
+            .get(j + 1)
+            .map(|t| t.text.clone())
+            .unwrap_or_default();
+        let member_path = match scope {
+            Scope::Module => join(prefix, &name),
+            Scope::Impl(owner) | Scope::Trait(owner) => {
+                join(prefix, &format!("{}::{}", owner, name))
+            }
+        };
+        let line = tokens[item_start].line;
+        let mut push = |kind: &'static str, path: String, signature: String| {
+            out.push(ApiItem {
+                kind,
+                path,
+                signature,
+                line,
+                documented,
+            });
+        };
+
+        match kw {
+            "fn" if public => {
+                let kind = if matches!(scope, Scope::Module) {
+                    "fn"
+                } else {
+                    "method"
+                };
+                push(kind, member_path, render(&tokens[item_start..stop]));
+            }
+            "struct" | "union" | "enum" if public => {
+                push(
+                    if kw == "enum" { "enum" } else { "struct" },
+                    member_path.clone(),
+                    render(&tokens[item_start..stop]),
+                );
+                if has_body {
+                    for (a, s, e) in body_entries(tokens, stop, body_close) {
+                        let entry = &tokens[s..e];
+                        let field_name = if kw == "enum" {
+                            entry.first()
+                        } else if entry.first().is_some_and(|t| t.is_ident("pub"))
+                            && !entry.get(1).is_some_and(|t| t.is_punct("("))
+                        {
+                            entry.get(1)
+                        } else {
+                            None
+                        };
+                        if let Some(field) = field_name {
+                            let kind = if kw == "enum" { "variant" } else { "field" };
+                            out.push(ApiItem {
+                                kind,
+                                path: format!("{}::{}", member_path, field.text),
+                                signature: render(entry),
+                                line: entry[0].line,
+                                documented: src.documented(a, s),
+                            });
+                        }
+                    }
+                }
+            }
+            "trait" if public => {
+                push("trait", member_path, render(&tokens[item_start..stop]));
+                if has_body {
+                    scan(src, stop + 1, body_close, &Scope::Trait(name), prefix, out);
+                }
+            }
+            "type" | "const" | "static" if public => {
+                // Drop the value: `const X: u8 = 1;` -> `const X: u8`
+                let value_at = (j..stop).find(|&k| tokens[k].is_punct("=")).unwrap_or(stop);
+                let kind = match kw {
+                    "type" => "type",
+                    "const" => "const",
+                    _ => "static",
+                };
+                let path = if kw == "static" && name == "mut" {
+                    join(
+                        prefix,
+                        &tokens
+                            .get(j + 2)
+                            .map(|t| t.text.clone())
+                            .unwrap_or_default(),
+                    )
+                } else {
+                    member_path
+                };
+                let signature_end = if kw == "type" { stop } else { value_at };
+                push(kind, path, render(&tokens[item_start..signature_end]));
+            }
+            "use" if public => {
+                let tree = render(&tokens[j + 1..stop]);
+                push(
+                    "use",
+                    join(prefix, &tree),
+                    render(&tokens[item_start..stop]),
+                );
+            }
+            "mod" if public && has_body => {
+                scan(
+                    src,
+                    stop + 1,
+                    body_close,
+                    &Scope::Module,
+                    &join(prefix, &name),
+                    out,
+                );
+            }
+            "impl" if matches!(scope, Scope::Module) && has_body => {
+                let header = &tokens[j..stop];
+                let mut depth = 0i32;
+                let mut for_at = None;
+                for (k, t) in header.iter().enumerate() {
+                    if t.is_punct("<") {
+                        depth += 1;
+                    } else if t.is_punct(">") {
+                        depth -= 1;
+                    } else if depth == 0 && t.is_ident("for") {
+                        for_at = Some(k);
+                    } else if depth == 0 && t.is_ident("where") {
+                        break;
+                    }
+                }
+                if for_at.is_some() {
+                    // Trait implementations are part of the API of the implementing type
+                    let signature = render(&tokens[item_start..stop]);
+                    push("impl", join(prefix, &signature), signature);
+                } else {
+                    // `impl<T> a::Type<T> where ...` -> Type
+                    let owner = header
+                        .iter()
+                        .skip(1)
+                        .scan(0i32, |depth, t| {
+                            if t.is_punct("<") {
+                                *depth += 1;
+                            } else if t.is_punct(">") {
+                                *depth -= 1;
+                            }
+                            Some((*depth, t))
+                        })
+                        .filter(|(depth, t)| {
+                            *depth == 0 && t.kind == TokenKind::Ident && !t.is_ident("dyn")
+                        })
+                        .take_while(|(_, t)| !t.is_ident("where"))
+                        .last()
+                        .map(|(_, t)| t.text.clone())
+                        .unwrap_or_default();
+                    scan(src, stop + 1, body_close, &Scope::Impl(owner), prefix, out);
+                }
+            }
+            _ => {}
+        }
+
+        i = body_close.max(item_start) + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_public_items() {
+        let src = r#"
+//! Contracts
+use std::fmt;
+pub use inner::Thing;
+
+pub const LIMIT: usize = 10;
+const PRIVATE: u8 = 1;
+
+#[derive(Debug)]
+pub struct Order {
+    pub id: i64,
+    secret: String,
+    pub(crate) internal: u8,
+}
+
+pub enum Status { Open, Closed = 2 }
+
+impl Order {
+    pub fn new(id: i64) -> Self { Self { id, secret: String::new(), internal: 0 } }
+    fn hidden(&self) {}
+}
+
+impl fmt::Display for Order {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { Ok(()) }
+}
+
+pub trait Store { fn save(&self, order: &Order) -> Result<(), String>; }
+
+pub(crate) fn helper() {}
+
+pub mod inner {
+    pub struct Thing(pub u8);
+}
+
+#[cfg(test)]
+mod tests { pub fn t() {} }
+"#;
+        let items: Vec<(&str, String, String)> = public_items(src)
+            .into_iter()
+            .map(|i| (i.kind, i.path, i.signature))
+            .collect();
+        let expected: Vec<(&str, &str, &str)> = vec![
+            ("use", "inner::Thing", "pub use inner::Thing"),
+            ("const", "LIMIT", "pub const LIMIT: usize"),
+            ("struct", "Order", "pub struct Order"),
+            ("field", "Order::id", "pub id: i64"),
+            ("enum", "Status", "pub enum Status"),
+            ("variant", "Status::Open", "Open"),
+            ("variant", "Status::Closed", "Closed = 2"),
+            ("method", "Order::new", "pub fn new(id: i64) -> Self"),
+            (
+                "impl",
+                "impl fmt::Display for Order",
+                "impl fmt::Display for Order",
+            ),
+            ("trait", "Store", "pub trait Store"),
+            (
+                "method",
+                "Store::save",
+                "fn save(&self, order: &Order) -> Result<(), String>",
+            ),
+            ("struct", "inner::Thing", "pub struct Thing(pub u8)"),
+        ];
+        let expected: Vec<(&str, String, String)> = expected
+            .into_iter()
+            .map(|(k, p, s)| (k, p.to_string(), s.to_string()))
+            .collect();
+        assert_eq!(items, expected);
+
+        assert_eq!(module_path("lib.rs"), "");
+        assert_eq!(module_path("api/orders/mod.rs"), "api::orders");
+        assert_eq!(module_path("api/orders.rs"), "api::orders");
+    }
+
+    #[test]
+    fn detects_doc_comments() {
+        let src = r#"
+//! Orders
+#![allow(dead_code)]
+
+/// An order
+#[derive(Debug)]
+pub struct Order {
+    /// Identifier
+    pub id: i64,
+    pub total: u64,
+}
+
+#[doc = "Generated"]
+pub fn generated() {}
+
+//// Not a doc comment
+pub fn plain() {}
+
+#[cfg(feature = "x")]
+/** Block doc */
+pub const X: u8 = 1;
+"#;
+        let documented: Vec<(String, bool)> = public_items(src)
+            .into_iter()
+            .map(|i| (i.path, i.documented))
+            .collect();
+        let expected = [
+            ("Order", true),
+            ("Order::id", true),
+            ("Order::total", false),
+            ("generated", true),
+            ("plain", false),
+            ("X", true),
+        ];
+        let expected: Vec<(String, bool)> =
+            expected.iter().map(|(p, d)| (p.to_string(), *d)).collect();
+        assert_eq!(documented, expected);
+
+        assert!(has_module_doc(src));
+        assert!(has_module_doc("// License\n#![doc = \"x\"]\nfn f() {}"));
+        assert!(!has_module_doc("/// Item doc\npub fn f() {}\n//! late"));
+        assert!(needs_doc("fn") && !needs_doc("impl"));
+    }
+}
@@ -0,0 +1,131 @@
+use super::rust_syntax::{code_tokens, matching_close, test_ranges, tokenize, Token, TokenKind};
+
+/// One imported path of a `use` declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct UseItem {
+    /// `crate::domain::n001_project::repository`, without a trailing `self`, `as` rename or `*`
+    pub path: Vec<String>,
+    /// 1-based line of the `use` keyword
+    pub line: usize,
+    /// Inside a `#[test]` / `#[cfg(test)]` item
+    pub in_test: bool,
+}
+
+/// Expand the use tree starting at `i` into `out`; returns the index after it.
+/// `prefix` is restored to its length on entry.
+fn use_tree(
+    tokens: &[Token],
+    mut i: usize,
+    prefix: &mut Vec<String>,
+    out: &mut Vec<Vec<String>>,
+) -> usize {
+    let depth = prefix.len();
+    while let Some(t) = tokens.get(i) {
+        if t.is_punct("::") {
+            i += 1;
+        } else if t.kind == TokenKind::Ident && t.text != "as" {
+            prefix.push(t.text.clone());
+            i += 1;
+            if !tokens.get(i).is_some_and(|t| t.is_punct("::")) {
+                break;
+            }
+        } else if t.is_punct("{") {
+            let close = matching_close(tokens, i);
+            i += 1;
+            while i < close {
+                if tokens[i].is_punct(",") {
+                    i += 1;
+                    continue;
+                }
+                let next = use_tree(tokens, i, prefix, out);
+                i = if next > i { next } else { i + 1 };
+            }
+            prefix.truncate(depth);
+            return close + 1;
+        } else if t.is_punct("*") {
+            i += 1;
+            break;
+        } else {
+            break;
+        }
+    }
+    if tokens.get(i).is_some_and(|t| t.is_ident("as")) {
+        i += 2;
+    }
+    // `a::{self, b}` imports `a` itself
+    let is_self = prefix.len() > depth && prefix.last().is_some_and(|s| s == "self");
+    if is_self {
+        prefix.pop();
+    }
+    if prefix.len() > depth || (is_self && !prefix.is_empty()) {
+        out.push(prefix.clone());
+    }
+    prefix.truncate(depth);
+    i
+}
+
+/// Every path imported by `use` declarations, groups expanded:
+/// `use a::{b, c::{self, d as e}};` gives `a::b`, `a::c` and `a::c::d`
+pub fn use_items(src: &str) -> Vec<UseItem> {
+    let tokens = code_tokens(&tokenize(src));
+    let tests = test_ranges(&tokens);
+    let mut items = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let is_use = tokens[i].is_ident("use") && (i == 0 || !tokens[i - 1].is_punct("::"));
+        if !is_use {
+            i += 1;
+            continue;
+        }
+        let mut paths = Vec::new();
+        let end = use_tree(&tokens, i + 1, &mut Vec::new(), &mut paths);
+        let in_test = tests.iter().any(|&(start, end)| (start..=end).contains(&i));
+        items.extend(paths.into_iter().map(|path| UseItem {
+            path,
+            line: tokens[i].line,
+            in_test,
+        }));
+        i = end.max(i + 1);
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_use_trees() {
+        let src = "
+use std::collections::{HashMap, HashSet as Set};
+pub(crate) use crate::domain::{n001_project::{self, repository as repo}, n002_snapshot::model::*};
+use super::super::shared;
+use ::serde_json;
+
+fn f() { use self::inner::g; }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+}
+";
+        let items = use_items(src);
+        let paths: Vec<String> = items.iter().map(|u| u.path.join("::")).collect();
+        assert_eq!(
+            paths,
+            [
+                "std::collections::HashMap",
+                "std::collections::HashSet",
+                "crate::domain::n001_project",
+                "crate::domain::n001_project::repository",
+                "crate::domain::n002_snapshot::model",
+                "super::super::shared",
+                "serde_json",
+                "self::inner::g",
+                "super",
+            ]
+        );
+        assert_eq!(items[2].line, 3);
+        assert!(items.iter().all(|u| u.in_test == (u.path == ["super"])));
+    }
+}
@@ -0,0 +1,539 @@
+//! Minimal Rust lexer and item finder.
+//!
+//! Not a parser: it only knows enough about literals, comments and brackets to
+//! locate `fn` items and `impl`/`trait` blocks reliably in ordinary code.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident,
+    /// `'a` (also loop labels)
+    Lifetime,
+    /// String, char, byte and number literals
+    Literal,
+    /// Single character, or one of `=>`, `&&`, `||`, `::`, `->`
+    Punct,
+    /// Line or block comment, including doc comments
+    Comment,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    /// 1-based line the token starts on
+    pub line: usize,
+}
+
+impl Token {
+    pub fn is_ident(&self, text: &str) -> bool {
+        self.kind == TokenKind::Ident && self.text == text
+    }
+
+    pub fn is_punct(&self, text: &str) -> bool {
+        self.kind == TokenKind::Punct && self.text == text
+    }
+}
+
+const TWO_CHAR_PUNCT: [&str; 5] = ["=>", "&&", "||", "::", "->"];
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Split Rust source into tokens. Unterminated literals and comments run to the end of input.
+pub fn tokenize(src: &str) -> Vec<Token> {
+    let chars: Vec<char> = src.chars().collect();
+    let at = |i: usize| chars.get(i).copied().unwrap_or('\0');
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let start_line = line;
+
+        let kind = if c == '\n' {
+            line += 1;
+            i += 1;
+            continue;
+        } else if c.is_whitespace() {
+            i += 1;
+            continue;
+        } else if c == '/' && at(i + 1) == '/' {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            TokenKind::Comment
+        } else if c == '/' && at(i + 1) == '*' {
+            // Block comments nest in Rust
+            let mut depth = 0;
+            while i < chars.len() {
+                if chars[i] == '/' && at(i + 1) == '*' {
+                    depth += 1;
+                    i += 2;
+                } else if chars[i] == '*' && at(i + 1) == '/' {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+            }
+            TokenKind::Comment
+        } else if let Some(len) = raw_string_prefix(&chars, i) {
+            // r"..", r#".."#, br"..", cr".."
+            let hashes = chars[i..i + len].iter().filter(|&&c| c == '#').count();
+            i += len;
+            while i < chars.len() {
+                if chars[i] == '"' && (1..=hashes).all(|h| at(i + h) == '#') {
+                    i += 1 + hashes;
+                    break;
+                }
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            TokenKind::Literal
+        } else if c == 'r' && at(i + 1) == '#' && is_ident_start(at(i + 2)) {
+            // Raw identifier: keep the bare name
+            i += 2;
+            let name_start = i;
+            while i < chars.len() && is_ident_char(chars[i]) {
+                i += 1;
+            }
+            tokens.push(Token {
+                kind: TokenKind::Ident,
+                text: chars[name_start..i].iter().collect(),
+                line: start_line,
+            });
+            continue;
+        } else if c == '"' || ((c == 'b' || c == 'c') && at(i + 1) == '"') {
+            i += if c == '"' { 1 } else { 2 };
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                if at(i) == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+            TokenKind::Literal
+        } else if c == '\'' || (c == 'b' && at(i + 1) == '\'') {
+            let quote = if c == '\'' { i } else { i + 1 };
+            if at(quote + 1) == '\\' {
+                i = quote + 2;
+                while i < chars.len() && chars[i] != '\'' && chars[i] != '\n' {
+                    i += 1;
+                }
+                i += 1;
+                TokenKind::Literal
+            } else if at(quote + 2) == '\'' {
+                i = quote + 3;
+                TokenKind::Literal
+            } else {
+                i = quote + 1;
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+                TokenKind::Lifetime
+            }
+        } else if c.is_ascii_digit() {
+            while i < chars.len()
+                && (is_ident_char(chars[i]) || (chars[i] == '.' && at(i + 1).is_ascii_digit()))
+            {
+                i += 1;
+            }
+            TokenKind::Literal
+        } else if is_ident_start(c) {
+            while i < chars.len() && is_ident_char(chars[i]) {
+                i += 1;
+            }
+            TokenKind::Ident
+        } else {
+            let pair: String = [c, at(i + 1)].iter().collect();
+            i += if TWO_CHAR_PUNCT.contains(&pair.as_str()) {
+                2
+            } else {
+                1
+            };
+            TokenKind::Punct
+        };
+
+        tokens.push(Token {
+            kind,
+            text: chars[start..i.min(chars.len())].iter().collect(),
+            line: start_line,
+        });
+    }
+
+    tokens
+}
+
+/// Length of a raw string opening (`r#"`, `br"`, ...) starting at `i`
+fn raw_string_prefix(chars: &[char], i: usize) -> Option<usize> {
+    let mut j = i;
+    if matches!(chars.get(j), Some('b') | Some('c')) {
+        j += 1;
+    }
+    if chars.get(j) != Some(&'r') {
+        return None;
+    }
+    j += 1;
+    while chars.get(j) == Some(&'#') {
+        j += 1;
+    }
+    (chars.get(j) == Some(&'"')).then_some(j + 1 - i)
+}
+
+/// Tokens without comments
+pub fn code_tokens(tokens: &[Token]) -> Vec<Token> {
+    tokens
+        .iter()
+        .filter(|t| t.kind != TokenKind::Comment)
+        .cloned()
+        .collect()
+}
+
+/// Index of the bracket closing the one at `open` (`(`, `[` or `{`); last index if unbalanced
+pub fn matching_close(tokens: &[Token], open: usize) -> usize {
+    let (opening, closing) = match tokens[open].text.as_str() {
+        "(" => ("(", ")"),
+        "[" => ("[", "]"),
+        _ => ("{", "}"),
+    };
+    let mut depth = 0;
+    for (idx, t) in tokens.iter().enumerate().skip(open) {
+        if t.is_punct(opening) {
+            depth += 1;
+        } else if t.is_punct(closing) {
+            depth -= 1;
+            if depth == 0 {
+                return idx;
+            }
+        }
+    }
+    tokens.len().saturating_sub(1)
+}
+
+/// A function with a body
+#[derive(Debug, Clone, PartialEq)]
+pub struct FnItem {
+    pub name: String,
+    /// `Type::name` for methods, otherwise the bare name
+    pub qualified_name: String,
+    /// Index of the `fn` keyword
+    pub fn_idx: usize,
+    pub body_open: usize,
+    pub body_close: usize,
+    /// Parameters excluding a `self` receiver
+    pub params: usize,
+}
+
+/// Functions with bodies, in source order. Expects comment-free tokens (see [`code_tokens`]).
+pub fn functions(tokens: &[Token]) -> Vec<FnItem> {
+    // Enclosing impl/trait blocks: (closing brace index, type name)
+    let mut scopes: Vec<(usize, String)> = Vec::new();
+    let mut out = Vec::new();
+
+    for i in 0..tokens.len() {
+        while scopes.last().is_some_and(|(close, _)| *close < i) {
+            scopes.pop();
+        }
+        let t = &tokens[i];
+
+        if (t.is_ident("impl") || t.is_ident("trait")) && starts_item(tokens, i) {
+            if let Some((open, name)) = block_header(tokens, i) {
+                scopes.push((matching_close(tokens, open), name));
+            }
+            continue;
+        }
+
+        if !t.is_ident("fn") {
+            continue;
+        }
+        let Some(name) = tokens.get(i + 1).filter(|n| n.kind == TokenKind::Ident) else {
+            continue;
+        };
+
+        let mut j = i + 2;
+        if tokens.get(j).is_some_and(|t| t.is_punct("<")) {
+            j = skip_angles(tokens, j);
+        }
+        if !tokens.get(j).is_some_and(|t| t.is_punct("(")) {
+            continue;
+        }
+        let params_close = matching_close(tokens, j);
+        let params = count_params(&tokens[j + 1..params_close]);
+
+        // Return type and where clause up to the body (or `;` for declarations)
+        let mut k = params_close + 1;
+        let mut depth = 0i32;
+        while k < tokens.len() {
+            let t = &tokens[k];
+            if t.is_punct("(") || t.is_punct("[") || t.is_punct("<") {
+                depth += 1;
+            } else if t.is_punct(")") || t.is_punct("]") || t.is_punct(">") {
+                depth -= 1;
+            } else if depth <= 0 && (t.is_punct("{") || t.is_punct(";")) {
+                break;
+            }
+            k += 1;
+        }
+        if !tokens.get(k).is_some_and(|t| t.is_punct("{")) {
+            continue;
+        }
+
+        let qualified_name = match scopes.last() {
+            Some((_, scope)) => format!("{}::{}", scope, name.text),
+            None => name.text.clone(),
+        };
+        out.push(FnItem {
+            name: name.text.clone(),
+            qualified_name,
+            fn_idx: i,
+            body_open: k,
+            body_close: matching_close(tokens, k),
+            params,
+        });
+    }
+
+    out
+}
+
+/// Inclusive token ranges of items annotated `#[test]` (also `#[tokio::test]` etc.) or `#[cfg(test)]`,
+/// attributes included. Expects comment-free tokens.
+pub fn test_ranges(tokens: &[Token]) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i + 1 < tokens.len() {
+        if !(tokens[i].is_punct("#") && tokens[i + 1].is_punct("[")) {
+            i += 1;
+            continue;
+        }
+        let attr_close = matching_close(tokens, i + 1);
+        if !is_test_attribute(&tokens[i + 2..attr_close]) {
+            i = attr_close + 1;
+            continue;
+        }
+
+        // The annotated item ends at its closing brace or at `;`
+        let mut j = attr_close + 1;
+        let mut depth = 0i32;
+        while j < tokens.len() {
+            let t = &tokens[j];
+            if t.is_punct("(") || t.is_punct("[") {
+                depth += 1;
+            } else if t.is_punct(")") || t.is_punct("]") {
+                depth -= 1;
+            } else if depth == 0 && t.is_punct("{") {
+                j = matching_close(tokens, j);
+                break;
+            } else if depth == 0 && t.is_punct(";") {
+                break;
+            }
+            j += 1;
+        }
+        let end = j.min(tokens.len() - 1);
+        out.push((i, end));
+        i = end + 1;
+    }
+    out
+}
+
+/// `test`, `a::test`, or `cfg(...)` mentioning `test` outside `not(...)`
+fn is_test_attribute(attr: &[Token]) -> bool {
+    let path_end = attr
+        .iter()
+        .position(|t| t.is_punct("(") || t.is_punct("="))
+        .unwrap_or(attr.len());
+    let path = &attr[..path_end];
+    if path.last().is_some_and(|t| t.is_ident("test")) {
+        return true;
+    }
+    path.len() == 1
+        && path[0].is_ident("cfg")
+        && attr.iter().any(|t| t.is_ident("test"))
+        && !attr.iter().any(|t| t.is_ident("not"))
+}
+
+/// `impl`/`trait` in item position (not `impl Trait` in a type)
+fn starts_item(tokens: &[Token], i: usize) -> bool {
+    match i.checked_sub(1).map(|p| &tokens[p]) {
+        None => true,
+        Some(prev) => {
+            prev.is_punct("}")
+                || prev.is_punct(";")
+                || prev.is_punct("{")
+                || prev.is_punct("]")
+                || prev.is_punct(")")
+                || prev.is_ident("pub")
+                || prev.is_ident("unsafe")
+                || prev.is_ident("default")
+                || prev.is_ident("auto")
+        }
+    }
+}
+
+/// Opening brace and type name of an `impl`/`trait` block
+fn block_header(tokens: &[Token], i: usize) -> Option<(usize, String)> {
+    let mut names: Vec<&str> = Vec::new();
+    let mut after_for: Option<usize> = None;
+    let mut depth = 0i32;
+    let mut in_where = false;
+    let mut j = i + 1;
+    while j < tokens.len() {
+        let t = &tokens[j];
+        if t.is_punct("<") || t.is_punct("(") || t.is_punct("[") {
+            depth += 1;
+        } else if t.is_punct(">") || t.is_punct(")") || t.is_punct("]") {
+            depth -= 1;
+        } else if depth <= 0 && t.is_punct(";") {
+            return None;
+        } else if depth <= 0 && t.is_punct("{") {
+            break;
+        } else if depth <= 0 && !in_where && t.kind == TokenKind::Ident {
+            match t.text.as_str() {
+                "where" => in_where = true,
+                "for" => after_for = Some(names.len()),
+                "unsafe" | "dyn" | "const" => {}
+                _ => names.push(&t.text),
+            }
+        }
+        j += 1;
+    }
+    if j >= tokens.len() {
+        return None;
+    }
+
+    let name = if tokens[i].is_ident("trait") {
+        names.first()
+    } else {
+        // `impl a::Trait for b::Type` -> Type, `impl a::Type` -> Type
+        names[after_for.unwrap_or(0)..].last()
+    };
+    Some((j, name.map(|n| n.to_string()).unwrap_or_default()))
+}
+
+/// Index after the `>` closing the `<` at `open`
+fn skip_angles(tokens: &[Token], open: usize) -> usize {
+    let mut depth = 0;
+    for (idx, t) in tokens.iter().enumerate().skip(open) {
+        if t.is_punct("<") {
+            depth += 1;
+        } else if t.is_punct(">") {
+            depth -= 1;
+            if depth == 0 {
+                return idx + 1;
+            }
+        }
+    }
+    tokens.len()
+}
+
+/// Comma-separated parameters at depth 0, not counting `self`, `&self`, `mut self`, `self: Box<Self>`
+fn count_params(tokens: &[Token]) -> usize {
+    let mut segments: Vec<Vec<&Token>> = vec![Vec::new()];
+    let mut depth = 0i32;
+    for t in tokens {
+        if t.is_punct("(") || t.is_punct("[") || t.is_punct("{") || t.is_punct("<") {
+            depth += 1;
+        } else if t.is_punct(")") || t.is_punct("]") || t.is_punct("}") || t.is_punct(">") {
+            depth -= 1;
+        } else if depth == 0 && t.is_punct(",") {
+            segments.push(Vec::new());
+            continue;
+        }
+        segments.last_mut().expect("at least one segment").push(t);
+    }
+
+    segments
+        .iter()
+        .filter(|s| !s.is_empty())
+        .filter(|s| {
+            let receiver = s
+                .iter()
+                .find(|t| !(t.is_punct("&") || t.is_ident("mut") || t.kind == TokenKind::Lifetime))
+                .is_some_and(|t| t.is_ident("self"));
+            !receiver
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(src: &str) -> Vec<String> {
+        tokenize(src).into_iter().map(|t| t.text).collect()
+    }
+
+    #[test]
+    fn lexes_literals_comments_and_lifetimes() {
+        assert_eq!(
+            texts("a && b || 'x' == '\\n'"),
+            ["a", "&&", "b", "||", "'x'", "=", "=", "'\\n'"]
+        );
+        assert_eq!(
+            texts("fn f<'a>(s: &'a str)"),
+            ["fn", "f", "<", "'a", ">", "(", "s", ":", "&", "'a", "str", ")"]
+        );
+        assert_eq!(
+            texts(r##"r#"a { "b" }"# /* x /* y */ z */ {"##),
+            [r##"r#"a { "b" }"#"##, "/* x /* y */ z */", "{"]
+        );
+        let tokens = tokenize("\"multi\nline\" x");
+        assert_eq!(tokens[1].line, 2);
+    }
+
+    #[test]
+    fn finds_test_items() {
+        let src = "fn a() {} #[test] fn b() { x[0]; } #[cfg(not(test))] fn c() {} #[cfg(test)] mod tests { fn d() {} } #[tokio::test] async fn e() {}";
+        let tokens = code_tokens(&tokenize(src));
+        let covered: Vec<&str> = tokens
+            .iter()
+            .enumerate()
+            .filter(|(i, t)| {
+                t.is_ident("fn") && test_ranges(&tokens).iter().any(|(s, e)| s <= i && i <= e)
+            })
+            .map(|(i, _)| tokens[i + 1].text.as_str())
+            .collect();
+        assert_eq!(covered, ["b", "d", "e"]);
+    }
+
+    #[test]
+    fn finds_functions_in_impls() {
+        let src = r#"
+            impl<T: Clone> fmt::Display for Wrapper<T> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { Ok(()) }
+            }
+            trait Shape { fn area(&self) -> f64; fn name(&self) -> String { String::new() } }
+            pub fn free(a: HashMap<u8, u8>, b: impl Fn(u8, u8) -> u8) -> [u8; 2] { [0; 2] }
+        "#;
+        let tokens = code_tokens(&tokenize(src));
+        let found: Vec<(String, usize)> = functions(&tokens)
+            .into_iter()
+            .map(|f| (f.qualified_name, f.params))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Wrapper::fmt".to_string(), 1),
+                ("Shape::name".to_string(), 0),
+                ("free".to_string(), 2),
+            ]
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use super::rust_syntax::{code_tokens, functions, test_ranges, tokenize, Token, TokenKind};
+
+/// Unsafe code and potential panic sites
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SiteKind {
+    UnsafeBlock,
+    UnsafeFn,
+    UnsafeImpl,
+    Unwrap,
+    Expect,
+    Panic,
+    /// `x[i]` / `x[a..b]`, which panics when out of bounds
+    Index,
+}
+
+impl SiteKind {
+    pub const ALL: [SiteKind; 7] = [
+        SiteKind::UnsafeBlock,
+        SiteKind::UnsafeFn,
+        SiteKind::UnsafeImpl,
+        SiteKind::Unwrap,
+        SiteKind::Expect,
+        SiteKind::Panic,
+        SiteKind::Index,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SiteKind::UnsafeBlock => "unsafe block",
+            SiteKind::UnsafeFn => "unsafe fn",
+            SiteKind::UnsafeImpl => "unsafe impl",
+            SiteKind::Unwrap => "unwrap",
+            SiteKind::Expect => "expect",
+            SiteKind::Panic => "panic!",
+            SiteKind::Index => "index",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.as_str() == s)
+    }
+
+    pub fn is_unsafe(self) -> bool {
+        matches!(
+            self,
+            SiteKind::UnsafeBlock | SiteKind::UnsafeFn | SiteKind::UnsafeImpl
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Site {
+    pub kind: SiteKind,
+    /// 1-based line
+    pub line: usize,
+    /// Innermost enclosing function (`Type::name`), or the unsafe fn itself
+    pub function: Option<String>,
+}
+
+/// Keywords after which `[` starts a pattern, type or array rather than an index
+const NON_OPERAND_KEYWORDS: [&str; 12] = [
+    "let", "in", "for", "mut", "return", "match", "if", "while", "else", "break", "move", "as",
+];
+
+fn is_index(tokens: &[Token], i: usize) -> bool {
+    let Some(prev) = i.checked_sub(1).map(|p| &tokens[p]) else {
+        return false;
+    };
+    match prev.kind {
+        TokenKind::Ident => !NON_OPERAND_KEYWORDS.contains(&prev.text.as_str()),
+        TokenKind::Punct => prev.is_punct(")") || prev.is_punct("]") || prev.is_punct("?"),
+        _ => false,
+    }
+}
+
+/// Unsafe code and panic sites of a Rust source file, excluding `#[test]`/`#[cfg(test)]` items
+pub fn find_sites(src: &str) -> Vec<Site> {
+    let tokens = code_tokens(&tokenize(src));
+    let items = functions(&tokens);
+    let tests = test_ranges(&tokens);
+    let mut out = Vec::new();
+
+    let enclosing = |idx: usize| {
+        items
+            .iter()
+            .filter(|f| f.body_open < idx && idx < f.body_close)
+            .max_by_key(|f| f.body_open)
+            .map(|f| f.qualified_name.clone())
+    };
+
+    for (i, t) in tokens.iter().enumerate() {
+        if tests.iter().any(|&(start, end)| start <= i && i <= end) {
+            continue;
+        }
+        let next = tokens.get(i + 1);
+        let method_call =
+            i > 0 && tokens[i - 1].is_punct(".") && next.is_some_and(|n| n.is_punct("("));
+        let kind = if t.is_ident("unsafe") {
+            if next.is_some_and(|n| n.is_punct("{")) {
+                Some(SiteKind::UnsafeBlock)
+            } else if next.is_some_and(|n| n.is_ident("impl")) {
+                Some(SiteKind::UnsafeImpl)
+            } else if next.is_some_and(|n| n.is_ident("trait")) {
+                // Declaring an unsafe trait is safe; its `unsafe impl`s are counted
+                None
+            } else if tokens[i + 1..]
+                .iter()
+                .take(4)
+                .take_while(|n| !n.is_punct("{") && !n.is_punct(";"))
+                .any(|n| n.is_ident("fn"))
+            {
+                // `unsafe fn`, `unsafe extern "C" fn`. An `unsafe extern "C" { .. }` block stops
+                // at `{` and is skipped: calls into it need unsafe blocks, which are counted.
+                Some(SiteKind::UnsafeFn)
+            } else {
+                None
+            }
+        } else if t.is_ident("unwrap") && method_call {
+            Some(SiteKind::Unwrap)
+        } else if t.is_ident("expect") && method_call {
+            Some(SiteKind::Expect)
+        } else if t.is_ident("panic") && next.is_some_and(|n| n.is_punct("!")) {
+            Some(SiteKind::Panic)
+        } else if t.is_punct("[") && is_index(&tokens, i) {
+            Some(SiteKind::Index)
+        } else {
+            None
+        };
+
+        let Some(kind) = kind else { continue };
+        let function = if kind == SiteKind::UnsafeFn {
+            items
+                .iter()
+                .find(|f| f.fn_idx > i && f.fn_idx <= i + 4)
+                .map(|f| f.qualified_name.clone())
+        } else {
+            enclosing(i)
+        };
+        out.push(Site {
+            kind,
+            line: t.line,
+            function,
+        });
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_unsafe_and_panic_sites() {
+        let src = r#"
+unsafe impl Send for Db {}
+impl Db {
+    pub unsafe fn raw(&self) -> *const u8 { self.ptr }
+    fn open(path: &str, parts: &[u8; 2]) -> Conn {
+        let v = vec![1, 2];
+        let [a, b] = *parts;
+        let c = v[0] + parts[a as usize];
+        let conn = Conn::open(path).expect("Failed to open database");
+        if c > 3 { panic!("too big") }
+        unsafe { conn.raw().read() };
+        conn.check().unwrap()
+    }
+}
+#[test]
+fn t() { None::<u8>.unwrap(); }
+"#;
+        let sites: Vec<(SiteKind, usize, Option<String>)> = find_sites(src)
+            .into_iter()
+            .map(|s| (s.kind, s.line, s.function))
+            .collect();
+        let open = Some("Db::open".to_string());
+        assert_eq!(
+            sites,
+            vec![
+                (SiteKind::UnsafeImpl, 2, None),
+                (SiteKind::UnsafeFn, 4, Some("Db::raw".to_string())),
+                (SiteKind::Index, 8, open.clone()),
+                (SiteKind::Index, 8, open.clone()),
+                (SiteKind::Expect, 9, open.clone()),
+                (SiteKind::Panic, 10, open.clone()),
+                (SiteKind::UnsafeBlock, 11, open.clone()),
+                (SiteKind::Unwrap, 12, open.clone()),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_unsafe_trait_and_extern_block_declarations() {
+        let src = r#"
+unsafe trait Zeroed { fn zeroed() -> Self; }
+unsafe extern "C" { fn abs(x: i32) -> i32; }
+unsafe extern "C" fn callback() {}
+"#;
+        let sites: Vec<(SiteKind, usize)> = find_sites(src)
+            .into_iter()
+            .map(|s| (s.kind, s.line))
+            .collect();
+        assert_eq!(sites, vec![(SiteKind::UnsafeFn, 4)]);
+    }
+}
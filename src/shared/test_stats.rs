@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+
+use super::rust_syntax::{code_tokens, matching_close, test_ranges, tokenize, Token};
+
+/// Test functions and test/production line counts of a Rust source file
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TestStats {
+    /// `#[test]` / `#[bench]` functions (also `#[tokio::test]` etc.)
+    pub test_fns: usize,
+    /// Non-blank lines inside `#[test]`/`#[cfg(test)]` items
+    pub test_loc: usize,
+    /// Remaining non-blank lines
+    pub code_loc: usize,
+}
+
+/// `tests/` and `benches/` directories hold test code only
+pub fn is_test_path(path: &str) -> bool {
+    let normalized = path.replace('\\', "/");
+    let mut dirs: Vec<&str> = normalized.split('/').collect();
+    dirs.pop();
+    dirs.iter().any(|d| *d == "tests" || *d == "benches")
+}
+
+/// Attribute path ends in `test` or `bench` (not `cfg(test)`)
+fn is_test_fn_attribute(attr: &[Token]) -> bool {
+    let path_end = attr
+        .iter()
+        .position(|t| t.is_punct("(") || t.is_punct("="))
+        .unwrap_or(attr.len());
+    attr[..path_end]
+        .last()
+        .is_some_and(|t| t.is_ident("test") || t.is_ident("bench"))
+}
+
+pub fn analyze_tests(src: &str, whole_file_is_test: bool) -> TestStats {
+    let tokens = code_tokens(&tokenize(src));
+
+    let mut test_fns = 0;
+    let mut i = 0;
+    while i + 1 < tokens.len() {
+        if tokens[i].is_punct("#") && tokens[i + 1].is_punct("[") {
+            let close = matching_close(&tokens, i + 1);
+            if is_test_fn_attribute(&tokens[i + 2..close]) {
+                test_fns += 1;
+            }
+            i = close + 1;
+        } else {
+            i += 1;
+        }
+    }
+
+    let lines: Vec<&str> = src.lines().collect();
+    let non_blank = |line: usize| lines.get(line - 1).is_some_and(|l| !l.trim().is_empty());
+    let total = (1..=lines.len()).filter(|&l| non_blank(l)).count();
+
+    let test_loc = if whole_file_is_test {
+        total
+    } else {
+        let test_lines: HashSet<usize> = test_ranges(&tokens)
+            .into_iter()
+            .flat_map(|(start, end)| tokens[start].line..=tokens[end].line)
+            .collect();
+        test_lines.into_iter().filter(|&l| non_blank(l)).count()
+    };
+
+    TestStats {
+        test_fns,
+        test_loc,
+        code_loc: total - test_loc,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_test_and_production_lines() {
+        let src = r#"
+pub fn parse(s: &str) -> u8 {
+    s.len() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses() {
+        assert_eq!(parse("ab"), 2);
+    }
+
+    #[tokio::test]
+    async fn parses_async() {}
+}
+"#;
+        assert_eq!(
+            analyze_tests(src, false),
+            TestStats {
+                test_fns: 2,
+                test_loc: 10,
+                code_loc: 3
+            }
+        );
+        assert_eq!(
+            analyze_tests(src, true),
+            TestStats {
+                test_fns: 2,
+                test_loc: 13,
+                code_loc: 0
+            }
+        );
+        assert!(is_test_path("tests/scan.rs"));
+        assert!(is_test_path("crates/core/benches/parse.rs"));
+        assert!(!is_test_path("src/tests.rs"));
+    }
+}
@@ -0,0 +1,25 @@
+use std::fs;
+use std::path::Path;
+
+/// Files larger than this are not stored for full-text search
+pub const MAX_TEXT_BYTES: i64 = 1024 * 1024;
+
+/// Number of leading bytes inspected for NUL when detecting binaries
+const BINARY_SNIFF_BYTES: usize = 8000;
+
+/// Read a file as UTF-8 text; None for binaries, oversized or unreadable files
+pub fn read_text_file(path: &Path, size_bytes: i64) -> Option<String> {
+    if size_bytes > MAX_TEXT_BYTES {
+        return None;
+    }
+    let bytes = fs::read(path).ok()?;
+    if is_binary(&bytes) {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Heuristic used by git: a NUL byte near the start means binary content
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(BINARY_SNIFF_BYTES).any(|b| *b == 0)
+}
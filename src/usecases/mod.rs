@@ -1,2 +1,22 @@
 pub mod s501_create_snapshot;
-
+pub mod s502_audit_dependencies;
+pub mod s503_search_contents;
+pub mod s504_regex_search;
+pub mod s505_compare_snapshots;
+pub mod s506_git_churn;
+pub mod s507_ownership;
+pub mod s508_complexity;
+pub mod s509_markers;
+pub mod s510_safety;
+pub mod s511_coverage;
+pub mod s512_api_surface;
+pub mod s513_doc_coverage;
+pub mod s514_languages;
+pub mod s515_export;
+pub mod s516_import;
+pub mod s517_report;
+pub mod s518_dependencies;
+pub mod s519_quality_gates;
+pub mod s520_project_dashboard;
+pub mod s521_new_scan;
+pub mod s522_delete_snapshot;
@@ -0,0 +1,91 @@
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n004_snapshot_aggregate::model::code_for_artifact;
+use crate::domain::n004_snapshot_aggregate::repository as aggregate_repo;
+use crate::domain::n013_file_tests::repository as tests_repo;
+use crate::domain::n015_public_item::repository as public_item_repo;
+use crate::shared::rust_api::needs_doc;
+
+/// Rebuild n004 aggregates of a snapshot: one per artifact id (n001_project, s501_...)
+pub fn build_aggregates(conn: &Connection, snapshot_id: i64) -> Result<usize, String> {
+    aggregate_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete aggregates: {}", e))?;
+
+    let files = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?;
+
+    // code -> (name, artifact type, file count, total size)
+    let mut groups: BTreeMap<String, (String, Option<String>, i64, i64)> = BTreeMap::new();
+    for file in files.iter().filter(|f| !f.is_directory) {
+        let Some(artifact_id) = &file.artifact_id else {
+            continue;
+        };
+        let entry = groups
+            .entry(code_for_artifact(artifact_id))
+            .or_insert_with(|| {
+                (
+                    file.artifact_name.clone().unwrap_or_default(),
+                    file.artifact_type.clone(),
+                    0,
+                    0,
+                )
+            });
+        entry.2 += 1;
+        entry.3 += file.size_bytes;
+    }
+
+    // code -> (test fns, test LOC, production LOC); integration tests count toward the artifact they name
+    let mut tests: HashMap<String, (i64, i64, i64)> = HashMap::new();
+    let file_tests = tests_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load test stats: {}", e))?;
+    for t in file_tests {
+        let Some(artifact_id) = &t.covers_artifact else {
+            continue;
+        };
+        let entry = tests.entry(code_for_artifact(artifact_id)).or_default();
+        entry.0 += t.test_fns;
+        entry.1 += t.test_loc;
+        entry.2 += t.code_loc;
+    }
+
+    // code -> (public items needing docs, documented ones)
+    let artifact_by_file: HashMap<i64, &str> = files
+        .iter()
+        .filter_map(|f| f.artifact_id.as_deref().map(|a| (f.id, a)))
+        .collect();
+    let mut docs: HashMap<String, (i64, i64)> = HashMap::new();
+    let items = public_item_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load public items: {}", e))?;
+    for item in items.iter().filter(|i| needs_doc(&i.kind)) {
+        let Some(artifact_id) = artifact_by_file.get(&item.file_id) else {
+            continue;
+        };
+        let entry = docs.entry(code_for_artifact(artifact_id)).or_default();
+        entry.0 += 1;
+        entry.1 += item.documented as i64;
+    }
+
+    for (code, (name, artifact_type, file_count, total_size)) in &groups {
+        let id = aggregate_repo::create(
+            conn,
+            snapshot_id,
+            code,
+            name,
+            artifact_type.as_deref(),
+            *file_count,
+            *total_size,
+        )
+        .map_err(|e| format!("Failed to create aggregate {}: {}", code, e))?;
+        if let Some((test_fns, test_loc, code_loc)) = tests.get(code) {
+            aggregate_repo::set_test_stats(conn, id, *test_fns, *test_loc, *code_loc)
+                .map_err(|e| format!("Failed to store test stats of {}: {}", code, e))?;
+        }
+        if let Some((doc_items, doc_documented)) = docs.get(code) {
+            aggregate_repo::set_doc_stats(conn, id, *doc_items, *doc_documented)
+                .map_err(|e| format!("Failed to store doc stats of {}: {}", code, e))?;
+        }
+    }
+    Ok(groups.len())
+}
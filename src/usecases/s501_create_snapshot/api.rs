@@ -0,0 +1,190 @@
+use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
+
+use crate::domain::n003_snapshot_file::model::SnapshotFile;
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n006_file_content::repository as content_repo;
+use crate::domain::n015_public_item::model::PublicItem;
+use crate::domain::n015_public_item::repository as item_repo;
+use crate::domain::n016_module_doc::model::ModuleDoc;
+use crate::domain::n016_module_doc::repository as module_doc_repo;
+use crate::shared::rust_api::{has_module_doc, module_path, public_items};
+
+/// A crate (package) of a snapshot
+#[derive(Debug, Clone)]
+pub struct SnapshotCrate {
+    /// Directory of its Cargo.toml, "" for the project root
+    pub dir: String,
+    /// `[package] name`
+    pub name: String,
+    /// README file in the crate directory (or the one `package.readme` points to) is present
+    pub has_readme: bool,
+}
+
+/// `[package]` table of a Cargo.toml; workspace-only manifests have none
+fn package_table(manifest: &str) -> Option<toml::Table> {
+    let table: toml::Table = manifest.parse().ok()?;
+    table.get("package")?.as_table().cloned()
+}
+
+fn crates_of(
+    conn: &Connection,
+    files: &[SnapshotFile],
+    text_ids: &HashSet<i64>,
+) -> Vec<SnapshotCrate> {
+    let paths: HashSet<String> = files.iter().map(|f| f.path.replace('\\', "/")).collect();
+    let mut crates = Vec::new();
+    for file in files
+        .iter()
+        .filter(|f| f.name == "Cargo.toml" && text_ids.contains(&f.id))
+    {
+        let Some(manifest) = content_repo::get_content(conn, file.id).ok().flatten() else {
+            continue;
+        };
+        let Some(package) = package_table(&manifest) else {
+            continue;
+        };
+        let Some(name) = package.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+
+        let path = file.path.replace('\\', "/");
+        let dir = path
+            .strip_suffix("Cargo.toml")
+            .unwrap_or("")
+            .trim_end_matches('/')
+            .to_string();
+        let in_dir = |name: &str| {
+            if dir.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}/{}", dir, name)
+            }
+        };
+        let has_readme = match package.get("readme") {
+            Some(toml::Value::Boolean(false)) => false,
+            Some(toml::Value::String(readme)) => {
+                paths.contains(&in_dir(readme.trim_start_matches("./")))
+            }
+            _ => files.iter().any(|f| {
+                !f.is_directory
+                    && f.name.to_lowercase().starts_with("readme")
+                    && f.path.replace('\\', "/") == in_dir(&f.name)
+            }),
+        };
+        crates.push(SnapshotCrate {
+            dir,
+            name: name.to_string(),
+            has_readme,
+        });
+    }
+    crates.sort_by(|a, b| a.name.cmp(&b.name));
+    crates
+}
+
+/// Crates of a snapshot, found by the `[package]` tables of its Cargo.toml files
+pub fn snapshot_crates(conn: &Connection, snapshot_id: i64) -> Result<Vec<SnapshotCrate>, String> {
+    let text_ids: HashSet<i64> = content_repo::list_file_ids(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load contents: {}", e))?
+        .into_iter()
+        .collect();
+    let files = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?;
+    Ok(crates_of(conn, &files, &text_ids))
+}
+
+/// Store the public items and module doc headers of every crate's `src/` tree
+/// (binaries under `src/bin/` excluded).
+/// Expects the snapshot's previous items and module docs to be deleted already.
+/// Returns number of stored items.
+pub fn collect_api_items(conn: &Connection, snapshot_id: i64) -> Result<i64, String> {
+    let text_ids: HashSet<i64> = content_repo::list_file_ids(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load contents: {}", e))?
+        .into_iter()
+        .collect();
+    let files = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?;
+
+    // crate directory ("" for the project root) -> crate name
+    let crates: HashMap<String, String> = crates_of(conn, &files, &text_ids)
+        .into_iter()
+        .map(|c| (c.dir, c.name))
+        .collect();
+
+    let mut count = 0;
+    for file in files
+        .iter()
+        .filter(|f| f.file_extension.as_deref() == Some("rs") && text_ids.contains(&f.id))
+    {
+        let path = file.path.replace('\\', "/");
+
+        // Nearest enclosing crate directory
+        let mut dir = path.as_str();
+        let crate_entry = loop {
+            dir = match dir.rfind('/') {
+                Some(pos) => &dir[..pos],
+                None if !dir.is_empty() => "",
+                None => break None,
+            };
+            if let Some(name) = crates.get(dir) {
+                break Some((dir, name));
+            }
+            if dir.is_empty() {
+                break None;
+            }
+        };
+        let Some((crate_dir, crate_name)) = crate_entry else {
+            continue;
+        };
+
+        let in_crate = if crate_dir.is_empty() {
+            path.as_str()
+        } else {
+            &path[crate_dir.len() + 1..]
+        };
+        let Some(in_src) = in_crate.strip_prefix("src/") else {
+            continue;
+        };
+        if in_src.starts_with("bin/") {
+            continue;
+        }
+
+        let Some(text) = content_repo::get_content(conn, file.id).ok().flatten() else {
+            continue;
+        };
+        let module = module_path(in_src);
+        let module_doc = ModuleDoc::new(
+            file.id,
+            snapshot_id,
+            crate_name.clone(),
+            module.clone(),
+            has_module_doc(&text),
+        );
+        module_doc_repo::create(conn, &module_doc)
+            .map_err(|e| format!("Failed to store module doc: {}", e))?;
+
+        for item in public_items(&text) {
+            let full_path = if module.is_empty() {
+                item.path
+            } else {
+                format!("{}::{}", module, item.path)
+            };
+            let record = PublicItem::new(
+                0,
+                snapshot_id,
+                file.id,
+                crate_name.clone(),
+                item.kind.to_string(),
+                full_path,
+                item.signature,
+                item.line as i64,
+                item.documented,
+            );
+            item_repo::create(conn, &record)
+                .map_err(|e| format!("Failed to store public item: {}", e))?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
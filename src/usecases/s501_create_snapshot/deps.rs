@@ -0,0 +1,157 @@
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+use crate::domain::n006_file_content::repository as content_repo;
+use crate::domain::n016_module_doc::repository as module_doc_repo;
+use crate::domain::n018_module_dependency::model::ModuleDependency;
+use crate::domain::n018_module_dependency::repository as dependency_repo;
+use crate::shared::rust_imports::use_items;
+
+/// Module files of a snapshot: (crate ident, module path) -> file id
+type ModuleIndex = HashMap<(String, String), i64>;
+
+/// Crate name as written in paths (`my-crate` -> `my_crate`)
+fn crate_ident(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+fn join(module: &[&str], name: &str) -> String {
+    module
+        .iter()
+        .copied()
+        .chain([name])
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// File of the module a `use` path points into: the longest module prefix of the path
+/// that exists in the target crate, the crate root when none does.
+/// `crate::`, `self::`, `super::` and child module names resolve against the importing
+/// module; a leading workspace crate name against that crate. Paths into other crates give None.
+fn resolve(
+    path: &[String],
+    own_crate: &str,
+    own_module: &str,
+    modules: &ModuleIndex,
+) -> Option<i64> {
+    let mut segments: Vec<&str> = own_module.split("::").filter(|s| !s.is_empty()).collect();
+    let mut rest = path.iter().map(String::as_str).peekable();
+    let target_crate = match rest.peek().copied()? {
+        "crate" => {
+            rest.next();
+            segments.clear();
+            own_crate.to_string()
+        }
+        "self" | "super" => {
+            while let Some(&s) = rest.peek() {
+                match s {
+                    "self" => {}
+                    "super" => {
+                        segments.pop()?;
+                    }
+                    _ => break,
+                }
+                rest.next();
+            }
+            own_crate.to_string()
+        }
+        // Uniform paths: a child module of the importing one
+        first if modules.contains_key(&(own_crate.to_string(), join(&segments, first))) => {
+            own_crate.to_string()
+        }
+        first if modules.contains_key(&(first.to_string(), String::new())) => {
+            rest.next();
+            segments.clear();
+            first.to_string()
+        }
+        _ => return None,
+    };
+    segments.extend(rest);
+
+    (0..=segments.len())
+        .rev()
+        .find_map(|n| modules.get(&(target_crate.clone(), segments[..n].join("::"))))
+        .copied()
+}
+
+/// Store the `use` declarations between module files of the snapshot's crates.
+/// Relies on the module docs stored by `collect_api_items`; imports of the module
+/// itself and of external crates are dropped.
+/// Expects the snapshot's previous dependencies to be deleted already.
+/// Returns number of stored dependencies.
+pub fn collect_dependencies(conn: &Connection, snapshot_id: i64) -> Result<i64, String> {
+    let modules = module_doc_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load modules: {}", e))?;
+
+    let mut index = ModuleIndex::new();
+    for m in &modules {
+        index
+            .entry((crate_ident(&m.crate_name), m.module_path.clone()))
+            .or_insert(m.file_id);
+    }
+
+    let mut count = 0;
+    for module in &modules {
+        let Some(text) = content_repo::get_content(conn, module.file_id)
+            .ok()
+            .flatten()
+        else {
+            continue;
+        };
+        let own_crate = crate_ident(&module.crate_name);
+        for item in use_items(&text) {
+            let Some(target) = resolve(&item.path, &own_crate, &module.module_path, &index) else {
+                continue;
+            };
+            if target == module.file_id {
+                continue;
+            }
+            let dependency = ModuleDependency::new(
+                0,
+                snapshot_id,
+                module.file_id,
+                target,
+                item.line as i64,
+                item.path.join("::"),
+                item.in_test,
+            );
+            dependency_repo::create(conn, &dependency)
+                .map_err(|e| format!("Failed to store dependency: {}", e))?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_paths_to_module_files() {
+        let mut index = ModuleIndex::new();
+        for (id, krate, module) in [
+            (1, "app", ""),
+            (2, "app", "domain"),
+            (3, "app", "domain::a"),
+            (4, "app", "shared"),
+            (5, "core_lib", ""),
+        ] {
+            index.insert((krate.to_string(), module.to_string()), id);
+        }
+        let path = |p: &str| p.split("::").map(String::from).collect::<Vec<_>>();
+        let from_a = |p: &str| resolve(&path(p), "app", "domain::a", &index);
+
+        assert_eq!(from_a("crate::shared::text::Thing"), Some(4));
+        assert_eq!(from_a("crate::domain::a::model"), Some(3));
+        assert_eq!(from_a("super::super::shared"), Some(4));
+        assert_eq!(from_a("super::Other"), Some(2));
+        assert_eq!(from_a("self::inner"), Some(3));
+        assert_eq!(from_a("crate::Config"), Some(1));
+        assert_eq!(resolve(&path("domain::a::f"), "app", "", &index), Some(3));
+        assert_eq!(from_a("core_lib::Value"), Some(5));
+        assert_eq!(from_a("std::collections::HashMap"), None);
+        assert_eq!(resolve(&path("super::x"), "app", "", &index), None);
+    }
+}
@@ -0,0 +1,139 @@
+use chrono::{TimeZone, Utc};
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n006_file_content::repository as content_repo;
+use crate::domain::n011_debt_marker::model::DebtMarker;
+use crate::domain::n011_debt_marker::repository as marker_repo;
+use crate::shared::git;
+
+/// Longest stored marker text, in characters
+const MAX_MARKER_TEXT: usize = 300;
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// First marker kind on a line: an uppercase word (`TODO`, ...) or a macro call (`todo!`, ...)
+fn marker_kind(line: &str) -> Option<&'static str> {
+    const WORDS: [&str; 4] = ["TODO", "FIXME", "HACK", "XXX"];
+    const MACROS: [&str; 2] = ["todo!", "unimplemented!"];
+
+    let mut best: Option<(usize, &'static str)> = None;
+    for kind in WORDS.iter().chain(MACROS.iter()) {
+        let is_macro = kind.ends_with('!');
+        for (pos, _) in line.match_indices(kind) {
+            let before_ok = !line[..pos].chars().next_back().is_some_and(is_word_char);
+            let after_ok = is_macro
+                || !line[pos + kind.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(is_word_char);
+            if before_ok && after_ok {
+                if best.is_none_or(|(p, _)| p > pos) {
+                    best = Some((pos, kind));
+                }
+                break;
+            }
+        }
+    }
+    best.map(|(_, kind)| kind)
+}
+
+/// (1-based line, kind, trimmed text) of every marker in a text
+pub fn find_markers(text: &str) -> Vec<(usize, &'static str, String)> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let kind = marker_kind(line)?;
+            let text: String = line.trim().chars().take(MAX_MARKER_TEXT).collect();
+            Some((idx + 1, kind, text))
+        })
+        .collect()
+}
+
+/// Store debt markers of every text file of the snapshot, with git authorship when available.
+/// Expects the snapshot's previous markers to be deleted already.
+/// Returns number of stored markers.
+pub fn collect_markers(
+    conn: &Connection,
+    snapshot_id: i64,
+    root_path: &Path,
+) -> Result<i64, String> {
+    let text_ids: HashSet<i64> = content_repo::list_file_ids(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load contents: {}", e))?
+        .into_iter()
+        .collect();
+    let files = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?;
+    let is_git = git::is_repository(root_path);
+
+    let mut count = 0;
+    for file in files.iter().filter(|f| text_ids.contains(&f.id)) {
+        let Some(text) = content_repo::get_content(conn, file.id).ok().flatten() else {
+            continue;
+        };
+        let found = find_markers(&text);
+        if found.is_empty() {
+            continue;
+        }
+
+        // Untracked files fail to blame; their markers are stored without author
+        let blame = if is_git {
+            let lines: Vec<usize> = found.iter().map(|(line, _, _)| *line).collect();
+            git::blame_lines(root_path, &file.path, &lines).unwrap_or_default()
+        } else {
+            Default::default()
+        };
+
+        for (line, kind, text) in found {
+            let (author, authored_at) = match blame.get(&line) {
+                Some((name, time)) => (
+                    Some(name.clone()),
+                    Utc.timestamp_opt(*time, 0).single().map(|t| t.to_rfc3339()),
+                ),
+                None => (None, None),
+            };
+            let marker = DebtMarker::new(
+                0,
+                snapshot_id,
+                file.id,
+                line as i64,
+                kind.to_string(),
+                text,
+                author,
+                authored_at,
+            );
+            marker_repo::create(conn, &marker)
+                .map_err(|e| format!("Failed to store marker: {}", e))?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_words_and_macros() {
+        let text = "// TODO: split\nlet todos = 1; // TODOS are not markers\nfn f() { todo!() }\n/* FIXME later; XXX */\nunimplemented!(\"x\")\nmy_todo!()";
+        let kinds: Vec<(usize, &str)> = find_markers(text)
+            .into_iter()
+            .map(|(l, k, _)| (l, k))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (1, "TODO"),
+                (3, "todo!"),
+                (4, "FIXME"),
+                (5, "unimplemented!")
+            ]
+        );
+    }
+}
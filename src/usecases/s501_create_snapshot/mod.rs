@@ -1,14 +1,19 @@
+mod aggregates;
+pub mod api;
+mod deps;
 mod gitignore;
+mod markers;
+mod packages;
 mod scanner;
 
 use eframe::egui;
 use rusqlite::Connection;
 use std::path::PathBuf;
 
-use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
-use crate::domain::n002_snapshot::repository as snapshot_repo;
 use crate::domain::n001_project::repository as project_repo;
-use scanner::{scan_directory, ScanProgress};
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+pub use scanner::{scan_directory, ScanProgress, ScanResult};
 
 #[derive(Default)]
 pub struct ScanSnapshotState {
@@ -27,27 +32,32 @@ impl ScanSnapshotState {
     pub fn open_picker(&mut self) {
         self.snapshot_picker.open();
     }
-}
 
-pub fn ui_scan_snapshot(ui: &mut egui::Ui, conn: &Connection, state: &mut ScanSnapshotState) {
-    ui.heading("Scan Snapshot");
-    ui.add_space(12.0);
-    
-    // Snapshot picker
-    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
-        state.selected_snapshot_id = Some(snapshot_id);
-        
+    /// Select `snapshot_id` for scanning, with its project's root path
+    pub fn select(&mut self, conn: &Connection, snapshot_id: i64) {
+        self.selected_snapshot_id = Some(snapshot_id);
+
         // Load snapshot details
         if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
-            state.selected_snapshot_name = snapshot.name.clone();
-            
+            self.selected_snapshot_name = snapshot.name.clone();
+
             // Load project to get root path
             if let Ok(project) = project_repo::get_by_id(conn, snapshot.project_id) {
-                state.project_root_path = project.root_path;
+                self.project_root_path = project.root_path;
             }
         }
     }
-    
+}
+
+pub fn ui_scan_snapshot(ui: &mut egui::Ui, conn: &Connection, state: &mut ScanSnapshotState) {
+    ui.heading("Scan Snapshot");
+    ui.add_space(12.0);
+
+    // Snapshot picker
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.select(conn, snapshot_id);
+    }
+
     ui.horizontal(|ui| {
         ui.label("Selected Snapshot:");
         if state.selected_snapshot_id.is_some() {
@@ -55,26 +65,29 @@ pub fn ui_scan_snapshot(ui: &mut egui::Ui, conn: &Connection, state: &mut ScanSn
         } else {
             ui.label(egui::RichText::new("None").weak());
         }
-        
+
         if ui.button("Select...").clicked() {
             state.open_picker();
         }
     });
-    
+
     if !state.project_root_path.is_empty() {
         ui.label(format!("Project Path: {}", state.project_root_path));
     }
-    
+
     ui.add_space(12.0);
-    
+
     // Scan button
     let can_scan = state.selected_snapshot_id.is_some() && !state.is_scanning;
-    if ui.add_enabled(can_scan, egui::Button::new("Scan")).clicked() {
+    if ui
+        .add_enabled(can_scan, egui::Button::new("Scan"))
+        .clicked()
+    {
         start_scan(conn, state);
     }
-    
+
     ui.add_space(12.0);
-    
+
     // Progress display
     if state.is_scanning || state.progress_files > 0 || state.progress_dirs > 0 {
         ui.group(|ui| {
@@ -84,16 +97,16 @@ pub fn ui_scan_snapshot(ui: &mut egui::Ui, conn: &Connection, state: &mut ScanSn
             if !state.progress_current.is_empty() {
                 ui.label(format!("Current: {}", state.progress_current));
             }
-            
+
             if state.is_scanning {
                 ui.add_space(6.0);
                 ui.spinner();
             }
         });
     }
-    
+
     ui.add_space(12.0);
-    
+
     // Status messages
     if !state.status.is_empty() {
         let color = if state.status.starts_with("Error") || state.status.starts_with("Failed") {
@@ -103,7 +116,7 @@ pub fn ui_scan_snapshot(ui: &mut egui::Ui, conn: &Connection, state: &mut ScanSn
         } else {
             egui::Color32::LIGHT_BLUE
         };
-        
+
         ui.colored_label(color, &state.status);
     }
 }
@@ -115,33 +128,23 @@ fn start_scan(conn: &Connection, state: &mut ScanSnapshotState) {
         state.progress_dirs = 0;
         state.progress_current = String::new();
         state.status = "Starting scan...".to_string();
-        
+
         let root_path = PathBuf::from(&state.project_root_path);
-        
+
         // Perform scan with progress callback
-        let result = scan_directory(
-            conn,
-            snapshot_id,
-            &root_path,
-            |progress: ScanProgress| {
-                // Note: In a real async implementation, this would update the UI
-                // For now, we'll just collect the final state
-                state.progress_files = progress.files_scanned;
-                state.progress_dirs = progress.dirs_scanned;
-                state.progress_current = progress.current_path;
-            },
-        );
-        
+        let result = scan_directory(conn, snapshot_id, &root_path, |progress: ScanProgress| {
+            // Note: In a real async implementation, this would update the UI
+            // For now, we'll just collect the final state
+            state.progress_files = progress.files_scanned;
+            state.progress_dirs = progress.dirs_scanned;
+            state.progress_current = progress.current_path;
+        });
+
         state.is_scanning = false;
-        
+
         match result {
             Ok(scan_result) => {
-                state.status = format!(
-                    "Completed! Files: {}, Dirs: {}, Total Size: {} bytes",
-                    scan_result.files_count,
-                    scan_result.dirs_count,
-                    scan_result.total_size
-                );
+                state.status = format!("Completed! {}", scan_result.describe());
             }
             Err(e) => {
                 state.status = format!("Error: {}", e);
@@ -149,4 +152,3 @@ fn start_scan(conn: &Connection, state: &mut ScanSnapshotState) {
         }
     }
 }
-
@@ -0,0 +1,150 @@
+use rusqlite::Connection;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::domain::n005_snapshot_package::repository as package_repo;
+
+/// Package entry read from `Cargo.lock`
+struct LockPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+    checksum: Option<String>,
+}
+
+/// Store packages from `Cargo.lock` (if present) for the snapshot.
+/// Returns number of stored packages.
+pub fn collect_lockfile_packages(
+    conn: &Connection,
+    snapshot_id: i64,
+    root_path: &Path,
+) -> Result<i64, String> {
+    package_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing packages: {}", e))?;
+
+    let lock_path = root_path.join("Cargo.lock");
+    if !lock_path.exists() {
+        return Ok(0);
+    }
+
+    let packages = parse_lockfile(&lock_path)?;
+    let registry_dirs = registry_src_dirs();
+
+    let mut count = 0;
+    for pkg in packages {
+        let license = resolve_license(root_path, &registry_dirs, &pkg.name, &pkg.version);
+        package_repo::create(
+            conn,
+            snapshot_id,
+            &pkg.name,
+            &pkg.version,
+            pkg.source.as_deref(),
+            pkg.checksum.as_deref(),
+            license.as_deref(),
+        )
+        .map_err(|e| format!("Failed to insert package record: {}", e))?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+fn parse_lockfile(lock_path: &Path) -> Result<Vec<LockPackage>, String> {
+    let content =
+        fs::read_to_string(lock_path).map_err(|e| format!("Failed to read Cargo.lock: {}", e))?;
+    let table: toml::Table = content
+        .parse()
+        .map_err(|e| format!("Failed to parse Cargo.lock: {}", e))?;
+
+    let mut out = Vec::new();
+    if let Some(packages) = table.get("package").and_then(|v| v.as_array()) {
+        for pkg in packages {
+            let Some(pkg) = pkg.as_table() else { continue };
+            let name = pkg.get("name").and_then(|v| v.as_str());
+            let version = pkg.get("version").and_then(|v| v.as_str());
+            if let (Some(name), Some(version)) = (name, version) {
+                out.push(LockPackage {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    source: pkg
+                        .get("source")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string()),
+                    checksum: pkg
+                        .get("checksum")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string()),
+                });
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Unpacked registry sources: `$CARGO_HOME/registry/src/<index>/`
+fn registry_src_dirs() -> Vec<PathBuf> {
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .or_else(|| std::env::var_os("USERPROFILE"))
+                .map(|home| PathBuf::from(home).join(".cargo"))
+        });
+
+    let Some(cargo_home) = cargo_home else {
+        return Vec::new();
+    };
+
+    match fs::read_dir(cargo_home.join("registry").join("src")) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Look up the license of a package in `vendor/` first, then in the registry cache
+fn resolve_license(
+    root_path: &Path,
+    registry_dirs: &[PathBuf],
+    name: &str,
+    version: &str,
+) -> Option<String> {
+    let versioned = format!("{}-{}", name, version);
+    let mut candidates = vec![
+        root_path.join("vendor").join(&versioned).join("Cargo.toml"),
+        root_path.join("vendor").join(name).join("Cargo.toml"),
+    ];
+    for dir in registry_dirs {
+        candidates.push(dir.join(&versioned).join("Cargo.toml"));
+    }
+
+    candidates
+        .iter()
+        .filter(|p| p.exists())
+        .find_map(|p| read_manifest_license(p, version))
+}
+
+fn read_manifest_license(manifest_path: &Path, version: &str) -> Option<String> {
+    let content = fs::read_to_string(manifest_path).ok()?;
+    let table: toml::Table = content.parse().ok()?;
+    let package = table.get("package")?.as_table()?;
+
+    // Unversioned vendor directories may hold another version of the crate
+    if let Some(v) = package.get("version").and_then(|v| v.as_str()) {
+        if v != version {
+            return None;
+        }
+    }
+
+    if let Some(license) = package.get("license").and_then(|v| v.as_str()) {
+        return Some(license.to_string());
+    }
+    package
+        .get("license-file")
+        .and_then(|v| v.as_str())
+        .map(|f| format!("file:{}", f))
+}
@@ -1,10 +1,40 @@
 use rusqlite::Connection;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
+use super::aggregates::build_aggregates;
+use super::api::collect_api_items;
+use super::deps::collect_dependencies;
 use super::gitignore::GitignoreParser;
+use super::markers::collect_markers;
+use super::packages::collect_lockfile_packages;
 use crate::domain::n002_snapshot::repository as snapshot_repo;
 use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n006_file_content::repository as content_repo;
+use crate::domain::n007_file_churn::repository as churn_repo;
+use crate::domain::n008_file_ownership::repository as ownership_repo;
+use crate::domain::n010_function_metric::model::FunctionMetric;
+use crate::domain::n010_function_metric::repository as function_repo;
+use crate::domain::n011_debt_marker::repository as marker_repo;
+use crate::domain::n012_safety_site::repository as safety_repo;
+use crate::domain::n013_file_tests::model::FileTests;
+use crate::domain::n013_file_tests::repository as tests_repo;
+use crate::domain::n014_file_coverage::repository as coverage_repo;
+use crate::domain::n015_public_item::repository as public_item_repo;
+use crate::domain::n016_module_doc::repository as module_doc_repo;
+use crate::domain::n017_file_language::model::FileLanguage;
+use crate::domain::n017_file_language::repository as language_repo;
+use crate::domain::n018_module_dependency::repository as dependency_repo;
+use crate::domain::n020_gate_result::model::GateResult;
+use crate::shared::complexity::analyze_rust;
+use crate::shared::git;
+use crate::shared::language::{count_loc, detect_language};
+use crate::shared::rust_syntax::{tokenize, TokenKind};
+use crate::shared::safety::find_sites;
+use crate::shared::test_stats::{analyze_tests, is_test_path};
+use crate::shared::text::read_text_file;
+use crate::usecases::s519_quality_gates::{gates_summary, record_gates};
 
 /// Extract file extension from filename
 fn extract_file_extension(name: &str) -> Option<String> {
@@ -115,6 +145,28 @@ pub struct ScanResult {
     pub files_count: i64,
     pub dirs_count: i64,
     pub total_size: i64,
+    pub packages_count: i64,
+    pub markers_count: i64,
+    /// Outcome of every rule of the project's quality gate
+    pub gates: Vec<GateResult>,
+}
+
+impl ScanResult {
+    /// `Files: .., Dirs: .., ...` followed by the quality gate summary, if any
+    pub fn describe(&self) -> String {
+        let mut text = format!(
+            "Files: {}, Dirs: {}, Total Size: {} bytes, Packages: {}, Markers: {}",
+            self.files_count,
+            self.dirs_count,
+            self.total_size,
+            self.packages_count,
+            self.markers_count
+        );
+        if let Some(gates) = gates_summary(&self.gates) {
+            text.push_str(&format!(". {}", gates));
+        }
+        text
+    }
 }
 
 pub fn scan_directory<F>(
@@ -135,7 +187,36 @@ where
     // Parse .gitignore
     let gitignore = GitignoreParser::from_file(&gitignore_path)?;
 
-    // Delete existing file records for this snapshot
+    // Single transaction: a failed scan leaves the previous data intact
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    // Delete existing file records (and metrics keyed by them) for this snapshot
+    churn_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing churn: {}", e))?;
+    safety_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing safety sites: {}", e))?;
+    tests_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing test stats: {}", e))?;
+    coverage_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing coverage: {}", e))?;
+    dependency_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing dependencies: {}", e))?;
+    public_item_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing public items: {}", e))?;
+    module_doc_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing module docs: {}", e))?;
+    language_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing languages: {}", e))?;
+    marker_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing markers: {}", e))?;
+    function_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing function metrics: {}", e))?;
+    ownership_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing ownership: {}", e))?;
+    content_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing contents: {}", e))?;
     file_repo::delete_by_snapshot(conn, snapshot_id)
         .map_err(|e| format!("Failed to delete existing records: {}", e))?;
 
@@ -164,23 +245,170 @@ where
     let total_size = file_repo::sum_file_sizes(conn, snapshot_id)
         .map_err(|e| format!("Failed to sum file sizes: {}", e))?;
 
+    // Store Cargo.lock packages for dependency audit
+    let packages_count = collect_lockfile_packages(conn, snapshot_id, root_path)?;
+
+    // TODO/FIXME/... markers with git authorship
+    let markers_count = collect_markers(conn, snapshot_id, root_path)?;
+
+    // Public API surface and module docs of every crate
+    collect_api_items(conn, snapshot_id)?;
+
+    // `use` dependencies between those modules
+    collect_dependencies(conn, snapshot_id)?;
+
+    // One n004 aggregate per domain module / use case
+    build_aggregates(conn, snapshot_id)?;
+
+    // LOC of files in a recognized language
+    let loc_count: i64 = language_repo::breakdown_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to sum LOC: {}", e))?
+        .iter()
+        .map(|l| l.loc)
+        .sum();
+
     // Update snapshot aggregate with new counts
     if let Ok(mut snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
         snapshot.files_count = files_count;
         snapshot.dirs_count = dirs_count;
         snapshot.files_size_bytes = total_size;
-        // Keep loc_count unchanged for now
+        snapshot.loc_count = loc_count;
 
         let _ = snapshot_repo::update(conn, &snapshot);
     }
 
+    // Remember the commit the working tree was at, for blob lookups later
+    if let Some(revision) = git::head_revision(root_path) {
+        snapshot_repo::set_git_revision(conn, snapshot_id, Some(&revision))
+            .map_err(|e| format!("Failed to store git revision: {}", e))?;
+    }
+
+    // Quality gate of the project, against the stored data
+    let gates = record_gates(conn, snapshot_id)?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit scan: {}", e))?;
+
     Ok(ScanResult {
         files_count,
         dirs_count,
         total_size,
+        packages_count,
+        markers_count,
+        gates,
     })
 }
 
+/// Complexity of every function of a Rust file
+fn store_function_metrics(
+    conn: &Connection,
+    snapshot_id: i64,
+    file_id: i64,
+    text: &str,
+) -> Result<(), String> {
+    for m in analyze_rust(text) {
+        let metric = FunctionMetric::new(
+            0,
+            snapshot_id,
+            file_id,
+            m.name,
+            m.line as i64,
+            m.length as i64,
+            m.params as i64,
+            m.cyclomatic as i64,
+            m.cognitive as i64,
+            m.nesting as i64,
+        );
+        function_repo::create(conn, &metric)
+            .map_err(|e| format!("Failed to store function metrics: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Unsafe code and panic sites of a Rust file
+fn store_safety_sites(
+    conn: &Connection,
+    snapshot_id: i64,
+    file_id: i64,
+    text: &str,
+) -> Result<(), String> {
+    for site in find_sites(text) {
+        safety_repo::create(
+            conn,
+            snapshot_id,
+            file_id,
+            site.kind.as_str(),
+            site.line as i64,
+            site.function.as_deref(),
+        )
+        .map_err(|e| format!("Failed to store safety site: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Language (by name, extension or shebang) and LOC of a file; unknown languages are not stored
+fn store_language(
+    conn: &Connection,
+    snapshot_id: i64,
+    file_id: i64,
+    name: &str,
+    text: Option<&str>,
+) -> Result<(), String> {
+    let first_line = text.and_then(|t| t.lines().next());
+    let Some(language) = detect_language(name, first_line) else {
+        return Ok(());
+    };
+    let loc = text.map(count_loc).unwrap_or(0);
+    language_repo::create(
+        conn,
+        &FileLanguage::new(file_id, snapshot_id, language.to_string(), loc),
+    )
+    .map_err(|e| format!("Failed to store file language: {}", e))
+}
+
+/// Test functions and test/production LOC of a Rust file
+fn store_test_stats(
+    conn: &Connection,
+    snapshot_id: i64,
+    file_id: i64,
+    rel_path: &str,
+    artifact_id: Option<&str>,
+    text: &str,
+) -> Result<(), String> {
+    let in_test_dir = is_test_path(rel_path);
+    let stats = analyze_tests(text, in_test_dir);
+    let covers = match artifact_id {
+        Some(id) => Some(id.to_string()),
+        None if in_test_dir => mentioned_artifact(text),
+        None => None,
+    };
+    let tests = FileTests::new(
+        file_id,
+        snapshot_id,
+        stats.test_fns as i64,
+        stats.test_loc as i64,
+        stats.code_loc as i64,
+        covers,
+    );
+    tests_repo::create(conn, &tests).map_err(|e| format!("Failed to store test stats: {}", e))
+}
+
+/// Artifact id most often named in an integration test (`use app::usecases::s501_create_snapshot::...`)
+fn mentioned_artifact(text: &str) -> Option<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for token in tokenize(text).iter().filter(|t| t.kind == TokenKind::Ident) {
+        if let (Some(id), Some(name)) = extract_artifact_id_name(&token.text) {
+            if !name.is_empty() {
+                *counts.entry(id).or_default() += 1;
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(id, _)| id)
+}
+
 fn scan_recursive<F>(
     conn: &Connection,
     snapshot_id: i64,
@@ -266,6 +494,28 @@ where
         )
         .map_err(|e| format!("Failed to insert file record: {}", e))?;
 
+        // Store text content for full-text search
+        if !is_directory {
+            let text = read_text_file(&path, size_bytes);
+            if let Some(text) = &text {
+                content_repo::create(conn, snapshot_id, file_id, &rel_path, text)
+                    .map_err(|e| format!("Failed to store file content: {}", e))?;
+                if file_extension.as_deref() == Some("rs") {
+                    store_function_metrics(conn, snapshot_id, file_id, text)?;
+                    store_safety_sites(conn, snapshot_id, file_id, text)?;
+                    store_test_stats(
+                        conn,
+                        snapshot_id,
+                        file_id,
+                        &rel_path,
+                        artifact_id.as_deref(),
+                        text,
+                    )?;
+                }
+            }
+            store_language(conn, snapshot_id, file_id, &name, text.as_deref())?;
+        }
+
         // Update progress
         if is_directory {
             progress.dirs_scanned += 1;
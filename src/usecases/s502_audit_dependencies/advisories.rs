@@ -0,0 +1,187 @@
+use semver::{Version, VersionReq};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Single advisory from a RustSec-formatted database
+#[derive(Debug, Clone)]
+pub struct Advisory {
+    pub id: String,
+    pub package: String,
+    pub title: String,
+    pub url: Option<String>,
+    /// Informational kind (`unmaintained`, `unsound`, `notice`); None for vulnerabilities
+    pub informational: Option<String>,
+    pub withdrawn: bool,
+    pub patched: Vec<VersionReq>,
+    pub unaffected: Vec<VersionReq>,
+}
+
+impl Advisory {
+    /// True if the given package version is covered by this advisory
+    pub fn affects(&self, version: &str) -> bool {
+        if self.withdrawn {
+            return false;
+        }
+        let Ok(version) = Version::parse(version) else {
+            return false;
+        };
+        let patched = self.patched.iter().any(|r| r.matches(&version));
+        let unaffected = self.unaffected.iter().any(|r| r.matches(&version));
+        !patched && !unaffected
+    }
+
+    /// Patched version ranges for display
+    pub fn patched_display(&self) -> String {
+        if self.patched.is_empty() {
+            "no patched versions".to_string()
+        } else {
+            self.patched
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>()
+                .join(" | ")
+        }
+    }
+}
+
+/// Advisories indexed by package name
+#[derive(Default)]
+pub struct AdvisoryDb {
+    by_package: HashMap<String, Vec<Advisory>>,
+    pub count: usize,
+    /// Advisory files that could not be parsed, with the reason
+    pub skipped: Vec<String>,
+}
+
+impl AdvisoryDb {
+    /// Load every advisory under `<db>/crates/<package>/` (`.md` and `.toml` files)
+    pub fn load(db_path: &Path) -> Result<Self, String> {
+        let crates_dir = db_path.join("crates");
+        if !crates_dir.is_dir() {
+            return Err(format!(
+                "Advisory database not found: {:?} has no 'crates' directory",
+                db_path
+            ));
+        }
+
+        let mut db = AdvisoryDb::default();
+        let package_dirs = fs::read_dir(&crates_dir)
+            .map_err(|e| format!("Failed to read {:?}: {}", crates_dir, e))?;
+
+        for package_dir in package_dirs.filter_map(|e| e.ok()) {
+            let path = package_dir.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let Ok(files) = fs::read_dir(&path) else {
+                continue;
+            };
+            for file in files.filter_map(|e| e.ok()) {
+                let file_path = file.path();
+                let ext = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
+                if ext != "md" && ext != "toml" {
+                    continue;
+                }
+                let Ok(content) = fs::read_to_string(&file_path) else {
+                    continue;
+                };
+                let parsed = if ext == "md" {
+                    parse_markdown_advisory(&content)
+                } else {
+                    parse_toml_advisory(&content, None)
+                };
+                match parsed {
+                    Ok(advisory) => {
+                        db.count += 1;
+                        db.by_package
+                            .entry(advisory.package.clone())
+                            .or_default()
+                            .push(advisory);
+                    }
+                    Err(e) => db
+                        .skipped
+                        .push(format!("Skipped advisory {:?}: {}", file_path, e)),
+                }
+            }
+        }
+
+        Ok(db)
+    }
+
+    /// Advisories that affect the given package version
+    pub fn matching(&self, package: &str, version: &str) -> Vec<&Advisory> {
+        self.by_package
+            .get(package)
+            .map(|list| list.iter().filter(|a| a.affects(version)).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Current format: TOML front matter in a ```toml fence, followed by `# Title`
+fn parse_markdown_advisory(content: &str) -> Result<Advisory, String> {
+    let start = content
+        .find("```toml")
+        .ok_or_else(|| "missing ```toml front matter".to_string())?;
+    let body_start = start + "```toml".len();
+    let end = content[body_start..]
+        .find("```")
+        .map(|i| body_start + i)
+        .ok_or_else(|| "unterminated front matter".to_string())?;
+
+    let title = content[end + 3..]
+        .lines()
+        .map(|l| l.trim())
+        .find_map(|l| l.strip_prefix("# "))
+        .map(|t| t.trim().to_string());
+
+    parse_toml_advisory(&content[body_start..end], title)
+}
+
+/// Legacy format: the whole file is TOML with `[advisory]` and `[versions]` tables
+fn parse_toml_advisory(content: &str, title: Option<String>) -> Result<Advisory, String> {
+    let table: toml::Table = content
+        .parse()
+        .map_err(|e| format!("invalid TOML: {}", e))?;
+    let advisory = table
+        .get("advisory")
+        .and_then(|v| v.as_table())
+        .ok_or_else(|| "missing [advisory] table".to_string())?;
+
+    let get_str = |key: &str| {
+        advisory
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+
+    let id = get_str("id").ok_or_else(|| "missing advisory.id".to_string())?;
+    let package = get_str("package").ok_or_else(|| "missing advisory.package".to_string())?;
+
+    let versions = table.get("versions").and_then(|v| v.as_table());
+    let read_reqs = |key: &str| -> Vec<VersionReq> {
+        versions
+            .and_then(|v| v.get(key))
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str())
+                    .filter_map(|s| VersionReq::parse(s).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    Ok(Advisory {
+        title: title
+            .or_else(|| get_str("title"))
+            .unwrap_or_else(|| id.clone()),
+        id,
+        package,
+        url: get_str("url"),
+        informational: get_str("informational"),
+        withdrawn: advisory.contains_key("withdrawn"),
+        patched: read_reqs("patched"),
+        unaffected: read_reqs("unaffected"),
+    })
+}
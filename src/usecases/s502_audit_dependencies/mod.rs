@@ -0,0 +1,340 @@
+mod advisories;
+mod policy;
+
+use eframe::egui;
+use rusqlite::Connection;
+use std::path::Path;
+
+use crate::app_settings::AppSettings;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::domain::n005_snapshot_package::repository as package_repo;
+use advisories::AdvisoryDb;
+use policy::{advisory_denied, from_registry, license_allowed, license_file};
+
+/// Advisory that matched a package version
+#[derive(Debug, Clone)]
+pub struct AdvisoryHit {
+    pub id: String,
+    pub title: String,
+    pub url: Option<String>,
+    pub informational: Option<String>,
+    pub patched: String,
+    pub denied: bool,
+}
+
+/// Audit outcome for one lockfile package
+#[derive(Debug, Clone)]
+pub struct PackageAudit {
+    pub name: String,
+    pub version: String,
+    pub license: Option<String>,
+    /// None when no allowed-license policy is configured or the license is unknown
+    /// or only given as a license file
+    pub license_allowed: Option<bool>,
+    pub advisories: Vec<AdvisoryHit>,
+}
+
+impl PackageAudit {
+    pub fn is_vulnerable(&self) -> bool {
+        self.advisories.iter().any(|a| a.informational.is_none())
+    }
+
+    pub fn has_issues(&self) -> bool {
+        !self.advisories.is_empty() || self.license_allowed == Some(false)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditReport {
+    pub packages: Vec<PackageAudit>,
+    pub advisories_loaded: usize,
+    pub vulnerable_count: usize,
+    pub warnings: Vec<String>,
+    pub violations: Vec<String>,
+}
+
+impl AuditReport {
+    pub fn passed(&self) -> bool {
+        self.vulnerable_count == 0 && self.violations.is_empty()
+    }
+}
+
+/// Audit stored lockfile packages of a snapshot against the configured advisory DB and policy
+pub fn run_audit(
+    conn: &Connection,
+    snapshot_id: i64,
+    settings: &AppSettings,
+) -> Result<AuditReport, String> {
+    let packages = package_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load packages: {}", e))?;
+
+    let mut report = AuditReport::default();
+
+    let db = if settings.advisory_db_path.trim().is_empty() {
+        report.warnings.push(
+            "Advisory database path is not configured (Settings → Dependency Audit)".to_string(),
+        );
+        AdvisoryDb::default()
+    } else {
+        AdvisoryDb::load(Path::new(settings.advisory_db_path.trim()))?
+    };
+    report.advisories_loaded = db.count;
+    report.warnings.extend(db.skipped.iter().cloned());
+
+    if packages.is_empty() {
+        report.warnings.push(
+            "No lockfile packages stored for this snapshot (is there a Cargo.lock?)".to_string(),
+        );
+    }
+
+    let allowed = settings.allowed_licenses();
+    let denied = settings.denied_advisories();
+
+    for pkg in packages {
+        // Local and git packages are not the registry crate an advisory is about
+        let matching = if from_registry(pkg.source.as_deref()) {
+            db.matching(&pkg.name, &pkg.version)
+        } else {
+            Vec::new()
+        };
+        let advisories: Vec<AdvisoryHit> = matching
+            .into_iter()
+            .map(|a| AdvisoryHit {
+                id: a.id.clone(),
+                title: a.title.clone(),
+                url: a.url.clone(),
+                informational: a.informational.clone(),
+                patched: a.patched_display(),
+                denied: advisory_denied(&a.id, &denied),
+            })
+            .collect();
+
+        let license_ok = match (&pkg.license, allowed.is_empty()) {
+            (_, true) => None,
+            (Some(license), false) => match license_file(license) {
+                Some(file) => {
+                    report.warnings.push(format!(
+                        "License of {} {} is only given as a file ({}), needs review",
+                        pkg.name, pkg.version, file
+                    ));
+                    None
+                }
+                None => Some(license_allowed(license, &allowed)),
+            },
+            (None, false) => {
+                report.warnings.push(format!(
+                    "License of {} {} could not be determined",
+                    pkg.name, pkg.version
+                ));
+                None
+            }
+        };
+
+        if license_ok == Some(false) {
+            report.violations.push(format!(
+                "{} {}: license '{}' is not allowed",
+                pkg.name,
+                pkg.version,
+                pkg.license.as_deref().unwrap_or("")
+            ));
+        }
+        for hit in advisories.iter().filter(|a| a.denied) {
+            report.violations.push(format!(
+                "{} {}: denied advisory {} ({})",
+                pkg.name, pkg.version, hit.id, hit.title
+            ));
+        }
+
+        let audit = PackageAudit {
+            name: pkg.name,
+            version: pkg.version,
+            license: pkg.license,
+            license_allowed: license_ok,
+            advisories,
+        };
+        if audit.is_vulnerable() {
+            report.vulnerable_count += 1;
+        }
+        report.packages.push(audit);
+    }
+
+    Ok(report)
+}
+
+#[derive(Default)]
+pub struct AuditState {
+    pub snapshot_picker: SnapshotPickerState,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    pub only_issues: bool,
+    pub report: Option<AuditReport>,
+    pub status: String,
+}
+
+pub fn ui_audit(ui: &mut egui::Ui, conn: &Connection, state: &mut AuditState) {
+    ui.heading("Dependency Audit");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.snapshot_id = Some(snapshot_id);
+        state.report = None;
+        if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
+            state.snapshot_name = snapshot.name;
+        }
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Selected Snapshot:");
+        if state.snapshot_id.is_some() {
+            ui.label(egui::RichText::new(&state.snapshot_name).strong());
+        } else {
+            ui.label(egui::RichText::new("None").weak());
+        }
+        if ui.button("Select...").clicked() {
+            state.snapshot_picker.open();
+        }
+    });
+
+    ui.add_space(8.0);
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(state.snapshot_id.is_some(), egui::Button::new("Run Audit"))
+            .clicked()
+        {
+            if let Some(snapshot_id) = state.snapshot_id {
+                let settings = AppSettings::load_from_db(conn).unwrap_or_default();
+                match run_audit(conn, snapshot_id, &settings) {
+                    Ok(report) => {
+                        state.status = if report.passed() {
+                            "Completed: audit passed".to_string()
+                        } else {
+                            "Completed: audit found issues".to_string()
+                        };
+                        state.report = Some(report);
+                    }
+                    Err(e) => {
+                        state.status = format!("Error: {}", e);
+                        state.report = None;
+                    }
+                }
+            }
+        }
+        ui.checkbox(&mut state.only_issues, "Only packages with issues");
+    });
+
+    if !state.status.is_empty() {
+        let color = if state.status.starts_with("Error") {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        ui.colored_label(color, &state.status);
+    }
+
+    let Some(report) = &state.report else {
+        return;
+    };
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label(format!("Packages: {}", report.packages.len()));
+        ui.label(format!("Advisories loaded: {}", report.advisories_loaded));
+        ui.label(format!("Vulnerable: {}", report.vulnerable_count));
+        ui.label(format!("Policy violations: {}", report.violations.len()));
+    });
+
+    if !report.violations.is_empty() {
+        ui.add_space(6.0);
+        ui.group(|ui| {
+            ui.label(egui::RichText::new("Policy violations").strong());
+            for v in &report.violations {
+                ui.colored_label(egui::Color32::LIGHT_RED, v);
+            }
+        });
+    }
+    if !report.warnings.is_empty() {
+        ui.add_space(6.0);
+        ui.collapsing(format!("Warnings ({})", report.warnings.len()), |ui| {
+            for w in &report.warnings {
+                ui.colored_label(egui::Color32::YELLOW, w);
+            }
+        });
+    }
+
+    ui.add_space(8.0);
+    let rows: Vec<&PackageAudit> = report
+        .packages
+        .iter()
+        .filter(|p| !state.only_issues || p.has_issues())
+        .collect();
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            render_table(ui, &rows);
+        });
+}
+
+fn render_table(ui: &mut egui::Ui, rows: &[&PackageAudit]) {
+    use egui_extras::{Column, TableBuilder};
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto().at_least(180.0)) // Package
+        .column(Column::auto().at_least(80.0)) // Version
+        .column(Column::auto().at_least(180.0)) // License
+        .column(Column::remainder().at_least(300.0)) // Advisories
+        .header(20.0, |mut header| {
+            for title in ["Package", "Version", "License", "Advisories"] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|mut body| {
+            for pkg in rows {
+                body.row(18.0, |mut row| {
+                    row.col(|ui| {
+                        ui.label(&pkg.name);
+                    });
+                    row.col(|ui| {
+                        ui.label(&pkg.version);
+                    });
+                    row.col(|ui| {
+                        let text = pkg.license.as_deref().unwrap_or("unknown");
+                        match pkg.license_allowed {
+                            Some(false) => ui.colored_label(egui::Color32::LIGHT_RED, text),
+                            Some(true) => ui.colored_label(egui::Color32::LIGHT_GREEN, text),
+                            None if license_file(text).is_some() => {
+                                ui.colored_label(egui::Color32::YELLOW, text).on_hover_text(
+                                    "License file, not an SPDX expression: needs review",
+                                )
+                            }
+                            None => ui.label(text),
+                        };
+                    });
+                    row.col(|ui| {
+                        for hit in &pkg.advisories {
+                            let color = if hit.informational.is_some() {
+                                egui::Color32::YELLOW
+                            } else {
+                                egui::Color32::LIGHT_RED
+                            };
+                            let kind = hit.informational.as_deref().unwrap_or("vulnerability");
+                            ui.colored_label(color, format!("{} [{}]", hit.id, kind))
+                                .on_hover_text(format!(
+                                    "{}\nPatched: {}\n{}",
+                                    hit.title,
+                                    hit.patched,
+                                    hit.url.as_deref().unwrap_or("")
+                                ));
+                        }
+                    });
+                });
+            }
+        });
+}
@@ -0,0 +1,166 @@
+use std::iter::Peekable;
+use std::slice::Iter;
+
+/// Parsed SPDX license expression; `WITH` exceptions are dropped
+#[derive(Debug, PartialEq)]
+enum Expr {
+    License(String),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn allowed(&self, allowed: &[String]) -> bool {
+        match self {
+            Expr::License(id) => allowed.iter().any(|a| a.eq_ignore_ascii_case(id)),
+            Expr::And(a, b) => a.allowed(allowed) && b.allowed(allowed),
+            Expr::Or(a, b) => a.allowed(allowed) || b.allowed(allowed),
+        }
+    }
+}
+
+type Tokens<'a> = Peekable<Iter<'a, String>>;
+
+fn is_keyword(token: &str, keyword: &str) -> bool {
+    token.eq_ignore_ascii_case(keyword)
+}
+
+/// `WITH` binds tighter than `AND`, which binds tighter than `OR`; legacy `A/B` is `A OR B`.
+/// None for a malformed expression.
+fn parse(expression: &str) -> Option<Expr> {
+    let tokens: Vec<String> = expression
+        .replace('(', " ( ")
+        .replace(')', " ) ")
+        .replace('/', " OR ")
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    let mut tokens = tokens.iter().peekable();
+    let expr = parse_or(&mut tokens)?;
+    tokens.peek().is_none().then_some(expr)
+}
+
+fn parse_or(tokens: &mut Tokens) -> Option<Expr> {
+    let mut expr = parse_and(tokens)?;
+    while tokens.next_if(|t| is_keyword(t, "OR")).is_some() {
+        expr = Expr::Or(Box::new(expr), Box::new(parse_and(tokens)?));
+    }
+    Some(expr)
+}
+
+fn parse_and(tokens: &mut Tokens) -> Option<Expr> {
+    let mut expr = parse_term(tokens)?;
+    while tokens.next_if(|t| is_keyword(t, "AND")).is_some() {
+        expr = Expr::And(Box::new(expr), Box::new(parse_term(tokens)?));
+    }
+    Some(expr)
+}
+
+fn parse_term(tokens: &mut Tokens) -> Option<Expr> {
+    let token = tokens.next()?;
+    let expr = if token == "(" {
+        let inner = parse_or(tokens)?;
+        tokens.next_if(|t| *t == ")")?;
+        inner
+    } else if token == ")" || ["AND", "OR", "WITH"].iter().any(|k| is_keyword(token, k)) {
+        return None;
+    } else {
+        Expr::License(token.clone())
+    };
+    if tokens.next_if(|t| is_keyword(t, "WITH")).is_some() {
+        tokens.next()?;
+    }
+    Some(expr)
+}
+
+/// Check an SPDX license expression against the allowed list.
+/// `A OR B` passes if any branch passes, `A AND B` needs both; a malformed expression fails.
+pub fn license_allowed(expression: &str, allowed: &[String]) -> bool {
+    parse(expression).is_some_and(|expr| expr.allowed(allowed))
+}
+
+/// Path of a `license-file` manifest entry, stored by the scanner as `file:<path>`.
+/// Such licenses are not SPDX expressions and need a manual review.
+pub fn license_file(license: &str) -> Option<&str> {
+    license.strip_prefix("file:")
+}
+
+/// True for Cargo.lock registry sources. Advisories describe registry releases only;
+/// path, workspace and git packages may merely share a name with a registry crate.
+pub fn from_registry(source: Option<&str>) -> bool {
+    source.is_some_and(|s| s.starts_with("registry+") || s.starts_with("sparse+"))
+}
+
+/// True if the advisory ID is listed as denied by policy
+pub fn advisory_denied(id: &str, denied: &[String]) -> bool {
+    denied.iter().any(|d| d.eq_ignore_ascii_case(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_license_expressions() {
+        let allowed = list(&["MIT", "Apache-2.0"]);
+
+        assert!(license_allowed("MIT", &allowed));
+        assert!(license_allowed("MIT OR Apache-2.0", &allowed));
+        assert!(license_allowed("GPL-3.0 OR MIT", &allowed));
+        assert!(license_allowed("MIT/Apache-2.0", &allowed));
+        assert!(license_allowed(
+            "(MIT OR Apache-2.0) AND Apache-2.0",
+            &allowed
+        ));
+        assert!(license_allowed("Apache-2.0 WITH LLVM-exception", &allowed));
+        assert!(!license_allowed("MIT AND GPL-3.0", &allowed));
+        assert!(!license_allowed("GPL-3.0", &allowed));
+        assert!(!license_allowed("", &allowed));
+        assert!(!license_allowed("(MIT OR Apache-2.0", &allowed));
+
+        let mit = list(&["MIT"]);
+        assert!(!license_allowed("(MIT OR GPL-3.0) AND BSD-3-Clause", &mit));
+        assert!(license_allowed(
+            "(MIT OR GPL-3.0) AND BSD-3-Clause",
+            &list(&["MIT", "BSD-3-Clause"])
+        ));
+        assert!(license_allowed("MIT OR GPL-3.0 AND BSD-3-Clause", &mit));
+        assert!(!license_allowed(
+            "GPL-2.0 WITH Classpath-exception-2.0 OR BSD-3-Clause",
+            &mit
+        ));
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        let id = |s: &str| Box::new(Expr::License(s.to_string()));
+        assert_eq!(
+            parse("A OR B AND C WITH X"),
+            Some(Expr::Or(id("A"), Box::new(Expr::And(id("B"), id("C")))))
+        );
+        assert_eq!(
+            parse("(A OR B) AND C"),
+            Some(Expr::And(Box::new(Expr::Or(id("A"), id("B"))), id("C")))
+        );
+        assert_eq!(parse("A AND"), None);
+        assert_eq!(parse("A B"), None);
+    }
+
+    #[test]
+    fn test_license_files_and_sources() {
+        assert_eq!(license_file("file:LICENSE.txt"), Some("LICENSE.txt"));
+        assert_eq!(license_file("MIT"), None);
+        assert!(from_registry(Some(
+            "registry+https://github.com/rust-lang/crates.io-index"
+        )));
+        assert!(from_registry(Some("sparse+https://index.crates.io/")));
+        assert!(!from_registry(Some(
+            "git+https://github.com/serde-rs/serde?rev=1#abc"
+        )));
+        assert!(!from_registry(None));
+    }
+}
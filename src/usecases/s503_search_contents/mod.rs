@@ -0,0 +1,263 @@
+use eframe::egui;
+use egui::text::{LayoutJob, TextFormat};
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::domain::n006_file_content::model::{ContentSearchHit, HIGHLIGHT_END, HIGHLIGHT_START};
+use crate::domain::n006_file_content::repository as content_repo;
+
+const RESULTS_LIMIT: i64 = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum QueryMode {
+    /// Every word must occur somewhere in the file
+    #[default]
+    Words,
+    /// Words must occur next to each other, in order
+    Phrase,
+    /// FTS5 query syntax as typed (AND/OR/NOT, NEAR, column filters)
+    Raw,
+}
+
+#[derive(Default)]
+pub struct SearchState {
+    pub snapshot_picker: SnapshotPickerState,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    pub query: String,
+    pub mode: QueryMode,
+    pub prefix: bool,
+    pub results: Vec<ContentSearchHit>,
+    /// Snapshot names of the current results, shown when searching all snapshots
+    pub snapshot_names: HashMap<i64, String>,
+    /// FTS5 expression used for the current results
+    pub fts_query: String,
+    pub status: String,
+}
+
+/// Translate user input into an FTS5 MATCH expression
+pub fn build_match_query(input: &str, mode: QueryMode, prefix: bool) -> String {
+    let quote = |s: &str| format!("\"{}\"", s.replace('"', "\"\""));
+    let star = if prefix { "*" } else { "" };
+    match mode {
+        QueryMode::Raw => input.trim().to_string(),
+        QueryMode::Phrase => format!("{}{}", quote(input.trim()), star),
+        QueryMode::Words => input
+            .split_whitespace()
+            .map(|w| format!("{}{}", quote(w), star))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// Renders the Search tab. Returns `(file_id, line)` when a hit is opened.
+pub fn ui_search(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut SearchState,
+) -> Option<(i64, Option<usize>)> {
+    let mut opened = None;
+
+    ui.heading("Search");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.snapshot_id = Some(snapshot_id);
+        if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
+            state.snapshot_name = snapshot.name;
+        }
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Snapshot:");
+        if state.snapshot_id.is_some() {
+            ui.label(egui::RichText::new(&state.snapshot_name).strong());
+        } else {
+            ui.label(egui::RichText::new("All snapshots").weak());
+        }
+        if ui.button("Select...").clicked() {
+            state.snapshot_picker.open();
+        }
+        if state.snapshot_id.is_some() && ui.button("All").clicked() {
+            state.snapshot_id = None;
+            state.snapshot_name.clear();
+        }
+    });
+
+    ui.add_space(6.0);
+
+    let mut run = false;
+    ui.horizontal(|ui| {
+        ui.label("Query:");
+        let resp = ui.text_edit_singleline(&mut state.query);
+        if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            run = true;
+        }
+        if ui.button("Search").clicked() {
+            run = true;
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut state.mode, QueryMode::Words, "All words");
+        ui.selectable_value(&mut state.mode, QueryMode::Phrase, "Phrase");
+        ui.selectable_value(&mut state.mode, QueryMode::Raw, "FTS5 syntax");
+        ui.checkbox(&mut state.prefix, "Prefix match");
+    });
+
+    if run && !state.query.trim().is_empty() {
+        state.fts_query = build_match_query(&state.query, state.mode, state.prefix);
+        match content_repo::search(conn, state.snapshot_id, &state.fts_query, RESULTS_LIMIT) {
+            Ok(hits) => {
+                state.status = format!("{} hits", hits.len());
+                state.snapshot_names.clear();
+                for hit in &hits {
+                    state
+                        .snapshot_names
+                        .entry(hit.snapshot_id)
+                        .or_insert_with(|| {
+                            snapshot_repo::get_by_id(conn, hit.snapshot_id)
+                                .map(|s| s.name)
+                                .unwrap_or_else(|_| format!("#{}", hit.snapshot_id))
+                        });
+                }
+                state.results = hits;
+            }
+            Err(e) => {
+                state.status = format!("Error: {}", e);
+                state.results.clear();
+            }
+        }
+    }
+
+    if !state.status.is_empty() {
+        let color = if state.status.starts_with("Error") {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        ui.colored_label(color, &state.status);
+    }
+
+    ui.add_space(6.0);
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            for hit in &state.results {
+                ui.horizontal(|ui| {
+                    if ui.link(&hit.path).clicked() {
+                        let line =
+                            content_repo::first_match_line(conn, hit.file_id, &state.fts_query)
+                                .ok()
+                                .flatten();
+                        opened = Some((hit.file_id, line));
+                    }
+                    if state.snapshot_id.is_none() {
+                        if let Some(name) = state.snapshot_names.get(&hit.snapshot_id) {
+                            ui.label(egui::RichText::new(name).weak());
+                        }
+                    }
+                    ui.label(egui::RichText::new(format!("rank {:.2}", -hit.rank)).weak());
+                });
+                ui.label(snippet_job(ui, &hit.snippet));
+                ui.separator();
+            }
+        });
+
+    opened
+}
+
+/// Layout a snippet with highlight markers as monospace text with emphasized matches
+fn snippet_job(ui: &egui::Ui, snippet: &str) -> LayoutJob {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let normal = TextFormat {
+        font_id: font_id.clone(),
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+    let highlighted = TextFormat {
+        font_id,
+        color: ui.visuals().strong_text_color(),
+        background: egui::Color32::from_rgba_unmultiplied(255, 200, 0, 60),
+        ..Default::default()
+    };
+
+    let mut job = LayoutJob::default();
+    let text = snippet.replace(['\n', '\r'], " ");
+    let mut in_match = false;
+    let mut buf = String::new();
+    for ch in text.chars() {
+        if ch == HIGHLIGHT_START || ch == HIGHLIGHT_END {
+            let format = if in_match {
+                highlighted.clone()
+            } else {
+                normal.clone()
+            };
+            job.append(&buf, 0.0, format);
+            buf.clear();
+            in_match = ch == HIGHLIGHT_START;
+        } else {
+            buf.push(ch);
+        }
+    }
+    let format = if in_match { highlighted } else { normal };
+    job.append(&buf, 0.0, format);
+    job
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_match_queries() {
+        assert_eq!(
+            build_match_query("  foo   bar ", QueryMode::Words, false),
+            "\"foo\" \"bar\""
+        );
+        assert_eq!(
+            build_match_query("foo bar", QueryMode::Words, true),
+            "\"foo\"* \"bar\"*"
+        );
+        assert_eq!(
+            build_match_query(" foo bar ", QueryMode::Phrase, false),
+            "\"foo bar\""
+        );
+        assert_eq!(
+            build_match_query("foo bar", QueryMode::Phrase, true),
+            "\"foo bar\"*"
+        );
+        assert_eq!(
+            build_match_query(" foo NEAR bar ", QueryMode::Raw, true),
+            "foo NEAR bar"
+        );
+        assert_eq!(build_match_query("   ", QueryMode::Words, false), "");
+    }
+
+    #[test]
+    fn quotes_user_input() {
+        assert_eq!(
+            build_match_query("say \"hi\"", QueryMode::Words, false),
+            "\"say\" \"\"\"hi\"\"\""
+        );
+        assert_eq!(
+            build_match_query("a \"b\"", QueryMode::Phrase, false),
+            "\"a \"\"b\"\"\""
+        );
+        // Operators and syntax characters are searched for literally
+        assert_eq!(
+            build_match_query("foo AND NOT bar*", QueryMode::Words, false),
+            "\"foo\" \"AND\" \"NOT\" \"bar*\""
+        );
+        assert_eq!(
+            build_match_query("path:src -x", QueryMode::Words, false),
+            "\"path:src\" \"-x\""
+        );
+        assert_eq!(
+            build_match_query("NEAR(a b)", QueryMode::Phrase, false),
+            "\"NEAR(a b)\""
+        );
+    }
+}
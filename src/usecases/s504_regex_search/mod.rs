@@ -0,0 +1,388 @@
+mod worker;
+
+use eframe::egui;
+use egui::text::{LayoutJob, TextFormat};
+use rusqlite::Connection;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::shared::glob::matches_any;
+use worker::{spawn_search, FileMatchGroup, SearchEvent, SearchHandle, SearchParams};
+
+pub struct RegexSearchState {
+    pub snapshot_picker: SnapshotPickerState,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    pub pattern: String,
+    pub case_insensitive: bool,
+    /// Comma-separated globs, e.g. `src/**/*.rs, *.toml`
+    pub include_globs: String,
+    pub exclude_globs: String,
+    /// Metadata filters (empty = any)
+    pub crate_layer: String,
+    pub artifact_type: String,
+    pub role: String,
+    pub context_lines: usize,
+    pub max_matches: usize,
+    // Results
+    pub groups: Vec<FileMatchGroup>,
+    pub total_files: usize,
+    pub files_searched: usize,
+    pub total_matches: usize,
+    handle: Option<SearchHandle>,
+    // Distinct metadata values of the selected snapshot for filter combos
+    layer_options: Vec<String>,
+    type_options: Vec<String>,
+    role_options: Vec<String>,
+    pub status: String,
+}
+
+impl Default for RegexSearchState {
+    fn default() -> Self {
+        Self {
+            snapshot_picker: SnapshotPickerState::default(),
+            snapshot_id: None,
+            snapshot_name: String::new(),
+            pattern: String::new(),
+            case_insensitive: false,
+            include_globs: String::new(),
+            exclude_globs: String::new(),
+            crate_layer: String::new(),
+            artifact_type: String::new(),
+            role: String::new(),
+            context_lines: 2,
+            max_matches: 1000,
+            groups: Vec::new(),
+            total_files: 0,
+            files_searched: 0,
+            total_matches: 0,
+            handle: None,
+            layer_options: Vec::new(),
+            type_options: Vec::new(),
+            role_options: Vec::new(),
+            status: String::new(),
+        }
+    }
+}
+
+impl RegexSearchState {
+    pub fn is_running(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    fn load_filter_options(&mut self, conn: &Connection, snapshot_id: i64) {
+        let files = file_repo::list_by_snapshot(conn, snapshot_id).unwrap_or_default();
+        self.layer_options = distinct_values(files.iter().map(|f| f.crate_layer.as_ref()));
+        self.type_options = distinct_values(files.iter().map(|f| f.artifact_type.as_ref()));
+        self.role_options = distinct_values(files.iter().map(|f| f.role.as_ref()));
+    }
+
+    fn start(&mut self, conn: &Connection) {
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+
+        let files = match file_repo::list_by_snapshot(conn, snapshot_id) {
+            Ok(files) => files,
+            Err(e) => {
+                self.status = format!("Error loading files: {}", e);
+                return;
+            }
+        };
+        let matches_filter = |value: &Option<String>, filter: &str| {
+            filter.is_empty() || value.as_deref() == Some(filter)
+        };
+        let selected: Vec<(i64, String)> = files
+            .into_iter()
+            .filter(|f| !f.is_directory)
+            .filter(|f| matches_filter(&f.crate_layer, &self.crate_layer))
+            .filter(|f| matches_filter(&f.artifact_type, &self.artifact_type))
+            .filter(|f| matches_filter(&f.role, &self.role))
+            .filter(|f| {
+                self.include_globs.trim().is_empty() || matches_any(&self.include_globs, &f.path)
+            })
+            .filter(|f| !matches_any(&self.exclude_globs, &f.path))
+            .map(|f| (f.id, f.path))
+            .collect();
+
+        let root_path = snapshot_repo::get_by_id(conn, snapshot_id)
+            .and_then(|s| project_repo::get_by_id(conn, s.project_id))
+            .ok()
+            .map(|p| PathBuf::from(p.root_path));
+
+        self.groups.clear();
+        self.total_files = selected.len();
+        self.files_searched = 0;
+        self.total_matches = 0;
+
+        let params = SearchParams {
+            pattern: self.pattern.clone(),
+            case_insensitive: self.case_insensitive,
+            context_lines: self.context_lines,
+            max_matches: self.max_matches,
+            files: selected,
+            root_path,
+        };
+        match spawn_search(params) {
+            Ok(handle) => {
+                self.handle = Some(handle);
+                self.status = "Searching...".to_string();
+            }
+            Err(e) => self.status = format!("Error: {}", e),
+        }
+    }
+
+    fn cancel(&mut self) {
+        if let Some(handle) = &self.handle {
+            handle.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Drain events sent by the worker since the last frame
+    fn poll(&mut self) {
+        let Some(handle) = &self.handle else { return };
+        let events: Vec<SearchEvent> = handle.receiver.try_iter().collect();
+
+        for event in events {
+            match event {
+                SearchEvent::Group(group) => {
+                    self.total_matches += group.match_count;
+                    self.groups.push(group);
+                }
+                SearchEvent::Progress { files_searched } => self.files_searched = files_searched,
+                SearchEvent::Finished {
+                    truncated,
+                    cancelled,
+                } => {
+                    self.handle = None;
+                    self.status = if cancelled {
+                        format!(
+                            "Cancelled: {} matches in {} files",
+                            self.total_matches,
+                            self.groups.len()
+                        )
+                    } else if truncated {
+                        format!(
+                            "Completed: stopped at match cap ({} matches in {} files)",
+                            self.total_matches,
+                            self.groups.len()
+                        )
+                    } else {
+                        format!(
+                            "Completed: {} matches in {} files",
+                            self.total_matches,
+                            self.groups.len()
+                        )
+                    };
+                }
+                SearchEvent::Failed(e) => {
+                    self.handle = None;
+                    self.status = format!("Error: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Renders the Regex Search tab. Returns `(file_id, line)` when a result line is clicked.
+pub fn ui_regex_search(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut RegexSearchState,
+) -> Option<(i64, Option<usize>)> {
+    let mut opened = None;
+
+    state.poll();
+    if state.is_running() {
+        ui.ctx().request_repaint_after(Duration::from_millis(100));
+    }
+
+    ui.heading("Regex Search");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.snapshot_id = Some(snapshot_id);
+        if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
+            state.snapshot_name = snapshot.name;
+        }
+        state.load_filter_options(conn, snapshot_id);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Selected Snapshot:");
+        if state.snapshot_id.is_some() {
+            ui.label(egui::RichText::new(&state.snapshot_name).strong());
+        } else {
+            ui.label(egui::RichText::new("None").weak());
+        }
+        if ui.button("Select...").clicked() {
+            state.snapshot_picker.open();
+        }
+    });
+
+    ui.add_space(6.0);
+
+    let mut run = false;
+    ui.horizontal(|ui| {
+        ui.label("Pattern:");
+        let resp = ui.text_edit_singleline(&mut state.pattern);
+        if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            run = true;
+        }
+        ui.checkbox(&mut state.case_insensitive, "Ignore case");
+    });
+    ui.horizontal(|ui| {
+        ui.label("Include:");
+        ui.text_edit_singleline(&mut state.include_globs);
+        ui.label("Exclude:");
+        ui.text_edit_singleline(&mut state.exclude_globs);
+    });
+    ui.horizontal(|ui| {
+        filter_combo(ui, "Crate", &mut state.crate_layer, &state.layer_options);
+        filter_combo(ui, "Type", &mut state.artifact_type, &state.type_options);
+        filter_combo(ui, "Role", &mut state.role, &state.role_options);
+    });
+    ui.horizontal(|ui| {
+        ui.label("Context lines:");
+        ui.add(egui::DragValue::new(&mut state.context_lines).range(0..=20));
+        ui.label("Max matches:");
+        ui.add(egui::DragValue::new(&mut state.max_matches).range(1..=100_000));
+    });
+
+    ui.add_space(6.0);
+
+    ui.horizontal(|ui| {
+        let can_run =
+            state.snapshot_id.is_some() && !state.pattern.is_empty() && !state.is_running();
+        if ui
+            .add_enabled(can_run, egui::Button::new("Search"))
+            .clicked()
+        {
+            run = true;
+        }
+        if ui
+            .add_enabled(state.is_running(), egui::Button::new("Cancel"))
+            .clicked()
+        {
+            state.cancel();
+        }
+        if state.is_running() {
+            ui.spinner();
+            ui.label(format!(
+                "{}/{} files",
+                state.files_searched, state.total_files
+            ));
+        }
+    });
+
+    if run && state.snapshot_id.is_some() && !state.pattern.is_empty() && !state.is_running() {
+        state.start(conn);
+    }
+
+    if !state.status.is_empty() {
+        let color = if state.status.starts_with("Error") {
+            egui::Color32::LIGHT_RED
+        } else if state.status.starts_with("Completed") {
+            egui::Color32::LIGHT_GREEN
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        ui.colored_label(color, &state.status);
+    }
+
+    ui.add_space(6.0);
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            for group in &state.groups {
+                egui::CollapsingHeader::new(format!("{} ({})", group.path, group.match_count))
+                    .id_salt(("regex_group", group.file_id))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for (i, hunk) in group.hunks.iter().enumerate() {
+                            if i > 0 {
+                                ui.label(egui::RichText::new("  ⋯").weak());
+                            }
+                            for line in &hunk.lines {
+                                let job = line_job(ui, line.line_no, &line.text, &line.ranges);
+                                let resp =
+                                    ui.add(egui::Label::new(job).sense(egui::Sense::click()));
+                                if resp.clicked() {
+                                    opened = Some((group.file_id, Some(line.line_no)));
+                                }
+                            }
+                        }
+                    });
+            }
+        });
+
+    opened
+}
+
+fn distinct_values<'a>(values: impl Iterator<Item = Option<&'a String>>) -> Vec<String> {
+    let mut out: Vec<String> = values.flatten().cloned().collect();
+    out.sort();
+    out.dedup();
+    out
+}
+
+fn filter_combo(ui: &mut egui::Ui, label: &str, value: &mut String, options: &[String]) {
+    ui.label(format!("{}:", label));
+    let selected = if value.is_empty() {
+        "Any".to_string()
+    } else {
+        value.clone()
+    };
+    egui::ComboBox::from_id_salt(("regex_filter", label))
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(value, String::new(), "Any");
+            for option in options {
+                ui.selectable_value(value, option.clone(), option);
+            }
+        });
+}
+
+/// Monospace line with line number gutter and highlighted match ranges
+fn line_job(ui: &egui::Ui, line_no: usize, text: &str, ranges: &[(usize, usize)]) -> LayoutJob {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let gutter = TextFormat {
+        font_id: font_id.clone(),
+        color: ui.visuals().weak_text_color(),
+        ..Default::default()
+    };
+    let normal = TextFormat {
+        font_id: font_id.clone(),
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+    let highlighted = TextFormat {
+        font_id,
+        color: ui.visuals().strong_text_color(),
+        background: egui::Color32::from_rgba_unmultiplied(255, 200, 0, 60),
+        ..Default::default()
+    };
+
+    let mut job = LayoutJob::default();
+    let marker = if ranges.is_empty() { ' ' } else { ':' };
+    job.append(&format!("{:>6}{} ", line_no, marker), 0.0, gutter);
+
+    let mut pos = 0;
+    for &(start, end) in ranges {
+        if start > pos {
+            job.append(&text[pos..start], 0.0, normal.clone());
+        }
+        job.append(&text[start..end], 0.0, highlighted.clone());
+        pos = end;
+    }
+    if pos < text.len() {
+        job.append(&text[pos..], 0.0, normal);
+    }
+    job
+}
@@ -0,0 +1,329 @@
+use regex::{Regex, RegexBuilder};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+
+use crate::domain::n006_file_content::repository as content_repo;
+use crate::shared::db::DB_PATH;
+use crate::shared::text::is_binary;
+
+/// One line of a result hunk; `ranges` are byte ranges of matches (empty for context lines)
+#[derive(Debug, Clone)]
+pub struct ResultLine {
+    pub line_no: usize,
+    pub text: String,
+    pub ranges: Vec<(usize, usize)>,
+}
+
+/// Consecutive lines (matches plus surrounding context)
+#[derive(Debug, Clone)]
+pub struct Hunk {
+    pub lines: Vec<ResultLine>,
+}
+
+/// All matches found in a single file
+#[derive(Debug, Clone)]
+pub struct FileMatchGroup {
+    pub file_id: i64,
+    pub path: String,
+    pub match_count: usize,
+    pub hunks: Vec<Hunk>,
+}
+
+pub enum SearchEvent {
+    Group(FileMatchGroup),
+    Progress { files_searched: usize },
+    Finished { truncated: bool, cancelled: bool },
+    Failed(String),
+}
+
+pub struct SearchParams {
+    pub pattern: String,
+    pub case_insensitive: bool,
+    pub context_lines: usize,
+    pub max_matches: usize,
+    /// (file_id, relative path) of files to search
+    pub files: Vec<(i64, String)>,
+    /// Project root used when a file has no stored content
+    pub root_path: Option<PathBuf>,
+}
+
+pub struct SearchHandle {
+    pub receiver: Receiver<SearchEvent>,
+    pub cancel: Arc<AtomicBool>,
+}
+
+/// Compile the pattern and start searching on a background thread
+pub fn spawn_search(params: SearchParams) -> Result<SearchHandle, String> {
+    let regex = RegexBuilder::new(&params.pattern)
+        .case_insensitive(params.case_insensitive)
+        .build()
+        .map_err(|e| format!("Invalid regex: {}", e))?;
+
+    let (sender, receiver) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    let cancel_flag = cancel.clone();
+
+    thread::spawn(move || {
+        // SQLite connections are not shared across threads; open a dedicated one
+        let conn = match Connection::open(DB_PATH) {
+            Ok(conn) => conn,
+            Err(e) => {
+                let _ = sender.send(SearchEvent::Failed(format!(
+                    "Failed to open database: {}",
+                    e
+                )));
+                return;
+            }
+        };
+        let root = params.root_path.as_deref();
+        run_search(&regex, &params, &sender, &cancel_flag, |file_id, path| {
+            load_text(&conn, file_id, path, root)
+        });
+    });
+
+    Ok(SearchHandle { receiver, cancel })
+}
+
+/// Search `params.files`, reading each one through `load`
+fn run_search(
+    regex: &Regex,
+    params: &SearchParams,
+    sender: &Sender<SearchEvent>,
+    cancel: &AtomicBool,
+    mut load: impl FnMut(i64, &str) -> Option<String>,
+) {
+    let mut remaining = params.max_matches;
+    let mut truncated = false;
+    for (idx, (file_id, path)) in params.files.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            let _ = sender.send(SearchEvent::Finished {
+                truncated: false,
+                cancelled: true,
+            });
+            return;
+        }
+
+        if let Some(text) = load(*file_id, path) {
+            let (hunks, match_count, cut_off) =
+                collect_hunks(&text, regex, params.context_lines, &mut remaining);
+            truncated = cut_off;
+            if match_count > 0 {
+                let group = FileMatchGroup {
+                    file_id: *file_id,
+                    path: path.clone(),
+                    match_count,
+                    hunks,
+                };
+                if sender.send(SearchEvent::Group(group)).is_err() {
+                    // Receiver dropped: UI no longer interested
+                    return;
+                }
+            }
+            if truncated {
+                // Past the cap: stop at the first match that had to be left out
+                break;
+            }
+        }
+
+        if idx % 25 == 0 || idx + 1 == params.files.len() {
+            let _ = sender.send(SearchEvent::Progress {
+                files_searched: idx + 1,
+            });
+        }
+    }
+
+    let _ = sender.send(SearchEvent::Finished {
+        truncated,
+        cancelled: false,
+    });
+}
+
+fn load_text(conn: &Connection, file_id: i64, path: &str, root: Option<&Path>) -> Option<String> {
+    if let Ok(Some(text)) = content_repo::get_content(conn, file_id) {
+        return Some(text);
+    }
+    let bytes = std::fs::read(root?.join(path)).ok()?;
+    if is_binary(&bytes) {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Find matching lines (up to `remaining`) and merge them with context into hunks;
+/// the flag tells whether another matching line was left out
+fn collect_hunks(
+    text: &str,
+    regex: &Regex,
+    context: usize,
+    remaining: &mut usize,
+) -> (Vec<Hunk>, usize, bool) {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut matches: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+    let mut visible = vec![false; lines.len()];
+    let mut cut_off = false;
+
+    for (i, line) in lines.iter().enumerate() {
+        let ranges: Vec<(usize, usize)> = regex
+            .find_iter(line)
+            .map(|m| (m.start(), m.end()))
+            .collect();
+        if ranges.is_empty() {
+            continue;
+        }
+        if *remaining == 0 {
+            cut_off = true;
+            break;
+        }
+        *remaining -= 1;
+        let last = (i + context).min(lines.len() - 1);
+        for flag in &mut visible[i.saturating_sub(context)..=last] {
+            *flag = true;
+        }
+        matches.insert(i, ranges);
+    }
+
+    let match_count = matches.len();
+    let mut hunks = Vec::new();
+    let mut current: Vec<ResultLine> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if visible[i] {
+            current.push(ResultLine {
+                line_no: i + 1,
+                text: line.to_string(),
+                ranges: matches.remove(&i).unwrap_or_default(),
+            });
+        } else if !current.is_empty() {
+            hunks.push(Hunk {
+                lines: std::mem::take(&mut current),
+            });
+        }
+    }
+    if !current.is_empty() {
+        hunks.push(Hunk { lines: current });
+    }
+
+    (hunks, match_count, cut_off)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk_lines(hunks: &[Hunk]) -> Vec<Vec<usize>> {
+        hunks
+            .iter()
+            .map(|h| h.lines.iter().map(|l| l.line_no).collect())
+            .collect()
+    }
+
+    #[test]
+    fn merges_context_into_hunks() {
+        let text = "a\nx1\nb\nc\nx2\nd\ne\nf\ng\nx3\nh";
+        let regex = Regex::new("x\\d").unwrap();
+
+        let mut remaining = 100;
+        let (hunks, count, _) = collect_hunks(text, &regex, 1, &mut remaining);
+        assert_eq!(count, 3);
+        assert_eq!(remaining, 97);
+        // x1 and x2 windows overlap at line 3-4; x3 stands alone
+        assert_eq!(
+            hunk_lines(&hunks),
+            vec![vec![1, 2, 3, 4, 5, 6], vec![9, 10, 11]]
+        );
+        let x2 = &hunks[0].lines[4];
+        assert_eq!((x2.text.as_str(), x2.ranges.clone()), ("x2", vec![(0, 2)]));
+        assert!(hunks[0].lines[0].ranges.is_empty());
+
+        let mut remaining = 100;
+        let (hunks, _, _) = collect_hunks(text, &regex, 0, &mut remaining);
+        assert_eq!(hunk_lines(&hunks), vec![vec![2], vec![5], vec![10]]);
+    }
+
+    #[test]
+    fn stops_at_remaining_matches() {
+        let regex = Regex::new("x").unwrap();
+        let mut remaining = 2;
+        let (hunks, count, cut_off) = collect_hunks("x\nx x\nx\n", &regex, 0, &mut remaining);
+        assert_eq!((count, remaining, cut_off), (2, 0, true));
+        assert_eq!(hunk_lines(&hunks), vec![vec![1, 2]]);
+        assert_eq!(hunks[0].lines[1].ranges, vec![(0, 1), (2, 3)]);
+    }
+
+    fn search(
+        files: &[&str],
+        max_matches: usize,
+        cancelled: bool,
+    ) -> (Vec<FileMatchGroup>, bool, bool) {
+        let params = SearchParams {
+            pattern: "x".to_string(),
+            case_insensitive: false,
+            context_lines: 0,
+            max_matches,
+            files: (0..files.len())
+                .map(|i| (i as i64, format!("f{}", i)))
+                .collect(),
+            root_path: None,
+        };
+        let (sender, receiver) = mpsc::channel();
+        let cancel = AtomicBool::new(cancelled);
+        run_search(
+            &Regex::new("x").unwrap(),
+            &params,
+            &sender,
+            &cancel,
+            |id, _| Some(files[id as usize].to_string()),
+        );
+        drop(sender);
+
+        let mut groups = Vec::new();
+        let mut finished = (false, false);
+        for event in receiver {
+            match event {
+                SearchEvent::Group(g) => groups.push(g),
+                SearchEvent::Finished {
+                    truncated,
+                    cancelled,
+                } => finished = (truncated, cancelled),
+                _ => {}
+            }
+        }
+        (groups, finished.0, finished.1)
+    }
+
+    #[test]
+    fn cuts_off_at_max_matches() {
+        let files = ["x\nx", "none", "x\nx\nx", "x"];
+
+        let (groups, truncated, _) = search(&files, 10, false);
+        assert_eq!(
+            groups.iter().map(|g| g.match_count).collect::<Vec<_>>(),
+            vec![2, 3, 1]
+        );
+        assert!(!truncated);
+
+        let (groups, truncated, _) = search(&files, 4, false);
+        assert_eq!(
+            groups
+                .iter()
+                .map(|g| (g.file_id, g.match_count))
+                .collect::<Vec<_>>(),
+            vec![(0, 2), (2, 2)]
+        );
+        assert!(truncated);
+
+        // Cap hit exactly on the last match: nothing was dropped
+        let (groups, truncated, _) = search(&files, 6, false);
+        assert_eq!(groups.len(), 3);
+        assert!(!truncated);
+        let (_, truncated, _) = search(&files, 5, false);
+        assert!(truncated);
+
+        let (groups, _, cancelled) = search(&files, 10, true);
+        assert!(groups.is_empty() && cancelled);
+    }
+}
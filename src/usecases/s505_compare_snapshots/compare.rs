@@ -0,0 +1,164 @@
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n003_snapshot_file::model::SnapshotFile;
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n003_snapshot_file::source::load_file_bytes;
+use crate::domain::n006_file_content::repository as content_repo;
+use crate::shared::git;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareStatus {
+    Added,
+    Removed,
+    Modified,
+    Unchanged,
+    /// Same size, but neither stored content nor a git blob could be loaded to compare
+    Unavailable,
+}
+
+impl CompareStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompareStatus::Added => "added",
+            CompareStatus::Removed => "removed",
+            CompareStatus::Modified => "modified",
+            CompareStatus::Unchanged => "unchanged",
+            CompareStatus::Unavailable => "unavailable",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CompareEntry {
+    pub path: String,
+    pub status: CompareStatus,
+    pub base_file_id: Option<i64>,
+    pub target_file_id: Option<i64>,
+    pub base_size: Option<i64>,
+    pub target_size: Option<i64>,
+}
+
+/// Match files of two snapshots by path and classify each one.
+///
+/// Files with equal size are compared by stored content, the same content the diff
+/// view shows; git blob hashes are only used when neither file has stored content.
+pub fn compare_snapshots(
+    conn: &Connection,
+    base_id: i64,
+    target_id: i64,
+) -> Result<Vec<CompareEntry>, String> {
+    let load = |snapshot_id: i64| -> Result<BTreeMap<String, SnapshotFile>, String> {
+        let files = file_repo::list_by_snapshot(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load files: {}", e))?;
+        Ok(files
+            .into_iter()
+            .filter(|f| !f.is_directory)
+            .map(|f| (f.path.clone(), f))
+            .collect())
+    };
+    let base = load(base_id)?;
+    let target = load(target_id)?;
+    let base_blobs = snapshot_blob_ids(conn, base_id);
+    let target_blobs = snapshot_blob_ids(conn, target_id);
+
+    let mut paths: Vec<&String> = base.keys().chain(target.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut entries = Vec::with_capacity(paths.len());
+    for path in paths {
+        let (b, t) = (base.get(path), target.get(path));
+        let status = match (b, t) {
+            (Some(_), None) => CompareStatus::Removed,
+            (None, Some(_)) => CompareStatus::Added,
+            (Some(b), Some(t)) if b.size_bytes != t.size_bytes => CompareStatus::Modified,
+            (Some(b), Some(t)) => {
+                let blobs = (
+                    base_blobs.as_ref().and_then(|ids| ids.get(path)),
+                    target_blobs.as_ref().and_then(|ids| ids.get(path)),
+                );
+                match same_content(conn, b, t, blobs) {
+                    Some(true) => CompareStatus::Unchanged,
+                    Some(false) => CompareStatus::Modified,
+                    None => CompareStatus::Unavailable,
+                }
+            }
+            (None, None) => continue,
+        };
+        entries.push(CompareEntry {
+            path: path.clone(),
+            status,
+            base_file_id: b.map(|f| f.id),
+            target_file_id: t.map(|f| f.id),
+            base_size: b.map(|f| f.size_bytes),
+            target_size: t.map(|f| f.size_bytes),
+        });
+    }
+    Ok(entries)
+}
+
+/// Stored content wins over the blob at the scan revision, which misses uncommitted edits.
+/// None when the content of either file cannot be loaded.
+fn same_content(
+    conn: &Connection,
+    b: &SnapshotFile,
+    t: &SnapshotFile,
+    blobs: (Option<&String>, Option<&String>),
+) -> Option<bool> {
+    let b_text = content_repo::get_content(conn, b.id).ok().flatten();
+    let t_text = content_repo::get_content(conn, t.id).ok().flatten();
+    match (b_text, t_text, blobs) {
+        (Some(x), Some(y), _) => Some(x == y),
+        (None, None, (Some(x), Some(y))) => Some(x == y),
+        _ => {
+            let bytes = |f: &SnapshotFile| load_file_bytes(conn, f, false).ok().map(|c| c.bytes);
+            Some(bytes(b)? == bytes(t)?)
+        }
+    }
+}
+
+fn snapshot_blob_ids(conn: &Connection, snapshot_id: i64) -> Option<HashMap<String, String>> {
+    let snapshot = snapshot_repo::get_by_id(conn, snapshot_id).ok()?;
+    let revision = snapshot.git_revision?;
+    let project = project_repo::get_by_id(conn, snapshot.project_id).ok()?;
+    git::blob_ids(Path::new(&project.root_path), &revision).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::db::init_database;
+
+    #[test]
+    fn unloadable_content_is_not_unchanged() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_database(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO n001_project (id, name, root_path) VALUES (1, 'demo', '/nonexistent/demo');
+             INSERT INTO n002_snapshot (id, name, project_id, files_count, dirs_count, files_size_bytes,
+                                        loc_count, scanned_at)
+                 VALUES (1, 'a', 1, 2, 0, 0, 0, ''), (2, 'b', 1, 2, 0, 0, 0, '');
+             INSERT INTO n003_snapshot_file (snapshot_id, name, path, size_bytes, is_directory)
+                 VALUES (1, 'lib.rs', 'lib.rs', 10, 0), (2, 'lib.rs', 'lib.rs', 10, 0),
+                        (1, 'main.rs', 'main.rs', 10, 0), (2, 'main.rs', 'main.rs', 12, 0);",
+        )
+        .unwrap();
+
+        let entries = compare_snapshots(&conn, 1, 2).unwrap();
+        let statuses: Vec<(&str, CompareStatus)> = entries
+            .iter()
+            .map(|e| (e.path.as_str(), e.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("lib.rs", CompareStatus::Unavailable),
+                ("main.rs", CompareStatus::Modified)
+            ]
+        );
+    }
+}
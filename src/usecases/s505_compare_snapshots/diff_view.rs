@@ -0,0 +1,581 @@
+use eframe::egui;
+use egui::text::{LayoutJob, TextFormat};
+use egui::Color32;
+use rusqlite::Connection;
+use std::collections::HashSet;
+
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n003_snapshot_file::source::load_file_bytes;
+use crate::shared::diff::{diff, intraline_ranges, Edit, Ranges};
+use crate::shared::text::is_binary;
+
+/// Lines longer than this are not diffed character by character
+const MAX_INTRALINE_CHARS: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffMode {
+    Unified,
+    SideBySide,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineKind {
+    Context,
+    Removed,
+    Added,
+}
+
+/// Run of equal lines or a changed region (removed lines replaced by added lines)
+enum Block {
+    Equal {
+        old_start: usize,
+        new_start: usize,
+        len: usize,
+    },
+    Change {
+        old_start: usize,
+        old_len: usize,
+        new_start: usize,
+        new_len: usize,
+        /// Intra-line ranges for paired lines (index i pairs old_start+i with new_start+i)
+        pairs: Vec<(Ranges, Ranges)>,
+    },
+}
+
+#[derive(Clone)]
+struct DiffLine {
+    /// 1-based line number in its own version
+    number: usize,
+    text: String,
+    kind: LineKind,
+    ranges: Ranges,
+}
+
+enum Row {
+    Unified {
+        old_no: Option<usize>,
+        new_no: Option<usize>,
+        line: DiffLine,
+    },
+    Pair {
+        left: Option<DiffLine>,
+        right: Option<DiffLine>,
+    },
+    Collapsed {
+        block: usize,
+        hidden: usize,
+    },
+}
+
+pub struct DiffViewState {
+    pub path: String,
+    pub base_label: String,
+    pub target_label: String,
+    pub mode: DiffMode,
+    pub context_lines: usize,
+    old_lines: Vec<String>,
+    new_lines: Vec<String>,
+    blocks: Vec<Block>,
+    /// Equal blocks the user expanded
+    expanded: HashSet<usize>,
+    rows: Vec<Row>,
+    /// Row index where each hunk starts
+    hunk_rows: Vec<usize>,
+    current_hunk: usize,
+    scroll_pending: bool,
+    rows_dirty: bool,
+    pub added: usize,
+    pub removed: usize,
+    pub status: String,
+}
+
+impl Default for DiffViewState {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            base_label: String::new(),
+            target_label: String::new(),
+            mode: DiffMode::SideBySide,
+            context_lines: 3,
+            old_lines: Vec::new(),
+            new_lines: Vec::new(),
+            blocks: Vec::new(),
+            expanded: HashSet::new(),
+            rows: Vec::new(),
+            hunk_rows: Vec::new(),
+            current_hunk: 0,
+            scroll_pending: false,
+            rows_dirty: false,
+            added: 0,
+            removed: 0,
+            status: String::new(),
+        }
+    }
+}
+
+impl DiffViewState {
+    /// Load both versions (None = file absent in that snapshot) and compute the diff
+    pub fn open(
+        &mut self,
+        conn: &Connection,
+        base_file_id: Option<i64>,
+        target_file_id: Option<i64>,
+    ) {
+        self.old_lines.clear();
+        self.new_lines.clear();
+        self.blocks.clear();
+        self.expanded.clear();
+        self.current_hunk = 0;
+        self.status.clear();
+
+        let old = base_file_id.map(|id| load_version(conn, id)).transpose();
+        let new = target_file_id.map(|id| load_version(conn, id)).transpose();
+        let (old, new) = match (old, new) {
+            (Ok(old), Ok(new)) => (old, new),
+            (Err(e), _) | (_, Err(e)) => {
+                self.status = format!("Error: {}", e);
+                self.rows_dirty = true;
+                return;
+            }
+        };
+
+        self.path = new
+            .as_ref()
+            .or(old.as_ref())
+            .map(|v| v.path.clone())
+            .unwrap_or_default();
+        self.base_label = old
+            .as_ref()
+            .map(|v| v.origin.clone())
+            .unwrap_or_else(|| "absent".to_string());
+        self.target_label = new
+            .as_ref()
+            .map(|v| v.origin.clone())
+            .unwrap_or_else(|| "absent".to_string());
+
+        if old.as_ref().is_some_and(|v| v.binary) || new.as_ref().is_some_and(|v| v.binary) {
+            self.status = "Binary files differ".to_string();
+            self.rows_dirty = true;
+            return;
+        }
+
+        self.old_lines = old.map(|v| v.lines).unwrap_or_default();
+        self.new_lines = new.map(|v| v.lines).unwrap_or_default();
+        self.blocks = build_blocks(&self.old_lines, &self.new_lines);
+        self.removed = 0;
+        self.added = 0;
+        for block in &self.blocks {
+            if let Block::Change {
+                old_len, new_len, ..
+            } = block
+            {
+                self.removed += old_len;
+                self.added += new_len;
+            }
+        }
+        self.rows_dirty = true;
+        self.scroll_pending = true;
+    }
+
+    fn rebuild_rows(&mut self) {
+        self.rows.clear();
+        self.hunk_rows.clear();
+        let last_block = self.blocks.len().saturating_sub(1);
+
+        for (bi, block) in self.blocks.iter().enumerate() {
+            match block {
+                Block::Equal {
+                    old_start,
+                    new_start,
+                    len,
+                } => {
+                    let head = if bi == 0 {
+                        0
+                    } else {
+                        self.context_lines.min(*len)
+                    };
+                    let tail = if bi == last_block {
+                        0
+                    } else {
+                        self.context_lines.min(len - head)
+                    };
+                    // Hiding a single line saves nothing
+                    let collapse = !self.expanded.contains(&bi) && len - head - tail > 1;
+                    for i in 0..*len {
+                        if collapse && i == head {
+                            self.rows.push(Row::Collapsed {
+                                block: bi,
+                                hidden: len - head - tail,
+                            });
+                        }
+                        if collapse && i >= head && i < len - tail {
+                            continue;
+                        }
+                        let (o, n) = (old_start + i, new_start + i);
+                        let context = |number: usize, text: &str| DiffLine {
+                            number,
+                            text: text.to_string(),
+                            kind: LineKind::Context,
+                            ranges: Vec::new(),
+                        };
+                        self.rows.push(match self.mode {
+                            DiffMode::Unified => Row::Unified {
+                                old_no: Some(o + 1),
+                                new_no: Some(n + 1),
+                                line: context(o + 1, &self.old_lines[o]),
+                            },
+                            DiffMode::SideBySide => Row::Pair {
+                                left: Some(context(o + 1, &self.old_lines[o])),
+                                right: Some(context(n + 1, &self.new_lines[n])),
+                            },
+                        });
+                    }
+                }
+                Block::Change {
+                    old_start,
+                    old_len,
+                    new_start,
+                    new_len,
+                    pairs,
+                } => {
+                    self.hunk_rows.push(self.rows.len());
+                    let removed = |i: usize| DiffLine {
+                        number: old_start + i + 1,
+                        text: self.old_lines[old_start + i].clone(),
+                        kind: LineKind::Removed,
+                        ranges: pairs.get(i).map(|p| p.0.clone()).unwrap_or_default(),
+                    };
+                    let added = |i: usize| DiffLine {
+                        number: new_start + i + 1,
+                        text: self.new_lines[new_start + i].clone(),
+                        kind: LineKind::Added,
+                        ranges: pairs.get(i).map(|p| p.1.clone()).unwrap_or_default(),
+                    };
+                    match self.mode {
+                        DiffMode::Unified => {
+                            for i in 0..*old_len {
+                                self.rows.push(Row::Unified {
+                                    old_no: Some(old_start + i + 1),
+                                    new_no: None,
+                                    line: removed(i),
+                                });
+                            }
+                            for i in 0..*new_len {
+                                self.rows.push(Row::Unified {
+                                    old_no: None,
+                                    new_no: Some(new_start + i + 1),
+                                    line: added(i),
+                                });
+                            }
+                        }
+                        DiffMode::SideBySide => {
+                            for i in 0..(*old_len).max(*new_len) {
+                                self.rows.push(Row::Pair {
+                                    left: (i < *old_len).then(|| removed(i)),
+                                    right: (i < *new_len).then(|| added(i)),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        self.rows_dirty = false;
+    }
+}
+
+struct Version {
+    path: String,
+    origin: String,
+    lines: Vec<String>,
+    binary: bool,
+}
+
+/// Content of a file as recorded by its snapshot (never the current working tree)
+fn load_version(conn: &Connection, file_id: i64) -> Result<Version, String> {
+    let file =
+        file_repo::get_by_id(conn, file_id).map_err(|e| format!("Error loading file: {}", e))?;
+    let loaded = load_file_bytes(conn, &file, false)?;
+    let binary = is_binary(&loaded.bytes);
+    let lines = if binary {
+        Vec::new()
+    } else {
+        String::from_utf8_lossy(&loaded.bytes)
+            .lines()
+            .map(|l| l.to_string())
+            .collect()
+    };
+    Ok(Version {
+        path: file.path,
+        origin: loaded.origin.label(),
+        lines,
+        binary,
+    })
+}
+
+fn build_blocks(old: &[String], new: &[String]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let (mut oi, mut ni) = (0, 0);
+    let mut equal_len = 0;
+    let (mut del, mut ins) = (0, 0);
+
+    let flush_change = |blocks: &mut Vec<Block>, oi: usize, ni: usize, del: usize, ins: usize| {
+        if del == 0 && ins == 0 {
+            return;
+        }
+        let (old_start, new_start) = (oi - del, ni - ins);
+        let pairs = (0..del.min(ins))
+            .map(|i| {
+                let (a, b) = (&old[old_start + i], &new[new_start + i]);
+                if a.len() > MAX_INTRALINE_CHARS || b.len() > MAX_INTRALINE_CHARS {
+                    (Vec::new(), Vec::new())
+                } else {
+                    intraline_ranges(a, b)
+                }
+            })
+            .collect();
+        blocks.push(Block::Change {
+            old_start,
+            old_len: del,
+            new_start,
+            new_len: ins,
+            pairs,
+        });
+    };
+
+    for edit in diff(old, new) {
+        match edit {
+            Edit::Equal(..) => {
+                flush_change(&mut blocks, oi, ni, del, ins);
+                del = 0;
+                ins = 0;
+                equal_len += 1;
+                oi += 1;
+                ni += 1;
+            }
+            Edit::Delete(_) | Edit::Insert(_) => {
+                if equal_len > 0 {
+                    blocks.push(Block::Equal {
+                        old_start: oi - equal_len,
+                        new_start: ni - equal_len,
+                        len: equal_len,
+                    });
+                    equal_len = 0;
+                }
+                if matches!(edit, Edit::Delete(_)) {
+                    del += 1;
+                    oi += 1;
+                } else {
+                    ins += 1;
+                    ni += 1;
+                }
+            }
+        }
+    }
+    flush_change(&mut blocks, oi, ni, del, ins);
+    if equal_len > 0 {
+        blocks.push(Block::Equal {
+            old_start: oi - equal_len,
+            new_start: ni - equal_len,
+            len: equal_len,
+        });
+    }
+    blocks
+}
+
+pub fn ui_diff(ui: &mut egui::Ui, state: &mut DiffViewState) {
+    if state.path.is_empty() && state.status.is_empty() {
+        ui.label(
+            egui::RichText::new("Open a changed file from Compare Snapshots to see its diff")
+                .weak(),
+        );
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(&state.path).strong());
+        ui.label(
+            egui::RichText::new(format!("({} → {})", state.base_label, state.target_label)).weak(),
+        );
+        ui.colored_label(Color32::LIGHT_GREEN, format!("+{}", state.added));
+        ui.colored_label(Color32::LIGHT_RED, format!("-{}", state.removed));
+    });
+
+    ui.horizontal(|ui| {
+        let mode_before = state.mode;
+        ui.selectable_value(&mut state.mode, DiffMode::SideBySide, "Side by side");
+        ui.selectable_value(&mut state.mode, DiffMode::Unified, "Unified");
+        ui.label("Context:");
+        let context = ui.add(egui::DragValue::new(&mut state.context_lines).range(0..=50));
+        if state.mode != mode_before || context.changed() {
+            state.rows_dirty = true;
+        }
+        if ui.button("Expand all").clicked() {
+            state.expanded = (0..state.blocks.len()).collect();
+            state.rows_dirty = true;
+        }
+
+        ui.separator();
+        let hunks = state.hunk_rows.len();
+        if ui
+            .add_enabled(state.current_hunk > 0, egui::Button::new("◀ Prev hunk"))
+            .clicked()
+        {
+            state.current_hunk -= 1;
+            state.scroll_pending = true;
+        }
+        if hunks > 0 {
+            ui.label(format!("Hunk {} of {}", state.current_hunk + 1, hunks));
+        } else {
+            ui.label("No changes");
+        }
+        if ui
+            .add_enabled(
+                state.current_hunk + 1 < hunks,
+                egui::Button::new("Next hunk ▶"),
+            )
+            .clicked()
+        {
+            state.current_hunk += 1;
+            state.scroll_pending = true;
+        }
+    });
+
+    if !state.status.is_empty() {
+        ui.colored_label(Color32::LIGHT_RED, &state.status);
+    }
+
+    if state.rows_dirty {
+        state.rebuild_rows();
+        state.current_hunk = state
+            .current_hunk
+            .min(state.hunk_rows.len().saturating_sub(1));
+    }
+
+    ui.add_space(6.0);
+
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    let mut scroll = egui::ScrollArea::both().auto_shrink([false; 2]);
+    if state.scroll_pending {
+        if let Some(&row) = state.hunk_rows.get(state.current_hunk) {
+            let spacing = ui.spacing().item_spacing.y;
+            let offset = (row as f32 - 3.0).max(0.0) * (row_height + spacing);
+            scroll = scroll.vertical_scroll_offset(offset);
+        }
+        state.scroll_pending = false;
+    }
+
+    let digits = state
+        .old_lines
+        .len()
+        .max(state.new_lines.len())
+        .max(1)
+        .to_string()
+        .len();
+    let mut expand = None;
+    scroll.show_rows(ui, row_height, state.rows.len(), |ui, range| {
+        for row in &state.rows[range] {
+            match row {
+                Row::Unified {
+                    old_no,
+                    new_no,
+                    line,
+                } => {
+                    let mut job = LayoutJob::default();
+                    let number = |n: &Option<usize>| n.map(|n| n.to_string()).unwrap_or_default();
+                    let gutter =
+                        format!("{:>w$} {:>w$} ", number(old_no), number(new_no), w = digits);
+                    append_line(ui, &mut job, &gutter, line);
+                    ui.add(egui::Label::new(job).extend());
+                }
+                Row::Pair { left, right } => {
+                    let half = (ui.available_width() / 2.0).max(100.0);
+                    ui.horizontal(|ui| {
+                        for side in [left, right] {
+                            let mut job = LayoutJob::default();
+                            match side {
+                                Some(line) => {
+                                    let gutter = format!("{:>w$} ", line.number, w = digits);
+                                    append_line(ui, &mut job, &gutter, line);
+                                }
+                                None => job.append("", 0.0, TextFormat::default()),
+                            }
+                            ui.allocate_ui_with_layout(
+                                egui::vec2(half, row_height),
+                                egui::Layout::left_to_right(egui::Align::Center),
+                                |ui| {
+                                    ui.set_min_width(half);
+                                    ui.add(egui::Label::new(job).truncate());
+                                },
+                            );
+                        }
+                    });
+                }
+                Row::Collapsed { block, hidden } => {
+                    let text = egui::RichText::new(format!(
+                        "⋯ {} unchanged lines (click to expand)",
+                        hidden
+                    ))
+                    .monospace()
+                    .weak();
+                    if ui
+                        .add(egui::Label::new(text).sense(egui::Sense::click()))
+                        .clicked()
+                    {
+                        expand = Some(*block);
+                    }
+                }
+            }
+        }
+    });
+
+    if let Some(block) = expand {
+        state.expanded.insert(block);
+        state.rows_dirty = true;
+    }
+}
+
+/// Gutter plus line text with change background and stronger intra-line highlights
+fn append_line(ui: &egui::Ui, job: &mut LayoutJob, gutter: &str, line: &DiffLine) {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let (sign, background, strong) = match line.kind {
+        LineKind::Context => (' ', Color32::TRANSPARENT, Color32::TRANSPARENT),
+        LineKind::Removed => (
+            '-',
+            Color32::from_rgba_unmultiplied(220, 60, 60, 40),
+            Color32::from_rgba_unmultiplied(220, 60, 60, 110),
+        ),
+        LineKind::Added => (
+            '+',
+            Color32::from_rgba_unmultiplied(60, 180, 60, 40),
+            Color32::from_rgba_unmultiplied(60, 180, 60, 110),
+        ),
+    };
+    let format = |background: Color32| TextFormat {
+        font_id: font_id.clone(),
+        color: ui.visuals().text_color(),
+        background,
+        ..Default::default()
+    };
+
+    job.append(
+        &format!("{}{} ", gutter, sign),
+        0.0,
+        TextFormat {
+            font_id: font_id.clone(),
+            color: ui.visuals().weak_text_color(),
+            ..Default::default()
+        },
+    );
+    let mut pos = 0;
+    for &(start, end) in &line.ranges {
+        if start > pos {
+            job.append(&line.text[pos..start], 0.0, format(background));
+        }
+        job.append(&line.text[start..end], 0.0, format(strong));
+        pos = end;
+    }
+    if pos < line.text.len() || line.text.is_empty() {
+        job.append(&line.text[pos..], 0.0, format(background));
+    }
+}
@@ -0,0 +1,263 @@
+pub mod compare;
+pub mod diff_view;
+
+use eframe::egui;
+use rusqlite::Connection;
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::usecases::s515_export::file_stem;
+use crate::usecases::s517_report::markdown::{self, PrReport};
+use crate::usecases::s517_report::write_markdown_report;
+use compare::{compare_snapshots, CompareEntry, CompareStatus};
+
+pub use diff_view::{ui_diff, DiffViewState};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PickTarget {
+    Base,
+    Target,
+}
+
+#[derive(Default)]
+pub struct CompareState {
+    pub snapshot_picker: SnapshotPickerState,
+    picking: Option<PickTarget>,
+    pub base_id: Option<i64>,
+    pub base_name: String,
+    pub target_id: Option<i64>,
+    pub target_name: String,
+    pub entries: Vec<CompareEntry>,
+    pub show_unchanged: bool,
+    pub filter: String,
+    pub status: String,
+}
+
+impl CompareState {
+    /// Compare `base` with `target` right away, as if both were picked and Compare pressed
+    pub fn open(&mut self, conn: &Connection, base: i64, target: i64) {
+        let name = |id: i64| {
+            snapshot_repo::get_by_id(conn, id)
+                .map(|s| s.name)
+                .unwrap_or_default()
+        };
+        self.base_id = Some(base);
+        self.base_name = name(base);
+        self.target_id = Some(target);
+        self.target_name = name(target);
+        self.run(conn);
+    }
+
+    fn run(&mut self, conn: &Connection) {
+        let (Some(base), Some(target)) = (self.base_id, self.target_id) else {
+            return;
+        };
+        match compare_snapshots(conn, base, target) {
+            Ok(entries) => {
+                let count = |status| entries.iter().filter(|e| e.status == status).count();
+                let changed = entries.len()
+                    - count(CompareStatus::Unchanged)
+                    - count(CompareStatus::Unavailable);
+                self.status = format!("Completed: {} of {} files changed", changed, entries.len());
+                let unavailable = count(CompareStatus::Unavailable);
+                if unavailable > 0 {
+                    self.status.push_str(&format!(
+                        ", {} could not be compared (no stored content)",
+                        unavailable
+                    ));
+                }
+                self.entries = entries;
+            }
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.entries.clear();
+            }
+        }
+    }
+}
+
+/// "Copy Markdown" / "Save Markdown..." of the pull request report; returns a status message
+fn markdown_buttons(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    base: i64,
+    target: i64,
+    target_name: &str,
+) -> Option<String> {
+    let copy = ui
+        .button("Copy Markdown")
+        .on_hover_text("PR report: changes, new violations, TODOs, unsafe code, dependencies");
+    let save = ui.button("Save Markdown...");
+    if copy.clicked() {
+        return Some(match PrReport::load(conn, base, target) {
+            Ok(report) => {
+                ui.output_mut(|o| o.copied_text = markdown::render(&report));
+                "Markdown report copied to the clipboard".to_string()
+            }
+            Err(e) => format!("Error: {}", e),
+        });
+    }
+    if save.clicked() {
+        let path = rfd::FileDialog::new()
+            .set_file_name(format!("{}.md", file_stem(target_name)))
+            .add_filter("Markdown", &["md"])
+            .save_file()?;
+        return Some(match write_markdown_report(conn, base, target, &path) {
+            Ok(()) => format!("Wrote Markdown report to {}", path.display()),
+            Err(e) => format!("Error: {}", e),
+        });
+    }
+    None
+}
+
+/// Renders the Compare Snapshots tab.
+/// Returns `(base_file_id, target_file_id)` when a file is opened for diffing.
+pub fn ui_compare(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut CompareState,
+) -> Option<(Option<i64>, Option<i64>)> {
+    let mut opened = None;
+
+    ui.heading("Compare Snapshots");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        let name = snapshot_repo::get_by_id(conn, snapshot_id)
+            .map(|s| s.name)
+            .unwrap_or_default();
+        match state.picking.take() {
+            Some(PickTarget::Base) => {
+                state.base_id = Some(snapshot_id);
+                state.base_name = name;
+            }
+            Some(PickTarget::Target) => {
+                state.target_id = Some(snapshot_id);
+                state.target_name = name;
+            }
+            None => {}
+        }
+        state.entries.clear();
+    }
+
+    for (label, target) in [("Base:", PickTarget::Base), ("Target:", PickTarget::Target)] {
+        ui.horizontal(|ui| {
+            ui.label(label);
+            let name = match target {
+                PickTarget::Base => state.base_id.map(|_| &state.base_name),
+                PickTarget::Target => state.target_id.map(|_| &state.target_name),
+            };
+            match name {
+                Some(name) => ui.label(egui::RichText::new(name).strong()),
+                None => ui.label(egui::RichText::new("None").weak()),
+            };
+            if ui.button("Select...").clicked() {
+                state.picking = Some(target);
+                state.snapshot_picker.open();
+            }
+        });
+    }
+
+    ui.add_space(6.0);
+
+    ui.horizontal(|ui| {
+        let ready = state.base_id.is_some() && state.target_id.is_some();
+        if ui
+            .add_enabled(ready, egui::Button::new("Compare"))
+            .clicked()
+        {
+            state.run(conn);
+        }
+        ui.checkbox(&mut state.show_unchanged, "Show unchanged");
+        ui.label("Filter:");
+        ui.text_edit_singleline(&mut state.filter);
+    });
+
+    if !state.status.is_empty() {
+        let color = if state.status.starts_with("Error") {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        ui.colored_label(color, &state.status);
+    }
+
+    if state.entries.is_empty() {
+        return None;
+    }
+
+    let count = |status: CompareStatus| state.entries.iter().filter(|e| e.status == status).count();
+    ui.horizontal(|ui| {
+        ui.colored_label(
+            egui::Color32::LIGHT_GREEN,
+            format!("Added: {}", count(CompareStatus::Added)),
+        );
+        ui.colored_label(
+            egui::Color32::LIGHT_RED,
+            format!("Removed: {}", count(CompareStatus::Removed)),
+        );
+        ui.colored_label(
+            egui::Color32::YELLOW,
+            format!("Modified: {}", count(CompareStatus::Modified)),
+        );
+        ui.label(format!("Unchanged: {}", count(CompareStatus::Unchanged)));
+        let unavailable = count(CompareStatus::Unavailable);
+        if unavailable > 0 {
+            ui.colored_label(egui::Color32::GRAY, format!("Unavailable: {}", unavailable))
+                .on_hover_text("Same size, but no stored content or git blob to compare");
+        }
+        ui.separator();
+        if let (Some(base), Some(target)) = (state.base_id, state.target_id) {
+            if let Some(status) = markdown_buttons(ui, conn, base, target, &state.target_name) {
+                state.status = status;
+            }
+        }
+    });
+
+    ui.add_space(6.0);
+
+    let filter = state.filter.to_lowercase();
+    let visible: Vec<&CompareEntry> = state
+        .entries
+        .iter()
+        .filter(|e| state.show_unchanged || e.status != CompareStatus::Unchanged)
+        .filter(|e| filter.is_empty() || e.path.to_lowercase().contains(&filter))
+        .collect();
+
+    let row_height = ui.text_style_height(&egui::TextStyle::Body);
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show_rows(ui, row_height, visible.len(), |ui, range| {
+            for entry in &visible[range] {
+                ui.horizontal(|ui| {
+                    let color = match entry.status {
+                        CompareStatus::Added => egui::Color32::LIGHT_GREEN,
+                        CompareStatus::Removed => egui::Color32::LIGHT_RED,
+                        CompareStatus::Modified => egui::Color32::YELLOW,
+                        CompareStatus::Unchanged => ui.visuals().weak_text_color(),
+                        CompareStatus::Unavailable => egui::Color32::GRAY,
+                    };
+                    ui.add_sized(
+                        [80.0, row_height],
+                        egui::Label::new(egui::RichText::new(entry.status.label()).color(color)),
+                    );
+                    if ui.link(&entry.path).clicked() {
+                        opened = Some((entry.base_file_id, entry.target_file_id));
+                    }
+                    let size = |s: Option<i64>| {
+                        s.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string())
+                    };
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{} → {} B",
+                            size(entry.base_size),
+                            size(entry.target_size)
+                        ))
+                        .weak(),
+                    );
+                });
+            }
+        });
+
+    opened
+}
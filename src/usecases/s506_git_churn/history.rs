@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::shared::git;
+
+/// Separators used in the `git log` format string
+const RECORD_SEP: char = '\u{1e}';
+const FIELD_SEP: char = '\u{1f}';
+
+#[derive(Debug, Default, Clone)]
+pub struct PathHistory {
+    /// Commits within the window
+    pub commits: i64,
+    pub authors: HashSet<String>,
+    pub lines_added: i64,
+    pub lines_removed: i64,
+    /// Latest commit date touching the path (whole history), RFC 3339 in UTC
+    pub last_modified: Option<String>,
+}
+
+/// Per-path history of the repository at `root` up to `revision`, paths relative to `root`
+pub fn collect_history(
+    root: &Path,
+    revision: &str,
+    since: DateTime<Utc>,
+) -> Result<HashMap<String, PathHistory>, String> {
+    let out = git::run(
+        root,
+        &[
+            "-c",
+            "core.quotepath=off",
+            "log",
+            "--numstat",
+            "--no-renames",
+            "--relative",
+            "--format=%x1e%H%x1f%ae%x1f%aI",
+            revision,
+            "--",
+        ],
+    )?;
+    Ok(parse_log(&String::from_utf8_lossy(&out), since))
+}
+
+/// Parse `git log --numstat` output produced with the format above (newest first)
+pub fn parse_log(output: &str, since: DateTime<Utc>) -> HashMap<String, PathHistory> {
+    let mut history: HashMap<String, PathHistory> = HashMap::new();
+
+    for record in output.split(RECORD_SEP).filter(|r| !r.trim().is_empty()) {
+        let mut lines = record.lines();
+        let Some(header) = lines.next() else { continue };
+        let fields: Vec<&str> = header.split(FIELD_SEP).collect();
+        let (email, date) = match fields.as_slice() {
+            [_hash, email, date] => (email.to_lowercase(), date.trim().to_string()),
+            _ => continue,
+        };
+        // Normalized to UTC so stored dates compare correctly as strings
+        let Ok(date) = DateTime::parse_from_rfc3339(&date).map(|d| d.with_timezone(&Utc)) else {
+            continue;
+        };
+        let in_window = date >= since;
+        let date = date.to_rfc3339();
+
+        for line in lines.filter(|l| !l.trim().is_empty()) {
+            let mut parts = line.splitn(3, '\t');
+            let (Some(added), Some(removed), Some(path)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            let entry = history.entry(path.to_string()).or_default();
+            if entry.last_modified.is_none() {
+                entry.last_modified = Some(date.clone());
+            }
+            if in_window {
+                entry.commits += 1;
+                entry.authors.insert(email.clone());
+                // Binary files report "-" for both counts
+                entry.lines_added += added.parse::<i64>().unwrap_or(0);
+                entry.lines_removed += removed.parse::<i64>().unwrap_or(0);
+            }
+        }
+    }
+    history
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_limits_counts_but_not_last_modified() {
+        let log = "\u{1e}bbb\u{1f}Dev@x.io\u{1f}2024-05-10T10:00:00+02:00\n\n3\t1\tsrc/main.rs\n-\t-\tlogo.png\n\
+                   \u{1e}aaa\u{1f}dev@x.io\u{1f}2023-01-01T00:00:00+00:00\n\n10\t0\tsrc/main.rs\n";
+        let since = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let history = parse_log(log, since);
+
+        let main = &history["src/main.rs"];
+        assert_eq!(main.commits, 1);
+        assert_eq!((main.lines_added, main.lines_removed), (3, 1));
+        assert_eq!(main.authors.len(), 1);
+        assert_eq!(
+            main.last_modified.as_deref(),
+            Some("2024-05-10T08:00:00+00:00")
+        );
+        assert_eq!(history["logo.png"].lines_added, 0);
+    }
+}
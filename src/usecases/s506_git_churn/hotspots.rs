@@ -0,0 +1,161 @@
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n007_file_churn::model::FileChurn;
+use crate::domain::n007_file_churn::repository as churn_repo;
+
+/// A file or artifact ranked by churn × size
+#[derive(Debug, Clone)]
+pub struct Hotspot {
+    /// File path, or `artifact_id artifact_name` for roll-ups
+    pub name: String,
+    /// Set for file rows (used to open the preview)
+    pub file_id: Option<i64>,
+    pub files: usize,
+    pub commits: i64,
+    /// Distinct authors (files); highest per-file count (artifacts)
+    pub authors: i64,
+    pub lines_added: i64,
+    pub lines_removed: i64,
+    pub loc: i64,
+    pub last_modified: Option<String>,
+    /// 0..=100 for files; sum of file scores for artifacts
+    pub score: f64,
+}
+
+/// Files of a snapshot with computed churn, highest score first
+pub fn rank_files(conn: &Connection, snapshot_id: i64) -> Result<Vec<Hotspot>, String> {
+    let churn = churn_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load churn: {}", e))?;
+    let files: HashMap<i64, String> = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?
+        .into_iter()
+        .map(|f| (f.id, f.path))
+        .collect();
+    Ok(score_files(churn, &files))
+}
+
+/// Hotspots of the churn rows whose file is in `paths`, highest score first.
+///
+/// Score = normalized commits × normalized LOC, so only files that are both
+/// large and frequently changed rank high.
+fn score_files(churn: Vec<FileChurn>, paths: &HashMap<i64, String>) -> Vec<Hotspot> {
+    let max_commits = churn.iter().map(|c| c.commits).max().unwrap_or(0).max(1) as f64;
+    let max_loc = churn.iter().map(|c| c.loc).max().unwrap_or(0).max(1) as f64;
+
+    let mut out: Vec<Hotspot> = churn
+        .into_iter()
+        .filter_map(|c| {
+            let path = paths.get(&c.file_id)?.clone();
+            Some(Hotspot {
+                name: path,
+                file_id: Some(c.file_id),
+                files: 1,
+                commits: c.commits,
+                authors: c.authors,
+                lines_added: c.lines_added,
+                lines_removed: c.lines_removed,
+                loc: c.loc,
+                last_modified: c.last_modified,
+                score: 100.0 * (c.commits as f64 / max_commits) * (c.loc as f64 / max_loc),
+            })
+        })
+        .collect();
+    sort_by_score(&mut out);
+    out
+}
+
+/// File hotspots rolled up per `artifact_id` (files without an artifact are skipped)
+pub fn rank_artifacts(conn: &Connection, snapshot_id: i64) -> Result<Vec<Hotspot>, String> {
+    let file_hotspots = rank_files(conn, snapshot_id)?;
+    let artifacts: HashMap<i64, String> = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?
+        .into_iter()
+        .filter_map(|f| {
+            let id = f.artifact_id?;
+            let name = match f.artifact_name {
+                Some(name) => format!("{} {}", id, name),
+                None => id,
+            };
+            Some((f.id, name))
+        })
+        .collect();
+
+    let mut groups: BTreeMap<String, Hotspot> = BTreeMap::new();
+    for h in file_hotspots {
+        let Some(artifact) = h.file_id.and_then(|id| artifacts.get(&id)) else {
+            continue;
+        };
+        let g = groups.entry(artifact.clone()).or_insert_with(|| Hotspot {
+            name: artifact.clone(),
+            file_id: None,
+            files: 0,
+            commits: 0,
+            authors: 0,
+            lines_added: 0,
+            lines_removed: 0,
+            loc: 0,
+            last_modified: None,
+            score: 0.0,
+        });
+        g.files += 1;
+        g.commits += h.commits;
+        g.authors = g.authors.max(h.authors);
+        g.lines_added += h.lines_added;
+        g.lines_removed += h.lines_removed;
+        g.loc += h.loc;
+        g.score += h.score;
+        // Dates are stored as UTC RFC 3339, so string order is chronological
+        if h.last_modified > g.last_modified {
+            g.last_modified = h.last_modified;
+        }
+    }
+
+    let mut out: Vec<Hotspot> = groups.into_values().collect();
+    sort_by_score(&mut out);
+    Ok(out)
+}
+
+fn sort_by_score(items: &mut [Hotspot]) {
+    items.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.commits.cmp(&a.commits))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn churn(file_id: i64, commits: i64, loc: i64) -> FileChurn {
+        FileChurn::new(file_id, 1, commits, 1, 0, 0, None, loc, 180, String::new())
+    }
+
+    #[test]
+    fn scores_commits_times_size() {
+        let paths: HashMap<i64, String> = (1..=4).map(|id| (id, format!("f{}.rs", id))).collect();
+        // File 9 is not in the snapshot and is left out
+        let rows = vec![
+            churn(1, 5, 200),
+            churn(2, 10, 100),
+            churn(3, 2, 50),
+            churn(4, 0, 150),
+            churn(9, 1, 10),
+        ];
+
+        let ranked = score_files(rows, &paths);
+        let scores: Vec<(&str, f64)> = ranked.iter().map(|h| (h.name.as_str(), h.score)).collect();
+        // Equal scores rank the file with more commits first
+        assert_eq!(
+            scores,
+            [
+                ("f2.rs", 50.0),
+                ("f1.rs", 50.0),
+                ("f3.rs", 5.0),
+                ("f4.rs", 0.0)
+            ]
+        );
+    }
+}
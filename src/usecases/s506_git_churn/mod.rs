@@ -0,0 +1,308 @@
+pub mod history;
+pub mod hotspots;
+
+use chrono::{Duration, Utc};
+use eframe::egui;
+use rusqlite::Connection;
+use std::path::Path;
+
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n006_file_content::repository as content_repo;
+use crate::domain::n007_file_churn::model::FileChurn;
+use crate::domain::n007_file_churn::repository as churn_repo;
+use crate::shared::git;
+use history::collect_history;
+use hotspots::{rank_artifacts, rank_files, Hotspot};
+
+pub const DEFAULT_WINDOW_DAYS: i64 = 180;
+
+/// Compute churn for every file of a snapshot from the project's git history up to
+/// the snapshot's revision (HEAD for snapshots without one).
+/// Returns the number of files that had commits within the window.
+pub fn compute_churn(
+    conn: &Connection,
+    snapshot_id: i64,
+    window_days: i64,
+) -> Result<usize, String> {
+    let snapshot = snapshot_repo::get_by_id(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load snapshot: {}", e))?;
+    let project = project_repo::get_by_id(conn, snapshot.project_id)
+        .map_err(|e| format!("Failed to load project: {}", e))?;
+    let root = Path::new(&project.root_path);
+    if !git::is_repository(root) {
+        return Err(format!("{} is not a git repository", project.root_path));
+    }
+
+    // The window ends at the scanned commit, so older snapshots get their own history
+    let (revision, until) = match &snapshot.git_revision {
+        Some(revision) => (revision.as_str(), git::commit_date(root, revision)?),
+        None => ("HEAD", Utc::now()),
+    };
+    let history = collect_history(root, revision, until - Duration::days(window_days))?;
+    let files = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?;
+    let computed_at = Utc::now().to_rfc3339();
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    churn_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete churn: {}", e))?;
+
+    let mut touched = 0;
+    for file in files.iter().filter(|f| !f.is_directory) {
+        // Snapshot paths use the platform separator, git always uses '/'
+        let h = history
+            .get(&file.path.replace('\\', "/"))
+            .cloned()
+            .unwrap_or_default();
+        if h.commits > 0 {
+            touched += 1;
+        }
+        let loc = content_repo::get_content(conn, file.id)
+            .ok()
+            .flatten()
+            .map(|text| text.lines().filter(|l| !l.trim().is_empty()).count() as i64)
+            .unwrap_or(0);
+        let churn = FileChurn::new(
+            file.id,
+            snapshot_id,
+            h.commits,
+            h.authors.len() as i64,
+            h.lines_added,
+            h.lines_removed,
+            h.last_modified,
+            loc,
+            window_days,
+            computed_at.clone(),
+        );
+        churn_repo::upsert(conn, &churn).map_err(|e| format!("Failed to store churn: {}", e))?;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit churn: {}", e))?;
+    Ok(touched)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HotspotView {
+    Files,
+    Artifacts,
+}
+
+pub struct ChurnState {
+    pub snapshot_picker: SnapshotPickerState,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    pub window_days: i64,
+    pub view: HotspotView,
+    pub rows: Vec<Hotspot>,
+    pub status: String,
+}
+
+impl Default for ChurnState {
+    fn default() -> Self {
+        Self {
+            snapshot_picker: SnapshotPickerState::default(),
+            snapshot_id: None,
+            snapshot_name: String::new(),
+            window_days: DEFAULT_WINDOW_DAYS,
+            view: HotspotView::Files,
+            rows: Vec::new(),
+            status: String::new(),
+        }
+    }
+}
+
+impl ChurnState {
+    fn reload(&mut self, conn: &Connection) {
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+        let result = match self.view {
+            HotspotView::Files => rank_files(conn, snapshot_id),
+            HotspotView::Artifacts => rank_artifacts(conn, snapshot_id),
+        };
+        match result {
+            Ok(rows) => self.rows = rows,
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.rows.clear();
+            }
+        }
+    }
+}
+
+/// Renders the Hotspots tab. Returns a file id when a file row is opened.
+pub fn ui_churn(ui: &mut egui::Ui, conn: &Connection, state: &mut ChurnState) -> Option<i64> {
+    ui.heading("Churn & Hotspots");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.snapshot_id = Some(snapshot_id);
+        if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
+            state.snapshot_name = snapshot.name;
+        }
+        state.status.clear();
+        state.reload(conn);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Selected Snapshot:");
+        if state.snapshot_id.is_some() {
+            ui.label(egui::RichText::new(&state.snapshot_name).strong());
+        } else {
+            ui.label(egui::RichText::new("None").weak());
+        }
+        if ui.button("Select...").clicked() {
+            state.snapshot_picker.open();
+        }
+    });
+
+    ui.add_space(8.0);
+
+    ui.horizontal(|ui| {
+        ui.label("Window (days):");
+        ui.add(egui::DragValue::new(&mut state.window_days).range(1..=3650));
+        if ui
+            .add_enabled(
+                state.snapshot_id.is_some(),
+                egui::Button::new("Compute churn"),
+            )
+            .clicked()
+        {
+            if let Some(snapshot_id) = state.snapshot_id {
+                match compute_churn(conn, snapshot_id, state.window_days) {
+                    Ok(touched) => {
+                        state.status = format!(
+                            "Completed: {} files changed in the last {} days",
+                            touched, state.window_days
+                        );
+                        state.reload(conn);
+                    }
+                    Err(e) => state.status = format!("Error: {}", e),
+                }
+            }
+        }
+        ui.separator();
+        let view_before = state.view;
+        ui.selectable_value(&mut state.view, HotspotView::Files, "Files");
+        ui.selectable_value(&mut state.view, HotspotView::Artifacts, "Artifacts");
+        if state.view != view_before {
+            state.reload(conn);
+        }
+    });
+
+    if !state.status.is_empty() {
+        let color = if state.status.starts_with("Error") {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        ui.colored_label(color, &state.status);
+    }
+
+    if state.snapshot_id.is_some() && state.rows.is_empty() {
+        ui.label(egui::RichText::new("No churn data. Press \"Compute churn\".").weak());
+        return None;
+    }
+
+    ui.add_space(6.0);
+    render_table(ui, &state.rows, state.view)
+}
+
+fn render_table(ui: &mut egui::Ui, rows: &[Hotspot], view: HotspotView) -> Option<i64> {
+    use egui_extras::{Column, TableBuilder};
+
+    let mut opened = None;
+    let first_header = if view == HotspotView::Files {
+        "File"
+    } else {
+        "Artifact"
+    };
+    let authors_header = if view == HotspotView::Files {
+        "Authors"
+    } else {
+        "Authors (max)"
+    };
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto().at_least(300.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::auto().at_least(100.0))
+        .column(Column::remainder().at_least(60.0))
+        .header(20.0, |mut header| {
+            for title in [
+                first_header,
+                "Score",
+                "Commits",
+                authors_header,
+                "Added",
+                "Removed",
+                "LOC",
+                "Last modified",
+                "Files",
+            ] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(18.0, rows.len(), |mut row| {
+                let h = &rows[row.index()];
+                row.col(|ui| {
+                    if let Some(file_id) = h.file_id {
+                        if ui.link(&h.name).clicked() {
+                            opened = Some(file_id);
+                        }
+                    } else {
+                        ui.label(&h.name);
+                    }
+                });
+                row.col(|ui| {
+                    ui.label(format!("{:.1}", h.score));
+                });
+                row.col(|ui| {
+                    ui.label(h.commits.to_string());
+                });
+                row.col(|ui| {
+                    ui.label(h.authors.to_string());
+                });
+                row.col(|ui| {
+                    ui.colored_label(egui::Color32::LIGHT_GREEN, format!("+{}", h.lines_added));
+                });
+                row.col(|ui| {
+                    ui.colored_label(egui::Color32::LIGHT_RED, format!("-{}", h.lines_removed));
+                });
+                row.col(|ui| {
+                    ui.label(h.loc.to_string());
+                });
+                row.col(|ui| {
+                    // Date part of the RFC 3339 timestamp
+                    let date = h
+                        .last_modified
+                        .as_deref()
+                        .map(|d| &d[..d.len().min(10)])
+                        .unwrap_or("-");
+                    ui.label(date);
+                });
+                row.col(|ui| {
+                    ui.label(h.files.to_string());
+                });
+            });
+        });
+
+    opened
+}
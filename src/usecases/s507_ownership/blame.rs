@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::shared::git;
+
+/// Lines per author email (with the last seen author name)
+pub type BlameCounts = BTreeMap<String, (String, i64)>;
+
+/// Blame a file at `revision` (or the working tree when None)
+pub fn blame_file(root: &Path, revision: Option<&str>, path: &str) -> Result<BlameCounts, String> {
+    let path = path.replace('\\', "/");
+    let mut args = vec!["blame", "-w", "--line-porcelain"];
+    if let Some(rev) = revision {
+        args.push(rev);
+    }
+    args.push("--");
+    args.push(&path);
+    let out = git::run(root, &args)?;
+    Ok(parse_blame(&String::from_utf8_lossy(&out)))
+}
+
+/// Count lines per author from `git blame --line-porcelain` output
+pub fn parse_blame(output: &str) -> BlameCounts {
+    let mut counts = BlameCounts::new();
+    let mut name = String::new();
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("author ") {
+            name = rest.to_string();
+        } else if let Some(rest) = line.strip_prefix("author-mail ") {
+            // Every blamed line carries exactly one author-mail header
+            let email = rest.trim_matches(|c| c == '<' || c == '>').to_lowercase();
+            let entry = counts.entry(email).or_insert_with(|| (name.clone(), 0));
+            entry.0 = name.clone();
+            entry.1 += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_lines_per_author() {
+        let out = "\
+abc 1 1 2
+author Ann
+author-mail <Ann@X.io>
+summary init
+\tfn main() {
+abc 2 2
+author Ann
+author-mail <ann@x.io>
+\t}
+def 3 3 1
+author Bob
+author-mail <bob@x.io>
+\t// note
+";
+        let counts = parse_blame(out);
+        assert_eq!(counts["ann@x.io"], ("Ann".to_string(), 2));
+        assert_eq!(counts["bob@x.io"], ("Bob".to_string(), 1));
+    }
+}
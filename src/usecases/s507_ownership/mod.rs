@@ -0,0 +1,334 @@
+pub mod blame;
+pub mod ownership;
+mod worker;
+
+use eframe::egui;
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n006_file_content::repository as content_repo;
+use crate::shared::git;
+use ownership::{ownership_rows, OwnershipLevel, OwnershipRow};
+use worker::{spawn_blame, BlameEvent, BlameHandle, BlameParams};
+
+pub struct OwnershipState {
+    pub snapshot_picker: SnapshotPickerState,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    pub level: OwnershipLevel,
+    /// Only rows whose top author is marked as having left
+    pub only_left: bool,
+    pub filter: String,
+    pub rows: Vec<OwnershipRow>,
+    pub total_files: usize,
+    pub files_done: usize,
+    handle: Option<BlameHandle>,
+    pub status: String,
+}
+
+impl Default for OwnershipState {
+    fn default() -> Self {
+        Self {
+            snapshot_picker: SnapshotPickerState::default(),
+            snapshot_id: None,
+            snapshot_name: String::new(),
+            level: OwnershipLevel::Files,
+            only_left: false,
+            filter: String::new(),
+            rows: Vec::new(),
+            total_files: 0,
+            files_done: 0,
+            handle: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl OwnershipState {
+    pub fn is_running(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    fn reload(&mut self, conn: &Connection) {
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+        match ownership_rows(conn, snapshot_id, self.level) {
+            Ok(rows) => self.rows = rows,
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.rows.clear();
+            }
+        }
+    }
+
+    fn start(&mut self, conn: &Connection) -> Result<(), String> {
+        let Some(snapshot_id) = self.snapshot_id else {
+            return Ok(());
+        };
+        let snapshot = snapshot_repo::get_by_id(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load snapshot: {}", e))?;
+        let project = project_repo::get_by_id(conn, snapshot.project_id)
+            .map_err(|e| format!("Failed to load project: {}", e))?;
+        let root = PathBuf::from(&project.root_path);
+        if !git::is_repository(&root) {
+            return Err(format!("{} is not a git repository", project.root_path));
+        }
+
+        // Only text files are blamed; binary files have no meaningful lines
+        let text_ids: HashSet<i64> = content_repo::list_file_ids(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load contents: {}", e))?
+            .into_iter()
+            .collect();
+        let files: Vec<(i64, String)> = file_repo::list_by_snapshot(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load files: {}", e))?
+            .into_iter()
+            .filter(|f| text_ids.contains(&f.id))
+            .map(|f| (f.id, f.path.replace('\\', "/")))
+            .collect();
+
+        self.total_files = files.len();
+        self.files_done = 0;
+        self.handle = Some(spawn_blame(BlameParams {
+            snapshot_id,
+            root,
+            // Snapshots without a recorded commit are blamed in the working tree
+            revision: snapshot.git_revision,
+            files,
+        }));
+        self.status = "Running git blame...".to_string();
+        Ok(())
+    }
+
+    fn cancel(&mut self) {
+        if let Some(handle) = &self.handle {
+            handle.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Drain events sent by the worker since the last frame
+    fn poll(&mut self, conn: &Connection) {
+        let Some(handle) = &self.handle else { return };
+        let events: Vec<BlameEvent> = handle.receiver.try_iter().collect();
+
+        for event in events {
+            match event {
+                BlameEvent::Progress { done } => self.files_done = done,
+                BlameEvent::Finished {
+                    blamed,
+                    skipped,
+                    cancelled,
+                } => {
+                    self.handle = None;
+                    if cancelled {
+                        self.status = "Cancelled: previous ownership data kept".to_string();
+                    } else {
+                        self.status = format!(
+                            "Completed: {} files blamed, {} skipped (untracked)",
+                            blamed, skipped
+                        );
+                        self.reload(conn);
+                    }
+                }
+                BlameEvent::Failed(e) => {
+                    self.handle = None;
+                    self.status = format!("Error: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Renders the Ownership tab. Returns a file id when a file row is opened.
+pub fn ui_ownership(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut OwnershipState,
+) -> Option<i64> {
+    state.poll(conn);
+    if state.is_running() {
+        ui.ctx().request_repaint_after(Duration::from_millis(100));
+    }
+
+    ui.heading("Ownership");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.snapshot_id = Some(snapshot_id);
+        if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
+            state.snapshot_name = snapshot.name;
+        }
+        state.status.clear();
+        state.reload(conn);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Selected Snapshot:");
+        if state.snapshot_id.is_some() {
+            ui.label(egui::RichText::new(&state.snapshot_name).strong());
+        } else {
+            ui.label(egui::RichText::new("None").weak());
+        }
+        if ui.button("Select...").clicked() {
+            state.snapshot_picker.open();
+        }
+    });
+
+    ui.add_space(8.0);
+
+    ui.horizontal(|ui| {
+        let can_run = state.snapshot_id.is_some() && !state.is_running();
+        if ui
+            .add_enabled(can_run, egui::Button::new("Compute ownership"))
+            .clicked()
+        {
+            if let Err(e) = state.start(conn) {
+                state.status = format!("Error: {}", e);
+            }
+        }
+        if ui
+            .add_enabled(state.is_running(), egui::Button::new("Cancel"))
+            .clicked()
+        {
+            state.cancel();
+        }
+        if state.is_running() {
+            ui.spinner();
+            ui.label(format!("{}/{} files", state.files_done, state.total_files));
+        }
+    });
+
+    ui.horizontal(|ui| {
+        let level_before = state.level;
+        ui.selectable_value(&mut state.level, OwnershipLevel::Files, "Files");
+        ui.selectable_value(&mut state.level, OwnershipLevel::Directories, "Directories");
+        ui.selectable_value(&mut state.level, OwnershipLevel::Aggregates, "Aggregates");
+        if state.level != level_before {
+            state.reload(conn);
+        }
+        ui.separator();
+        ui.checkbox(&mut state.only_left, "Top author has left");
+        ui.label("Filter:");
+        ui.text_edit_singleline(&mut state.filter);
+        if ui.button("Refresh").clicked() {
+            state.reload(conn);
+        }
+    });
+
+    if !state.status.is_empty() {
+        let color = if state.status.starts_with("Error") {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        ui.colored_label(color, &state.status);
+    }
+
+    if state.snapshot_id.is_some() && state.rows.is_empty() && !state.is_running() {
+        ui.label(egui::RichText::new("No ownership data. Press \"Compute ownership\".").weak());
+        return None;
+    }
+
+    let filter = state.filter.to_lowercase();
+    let visible: Vec<&OwnershipRow> = state
+        .rows
+        .iter()
+        .filter(|r| !state.only_left || r.top_author_left)
+        .filter(|r| filter.is_empty() || r.name.to_lowercase().contains(&filter))
+        .collect();
+
+    ui.add_space(6.0);
+    render_table(ui, &visible, state.level)
+}
+
+fn render_table(ui: &mut egui::Ui, rows: &[&OwnershipRow], level: OwnershipLevel) -> Option<i64> {
+    use egui_extras::{Column, TableBuilder};
+
+    let mut opened = None;
+    let first_header = match level {
+        OwnershipLevel::Files => "File",
+        OwnershipLevel::Directories => "Directory",
+        OwnershipLevel::Aggregates => "Aggregate",
+    };
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto().at_least(300.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::auto().at_least(160.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::remainder().at_least(80.0))
+        .header(20.0, |mut header| {
+            for title in [
+                first_header,
+                "Lines",
+                "Top author",
+                "Share",
+                "Authors",
+                "Bus factor",
+            ] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(18.0, rows.len(), |mut row| {
+                let r = rows[row.index()];
+                row.col(|ui| {
+                    if let Some(file_id) = r.file_id {
+                        if ui.link(&r.name).clicked() {
+                            opened = Some(file_id);
+                        }
+                    } else {
+                        ui.label(&r.name);
+                    }
+                });
+                row.col(|ui| {
+                    ui.label(r.total_lines.to_string());
+                });
+                row.col(|ui| {
+                    let resp = if r.top_author_left {
+                        ui.colored_label(
+                            egui::Color32::LIGHT_RED,
+                            format!("{} (left)", r.top_author),
+                        )
+                    } else {
+                        ui.label(&r.top_author)
+                    };
+                    let breakdown: Vec<String> = r
+                        .breakdown
+                        .iter()
+                        .map(|(name, pct)| format!("{}: {:.1}%", name, pct))
+                        .collect();
+                    resp.on_hover_text(breakdown.join("\n"));
+                });
+                row.col(|ui| {
+                    ui.label(format!("{:.1}%", r.top_share));
+                });
+                row.col(|ui| {
+                    ui.label(r.authors.to_string());
+                });
+                row.col(|ui| {
+                    // A single owner means the knowledge lives in one head
+                    if r.bus_factor <= 1 {
+                        ui.colored_label(egui::Color32::YELLOW, r.bus_factor.to_string());
+                    } else {
+                        ui.label(r.bus_factor.to_string());
+                    }
+                });
+            });
+        });
+
+    opened
+}
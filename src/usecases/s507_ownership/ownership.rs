@@ -0,0 +1,147 @@
+use rusqlite::Connection;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n004_snapshot_aggregate::model::code_for_artifact;
+use crate::domain::n008_file_ownership::repository as ownership_repo;
+use crate::domain::n009_author::model::Author;
+use crate::domain::n009_author::repository as author_repo;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OwnershipLevel {
+    Files,
+    Directories,
+    Aggregates,
+}
+
+#[derive(Debug, Clone)]
+pub struct OwnershipRow {
+    /// File path, directory path or aggregate code
+    pub name: String,
+    pub file_id: Option<i64>,
+    pub total_lines: i64,
+    pub top_author: String,
+    /// Share of lines owned by the top author, 0..=100
+    pub top_share: f64,
+    pub top_author_left: bool,
+    pub authors: usize,
+    pub bus_factor: usize,
+    /// `name: percent` of every author, highest first
+    pub breakdown: Vec<(String, f64)>,
+}
+
+/// Smallest number of authors that together own more than half of the lines
+pub fn bus_factor(lines_desc: &[i64]) -> usize {
+    let total: i64 = lines_desc.iter().sum();
+    let mut covered = 0;
+    for (i, lines) in lines_desc.iter().enumerate() {
+        covered += lines;
+        if covered * 2 > total {
+            return i + 1;
+        }
+    }
+    lines_desc.len()
+}
+
+/// Canonical person for a commit identity: (name, has left)
+fn resolve(authors: &[Author], email: &str, name: &str) -> (String, bool) {
+    match authors.iter().find(|a| a.matches(email, name)) {
+        Some(a) => (a.name.clone(), a.has_left),
+        None => (name.to_string(), false),
+    }
+}
+
+/// Ownership of a snapshot at the requested level, largest groups first
+pub fn ownership_rows(
+    conn: &Connection,
+    snapshot_id: i64,
+    level: OwnershipLevel,
+) -> Result<Vec<OwnershipRow>, String> {
+    let authors =
+        author_repo::list_all(conn).map_err(|e| format!("Failed to load authors: {}", e))?;
+    let files: HashMap<i64, _> = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect();
+    let entries = ownership_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load ownership: {}", e))?;
+
+    // group name -> (file id for file rows, person -> lines)
+    let mut groups: BTreeMap<String, (Option<i64>, HashMap<String, i64>)> = BTreeMap::new();
+    let mut left: HashMap<String, bool> = HashMap::new();
+    for e in &entries {
+        let Some(file) = files.get(&e.file_id) else {
+            continue;
+        };
+        let (person, has_left) = resolve(&authors, &e.author_email, &e.author_name);
+        left.insert(person.clone(), has_left);
+
+        let path = file.path.replace('\\', "/");
+        let keys: Vec<(String, Option<i64>)> = match level {
+            OwnershipLevel::Files => vec![(path, Some(file.id))],
+            // Every ancestor directory, so `src` includes `src/domain/...`
+            OwnershipLevel::Directories => {
+                let segments: Vec<&str> = path.split('/').collect();
+                (1..segments.len())
+                    .map(|n| (segments[..n].join("/"), None))
+                    .collect()
+            }
+            OwnershipLevel::Aggregates => match &file.artifact_id {
+                Some(id) => vec![(code_for_artifact(id), None)],
+                None => Vec::new(),
+            },
+        };
+        for (key, file_id) in keys {
+            let group = groups
+                .entry(key)
+                .or_insert_with(|| (file_id, HashMap::new()));
+            *group.1.entry(person.clone()).or_insert(0) += e.lines;
+        }
+    }
+
+    let mut rows: Vec<OwnershipRow> = groups
+        .into_iter()
+        .map(|(name, (file_id, per_person))| {
+            let mut people: Vec<(String, i64)> = per_person.into_iter().collect();
+            people.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            let total_lines: i64 = people.iter().map(|p| p.1).sum();
+            let share = |lines: i64| {
+                if total_lines > 0 {
+                    100.0 * lines as f64 / total_lines as f64
+                } else {
+                    0.0
+                }
+            };
+            let lines_desc: Vec<i64> = people.iter().map(|p| p.1).collect();
+            let (top_author, top_lines) = people.first().cloned().unwrap_or_default();
+            OwnershipRow {
+                name,
+                file_id,
+                total_lines,
+                top_author_left: left.get(&top_author).copied().unwrap_or(false),
+                top_share: share(top_lines),
+                top_author,
+                authors: people.len(),
+                bus_factor: bus_factor(&lines_desc),
+                breakdown: people.iter().map(|(p, l)| (p.clone(), share(*l))).collect(),
+            }
+        })
+        .collect();
+    rows.sort_by_key(|r| Reverse(r.total_lines));
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bus_factor_counts_majority_owners() {
+        assert_eq!(bus_factor(&[90, 5, 5]), 1);
+        assert_eq!(bus_factor(&[40, 30, 30]), 2);
+        assert_eq!(bus_factor(&[50, 50]), 2);
+        assert_eq!(bus_factor(&[]), 0);
+    }
+}
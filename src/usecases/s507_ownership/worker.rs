@@ -0,0 +1,111 @@
+use rusqlite::Connection;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+
+use super::blame::blame_file;
+use crate::domain::n008_file_ownership::repository as ownership_repo;
+use crate::shared::db::{BUSY_TIMEOUT, DB_PATH};
+
+pub enum BlameEvent {
+    Progress {
+        done: usize,
+    },
+    Finished {
+        blamed: usize,
+        skipped: usize,
+        cancelled: bool,
+    },
+    Failed(String),
+}
+
+pub struct BlameParams {
+    pub snapshot_id: i64,
+    pub root: PathBuf,
+    pub revision: Option<String>,
+    /// (file_id, relative path) of text files to blame
+    pub files: Vec<(i64, String)>,
+}
+
+pub struct BlameHandle {
+    pub receiver: Receiver<BlameEvent>,
+    pub cancel: Arc<AtomicBool>,
+}
+
+/// Blame files on a background thread, replacing the snapshot's ownership rows
+pub fn spawn_blame(params: BlameParams) -> BlameHandle {
+    let (sender, receiver) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    let cancel_flag = cancel.clone();
+
+    thread::spawn(move || {
+        if let Err(e) = run_blame(&params, &sender, &cancel_flag) {
+            let _ = sender.send(BlameEvent::Failed(e));
+        }
+    });
+
+    BlameHandle { receiver, cancel }
+}
+
+fn run_blame(
+    params: &BlameParams,
+    sender: &Sender<BlameEvent>,
+    cancel: &AtomicBool,
+) -> Result<(), String> {
+    // Blame everything before writing so the write transaction stays short
+    let mut rows = Vec::new();
+    let (mut blamed, mut skipped) = (0, 0);
+    for (idx, (file_id, path)) in params.files.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            // Nothing written yet: the previous ownership data stays
+            let _ = sender.send(BlameEvent::Finished {
+                blamed,
+                skipped,
+                cancelled: true,
+            });
+            return Ok(());
+        }
+
+        // Untracked files cannot be blamed; they are simply left out
+        match blame_file(&params.root, params.revision.as_deref(), path) {
+            Ok(counts) => {
+                rows.extend(
+                    counts
+                        .into_iter()
+                        .map(|(email, (name, lines))| (*file_id, email, name, lines)),
+                );
+                blamed += 1;
+            }
+            Err(_) => skipped += 1,
+        }
+
+        if idx % 10 == 0 || idx + 1 == params.files.len() {
+            let _ = sender.send(BlameEvent::Progress { done: idx + 1 });
+        }
+    }
+
+    // SQLite connections are not shared across threads; open a dedicated one
+    let conn = Connection::open(DB_PATH).map_err(|e| format!("Failed to open database: {}", e))?;
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .map_err(|e| format!("Failed to set busy timeout: {}", e))?;
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    ownership_repo::delete_by_snapshot(&conn, params.snapshot_id)
+        .map_err(|e| format!("Failed to delete ownership: {}", e))?;
+    for (file_id, email, name, lines) in rows {
+        ownership_repo::create(&conn, params.snapshot_id, file_id, &email, &name, lines)
+            .map_err(|e| format!("Failed to store ownership: {}", e))?;
+    }
+    tx.commit()
+        .map_err(|e| format!("Failed to commit ownership: {}", e))?;
+
+    let _ = sender.send(BlameEvent::Finished {
+        blamed,
+        skipped,
+        cancelled: false,
+    });
+    Ok(())
+}
@@ -0,0 +1,406 @@
+pub mod rollup;
+
+use eframe::egui;
+use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::baseline::{
+    delta_label, new_marker, removed_marker, ui_baseline_toggle,
+};
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use rollup::{complexity_rows, ComplexityLevel, ComplexityRow};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortColumn {
+    Name,
+    Location,
+    Functions,
+    Cyclomatic,
+    MaxCyclomatic,
+    Cognitive,
+    MaxCognitive,
+    Nesting,
+    Params,
+    Length,
+}
+
+impl SortColumn {
+    fn title(self, level: ComplexityLevel) -> &'static str {
+        match self {
+            SortColumn::Name => match level {
+                ComplexityLevel::Functions => "Function",
+                ComplexityLevel::Files => "File",
+                ComplexityLevel::Aggregates => "Aggregate",
+            },
+            SortColumn::Location => "File",
+            SortColumn::Functions => "Functions",
+            SortColumn::Cyclomatic => "Cyclomatic",
+            SortColumn::MaxCyclomatic => "Max cyclomatic",
+            SortColumn::Cognitive => "Cognitive",
+            SortColumn::MaxCognitive => "Max cognitive",
+            SortColumn::Nesting => "Nesting",
+            SortColumn::Params => "Params",
+            SortColumn::Length => "Length",
+        }
+    }
+
+    fn columns(level: ComplexityLevel) -> &'static [SortColumn] {
+        use SortColumn::*;
+        match level {
+            ComplexityLevel::Functions => &[
+                Name, Location, Cyclomatic, Cognitive, Nesting, Params, Length,
+            ],
+            _ => &[
+                Name,
+                Functions,
+                Cyclomatic,
+                MaxCyclomatic,
+                Cognitive,
+                MaxCognitive,
+                Nesting,
+                Length,
+            ],
+        }
+    }
+
+    fn value(self, row: &ComplexityRow) -> i64 {
+        match self {
+            SortColumn::Name | SortColumn::Location => 0,
+            SortColumn::Functions => row.functions,
+            SortColumn::Cyclomatic => row.cyclomatic,
+            SortColumn::MaxCyclomatic => row.max_cyclomatic,
+            SortColumn::Cognitive => row.cognitive,
+            SortColumn::MaxCognitive => row.max_cognitive,
+            SortColumn::Nesting => row.nesting,
+            SortColumn::Params => row.params,
+            SortColumn::Length => row.length,
+        }
+    }
+}
+
+/// Cognitive complexity above which a function is flagged
+const COGNITIVE_WARNING: i64 = 15;
+
+pub struct ComplexityState {
+    pub snapshot_picker: SnapshotPickerState,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    pub level: ComplexityLevel,
+    pub sort: SortColumn,
+    pub descending: bool,
+    pub filter: String,
+    pub rows: Vec<ComplexityRow>,
+    /// Show deltas against the project baseline
+    pub compare_baseline: bool,
+    /// Baseline rows by `ComplexityRow::key`, empty unless comparing
+    pub baseline: HashMap<(String, String), ComplexityRow>,
+    /// Baseline rows missing from the current snapshot
+    pub removed: Vec<ComplexityRow>,
+    pub status: String,
+}
+
+impl Default for ComplexityState {
+    fn default() -> Self {
+        Self {
+            snapshot_picker: SnapshotPickerState::default(),
+            snapshot_id: None,
+            snapshot_name: String::new(),
+            level: ComplexityLevel::Functions,
+            sort: SortColumn::Cognitive,
+            descending: true,
+            filter: String::new(),
+            rows: Vec::new(),
+            compare_baseline: false,
+            baseline: HashMap::new(),
+            removed: Vec::new(),
+            status: String::new(),
+        }
+    }
+}
+
+impl ComplexityState {
+    fn reload(&mut self, conn: &Connection) {
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+        match complexity_rows(conn, snapshot_id, self.level) {
+            Ok(rows) => {
+                self.rows = rows;
+                self.sort_rows();
+                if let Err(e) = self.load_baseline(conn, snapshot_id) {
+                    self.status = format!("Error: {}", e);
+                }
+            }
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.rows.clear();
+            }
+        }
+    }
+
+    fn load_baseline(&mut self, conn: &Connection, snapshot_id: i64) -> Result<(), String> {
+        self.baseline.clear();
+        self.removed.clear();
+        if !self.compare_baseline {
+            return Ok(());
+        }
+        let Some(baseline) = snapshot_repo::baseline_for_snapshot(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load baseline: {}", e))?
+        else {
+            return Ok(());
+        };
+
+        let current: HashSet<(&str, &str)> = self.rows.iter().map(ComplexityRow::key).collect();
+        for row in complexity_rows(conn, baseline.id, self.level)? {
+            if !current.contains(&row.key()) {
+                self.removed.push(row.clone());
+            }
+            self.baseline
+                .entry((row.name.clone(), row.location.clone()))
+                .or_insert(row);
+        }
+        self.removed.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(())
+    }
+
+    fn sort_rows(&mut self) {
+        let column = self.sort;
+        self.rows.sort_by(|a, b| {
+            let ord = match column {
+                SortColumn::Name => a.name.cmp(&b.name),
+                SortColumn::Location => a.location.cmp(&b.location).then(a.line.cmp(&b.line)),
+                _ => column.value(a).cmp(&column.value(b)),
+            };
+            if self.descending {
+                ord.reverse()
+            } else {
+                ord
+            }
+        });
+    }
+
+    fn toggle_sort(&mut self, column: SortColumn) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            // Names read best A-Z, metrics worst first
+            self.descending = !matches!(column, SortColumn::Name | SortColumn::Location);
+        }
+        self.sort_rows();
+    }
+}
+
+/// Renders the Complexity tab. Returns `(file_id, line)` when a row is opened.
+pub fn ui_complexity(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut ComplexityState,
+) -> Option<(i64, Option<usize>)> {
+    ui.heading("Complexity");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.snapshot_id = Some(snapshot_id);
+        if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
+            state.snapshot_name = snapshot.name;
+        }
+        state.status.clear();
+        state.reload(conn);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Selected Snapshot:");
+        if state.snapshot_id.is_some() {
+            ui.label(egui::RichText::new(&state.snapshot_name).strong());
+        } else {
+            ui.label(egui::RichText::new("None").weak());
+        }
+        if ui.button("Select...").clicked() {
+            state.snapshot_picker.open();
+        }
+        if let Some(snapshot_id) = state.snapshot_id {
+            ui.separator();
+            let compare_before = state.compare_baseline;
+            ui_baseline_toggle(ui, conn, snapshot_id, &mut state.compare_baseline);
+            if state.compare_baseline != compare_before {
+                state.reload(conn);
+            }
+        }
+    });
+
+    ui.add_space(8.0);
+
+    ui.horizontal(|ui| {
+        let level_before = state.level;
+        ui.selectable_value(&mut state.level, ComplexityLevel::Functions, "Functions");
+        ui.selectable_value(&mut state.level, ComplexityLevel::Files, "Files");
+        ui.selectable_value(&mut state.level, ComplexityLevel::Aggregates, "Aggregates");
+        if state.level != level_before {
+            if !SortColumn::columns(state.level).contains(&state.sort) {
+                state.sort = SortColumn::Cognitive;
+                state.descending = true;
+            }
+            state.reload(conn);
+        }
+        ui.separator();
+        ui.label("Filter:");
+        ui.text_edit_singleline(&mut state.filter);
+        if ui.button("Refresh").clicked() {
+            state.reload(conn);
+        }
+    });
+
+    if !state.status.is_empty() {
+        ui.colored_label(egui::Color32::LIGHT_RED, &state.status);
+    }
+
+    if state.snapshot_id.is_some() && state.rows.is_empty() {
+        ui.label(
+            egui::RichText::new("No function metrics. Rescan the snapshot to collect them.").weak(),
+        );
+        return None;
+    }
+
+    let filter = state.filter.to_lowercase();
+    let matches = |r: &ComplexityRow| {
+        filter.is_empty()
+            || r.name.to_lowercase().contains(&filter)
+            || r.location.to_lowercase().contains(&filter)
+    };
+    let visible: Vec<usize> = (0..state.rows.len())
+        .filter(|&i| matches(&state.rows[i]))
+        .collect();
+    let removed: Vec<usize> = (0..state.removed.len())
+        .filter(|&i| matches(&state.removed[i]))
+        .collect();
+
+    if state.compare_baseline && !state.baseline.is_empty() {
+        ui.label(format!("Removed since baseline: {}", removed.len()));
+    }
+
+    ui.add_space(6.0);
+    render_table(ui, state, &visible, &removed)
+}
+
+/// `visible` and `removed` index `state.rows` and `state.removed`; removed rows follow the current ones
+fn render_table(
+    ui: &mut egui::Ui,
+    state: &mut ComplexityState,
+    visible: &[usize],
+    removed: &[usize],
+) -> Option<(i64, Option<usize>)> {
+    use egui_extras::{Column, TableBuilder};
+
+    let mut opened = None;
+    let mut clicked_sort = None;
+    let columns = SortColumn::columns(state.level);
+
+    let mut table = TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto().at_least(260.0));
+    for column in &columns[1..] {
+        table = table.column(if *column == SortColumn::Location {
+            Column::auto().at_least(260.0)
+        } else {
+            Column::auto().at_least(70.0)
+        });
+    }
+
+    table
+        .header(20.0, |mut header| {
+            for column in columns {
+                header.col(|ui| {
+                    let arrow = match (state.sort == *column, state.descending) {
+                        (true, true) => " ⏷",
+                        (true, false) => " ⏶",
+                        _ => "",
+                    };
+                    let label =
+                        egui::RichText::new(format!("{}{}", column.title(state.level), arrow))
+                            .strong();
+                    if ui.selectable_label(state.sort == *column, label).clicked() {
+                        clicked_sort = Some(*column);
+                    }
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(18.0, visible.len() + removed.len(), |mut row| {
+                if row.index() >= visible.len() {
+                    let r = &state.removed[removed[row.index() - visible.len()]];
+                    for column in columns {
+                        row.col(|ui| match column {
+                            SortColumn::Name => {
+                                ui.label(egui::RichText::new(&r.name).weak());
+                                removed_marker(ui);
+                            }
+                            SortColumn::Location => {
+                                ui.label(egui::RichText::new(&r.location).weak());
+                            }
+                            _ => {
+                                ui.label(egui::RichText::new(column.value(r).to_string()).weak());
+                            }
+                        });
+                    }
+                    return;
+                }
+
+                let r = &state.rows[visible[row.index()]];
+                let base = if state.baseline.is_empty() {
+                    None
+                } else {
+                    state.baseline.get(&(r.name.clone(), r.location.clone()))
+                };
+                for column in columns {
+                    row.col(|ui| match column {
+                        SortColumn::Name => {
+                            if let Some(file_id) = r.file_id {
+                                if ui.link(&r.name).clicked() {
+                                    opened = Some((file_id, r.line));
+                                }
+                            } else {
+                                ui.label(&r.name);
+                            }
+                            if !state.baseline.is_empty() && base.is_none() {
+                                new_marker(ui);
+                            }
+                        }
+                        SortColumn::Location => {
+                            let line = r.line.map(|l| format!(":{}", l)).unwrap_or_default();
+                            ui.label(egui::RichText::new(format!("{}{}", r.location, line)).weak());
+                        }
+                        SortColumn::Cognitive | SortColumn::MaxCognitive
+                            if r.functions == 1 || *column == SortColumn::MaxCognitive =>
+                        {
+                            let value = column.value(r);
+                            if value > COGNITIVE_WARNING {
+                                ui.colored_label(egui::Color32::LIGHT_RED, value.to_string());
+                            } else {
+                                ui.label(value.to_string());
+                            }
+                            if let Some(base) = base {
+                                delta_label(ui, value - column.value(base), |n| n.to_string());
+                            }
+                        }
+                        _ => {
+                            ui.label(column.value(r).to_string());
+                            if let Some(base) = base {
+                                delta_label(ui, column.value(r) - column.value(base), |n| {
+                                    n.to_string()
+                                });
+                            }
+                        }
+                    });
+                }
+            });
+        });
+
+    if let Some(column) = clicked_sort {
+        state.toggle_sort(column);
+    }
+    opened
+}
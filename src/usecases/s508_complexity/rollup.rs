@@ -0,0 +1,125 @@
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n004_snapshot_aggregate::model::code_for_artifact;
+use crate::domain::n010_function_metric::repository as function_repo;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComplexityLevel {
+    Functions,
+    Files,
+    Aggregates,
+}
+
+/// A function, or the functions of a file / aggregate rolled up.
+/// Sums for roll-ups; for a function the sum and max are its own value.
+#[derive(Debug, Clone)]
+pub struct ComplexityRow {
+    /// Function name, file path or aggregate code
+    pub name: String,
+    /// File path of a function row
+    pub location: String,
+    /// Set for function and file rows (used to open the preview)
+    pub file_id: Option<i64>,
+    /// Line of a function row
+    pub line: Option<usize>,
+    pub functions: i64,
+    pub cyclomatic: i64,
+    pub max_cyclomatic: i64,
+    pub cognitive: i64,
+    pub max_cognitive: i64,
+    pub nesting: i64,
+    pub params: i64,
+    pub length: i64,
+}
+
+impl ComplexityRow {
+    /// Identity across snapshots: name and file path, ignoring the line
+    pub fn key(&self) -> (&str, &str) {
+        (&self.name, &self.location)
+    }
+
+    fn empty(name: String, file_id: Option<i64>) -> Self {
+        Self {
+            name,
+            location: String::new(),
+            file_id,
+            line: None,
+            functions: 0,
+            cyclomatic: 0,
+            max_cyclomatic: 0,
+            cognitive: 0,
+            max_cognitive: 0,
+            nesting: 0,
+            params: 0,
+            length: 0,
+        }
+    }
+
+    fn add(&mut self, other: &ComplexityRow) {
+        self.functions += other.functions;
+        self.cyclomatic += other.cyclomatic;
+        self.max_cyclomatic = self.max_cyclomatic.max(other.max_cyclomatic);
+        self.cognitive += other.cognitive;
+        self.max_cognitive = self.max_cognitive.max(other.max_cognitive);
+        self.nesting = self.nesting.max(other.nesting);
+        self.params = self.params.max(other.params);
+        self.length += other.length;
+    }
+}
+
+/// Function metrics of a snapshot at the requested level (unsorted)
+pub fn complexity_rows(
+    conn: &Connection,
+    snapshot_id: i64,
+    level: ComplexityLevel,
+) -> Result<Vec<ComplexityRow>, String> {
+    let files: HashMap<i64, _> = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect();
+    let metrics = function_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load function metrics: {}", e))?;
+
+    let function_rows = metrics.into_iter().filter_map(|m| {
+        let file = files.get(&m.file_id)?;
+        Some(ComplexityRow {
+            name: m.name,
+            location: file.path.clone(),
+            file_id: Some(m.file_id),
+            line: Some(m.line as usize),
+            functions: 1,
+            cyclomatic: m.cyclomatic,
+            max_cyclomatic: m.cyclomatic,
+            cognitive: m.cognitive,
+            max_cognitive: m.cognitive,
+            nesting: m.nesting,
+            params: m.params,
+            length: m.length,
+        })
+    });
+    if level == ComplexityLevel::Functions {
+        return Ok(function_rows.collect());
+    }
+
+    let mut groups: BTreeMap<String, ComplexityRow> = BTreeMap::new();
+    for row in function_rows {
+        let Some(file) = row.file_id.and_then(|id| files.get(&id)) else {
+            continue;
+        };
+        let (key, file_id) = match level {
+            ComplexityLevel::Files => (file.path.clone(), Some(file.id)),
+            _ => match &file.artifact_id {
+                Some(id) => (code_for_artifact(id), None),
+                None => continue,
+            },
+        };
+        groups
+            .entry(key.clone())
+            .or_insert_with(|| ComplexityRow::empty(key, file_id))
+            .add(&row);
+    }
+    Ok(groups.into_values().collect())
+}
@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n004_snapshot_aggregate::model::code_for_artifact;
+use crate::domain::n011_debt_marker::repository as marker_repo;
+
+/// Label of markers in files outside any artifact
+pub const NO_ARTIFACT: &str = "(no artifact)";
+
+/// A debt marker joined with its file
+#[derive(Debug, Clone)]
+pub struct MarkerEntry {
+    pub file_id: i64,
+    pub path: String,
+    /// Aggregate code of the file's artifact, or [`NO_ARTIFACT`]
+    pub artifact: String,
+    pub line: usize,
+    pub kind: String,
+    pub text: String,
+    pub author: Option<String>,
+    pub authored_at: Option<String>,
+}
+
+impl MarkerEntry {
+    /// Days since the line was authored
+    pub fn age_days(&self) -> Option<i64> {
+        let at = DateTime::parse_from_rfc3339(self.authored_at.as_deref()?).ok()?;
+        Some((Utc::now() - at.with_timezone(&Utc)).num_days())
+    }
+
+    /// Identity across snapshots: line numbers shift, so only path, kind and text count
+    fn key(&self) -> (String, &str, &str) {
+        (
+            self.path.replace('\\', "/"),
+            self.kind.as_str(),
+            self.text.as_str(),
+        )
+    }
+}
+
+/// Markers of a snapshot, ordered by artifact, path and line
+pub fn load_markers(conn: &Connection, snapshot_id: i64) -> Result<Vec<MarkerEntry>, String> {
+    let files: HashMap<i64, _> = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect();
+    let markers = marker_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load markers: {}", e))?;
+
+    let mut out: Vec<MarkerEntry> = markers
+        .into_iter()
+        .filter_map(|m| {
+            let file = files.get(&m.file_id)?;
+            Some(MarkerEntry {
+                file_id: m.file_id,
+                path: file.path.clone(),
+                artifact: file
+                    .artifact_id
+                    .as_deref()
+                    .map(code_for_artifact)
+                    .unwrap_or_else(|| NO_ARTIFACT.to_string()),
+                line: m.line as usize,
+                kind: m.kind,
+                text: m.text,
+                author: m.author,
+                authored_at: m.authored_at,
+            })
+        })
+        .collect();
+    out.sort_by(|a, b| (&a.artifact, &a.path, a.line).cmp(&(&b.artifact, &b.path, b.line)));
+    Ok(out)
+}
+
+/// Markers only in `target` (added) and only in `base` (resolved).
+/// Duplicates are matched one to one, so a copied TODO counts as added.
+pub fn diff_markers(
+    base: &[MarkerEntry],
+    target: &[MarkerEntry],
+) -> (Vec<MarkerEntry>, Vec<MarkerEntry>) {
+    let mut remaining: HashMap<(String, &str, &str), usize> = HashMap::new();
+    for m in base {
+        *remaining.entry(m.key()).or_insert(0) += 1;
+    }
+
+    let mut added = Vec::new();
+    for m in target {
+        match remaining.get_mut(&m.key()) {
+            Some(n) if *n > 0 => *n -= 1,
+            _ => added.push(m.clone()),
+        }
+    }
+
+    // Whatever is left unmatched in base was resolved
+    let mut resolved = Vec::new();
+    for m in base {
+        if let Some(n) = remaining.get_mut(&m.key()) {
+            if *n > 0 {
+                *n -= 1;
+                resolved.push(m.clone());
+            }
+        }
+    }
+    (added, resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(path: &str, line: usize, text: &str) -> MarkerEntry {
+        MarkerEntry {
+            file_id: 0,
+            path: path.to_string(),
+            artifact: NO_ARTIFACT.to_string(),
+            line,
+            kind: "TODO".to_string(),
+            text: text.to_string(),
+            author: None,
+            authored_at: None,
+        }
+    }
+
+    #[test]
+    fn matches_markers_ignoring_line_moves() {
+        let base = vec![
+            marker("a.rs", 1, "// TODO: a"),
+            marker("a.rs", 5, "// TODO: b"),
+        ];
+        let target = vec![
+            marker("a.rs", 9, "// TODO: a"),
+            marker("b.rs", 2, "// TODO: a"),
+        ];
+        let (added, resolved) = diff_markers(&base, &target);
+        assert_eq!(
+            added.iter().map(|m| m.path.as_str()).collect::<Vec<_>>(),
+            ["b.rs"]
+        );
+        assert_eq!(
+            resolved.iter().map(|m| m.text.as_str()).collect::<Vec<_>>(),
+            ["// TODO: b"]
+        );
+    }
+}
@@ -0,0 +1,268 @@
+pub mod markers;
+
+use eframe::egui;
+use rusqlite::Connection;
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::domain::n011_debt_marker::model::MARKER_KINDS;
+use markers::{diff_markers, load_markers, MarkerEntry};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PickTarget {
+    Snapshot,
+    Base,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarkersView {
+    List,
+    Diff,
+}
+
+pub struct MarkersState {
+    pub snapshot_picker: SnapshotPickerState,
+    picking: Option<PickTarget>,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    /// Earlier snapshot the selected one is diffed against
+    pub base_id: Option<i64>,
+    pub base_name: String,
+    pub view: MarkersView,
+    /// Marker kind to show (empty = any)
+    pub kind: String,
+    pub filter: String,
+    pub markers: Vec<MarkerEntry>,
+    pub added: Vec<MarkerEntry>,
+    pub resolved: Vec<MarkerEntry>,
+    pub status: String,
+}
+
+impl Default for MarkersState {
+    fn default() -> Self {
+        Self {
+            snapshot_picker: SnapshotPickerState::default(),
+            picking: None,
+            snapshot_id: None,
+            snapshot_name: String::new(),
+            base_id: None,
+            base_name: String::new(),
+            view: MarkersView::List,
+            kind: String::new(),
+            filter: String::new(),
+            markers: Vec::new(),
+            added: Vec::new(),
+            resolved: Vec::new(),
+            status: String::new(),
+        }
+    }
+}
+
+impl MarkersState {
+    fn reload(&mut self, conn: &Connection) {
+        self.status.clear();
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+        match load_markers(conn, snapshot_id) {
+            Ok(markers) => self.markers = markers,
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.markers.clear();
+            }
+        }
+
+        self.added.clear();
+        self.resolved.clear();
+        if let Some(base_id) = self.base_id {
+            match load_markers(conn, base_id) {
+                Ok(base) => {
+                    let (added, resolved) = diff_markers(&base, &self.markers);
+                    self.added = added;
+                    self.resolved = resolved;
+                }
+                Err(e) => self.status = format!("Error: {}", e),
+            }
+        }
+    }
+
+    fn matches(&self, m: &MarkerEntry) -> bool {
+        let filter = self.filter.to_lowercase();
+        (self.kind.is_empty() || m.kind == self.kind)
+            && (filter.is_empty()
+                || m.text.to_lowercase().contains(&filter)
+                || m.path.to_lowercase().contains(&filter))
+    }
+}
+
+/// Renders the Markers tab. Returns `(file_id, line)` when a marker is opened.
+pub fn ui_markers(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut MarkersState,
+) -> Option<(i64, Option<usize>)> {
+    let mut opened = None;
+
+    ui.heading("Debt Markers");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        let name = snapshot_repo::get_by_id(conn, snapshot_id)
+            .map(|s| s.name)
+            .unwrap_or_default();
+        match state.picking.take() {
+            Some(PickTarget::Snapshot) => {
+                state.snapshot_id = Some(snapshot_id);
+                state.snapshot_name = name;
+            }
+            Some(PickTarget::Base) => {
+                state.base_id = Some(snapshot_id);
+                state.base_name = name;
+            }
+            None => {}
+        }
+        state.reload(conn);
+    }
+
+    for (label, target) in [
+        ("Snapshot:", PickTarget::Snapshot),
+        ("Diff against:", PickTarget::Base),
+    ] {
+        ui.horizontal(|ui| {
+            ui.label(label);
+            let name = match target {
+                PickTarget::Snapshot => state.snapshot_id.map(|_| &state.snapshot_name),
+                PickTarget::Base => state.base_id.map(|_| &state.base_name),
+            };
+            match name {
+                Some(name) => ui.label(egui::RichText::new(name).strong()),
+                None => ui.label(egui::RichText::new("None").weak()),
+            };
+            if ui.button("Select...").clicked() {
+                state.picking = Some(target);
+                state.snapshot_picker.open();
+            }
+        });
+    }
+
+    ui.add_space(8.0);
+
+    ui.horizontal(|ui| {
+        ui.selectable_value(
+            &mut state.view,
+            MarkersView::List,
+            format!("Markers ({})", state.markers.len()),
+        );
+        let diff_label = format!(
+            "Added {} / Resolved {}",
+            state.added.len(),
+            state.resolved.len()
+        );
+        ui.add_enabled_ui(state.base_id.is_some(), |ui| {
+            ui.selectable_value(&mut state.view, MarkersView::Diff, diff_label);
+        });
+        ui.separator();
+        ui.label("Kind:");
+        let selected = if state.kind.is_empty() {
+            "Any".to_string()
+        } else {
+            state.kind.clone()
+        };
+        egui::ComboBox::from_id_salt("markers_kind")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut state.kind, String::new(), "Any");
+                for kind in MARKER_KINDS {
+                    ui.selectable_value(&mut state.kind, kind.to_string(), kind);
+                }
+            });
+        ui.label("Filter:");
+        ui.text_edit_singleline(&mut state.filter);
+        if ui.button("Refresh").clicked() {
+            state.reload(conn);
+        }
+    });
+
+    if !state.status.is_empty() {
+        ui.colored_label(egui::Color32::LIGHT_RED, &state.status);
+    }
+
+    if state.snapshot_id.is_some() && state.markers.is_empty() && state.resolved.is_empty() {
+        ui.label(egui::RichText::new("No markers. Rescan the snapshot to collect them.").weak());
+        return None;
+    }
+
+    ui.add_space(6.0);
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| match state.view {
+            MarkersView::List => {
+                let visible: Vec<&MarkerEntry> =
+                    state.markers.iter().filter(|m| state.matches(m)).collect();
+                // Markers are sorted by artifact, so groups are contiguous
+                for group in visible.chunk_by(|a, b| a.artifact == b.artifact) {
+                    egui::CollapsingHeader::new(format!("{} ({})", group[0].artifact, group.len()))
+                        .id_salt(("markers_artifact", &group[0].artifact))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            for m in group {
+                                if marker_row(ui, m) {
+                                    opened = Some((m.file_id, Some(m.line)));
+                                }
+                            }
+                        });
+                }
+            }
+            MarkersView::Diff => {
+                let sections = [
+                    ("Added", egui::Color32::LIGHT_RED, &state.added),
+                    ("Resolved", egui::Color32::LIGHT_GREEN, &state.resolved),
+                ];
+                for (title, color, entries) in sections {
+                    let visible: Vec<&MarkerEntry> =
+                        entries.iter().filter(|m| state.matches(m)).collect();
+                    egui::CollapsingHeader::new(
+                        egui::RichText::new(format!("{} ({})", title, visible.len())).color(color),
+                    )
+                    .id_salt(("markers_diff", title))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for m in visible {
+                            if marker_row(ui, m) {
+                                opened = Some((m.file_id, Some(m.line)));
+                            }
+                        }
+                    });
+                }
+            }
+        });
+
+    opened
+}
+
+/// One marker line; returns true when its location is clicked
+fn marker_row(ui: &mut egui::Ui, m: &MarkerEntry) -> bool {
+    let mut clicked = false;
+    ui.horizontal(|ui| {
+        let color = match m.kind.as_str() {
+            "FIXME" | "HACK" | "XXX" => egui::Color32::LIGHT_RED,
+            "todo!" | "unimplemented!" => egui::Color32::YELLOW,
+            _ => egui::Color32::LIGHT_BLUE,
+        };
+        ui.add_sized(
+            [110.0, 18.0],
+            egui::Label::new(egui::RichText::new(&m.kind).color(color).strong()),
+        );
+        clicked = ui.link(format!("{}:{}", m.path, m.line)).clicked();
+        ui.label(egui::RichText::new(&m.text).monospace());
+        if let Some(author) = &m.author {
+            let age = m
+                .age_days()
+                .map(|d| format!(", {}d ago", d))
+                .unwrap_or_default();
+            ui.label(egui::RichText::new(format!("— {}{}", author, age)).weak());
+        }
+    });
+    clicked
+}
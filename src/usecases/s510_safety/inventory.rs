@@ -0,0 +1,172 @@
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::domain::n001_project::model::Project;
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n004_snapshot_aggregate::model::code_for_artifact;
+use crate::domain::n012_safety_site::repository as safety_repo;
+use crate::shared::safety::SiteKind;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SafetyLevel {
+    Functions,
+    Files,
+    CrateLayers,
+    Aggregates,
+}
+
+/// Site counts of a function, file, crate layer or aggregate
+#[derive(Debug, Clone)]
+pub struct SafetyRow {
+    /// Function name, file path, crate layer or aggregate code
+    pub name: String,
+    /// `path:line` of the first site of a function row
+    pub location: String,
+    /// Set for function and file rows (used to open the preview)
+    pub file_id: Option<i64>,
+    pub line: Option<usize>,
+    /// Indexed like `SiteKind::ALL`
+    pub counts: [i64; 7],
+}
+
+impl SafetyRow {
+    pub fn count(&self, kind: SiteKind) -> i64 {
+        SiteKind::ALL
+            .iter()
+            .position(|k| *k == kind)
+            .map(|i| self.counts[i])
+            .unwrap_or(0)
+    }
+
+    pub fn unsafe_total(&self) -> i64 {
+        SiteKind::ALL
+            .iter()
+            .filter(|k| k.is_unsafe())
+            .map(|k| self.count(*k))
+            .sum()
+    }
+
+    pub fn panic_total(&self) -> i64 {
+        SiteKind::ALL
+            .iter()
+            .filter(|k| !k.is_unsafe())
+            .map(|k| self.count(*k))
+            .sum()
+    }
+}
+
+/// Site counts of a snapshot at the requested level, most sites first
+pub fn safety_rows(
+    conn: &Connection,
+    snapshot_id: i64,
+    level: SafetyLevel,
+) -> Result<Vec<SafetyRow>, String> {
+    let files: HashMap<i64, _> = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect();
+    let sites = safety_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load safety sites: {}", e))?;
+
+    let mut groups: BTreeMap<String, SafetyRow> = BTreeMap::new();
+    for site in sites {
+        let Some(file) = files.get(&site.file_id) else {
+            continue;
+        };
+        let Some(kind_idx) =
+            SiteKind::parse(&site.kind).and_then(|k| SiteKind::ALL.iter().position(|a| *a == k))
+        else {
+            continue;
+        };
+
+        let (key, name, file_id) = match level {
+            SafetyLevel::Functions => {
+                let function = site
+                    .function
+                    .clone()
+                    .unwrap_or_else(|| "(item level)".to_string());
+                (
+                    format!("{}\u{0}{}", file.path, function),
+                    function,
+                    Some(file.id),
+                )
+            }
+            SafetyLevel::Files => (file.path.clone(), file.path.clone(), Some(file.id)),
+            SafetyLevel::CrateLayers => {
+                let layer = file
+                    .crate_layer
+                    .clone()
+                    .unwrap_or_else(|| "(none)".to_string());
+                (layer.clone(), layer, None)
+            }
+            SafetyLevel::Aggregates => match &file.artifact_id {
+                Some(id) => (code_for_artifact(id), code_for_artifact(id), None),
+                None => continue,
+            },
+        };
+
+        // Sites are ordered by file and line, so the first one seen is the topmost
+        let row = groups.entry(key).or_insert_with(|| SafetyRow {
+            name,
+            location: format!("{}:{}", file.path, site.line),
+            file_id,
+            line: Some(site.line as usize),
+            counts: [0; 7],
+        });
+        row.counts[kind_idx] += 1;
+    }
+
+    let mut rows: Vec<SafetyRow> = groups.into_values().collect();
+    if level != SafetyLevel::Functions {
+        for row in &mut rows {
+            row.location.clear();
+            row.line = None;
+        }
+    }
+    rows.sort_by_key(|r| std::cmp::Reverse(r.unsafe_total() + r.panic_total()));
+    Ok(rows)
+}
+
+/// (unsafe sites, panic sites) of the whole snapshot
+pub fn snapshot_totals(conn: &Connection, snapshot_id: i64) -> Result<(i64, i64), String> {
+    let rows = safety_rows(conn, snapshot_id, SafetyLevel::CrateLayers)?;
+    Ok((
+        rows.iter().map(|r| r.unsafe_total()).sum(),
+        rows.iter().map(|r| r.panic_total()).sum(),
+    ))
+}
+
+/// Threshold violations of the totals; empty when within limits or no limits are set
+pub fn threshold_violations(
+    max_unsafe: Option<i64>,
+    max_panics: Option<i64>,
+    totals: (i64, i64),
+) -> Vec<String> {
+    let mut out = Vec::new();
+    if let Some(max) = max_unsafe.filter(|max| totals.0 > *max) {
+        out.push(format!(
+            "{} unsafe sites exceed the threshold of {}",
+            totals.0, max
+        ));
+    }
+    if let Some(max) = max_panics.filter(|max| totals.1 > *max) {
+        out.push(format!(
+            "{} panic sites exceed the threshold of {}",
+            totals.1, max
+        ));
+    }
+    out
+}
+
+/// Project thresholds, optionally overridden
+pub fn effective_thresholds(
+    project: &Project,
+    max_unsafe: Option<i64>,
+    max_panics: Option<i64>,
+) -> (Option<i64>, Option<i64>) {
+    (
+        max_unsafe.or(project.max_unsafe_sites),
+        max_panics.or(project.max_panic_sites),
+    )
+}
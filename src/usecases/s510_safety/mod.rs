@@ -0,0 +1,307 @@
+pub mod inventory;
+
+use eframe::egui;
+use rusqlite::Connection;
+
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::shared::safety::SiteKind;
+use inventory::{safety_rows, threshold_violations, SafetyLevel, SafetyRow};
+
+pub struct SafetyState {
+    pub snapshot_picker: SnapshotPickerState,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    pub project_id: Option<i64>,
+    pub level: SafetyLevel,
+    pub filter: String,
+    pub rows: Vec<SafetyRow>,
+    /// (unsafe sites, panic sites) of the snapshot
+    pub totals: (i64, i64),
+    // Threshold editor: enabled flag and value per limit
+    pub limit_unsafe: (bool, i64),
+    pub limit_panics: (bool, i64),
+    pub status: String,
+}
+
+impl Default for SafetyState {
+    fn default() -> Self {
+        Self {
+            snapshot_picker: SnapshotPickerState::default(),
+            snapshot_id: None,
+            snapshot_name: String::new(),
+            project_id: None,
+            level: SafetyLevel::Files,
+            filter: String::new(),
+            rows: Vec::new(),
+            totals: (0, 0),
+            limit_unsafe: (false, 0),
+            limit_panics: (false, 0),
+            status: String::new(),
+        }
+    }
+}
+
+impl SafetyState {
+    fn select_snapshot(&mut self, conn: &Connection, snapshot_id: i64) {
+        self.snapshot_id = Some(snapshot_id);
+        self.status.clear();
+        if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
+            self.snapshot_name = snapshot.name;
+            self.project_id = Some(snapshot.project_id);
+            if let Ok(project) = project_repo::get_by_id(conn, snapshot.project_id) {
+                self.limit_unsafe = (
+                    project.max_unsafe_sites.is_some(),
+                    project.max_unsafe_sites.unwrap_or(0),
+                );
+                self.limit_panics = (
+                    project.max_panic_sites.is_some(),
+                    project.max_panic_sites.unwrap_or(0),
+                );
+            }
+        }
+        self.reload(conn);
+    }
+
+    fn reload(&mut self, conn: &Connection) {
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+        let totals = inventory::snapshot_totals(conn, snapshot_id);
+        match safety_rows(conn, snapshot_id, self.level).and_then(|rows| Ok((rows, totals?))) {
+            Ok((rows, totals)) => {
+                self.rows = rows;
+                self.totals = totals;
+            }
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.rows.clear();
+            }
+        }
+    }
+
+    fn limits(&self) -> (Option<i64>, Option<i64>) {
+        (
+            self.limit_unsafe.0.then_some(self.limit_unsafe.1),
+            self.limit_panics.0.then_some(self.limit_panics.1),
+        )
+    }
+}
+
+/// Renders the Unsafe & Panics tab. Returns `(file_id, line)` when a row is opened.
+pub fn ui_safety(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut SafetyState,
+) -> Option<(i64, Option<usize>)> {
+    ui.heading("Unsafe & Panics");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.select_snapshot(conn, snapshot_id);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Selected Snapshot:");
+        if state.snapshot_id.is_some() {
+            ui.label(egui::RichText::new(&state.snapshot_name).strong());
+        } else {
+            ui.label(egui::RichText::new("None").weak());
+        }
+        if ui.button("Select...").clicked() {
+            state.snapshot_picker.open();
+        }
+    });
+
+    state.snapshot_id?;
+
+    ui.add_space(8.0);
+
+    // Project thresholds, enforced by `navigator safety`
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Project thresholds").strong());
+            ui.checkbox(&mut state.limit_unsafe.0, "Max unsafe sites:");
+            ui.add_enabled(
+                state.limit_unsafe.0,
+                egui::DragValue::new(&mut state.limit_unsafe.1).range(0..=1_000_000),
+            );
+            ui.checkbox(&mut state.limit_panics.0, "Max panic sites:");
+            ui.add_enabled(
+                state.limit_panics.0,
+                egui::DragValue::new(&mut state.limit_panics.1).range(0..=1_000_000),
+            );
+            if ui.button("Save").clicked() {
+                if let Some(project_id) = state.project_id {
+                    let (max_unsafe, max_panics) = state.limits();
+                    state.status = match project_repo::set_safety_thresholds(
+                        conn, project_id, max_unsafe, max_panics,
+                    ) {
+                        Ok(_) => "Thresholds saved".to_string(),
+                        Err(e) => format!("Error saving thresholds: {}", e),
+                    };
+                }
+            }
+        });
+
+        let (max_unsafe, max_panics) = state.limits();
+        let violations = threshold_violations(max_unsafe, max_panics, state.totals);
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "Unsafe sites: {}   Panic sites: {}",
+                state.totals.0, state.totals.1
+            ));
+            if max_unsafe.is_none() && max_panics.is_none() {
+                ui.label(egui::RichText::new("No thresholds set").weak());
+            } else if violations.is_empty() {
+                ui.colored_label(egui::Color32::LIGHT_GREEN, "✔ Within thresholds");
+            } else {
+                ui.colored_label(
+                    egui::Color32::LIGHT_RED,
+                    format!("✖ {}", violations.join("; ")),
+                );
+            }
+        });
+    });
+
+    ui.add_space(6.0);
+
+    ui.horizontal(|ui| {
+        let level_before = state.level;
+        ui.selectable_value(&mut state.level, SafetyLevel::Functions, "Functions");
+        ui.selectable_value(&mut state.level, SafetyLevel::Files, "Files");
+        ui.selectable_value(&mut state.level, SafetyLevel::CrateLayers, "Crate layers");
+        ui.selectable_value(&mut state.level, SafetyLevel::Aggregates, "Aggregates");
+        if state.level != level_before {
+            state.reload(conn);
+        }
+        ui.separator();
+        ui.label("Filter:");
+        ui.text_edit_singleline(&mut state.filter);
+        if ui.button("Refresh").clicked() {
+            state.reload(conn);
+        }
+    });
+
+    if !state.status.is_empty() {
+        let color = if state.status.starts_with("Error") {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        ui.colored_label(color, &state.status);
+    }
+
+    if state.rows.is_empty() {
+        ui.label(
+            egui::RichText::new("No sites recorded. Rescan the snapshot to collect them.").weak(),
+        );
+        return None;
+    }
+
+    let filter = state.filter.to_lowercase();
+    let visible: Vec<&SafetyRow> = state
+        .rows
+        .iter()
+        .filter(|r| {
+            filter.is_empty()
+                || r.name.to_lowercase().contains(&filter)
+                || r.location.to_lowercase().contains(&filter)
+        })
+        .collect();
+
+    ui.add_space(6.0);
+    render_table(ui, &visible, state.level)
+}
+
+fn render_table(
+    ui: &mut egui::Ui,
+    rows: &[&SafetyRow],
+    level: SafetyLevel,
+) -> Option<(i64, Option<usize>)> {
+    use egui_extras::{Column, TableBuilder};
+
+    let mut opened = None;
+    let first_header = match level {
+        SafetyLevel::Functions => "Function",
+        SafetyLevel::Files => "File",
+        SafetyLevel::CrateLayers => "Crate layer",
+        SafetyLevel::Aggregates => "Aggregate",
+    };
+    let with_location = level == SafetyLevel::Functions;
+
+    let mut table = TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto().at_least(260.0));
+    if with_location {
+        table = table.column(Column::auto().at_least(260.0));
+    }
+    for _ in 0..SiteKind::ALL.len() + 2 {
+        table = table.column(Column::auto().at_least(60.0));
+    }
+
+    table
+        .header(20.0, |mut header| {
+            header.col(|ui| {
+                ui.strong(first_header);
+            });
+            if with_location {
+                header.col(|ui| {
+                    ui.strong("First site");
+                });
+            }
+            for title in SiteKind::ALL
+                .iter()
+                .map(|k| k.as_str())
+                .chain(["Unsafe", "Panics"])
+            {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(18.0, rows.len(), |mut row| {
+                let r = rows[row.index()];
+                row.col(|ui| {
+                    if let Some(file_id) = r.file_id {
+                        if ui.link(&r.name).clicked() {
+                            opened = Some((file_id, r.line));
+                        }
+                    } else {
+                        ui.label(&r.name);
+                    }
+                });
+                if with_location {
+                    row.col(|ui| {
+                        ui.label(egui::RichText::new(&r.location).weak());
+                    });
+                }
+                for count in r.counts {
+                    row.col(|ui| {
+                        if count == 0 {
+                            ui.label(egui::RichText::new("·").weak());
+                        } else {
+                            ui.label(count.to_string());
+                        }
+                    });
+                }
+                row.col(|ui| {
+                    let total = r.unsafe_total();
+                    if total > 0 {
+                        ui.colored_label(egui::Color32::LIGHT_RED, total.to_string());
+                    } else {
+                        ui.label("0");
+                    }
+                });
+                row.col(|ui| {
+                    ui.strong(r.panic_total().to_string());
+                });
+            });
+        });
+
+    opened
+}
@@ -0,0 +1,78 @@
+use rusqlite::Connection;
+use std::fs;
+use std::path::Path;
+
+use super::report::{parse_report, PathMatcher};
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n014_file_coverage::model::{encode_line_hits, FileCoverage};
+use crate::domain::n014_file_coverage::repository as coverage_repo;
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportSummary {
+    pub matched: usize,
+    /// Report paths with no snapshot file
+    pub unmatched: Vec<String>,
+    pub lines_found: i64,
+    pub lines_hit: i64,
+}
+
+/// Replace the coverage of a snapshot with an lcov / Cobertura report
+pub fn import_report(
+    conn: &Connection,
+    snapshot_id: i64,
+    report_path: &Path,
+) -> Result<ImportSummary, String> {
+    let text = fs::read_to_string(report_path)
+        .map_err(|e| format!("Failed to read {}: {}", report_path.display(), e))?;
+    let report = parse_report(&text)?;
+
+    let snapshot = snapshot_repo::get_by_id(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load snapshot: {}", e))?;
+    let project = project_repo::get_by_id(conn, snapshot.project_id)
+        .map_err(|e| format!("Failed to load project: {}", e))?;
+    let files: Vec<_> = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?
+        .into_iter()
+        .filter(|f| !f.is_directory)
+        .collect();
+    let paths: Vec<String> = files.iter().map(|f| f.path.replace('\\', "/")).collect();
+    let matcher = PathMatcher::new(&project.root_path, &paths);
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    coverage_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing coverage: {}", e))?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let source = report_path.display().to_string();
+    let mut summary = ImportSummary::default();
+    for record in &report.files {
+        let Some(idx) = matcher.find(&record.path, &report.sources) else {
+            summary.unmatched.push(record.path.clone());
+            continue;
+        };
+        let coverage = FileCoverage::new(
+            files[idx].id,
+            snapshot_id,
+            record.lines.len() as i64,
+            record.lines_hit() as i64,
+            record.branches_found() as i64,
+            record.branches_hit() as i64,
+            encode_line_hits(&record.lines),
+            source.clone(),
+            now.clone(),
+        );
+        coverage_repo::upsert(conn, &coverage)
+            .map_err(|e| format!("Failed to store coverage: {}", e))?;
+        summary.matched += 1;
+        summary.lines_found += coverage.lines_found;
+        summary.lines_hit += coverage.lines_hit;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit coverage: {}", e))?;
+    Ok(summary)
+}
@@ -0,0 +1,266 @@
+pub mod import;
+pub mod report;
+pub mod rollup;
+
+use eframe::egui;
+use rusqlite::Connection;
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::domain::n014_file_coverage::model::rate;
+use crate::domain::n014_file_coverage::ui::coverage_label;
+use import::import_report;
+use rollup::{coverage_rows, CoverageLevel, CoverageRow};
+
+pub struct CoverageState {
+    pub snapshot_picker: SnapshotPickerState,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    pub level: CoverageLevel,
+    pub filter: String,
+    pub rows: Vec<CoverageRow>,
+    /// Report paths of the last import that matched no snapshot file
+    pub unmatched: Vec<String>,
+    pub status: String,
+}
+
+impl Default for CoverageState {
+    fn default() -> Self {
+        Self {
+            snapshot_picker: SnapshotPickerState::default(),
+            snapshot_id: None,
+            snapshot_name: String::new(),
+            level: CoverageLevel::Files,
+            filter: String::new(),
+            rows: Vec::new(),
+            unmatched: Vec::new(),
+            status: String::new(),
+        }
+    }
+}
+
+impl CoverageState {
+    fn reload(&mut self, conn: &Connection) {
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+        match coverage_rows(conn, snapshot_id, self.level) {
+            Ok(rows) => self.rows = rows,
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.rows.clear();
+            }
+        }
+    }
+
+    fn import(&mut self, conn: &Connection) {
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Coverage report", &["info", "lcov", "xml"])
+            .add_filter("All files", &["*"])
+            .pick_file()
+        else {
+            return;
+        };
+        match import_report(conn, snapshot_id, &path) {
+            Ok(summary) => {
+                let total = rate(summary.lines_hit, summary.lines_found).unwrap_or(0.0) * 100.0;
+                self.status = format!(
+                    "Imported {} files ({:.1}% lines), {} records unmatched",
+                    summary.matched,
+                    total,
+                    summary.unmatched.len()
+                );
+                self.unmatched = summary.unmatched;
+            }
+            Err(e) => self.status = format!("Error: {}", e),
+        }
+        self.reload(conn);
+    }
+}
+
+/// Renders the Coverage tab. Returns `(file_id, line)` when a file is opened.
+pub fn ui_coverage(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut CoverageState,
+) -> Option<(i64, Option<usize>)> {
+    ui.heading("Coverage");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.snapshot_id = Some(snapshot_id);
+        if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
+            state.snapshot_name = snapshot.name;
+        }
+        state.status.clear();
+        state.unmatched.clear();
+        state.reload(conn);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Selected Snapshot:");
+        if state.snapshot_id.is_some() {
+            ui.label(egui::RichText::new(&state.snapshot_name).strong());
+        } else {
+            ui.label(egui::RichText::new("None").weak());
+        }
+        if ui.button("Select...").clicked() {
+            state.snapshot_picker.open();
+        }
+        ui.separator();
+        if ui
+            .add_enabled(
+                state.snapshot_id.is_some(),
+                egui::Button::new("Import lcov / Cobertura..."),
+            )
+            .clicked()
+        {
+            state.import(conn);
+        }
+    });
+
+    ui.add_space(8.0);
+
+    ui.horizontal(|ui| {
+        let level_before = state.level;
+        ui.selectable_value(&mut state.level, CoverageLevel::Files, "Files");
+        ui.selectable_value(&mut state.level, CoverageLevel::CrateLayers, "Crate layers");
+        ui.selectable_value(&mut state.level, CoverageLevel::Aggregates, "Aggregates");
+        if state.level != level_before {
+            state.reload(conn);
+        }
+        ui.separator();
+        ui.label("Filter:");
+        ui.text_edit_singleline(&mut state.filter);
+    });
+
+    if !state.status.is_empty() {
+        let color = if state.status.starts_with("Error") {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        ui.colored_label(color, &state.status);
+    }
+
+    if !state.unmatched.is_empty() {
+        egui::CollapsingHeader::new(format!(
+            "Unmatched report paths ({})",
+            state.unmatched.len()
+        ))
+        .id_salt("coverage_unmatched")
+        .show(ui, |ui| {
+            for path in &state.unmatched {
+                ui.label(egui::RichText::new(path).monospace().weak());
+            }
+        });
+    }
+
+    if state.snapshot_id.is_some() && state.rows.is_empty() {
+        ui.label(egui::RichText::new("No coverage imported for this snapshot.").weak());
+        return None;
+    }
+
+    let total = state
+        .rows
+        .iter()
+        .fold(CoverageRow::default(), |mut acc, r| {
+            acc.lines_found += r.lines_found;
+            acc.lines_hit += r.lines_hit;
+            acc.branches_found += r.branches_found;
+            acc.branches_hit += r.branches_hit;
+            acc
+        });
+    ui.horizontal(|ui| {
+        ui.label("Lines:");
+        coverage_label(ui, total.line_rate());
+        ui.label(format!("({} / {})", total.lines_hit, total.lines_found));
+        ui.label("Branches:");
+        coverage_label(ui, total.branch_rate());
+        ui.label(format!(
+            "({} / {})",
+            total.branches_hit, total.branches_found
+        ));
+    });
+
+    let filter = state.filter.to_lowercase();
+    let visible: Vec<&CoverageRow> = state
+        .rows
+        .iter()
+        .filter(|r| filter.is_empty() || r.name.to_lowercase().contains(&filter))
+        .collect();
+
+    ui.add_space(6.0);
+    render_table(ui, &visible, state.level)
+}
+
+fn render_table(
+    ui: &mut egui::Ui,
+    rows: &[&CoverageRow],
+    level: CoverageLevel,
+) -> Option<(i64, Option<usize>)> {
+    use egui_extras::{Column, TableBuilder};
+
+    let mut opened = None;
+    let first_header = match level {
+        CoverageLevel::Files => "File",
+        CoverageLevel::CrateLayers => "Crate layer",
+        CoverageLevel::Aggregates => "Aggregate",
+    };
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto().at_least(320.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::auto().at_least(80.0))
+        .column(Column::auto().at_least(110.0))
+        .column(Column::auto().at_least(80.0))
+        .column(Column::auto().at_least(110.0))
+        .header(20.0, |mut header| {
+            for title in [
+                first_header,
+                "Files",
+                "Lines",
+                "Lines hit",
+                "Branches",
+                "Branches hit",
+            ] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(18.0, rows.len(), |mut row| {
+                let r = rows[row.index()];
+                row.col(|ui| match r.file_id {
+                    Some(file_id) => {
+                        if ui.link(&r.name).clicked() {
+                            opened = Some((file_id, None));
+                        }
+                    }
+                    None => {
+                        ui.label(&r.name);
+                    }
+                });
+                row.col(|ui| {
+                    ui.label(r.files.to_string());
+                });
+                row.col(|ui| coverage_label(ui, r.line_rate()));
+                row.col(|ui| {
+                    ui.label(format!("{} / {}", r.lines_hit, r.lines_found));
+                });
+                row.col(|ui| coverage_label(ui, r.branch_rate()));
+                row.col(|ui| {
+                    ui.label(format!("{} / {}", r.branches_hit, r.branches_found));
+                });
+            });
+        });
+
+    opened
+}
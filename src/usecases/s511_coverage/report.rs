@@ -0,0 +1,338 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// Coverage of one source file as read from a report
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReportFile {
+    /// Path as written in the report (absolute or relative)
+    pub path: String,
+    /// line -> execution count
+    pub lines: BTreeMap<usize, u64>,
+    /// line -> (branches taken, branches)
+    pub branches: BTreeMap<usize, (usize, usize)>,
+}
+
+impl ReportFile {
+    pub fn lines_hit(&self) -> usize {
+        self.lines.values().filter(|h| **h > 0).count()
+    }
+
+    pub fn branches_found(&self) -> usize {
+        self.branches.values().map(|b| b.1).sum()
+    }
+
+    pub fn branches_hit(&self) -> usize {
+        self.branches.values().map(|b| b.0).sum()
+    }
+
+    fn merge(&mut self, other: ReportFile) {
+        for (line, hits) in other.lines {
+            *self.lines.entry(line).or_default() += hits;
+        }
+        for (line, (hit, found)) in other.branches {
+            let entry = self.branches.entry(line).or_default();
+            entry.0 = entry.0.max(hit);
+            entry.1 = entry.1.max(found);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// Cobertura `<source>` directories report paths are relative to
+    pub sources: Vec<String>,
+    pub files: Vec<ReportFile>,
+}
+
+/// Parse an lcov tracefile or a Cobertura XML report (detected by content)
+pub fn parse_report(text: &str) -> Result<Report, String> {
+    let report = if text.trim_start().starts_with('<') {
+        parse_cobertura(text)
+    } else {
+        parse_lcov(text)
+    };
+    if report.files.is_empty() {
+        return Err("No file records found (expected lcov or Cobertura XML)".to_string());
+    }
+    Ok(report)
+}
+
+/// Records of the same file (several test binaries) are merged
+fn collect(records: Vec<ReportFile>) -> Vec<ReportFile> {
+    let mut by_path: BTreeMap<String, ReportFile> = BTreeMap::new();
+    for record in records {
+        match by_path.get_mut(&record.path) {
+            Some(existing) => existing.merge(record),
+            None => {
+                by_path.insert(record.path.clone(), record);
+            }
+        }
+    }
+    by_path.into_values().collect()
+}
+
+fn parse_lcov(text: &str) -> Report {
+    let mut records = Vec::new();
+    let mut current: Option<ReportFile> = None;
+    // (line, block, branch) -> taken, so repeated BRDA entries are not double counted
+    let mut branch_taken: BTreeMap<(usize, String, String), bool> = BTreeMap::new();
+
+    for line in text.lines().map(str::trim) {
+        if let Some(path) = line.strip_prefix("SF:") {
+            current = Some(ReportFile {
+                path: path.to_string(),
+                ..Default::default()
+            });
+            branch_taken.clear();
+        } else if line == "end_of_record" {
+            if let Some(mut file) = current.take() {
+                for ((line, _, _), taken) in std::mem::take(&mut branch_taken) {
+                    let entry = file.branches.entry(line).or_default();
+                    entry.1 += 1;
+                    if taken {
+                        entry.0 += 1;
+                    }
+                }
+                records.push(file);
+            }
+        } else if let Some(file) = current.as_mut() {
+            if let Some(data) = line.strip_prefix("DA:") {
+                let mut parts = data.split(',');
+                let line_no = parts.next().and_then(|v| v.parse::<usize>().ok());
+                let hits = parts.next().and_then(|v| v.parse::<f64>().ok());
+                if let (Some(line_no), Some(hits)) = (line_no, hits) {
+                    *file.lines.entry(line_no).or_default() += hits.max(0.0) as u64;
+                }
+            } else if let Some(data) = line.strip_prefix("BRDA:") {
+                let parts: Vec<&str> = data.split(',').collect();
+                if let [line_no, block, branch, taken] = parts[..] {
+                    if let Ok(line_no) = line_no.parse::<usize>() {
+                        let taken = taken != "-" && taken.parse::<u64>().is_ok_and(|t| t > 0);
+                        let entry = branch_taken
+                            .entry((line_no, block.to_string(), branch.to_string()))
+                            .or_default();
+                        *entry |= taken;
+                    }
+                }
+            }
+        }
+    }
+
+    Report {
+        sources: Vec::new(),
+        files: collect(records),
+    }
+}
+
+/// Value of `name="..."` in an XML start tag
+fn attr(tag: &str, name: &str) -> Option<String> {
+    let needle = format!(" {}=\"", name);
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')? + start;
+    Some(unescape(&tag[start..end]))
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn parse_cobertura(text: &str) -> Report {
+    let mut sources = Vec::new();
+    let mut records: Vec<ReportFile> = Vec::new();
+    let mut current: Option<ReportFile> = None;
+
+    let mut rest = text;
+    while let Some(open) = rest.find('<') {
+        let Some(close) = rest[open..].find('>').map(|c| c + open) else {
+            break;
+        };
+        let tag = &rest[open..=close];
+        let after = &rest[close + 1..];
+
+        if tag == "<source>" {
+            if let Some(end) = after.find("</source>") {
+                sources.push(unescape(after[..end].trim()));
+            }
+        } else if tag.starts_with("<class ") {
+            if let Some(file) = current.take() {
+                records.push(file);
+            }
+            current = attr(tag, "filename").map(|path| ReportFile {
+                path,
+                ..Default::default()
+            });
+        } else if tag.starts_with("</class") {
+            if let Some(file) = current.take() {
+                records.push(file);
+            }
+        } else if tag.starts_with("<line ") {
+            if let Some(file) = current.as_mut() {
+                let number = attr(tag, "number").and_then(|v| v.parse::<usize>().ok());
+                let hits = attr(tag, "hits").and_then(|v| v.parse::<u64>().ok());
+                if let (Some(number), Some(hits)) = (number, hits) {
+                    // Method and class sections repeat the same lines
+                    let entry = file.lines.entry(number).or_default();
+                    *entry = (*entry).max(hits);
+                    // condition-coverage="50% (1/2)"
+                    let conditions = attr(tag, "condition-coverage").and_then(|c| {
+                        let inner = c.split('(').nth(1)?.trim_end_matches(')').to_string();
+                        let (hit, found) = inner.split_once('/')?;
+                        Some((
+                            hit.trim().parse::<usize>().ok()?,
+                            found.trim().parse::<usize>().ok()?,
+                        ))
+                    });
+                    if let Some(branch) = conditions {
+                        file.branches.insert(number, branch);
+                    }
+                }
+            }
+        }
+        rest = after;
+    }
+    if let Some(file) = current.take() {
+        records.push(file);
+    }
+
+    Report {
+        sources,
+        files: collect(records),
+    }
+}
+
+fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.strip_prefix("./").unwrap_or(&path);
+    path.trim_end_matches('/').to_string()
+}
+
+/// Maps report paths onto snapshot file paths (relative to the project root)
+pub struct PathMatcher<'a> {
+    root: String,
+    exact: HashMap<&'a str, usize>,
+    /// file name -> indices of snapshot paths with that name
+    by_name: HashMap<&'a str, Vec<usize>>,
+    paths: &'a [String],
+}
+
+impl<'a> PathMatcher<'a> {
+    pub fn new(root: &str, paths: &'a [String]) -> Self {
+        let mut exact = HashMap::new();
+        let mut by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, path) in paths.iter().enumerate() {
+            exact.insert(path.as_str(), i);
+            let name = path.rsplit('/').next().unwrap_or(path);
+            by_name.entry(name).or_default().push(i);
+        }
+        Self {
+            root: normalize(root),
+            exact,
+            by_name,
+            paths,
+        }
+    }
+
+    /// Index of the snapshot path a report path refers to
+    pub fn find(&self, report_path: &str, sources: &[String]) -> Option<usize> {
+        let path = normalize(report_path);
+        let mut candidates = vec![path.clone()];
+        for source in sources {
+            candidates.push(format!("{}/{}", normalize(source), path));
+        }
+
+        for candidate in &candidates {
+            let relative = candidate
+                .strip_prefix(&self.root)
+                .and_then(|r| r.strip_prefix('/'))
+                .unwrap_or(candidate);
+            if let Some(&i) = self.exact.get(relative) {
+                return Some(i);
+            }
+        }
+
+        // Reports produced in another checkout: match on the longest common path suffix
+        let name = path.rsplit('/').next()?;
+        let mut best: Option<(usize, usize)> = None;
+        let mut tie = false;
+        for &i in self.by_name.get(name)? {
+            let overlap = common_suffix_segments(&path, &self.paths[i]);
+            match best {
+                Some((_, len)) if overlap < len => {}
+                Some((_, len)) if overlap == len => tie = true,
+                _ => {
+                    best = Some((i, overlap));
+                    tie = false;
+                }
+            }
+        }
+        if tie {
+            return None;
+        }
+        best.map(|(i, _)| i)
+    }
+}
+
+fn common_suffix_segments(a: &str, b: &str) -> usize {
+    a.rsplit('/')
+        .zip(b.rsplit('/'))
+        .take_while(|(x, y)| x == y)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lcov_and_cobertura() {
+        let lcov = "TN:\nSF:/work/app/src/main.rs\nDA:1,3\nDA:2,0\nBRDA:1,0,0,2\nBRDA:1,0,1,-\nend_of_record\n\
+                    SF:/work/app/src/main.rs\nDA:2,1\nend_of_record\n";
+        let report = parse_report(lcov).unwrap();
+        assert_eq!(report.files.len(), 1);
+        let file = &report.files[0];
+        assert_eq!(file.lines, BTreeMap::from([(1, 3), (2, 1)]));
+        assert_eq!(
+            (file.lines_hit(), file.branches_hit(), file.branches_found()),
+            (2, 1, 2)
+        );
+
+        let xml = r#"<?xml version="1.0" ?>
+<coverage><sources><source>/work/app</source></sources><packages><package name="app"><classes>
+<class name="lib" filename="src/lib.rs"><methods><method name="f"><lines><line number="4" hits="2"/></lines></method></methods>
+<lines><line number="4" hits="2" branch="true" condition-coverage="50% (1/2)"/><line number="5" hits="0"/></lines></class>
+</classes></package></packages></coverage>"#;
+        let report = parse_report(xml).unwrap();
+        assert_eq!(report.sources, vec!["/work/app".to_string()]);
+        let file = &report.files[0];
+        assert_eq!(file.path, "src/lib.rs");
+        assert_eq!(file.lines, BTreeMap::from([(4, 2), (5, 0)]));
+        assert_eq!(file.branches, BTreeMap::from([(4, (1, 2))]));
+    }
+
+    #[test]
+    fn matches_report_paths_to_snapshot_files() {
+        let paths: Vec<String> = [
+            "src/main.rs",
+            "src/a/mod.rs",
+            "src/b/mod.rs",
+            "crates/core/src/lib.rs",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let matcher = PathMatcher::new("/work/app", &paths);
+        assert_eq!(matcher.find("/work/app/src/main.rs", &[]), Some(0));
+        assert_eq!(matcher.find("src/a/mod.rs", &[]), Some(1));
+        assert_eq!(
+            matcher.find("lib.rs", &["/work/app/crates/core/src".to_string()]),
+            Some(3)
+        );
+        assert_eq!(matcher.find("/ci/build/src/b/mod.rs", &[]), Some(2));
+        // Ambiguous and unknown files stay unmatched
+        assert_eq!(matcher.find("/elsewhere/mod.rs", &[]), None);
+        assert_eq!(matcher.find("/work/app/src/other.rs", &[]), None);
+    }
+}
@@ -0,0 +1,91 @@
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n004_snapshot_aggregate::model::code_for_artifact;
+use crate::domain::n014_file_coverage::model::rate;
+use crate::domain::n014_file_coverage::repository as coverage_repo;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoverageLevel {
+    Files,
+    CrateLayers,
+    Aggregates,
+}
+
+/// Coverage of a file, or of the covered files of a crate layer / aggregate
+#[derive(Debug, Clone, Default)]
+pub struct CoverageRow {
+    /// File path, crate layer or aggregate code
+    pub name: String,
+    /// Set for file rows (used to open the preview)
+    pub file_id: Option<i64>,
+    pub files: i64,
+    pub lines_found: i64,
+    pub lines_hit: i64,
+    pub branches_found: i64,
+    pub branches_hit: i64,
+}
+
+impl CoverageRow {
+    pub fn line_rate(&self) -> Option<f64> {
+        rate(self.lines_hit, self.lines_found)
+    }
+
+    pub fn branch_rate(&self) -> Option<f64> {
+        rate(self.branches_hit, self.branches_found)
+    }
+}
+
+/// Coverage rows of a snapshot at the requested level, least covered first
+pub fn coverage_rows(
+    conn: &Connection,
+    snapshot_id: i64,
+    level: CoverageLevel,
+) -> Result<Vec<CoverageRow>, String> {
+    let files: HashMap<i64, _> = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect();
+    let coverage = coverage_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load coverage: {}", e))?;
+
+    let mut groups: BTreeMap<String, CoverageRow> = BTreeMap::new();
+    for c in coverage {
+        let Some(file) = files.get(&c.file_id) else {
+            continue;
+        };
+        let (name, file_id) = match level {
+            CoverageLevel::Files => (file.path.clone(), Some(file.id)),
+            CoverageLevel::CrateLayers => (
+                file.crate_layer
+                    .clone()
+                    .unwrap_or_else(|| "(none)".to_string()),
+                None,
+            ),
+            CoverageLevel::Aggregates => match &file.artifact_id {
+                Some(id) => (code_for_artifact(id), None),
+                None => continue,
+            },
+        };
+        let row = groups.entry(name.clone()).or_insert_with(|| CoverageRow {
+            name,
+            file_id,
+            ..Default::default()
+        });
+        row.files += 1;
+        row.lines_found += c.lines_found;
+        row.lines_hit += c.lines_hit;
+        row.branches_found += c.branches_found;
+        row.branches_hit += c.branches_hit;
+    }
+
+    let mut rows: Vec<CoverageRow> = groups.into_values().collect();
+    rows.sort_by(|a, b| {
+        a.line_rate()
+            .unwrap_or(1.0)
+            .total_cmp(&b.line_rate().unwrap_or(1.0))
+    });
+    Ok(rows)
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use crate::domain::n015_public_item::model::PublicItem;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    /// Potential semver break
+    Removed,
+    /// Potential semver break
+    Changed,
+    Added,
+}
+
+impl ChangeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ChangeKind::Removed => "removed",
+            ChangeKind::Changed => "changed",
+            ChangeKind::Added => "added",
+        }
+    }
+
+    pub fn is_breaking(self) -> bool {
+        !matches!(self, ChangeKind::Added)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiChange {
+    pub kind: ChangeKind,
+    pub crate_name: String,
+    pub item_kind: String,
+    pub path: String,
+    /// Signature in the base snapshot (removed / changed)
+    pub before: Option<String>,
+    /// Signature in the target snapshot (added / changed), with its location
+    pub after: Option<String>,
+    pub file_id: Option<i64>,
+    pub line: Option<usize>,
+}
+
+type Key = (String, String, String);
+
+fn key(item: &PublicItem) -> Key {
+    (
+        item.crate_name.clone(),
+        item.path.clone(),
+        item.kind.clone(),
+    )
+}
+
+/// Public items removed, changed or added between two snapshots, breaking changes first
+pub fn diff_api(base: &[PublicItem], target: &[PublicItem]) -> Vec<ApiChange> {
+    // cfg-duplicated items: the first declaration wins
+    let mut before: BTreeMap<Key, &PublicItem> = BTreeMap::new();
+    for item in base {
+        before.entry(key(item)).or_insert(item);
+    }
+    let mut after: BTreeMap<Key, &PublicItem> = BTreeMap::new();
+    for item in target {
+        after.entry(key(item)).or_insert(item);
+    }
+
+    let mut out = Vec::new();
+    for (k, old) in &before {
+        let change = match after.get(k) {
+            None => ApiChange {
+                kind: ChangeKind::Removed,
+                crate_name: k.0.clone(),
+                item_kind: k.2.clone(),
+                path: k.1.clone(),
+                before: Some(old.signature.clone()),
+                after: None,
+                file_id: None,
+                line: None,
+            },
+            Some(new) if new.signature != old.signature => ApiChange {
+                kind: ChangeKind::Changed,
+                crate_name: k.0.clone(),
+                item_kind: k.2.clone(),
+                path: k.1.clone(),
+                before: Some(old.signature.clone()),
+                after: Some(new.signature.clone()),
+                file_id: Some(new.file_id),
+                line: Some(new.line as usize),
+            },
+            Some(_) => continue,
+        };
+        out.push(change);
+    }
+    for (k, new) in &after {
+        if !before.contains_key(k) {
+            out.push(ApiChange {
+                kind: ChangeKind::Added,
+                crate_name: k.0.clone(),
+                item_kind: k.2.clone(),
+                path: k.1.clone(),
+                before: None,
+                after: Some(new.signature.clone()),
+                file_id: Some(new.file_id),
+                line: Some(new.line as usize),
+            });
+        }
+    }
+
+    out.sort_by(|a, b| {
+        (
+            a.crate_name.as_str(),
+            !a.kind.is_breaking(),
+            a.path.as_str(),
+        )
+            .cmp(&(
+                b.crate_name.as_str(),
+                !b.kind.is_breaking(),
+                b.path.as_str(),
+            ))
+    });
+    out
+}
+
+/// Markdown checklist for release reviews
+pub fn checklist(changes: &[ApiChange]) -> String {
+    let mut out = String::new();
+    let mut current_crate = "";
+    for c in changes {
+        if c.crate_name != current_crate {
+            current_crate = &c.crate_name;
+            out.push_str(&format!("\n### {}\n\n", current_crate));
+        }
+        let signature = match (&c.before, &c.after) {
+            (Some(before), Some(after)) => format!("`{}` → `{}`", before, after),
+            (Some(sig), None) | (None, Some(sig)) => format!("`{}`", sig),
+            (None, None) => String::new(),
+        };
+        let breaking = if c.kind.is_breaking() {
+            " **(breaking)**"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "- [ ] {} {} `{}`{}: {}\n",
+            c.kind.label(),
+            c.item_kind,
+            c.path,
+            breaking,
+            signature
+        ));
+    }
+    out.trim_start().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(path: &str, signature: &str) -> PublicItem {
+        PublicItem::new(
+            0,
+            1,
+            7,
+            "contracts".into(),
+            "fn".into(),
+            path.into(),
+            signature.into(),
+            3,
+            true,
+        )
+    }
+
+    #[test]
+    fn classifies_api_changes() {
+        let base = vec![
+            item("a", "pub fn a()"),
+            item("b", "pub fn b(x: u8)"),
+            item("c", "pub fn c()"),
+        ];
+        let target = vec![
+            item("a", "pub fn a()"),
+            item("b", "pub fn b(x: u16)"),
+            item("d", "pub fn d()"),
+        ];
+        let changes: Vec<(ChangeKind, String)> = diff_api(&base, &target)
+            .into_iter()
+            .map(|c| (c.kind, c.path))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (ChangeKind::Changed, "b".to_string()),
+                (ChangeKind::Removed, "c".to_string()),
+                (ChangeKind::Added, "d".to_string()),
+            ]
+        );
+    }
+}
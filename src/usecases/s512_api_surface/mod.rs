@@ -0,0 +1,352 @@
+pub mod diff;
+
+use eframe::egui;
+use rusqlite::Connection;
+use std::collections::HashSet;
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::domain::n015_public_item::model::PublicItem;
+use crate::domain::n015_public_item::repository as item_repo;
+use diff::{checklist, diff_api, ApiChange, ChangeKind};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PickTarget {
+    Snapshot,
+    Base,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ApiView {
+    Items,
+    Changes,
+}
+
+pub struct ApiSurfaceState {
+    pub snapshot_picker: SnapshotPickerState,
+    picking: Option<PickTarget>,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    /// Earlier snapshot (e.g. the last release) the selected one is compared against
+    pub base_id: Option<i64>,
+    pub base_name: String,
+    pub view: ApiView,
+    pub filter: String,
+    pub breaking_only: bool,
+    pub items: Vec<PublicItem>,
+    pub changes: Vec<ApiChange>,
+    /// Reviewed changes (crate, path, kind label)
+    pub reviewed: HashSet<(String, String, &'static str)>,
+    pub status: String,
+}
+
+impl Default for ApiSurfaceState {
+    fn default() -> Self {
+        Self {
+            snapshot_picker: SnapshotPickerState::default(),
+            picking: None,
+            snapshot_id: None,
+            snapshot_name: String::new(),
+            base_id: None,
+            base_name: String::new(),
+            view: ApiView::Items,
+            filter: String::new(),
+            breaking_only: false,
+            items: Vec::new(),
+            changes: Vec::new(),
+            reviewed: HashSet::new(),
+            status: String::new(),
+        }
+    }
+}
+
+impl ApiSurfaceState {
+    fn reload(&mut self, conn: &Connection) {
+        self.status.clear();
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+        match item_repo::list_by_snapshot(conn, snapshot_id) {
+            Ok(items) => self.items = items,
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.items.clear();
+            }
+        }
+
+        self.changes.clear();
+        self.reviewed.clear();
+        if let Some(base_id) = self.base_id {
+            match item_repo::list_by_snapshot(conn, base_id) {
+                Ok(base) => {
+                    self.changes = diff_api(&base, &self.items);
+                    self.view = ApiView::Changes;
+                }
+                Err(e) => self.status = format!("Error: {}", e),
+            }
+        }
+    }
+
+    fn visible_changes(&self) -> Vec<&ApiChange> {
+        let filter = self.filter.to_lowercase();
+        self.changes
+            .iter()
+            .filter(|c| !self.breaking_only || c.kind.is_breaking())
+            .filter(|c| {
+                filter.is_empty()
+                    || c.path.to_lowercase().contains(&filter)
+                    || c.crate_name.to_lowercase().contains(&filter)
+            })
+            .collect()
+    }
+}
+
+fn review_key(c: &ApiChange) -> (String, String, &'static str) {
+    (c.crate_name.clone(), c.path.clone(), c.kind.label())
+}
+
+/// Renders the API Surface tab. Returns `(file_id, line)` when an item is opened.
+pub fn ui_api_surface(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut ApiSurfaceState,
+) -> Option<(i64, Option<usize>)> {
+    let mut opened = None;
+
+    ui.heading("API Surface");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        let name = snapshot_repo::get_by_id(conn, snapshot_id)
+            .map(|s| s.name)
+            .unwrap_or_default();
+        match state.picking.take() {
+            Some(PickTarget::Snapshot) => {
+                state.snapshot_id = Some(snapshot_id);
+                state.snapshot_name = name;
+            }
+            Some(PickTarget::Base) => {
+                state.base_id = Some(snapshot_id);
+                state.base_name = name;
+            }
+            None => {}
+        }
+        state.reload(conn);
+    }
+
+    for (label, target) in [
+        ("Snapshot:", PickTarget::Snapshot),
+        ("Compare against:", PickTarget::Base),
+    ] {
+        ui.horizontal(|ui| {
+            ui.label(label);
+            let name = match target {
+                PickTarget::Snapshot => state.snapshot_id.map(|_| &state.snapshot_name),
+                PickTarget::Base => state.base_id.map(|_| &state.base_name),
+            };
+            match name {
+                Some(name) => ui.label(egui::RichText::new(name).strong()),
+                None => ui.label(egui::RichText::new("None").weak()),
+            };
+            if ui.button("Select...").clicked() {
+                state.picking = Some(target);
+                state.snapshot_picker.open();
+            }
+        });
+    }
+
+    ui.add_space(8.0);
+
+    let breaking = state
+        .changes
+        .iter()
+        .filter(|c| c.kind.is_breaking())
+        .count();
+    ui.horizontal(|ui| {
+        ui.selectable_value(
+            &mut state.view,
+            ApiView::Items,
+            format!("Public items ({})", state.items.len()),
+        );
+        let changes_label = format!("Changes ({}, {} breaking)", state.changes.len(), breaking);
+        ui.add_enabled_ui(state.base_id.is_some(), |ui| {
+            ui.selectable_value(&mut state.view, ApiView::Changes, changes_label);
+        });
+        ui.separator();
+        ui.label("Filter:");
+        ui.text_edit_singleline(&mut state.filter);
+        if state.view == ApiView::Changes {
+            ui.checkbox(&mut state.breaking_only, "Breaking only");
+            if ui.button("Copy checklist").clicked() {
+                let text = checklist(
+                    &state
+                        .visible_changes()
+                        .into_iter()
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                );
+                ui.output_mut(|o| o.copied_text = text);
+                state.status = "Checklist copied to clipboard".to_string();
+            }
+        }
+        if ui.button("Refresh").clicked() {
+            state.reload(conn);
+        }
+    });
+
+    if !state.status.is_empty() {
+        let color = if state.status.starts_with("Error") {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        ui.colored_label(color, &state.status);
+    }
+
+    if state.snapshot_id.is_some() && state.items.is_empty() && state.changes.is_empty() {
+        ui.label(
+            egui::RichText::new("No public items. Rescan the snapshot to collect them.").weak(),
+        );
+        return None;
+    }
+
+    ui.add_space(6.0);
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| match state.view {
+            ApiView::Items => {
+                let filter = state.filter.to_lowercase();
+                let visible: Vec<&PublicItem> = state
+                    .items
+                    .iter()
+                    .filter(|i| {
+                        filter.is_empty()
+                            || i.path.to_lowercase().contains(&filter)
+                            || i.signature.to_lowercase().contains(&filter)
+                    })
+                    .collect();
+                // Items are sorted by crate, so groups are contiguous
+                for group in visible.chunk_by(|a, b| a.crate_name == b.crate_name) {
+                    egui::CollapsingHeader::new(format!(
+                        "{} ({})",
+                        group[0].crate_name,
+                        group.len()
+                    ))
+                    .id_salt(("api_crate", &group[0].crate_name))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for item in group {
+                            ui.horizontal(|ui| {
+                                ui.add_sized(
+                                    [60.0, 18.0],
+                                    egui::Label::new(egui::RichText::new(&item.kind).weak()),
+                                );
+                                if ui.link(&item.path).clicked() {
+                                    opened = Some((item.file_id, Some(item.line as usize)));
+                                }
+                                ui.label(egui::RichText::new(&item.signature).monospace());
+                            });
+                        }
+                    });
+                }
+            }
+            ApiView::Changes => {
+                let visible: Vec<ApiChange> =
+                    state.visible_changes().into_iter().cloned().collect();
+                for group in visible.chunk_by(|a, b| a.crate_name == b.crate_name) {
+                    let done = group
+                        .iter()
+                        .filter(|c| state.reviewed.contains(&review_key(c)))
+                        .count();
+                    egui::CollapsingHeader::new(format!(
+                        "{} ({}/{} reviewed)",
+                        group[0].crate_name,
+                        done,
+                        group.len()
+                    ))
+                    .id_salt(("api_changes", &group[0].crate_name))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for change in group {
+                            if change_row(ui, change, &mut state.reviewed) {
+                                if let Some(file_id) = change.file_id {
+                                    opened = Some((file_id, change.line));
+                                }
+                            }
+                        }
+                    });
+                }
+            }
+        });
+
+    opened
+}
+
+/// One change with its review checkbox; returns true when its path is clicked
+fn change_row(
+    ui: &mut egui::Ui,
+    change: &ApiChange,
+    reviewed: &mut HashSet<(String, String, &'static str)>,
+) -> bool {
+    let mut clicked = false;
+    ui.horizontal(|ui| {
+        let key = review_key(change);
+        let mut checked = reviewed.contains(&key);
+        if ui.checkbox(&mut checked, "").changed() {
+            if checked {
+                reviewed.insert(key);
+            } else {
+                reviewed.remove(&key);
+            }
+        }
+        let color = match change.kind {
+            ChangeKind::Removed => egui::Color32::LIGHT_RED,
+            ChangeKind::Changed => egui::Color32::YELLOW,
+            ChangeKind::Added => egui::Color32::LIGHT_GREEN,
+        };
+        ui.add_sized(
+            [70.0, 18.0],
+            egui::Label::new(
+                egui::RichText::new(change.kind.label())
+                    .color(color)
+                    .strong(),
+            ),
+        );
+        ui.add_sized(
+            [60.0, 18.0],
+            egui::Label::new(egui::RichText::new(&change.item_kind).weak()),
+        );
+        clicked = if change.file_id.is_some() {
+            ui.link(&change.path).clicked()
+        } else {
+            ui.label(&change.path);
+            false
+        };
+    });
+    ui.indent(
+        (
+            "api_change",
+            &change.crate_name,
+            &change.path,
+            change.kind.label(),
+        ),
+        |ui| {
+            if let Some(before) = &change.before {
+                ui.label(
+                    egui::RichText::new(format!("- {}", before))
+                        .monospace()
+                        .color(egui::Color32::LIGHT_RED),
+                );
+            }
+            if let Some(after) = &change.after {
+                ui.label(
+                    egui::RichText::new(format!("+ {}", after))
+                        .monospace()
+                        .color(egui::Color32::LIGHT_GREEN),
+                );
+            }
+        },
+    );
+    clicked
+}
@@ -0,0 +1,286 @@
+pub mod rollup;
+
+use eframe::egui;
+use rusqlite::Connection;
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::domain::n014_file_coverage::ui::coverage_label;
+use crate::domain::n015_public_item::model::PublicItem;
+use rollup::{doc_rows, doc_summary, undocumented_items, DocLevel, DocRow, DocSummary};
+
+pub struct DocCoverageState {
+    pub snapshot_picker: SnapshotPickerState,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    pub level: DocLevel,
+    pub filter: String,
+    pub rows: Vec<DocRow>,
+    pub summary: DocSummary,
+    pub undocumented: Vec<PublicItem>,
+    /// Name and coverage of the project's previous snapshot
+    pub previous: Option<(String, Option<f64>)>,
+    pub status: String,
+}
+
+impl Default for DocCoverageState {
+    fn default() -> Self {
+        Self {
+            snapshot_picker: SnapshotPickerState::default(),
+            snapshot_id: None,
+            snapshot_name: String::new(),
+            level: DocLevel::Crates,
+            filter: String::new(),
+            rows: Vec::new(),
+            summary: DocSummary::default(),
+            undocumented: Vec::new(),
+            previous: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl DocCoverageState {
+    fn reload(&mut self, conn: &Connection) {
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+        self.status.clear();
+        let loaded = doc_rows(conn, snapshot_id, self.level).and_then(|rows| {
+            Ok((
+                rows,
+                doc_summary(conn, snapshot_id)?,
+                undocumented_items(conn, snapshot_id)?,
+            ))
+        });
+        match loaded {
+            Ok((rows, summary, undocumented)) => {
+                self.rows = rows;
+                self.summary = summary;
+                self.undocumented = undocumented;
+            }
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.rows.clear();
+                self.summary = DocSummary::default();
+                self.undocumented.clear();
+            }
+        }
+
+        self.previous = snapshot_repo::get_by_id(conn, snapshot_id)
+            .ok()
+            .and_then(|s| {
+                snapshot_repo::previous_for_project(conn, s.project_id, s.id)
+                    .ok()
+                    .flatten()
+            })
+            .and_then(|prev| Some((prev.name, doc_summary(conn, prev.id).ok()?.coverage())));
+    }
+}
+
+/// Renders the Doc Coverage tab. Returns `(file_id, line)` when a file or item is opened.
+pub fn ui_doc_coverage(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut DocCoverageState,
+) -> Option<(i64, Option<usize>)> {
+    let mut opened = None;
+
+    ui.heading("Doc Coverage");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.snapshot_id = Some(snapshot_id);
+        if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
+            state.snapshot_name = snapshot.name;
+        }
+        state.reload(conn);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Selected Snapshot:");
+        if state.snapshot_id.is_some() {
+            ui.label(egui::RichText::new(&state.snapshot_name).strong());
+        } else {
+            ui.label(egui::RichText::new("None").weak());
+        }
+        if ui.button("Select...").clicked() {
+            state.snapshot_picker.open();
+        }
+        if state.snapshot_id.is_some() && ui.button("Refresh").clicked() {
+            state.reload(conn);
+        }
+    });
+
+    if !state.status.is_empty() {
+        ui.colored_label(egui::Color32::LIGHT_RED, &state.status);
+    }
+    state.snapshot_id?;
+    if state.summary.items == 0 && state.summary.modules == 0 {
+        ui.label(
+            egui::RichText::new(
+                "No crate sources in this snapshot. Rescan it to collect public items.",
+            )
+            .weak(),
+        );
+        return None;
+    }
+
+    ui.add_space(8.0);
+
+    let summary = &state.summary;
+    ui.horizontal(|ui| {
+        ui.label("Documented public items:");
+        coverage_label(ui, summary.coverage());
+        ui.label(format!("({} / {})", summary.documented, summary.items));
+        if let Some((name, previous)) = &state.previous {
+            ui.separator();
+            ui.label(format!("Previous snapshot '{}':", name));
+            coverage_label(ui, *previous);
+            if let (Some(previous), Some(current)) = (previous, summary.coverage()) {
+                let delta = (current - previous) * 100.0;
+                let color = if delta < 0.0 {
+                    egui::Color32::LIGHT_RED
+                } else {
+                    egui::Color32::LIGHT_GREEN
+                };
+                ui.colored_label(color, format!("{:+.1} pts", delta));
+            }
+        }
+    });
+
+    egui::CollapsingHeader::new(format!(
+        "Modules without //! header ({} of {})",
+        summary.modules_without_header.len(),
+        summary.modules
+    ))
+    .id_salt("docs_modules")
+    .show(ui, |ui| {
+        for module in &summary.modules_without_header {
+            if ui.link(module.display_path()).clicked() {
+                opened = Some((module.file_id, Some(1)));
+            }
+        }
+    });
+
+    egui::CollapsingHeader::new(format!(
+        "Crates without README ({} of {})",
+        summary.crates_without_readme.len(),
+        summary.crates
+    ))
+    .id_salt("docs_readme")
+    .show(ui, |ui| {
+        for name in &summary.crates_without_readme {
+            ui.label(name);
+        }
+    });
+
+    egui::CollapsingHeader::new(format!("Undocumented items ({})", state.undocumented.len()))
+        .id_salt("docs_undocumented")
+        .show(ui, |ui| {
+            egui::ScrollArea::vertical()
+                .id_salt("docs_undocumented_scroll")
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    for item in &state.undocumented {
+                        ui.horizontal(|ui| {
+                            ui.add_sized(
+                                [60.0, 18.0],
+                                egui::Label::new(egui::RichText::new(&item.kind).weak()),
+                            );
+                            if ui
+                                .link(format!("{}::{}", item.crate_name, item.path))
+                                .clicked()
+                            {
+                                opened = Some((item.file_id, Some(item.line as usize)));
+                            }
+                        });
+                    }
+                });
+        });
+
+    ui.add_space(8.0);
+
+    ui.horizontal(|ui| {
+        let level_before = state.level;
+        ui.selectable_value(&mut state.level, DocLevel::Files, "Files");
+        ui.selectable_value(&mut state.level, DocLevel::Modules, "Modules");
+        ui.selectable_value(&mut state.level, DocLevel::Crates, "Crates");
+        ui.selectable_value(&mut state.level, DocLevel::Aggregates, "Aggregates");
+        if state.level != level_before {
+            state.reload(conn);
+        }
+        ui.separator();
+        ui.label("Filter:");
+        ui.text_edit_singleline(&mut state.filter);
+    });
+
+    let filter = state.filter.to_lowercase();
+    let visible: Vec<&DocRow> = state
+        .rows
+        .iter()
+        .filter(|r| filter.is_empty() || r.name.to_lowercase().contains(&filter))
+        .collect();
+
+    ui.add_space(6.0);
+    if let Some(file) = render_table(ui, &visible, state.level) {
+        opened = Some(file);
+    }
+    opened
+}
+
+fn render_table(
+    ui: &mut egui::Ui,
+    rows: &[&DocRow],
+    level: DocLevel,
+) -> Option<(i64, Option<usize>)> {
+    use egui_extras::{Column, TableBuilder};
+
+    let mut opened = None;
+    let first_header = match level {
+        DocLevel::Files => "File",
+        DocLevel::Modules => "Module",
+        DocLevel::Crates => "Crate",
+        DocLevel::Aggregates => "Aggregate",
+    };
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto().at_least(320.0))
+        .column(Column::auto().at_least(80.0))
+        .column(Column::auto().at_least(90.0))
+        .column(Column::auto().at_least(80.0))
+        .header(20.0, |mut header| {
+            for title in [first_header, "Public items", "Documented", "Coverage"] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(18.0, rows.len(), |mut row| {
+                let r = rows[row.index()];
+                row.col(|ui| match r.file_id {
+                    Some(file_id) => {
+                        if ui.link(&r.name).clicked() {
+                            opened = Some((file_id, None));
+                        }
+                    }
+                    None => {
+                        ui.label(&r.name);
+                    }
+                });
+                row.col(|ui| {
+                    ui.label(r.items.to_string());
+                });
+                row.col(|ui| {
+                    ui.label(r.documented.to_string());
+                });
+                row.col(|ui| coverage_label(ui, r.coverage()));
+            });
+        });
+
+    opened
+}
@@ -0,0 +1,186 @@
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n004_snapshot_aggregate::model::code_for_artifact;
+use crate::domain::n014_file_coverage::model::rate;
+use crate::domain::n015_public_item::model::PublicItem;
+use crate::domain::n015_public_item::repository as public_item_repo;
+use crate::domain::n016_module_doc::model::ModuleDoc;
+use crate::domain::n016_module_doc::repository as module_doc_repo;
+use crate::shared::rust_api::needs_doc;
+use crate::usecases::s501_create_snapshot::api::snapshot_crates;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocLevel {
+    Files,
+    /// Top-level modules of a crate (`crate::orders` includes `crate::orders::model`)
+    Modules,
+    Crates,
+    Aggregates,
+}
+
+/// Documented public items of a file, module, crate or aggregate
+#[derive(Debug, Clone, Default)]
+pub struct DocRow {
+    /// File path, `crate::module`, crate name or aggregate code
+    pub name: String,
+    /// Set for file rows (used to open the preview)
+    pub file_id: Option<i64>,
+    pub items: i64,
+    pub documented: i64,
+}
+
+impl DocRow {
+    pub fn coverage(&self) -> Option<f64> {
+        rate(self.documented, self.items)
+    }
+}
+
+/// Documentation state of a whole snapshot
+#[derive(Debug, Clone, Default)]
+pub struct DocSummary {
+    pub items: i64,
+    pub documented: i64,
+    pub modules: usize,
+    /// Module files without a `//!` header
+    pub modules_without_header: Vec<ModuleDoc>,
+    pub crates: usize,
+    /// Names of crates without a README
+    pub crates_without_readme: Vec<String>,
+}
+
+impl DocSummary {
+    pub fn coverage(&self) -> Option<f64> {
+        rate(self.documented, self.items)
+    }
+}
+
+/// Public items of a snapshot that count toward documentation coverage
+fn doc_items(conn: &Connection, snapshot_id: i64) -> Result<Vec<PublicItem>, String> {
+    let items = public_item_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load public items: {}", e))?;
+    Ok(items.into_iter().filter(|i| needs_doc(&i.kind)).collect())
+}
+
+/// Doc coverage rows of a snapshot at the requested level, least documented first
+pub fn doc_rows(
+    conn: &Connection,
+    snapshot_id: i64,
+    level: DocLevel,
+) -> Result<Vec<DocRow>, String> {
+    let files: HashMap<i64, _> = file_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load files: {}", e))?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect();
+    let modules: HashMap<i64, ModuleDoc> = module_doc_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load module docs: {}", e))?
+        .into_iter()
+        .map(|m| (m.file_id, m))
+        .collect();
+
+    let mut groups: BTreeMap<String, DocRow> = BTreeMap::new();
+    for item in doc_items(conn, snapshot_id)? {
+        let Some(file) = files.get(&item.file_id) else {
+            continue;
+        };
+        let (name, file_id) = match level {
+            DocLevel::Files => (file.path.clone(), Some(file.id)),
+            DocLevel::Modules => {
+                let top = modules.get(&item.file_id).and_then(|m| {
+                    m.module_path
+                        .split("::")
+                        .next()
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_string)
+                });
+                match top {
+                    Some(top) => (format!("{}::{}", item.crate_name, top), None),
+                    None => (item.crate_name.clone(), None),
+                }
+            }
+            DocLevel::Crates => (item.crate_name.clone(), None),
+            DocLevel::Aggregates => match &file.artifact_id {
+                Some(id) => (code_for_artifact(id), None),
+                None => continue,
+            },
+        };
+        let row = groups.entry(name.clone()).or_insert_with(|| DocRow {
+            name,
+            file_id,
+            ..Default::default()
+        });
+        row.items += 1;
+        row.documented += item.documented as i64;
+    }
+
+    let mut rows: Vec<DocRow> = groups.into_values().collect();
+    rows.sort_by(|a, b| {
+        a.coverage()
+            .unwrap_or(1.0)
+            .total_cmp(&b.coverage().unwrap_or(1.0))
+    });
+    Ok(rows)
+}
+
+/// Totals, modules without a `//!` header and crates without a README
+pub fn doc_summary(conn: &Connection, snapshot_id: i64) -> Result<DocSummary, String> {
+    let items = doc_items(conn, snapshot_id)?;
+    let modules = module_doc_repo::list_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load module docs: {}", e))?;
+    let crates = snapshot_crates(conn, snapshot_id)?;
+    Ok(DocSummary {
+        items: items.len() as i64,
+        documented: items.iter().filter(|i| i.documented).count() as i64,
+        modules: modules.len(),
+        modules_without_header: modules.into_iter().filter(|m| !m.has_doc).collect(),
+        crates: crates.len(),
+        crates_without_readme: crates
+            .into_iter()
+            .filter(|c| !c.has_readme)
+            .map(|c| c.name)
+            .collect(),
+    })
+}
+
+/// Public items without docs, by crate and path
+pub fn undocumented_items(conn: &Connection, snapshot_id: i64) -> Result<Vec<PublicItem>, String> {
+    Ok(doc_items(conn, snapshot_id)?
+        .into_iter()
+        .filter(|i| !i.documented)
+        .collect())
+}
+
+/// Failure message when coverage fell by more than `max_drop` percentage points since `previous`
+pub fn coverage_drop(previous: Option<f64>, current: Option<f64>, max_drop: f64) -> Option<String> {
+    let previous = previous? * 100.0;
+    let current = current.unwrap_or(0.0) * 100.0;
+    (previous - current > max_drop + 1e-9).then(|| {
+        format!(
+            "documentation coverage dropped from {:.1}% to {:.1}% ({:.1} points, allowed {:.1})",
+            previous,
+            current,
+            previous - current,
+            max_drop
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_coverage_drop() {
+        assert_eq!(coverage_drop(None, Some(0.5), 0.0), None);
+        assert_eq!(coverage_drop(Some(0.8), Some(0.8), 0.0), None);
+        assert_eq!(coverage_drop(Some(0.8), Some(0.9), 0.0), None);
+        assert_eq!(coverage_drop(Some(0.8), Some(0.79), 2.0), None);
+        assert!(coverage_drop(Some(0.8), Some(0.75), 2.0)
+            .unwrap()
+            .contains("80.0% to 75.0%"));
+        // All public items gone counts as a drop to zero
+        assert!(coverage_drop(Some(0.5), None, 0.0).is_some());
+    }
+}
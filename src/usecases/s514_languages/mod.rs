@@ -0,0 +1,229 @@
+use eframe::egui;
+use rusqlite::Connection;
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::domain::n017_file_language::model::{share, LanguageStats};
+use crate::domain::n017_file_language::repository as language_repo;
+use crate::domain::n017_file_language::ui::{legend_entry, share_bar};
+
+#[derive(Default)]
+pub struct LanguagesState {
+    pub snapshot_picker: SnapshotPickerState,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    pub stats: Vec<LanguageStats>,
+    /// Breakdown of every snapshot of the same project, oldest first
+    pub trend: Vec<(String, Vec<LanguageStats>)>,
+    pub status: String,
+}
+
+impl LanguagesState {
+    fn reload(&mut self, conn: &Connection) {
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+        self.status.clear();
+        match language_repo::breakdown_by_snapshot(conn, snapshot_id) {
+            Ok(stats) => self.stats = stats,
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.stats.clear();
+            }
+        }
+
+        self.trend.clear();
+        let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) else {
+            return;
+        };
+        let Ok(snapshots) = snapshot_repo::list_by_project(conn, snapshot.project_id) else {
+            return;
+        };
+        for s in snapshots.into_iter().rev() {
+            if let Ok(stats) = language_repo::breakdown_by_snapshot(conn, s.id) {
+                if !stats.is_empty() {
+                    self.trend.push((s.name, stats));
+                }
+            }
+        }
+    }
+}
+
+/// Renders the Languages tab
+pub fn ui_languages(ui: &mut egui::Ui, conn: &Connection, state: &mut LanguagesState) {
+    ui.heading("Languages");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.snapshot_id = Some(snapshot_id);
+        if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
+            state.snapshot_name = snapshot.name;
+        }
+        state.reload(conn);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Selected Snapshot:");
+        if state.snapshot_id.is_some() {
+            ui.label(egui::RichText::new(&state.snapshot_name).strong());
+        } else {
+            ui.label(egui::RichText::new("None").weak());
+        }
+        if ui.button("Select...").clicked() {
+            state.snapshot_picker.open();
+        }
+        if state.snapshot_id.is_some() && ui.button("Refresh").clicked() {
+            state.reload(conn);
+        }
+    });
+
+    if !state.status.is_empty() {
+        ui.colored_label(egui::Color32::LIGHT_RED, &state.status);
+    }
+    if state.snapshot_id.is_none() {
+        return;
+    }
+    if state.stats.is_empty() {
+        ui.label(
+            egui::RichText::new("No languages recorded. Rescan the snapshot to detect them.")
+                .weak(),
+        );
+        return;
+    }
+
+    ui.add_space(8.0);
+    let width = ui.available_width().min(600.0);
+    share_bar(ui, &state.stats, width);
+    ui.add_space(6.0);
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            render_table(ui, &state.stats);
+
+            if state.trend.len() > 1 {
+                ui.add_space(12.0);
+                ui.strong("Trend (LOC share per snapshot of the project)");
+                ui.add_space(4.0);
+                egui::Grid::new("languages_trend")
+                    .num_columns(3)
+                    .spacing([12.0, 6.0])
+                    .show(ui, |ui| {
+                        for (name, stats) in &state.trend {
+                            let label = egui::RichText::new(name);
+                            ui.label(if *name == state.snapshot_name {
+                                label.strong()
+                            } else {
+                                label
+                            });
+                            share_bar(ui, stats, 400.0);
+                            ui.label(format!("{} LOC", stats.iter().map(|s| s.loc).sum::<i64>()));
+                            ui.end_row();
+                        }
+                    });
+
+                let mut languages: Vec<&str> = Vec::new();
+                for (_, stats) in &state.trend {
+                    for s in stats {
+                        if !languages.contains(&s.language.as_str()) {
+                            languages.push(&s.language);
+                        }
+                    }
+                }
+                ui.add_space(4.0);
+                ui.horizontal_wrapped(|ui| {
+                    for language in languages {
+                        legend_entry(ui, language);
+                    }
+                });
+            }
+        });
+}
+
+fn render_table(ui: &mut egui::Ui, stats: &[LanguageStats]) {
+    use egui_extras::{Column, TableBuilder};
+
+    let total_files: i64 = stats.iter().map(|s| s.files).sum();
+    let total_bytes: i64 = stats.iter().map(|s| s.bytes).sum();
+    let total_loc: i64 = stats.iter().map(|s| s.loc).sum();
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .vscroll(false)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto().at_least(160.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::auto().at_least(90.0))
+        .column(Column::auto().at_least(80.0))
+        .column(Column::auto().at_least(70.0))
+        .column(Column::auto().at_least(70.0))
+        .header(20.0, |mut header| {
+            for title in [
+                "Language",
+                "Files",
+                "Size",
+                "LOC",
+                "LOC share",
+                "Size share",
+            ] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|mut body| {
+            for s in stats {
+                body.row(18.0, |mut row| {
+                    row.col(|ui| legend_entry(ui, &s.language));
+                    row.col(|ui| {
+                        ui.label(s.files.to_string());
+                    });
+                    row.col(|ui| {
+                        ui.label(format_size(s.bytes));
+                    });
+                    row.col(|ui| {
+                        ui.label(s.loc.to_string());
+                    });
+                    row.col(|ui| {
+                        ui.label(format!("{:.1}%", share(s.loc, total_loc) * 100.0));
+                    });
+                    row.col(|ui| {
+                        ui.label(format!("{:.1}%", share(s.bytes, total_bytes) * 100.0));
+                    });
+                });
+            }
+            body.row(18.0, |mut row| {
+                row.col(|ui| {
+                    ui.strong("Total");
+                });
+                row.col(|ui| {
+                    ui.strong(total_files.to_string());
+                });
+                row.col(|ui| {
+                    ui.strong(format_size(total_bytes));
+                });
+                row.col(|ui| {
+                    ui.strong(total_loc.to_string());
+                });
+                row.col(|_| {});
+                row.col(|_| {});
+            });
+        });
+}
+
+fn format_size(bytes: i64) -> String {
+    const KB: i64 = 1024;
+    const MB: i64 = KB * 1024;
+    const GB: i64 = MB * 1024;
+
+    if bytes >= GB {
+        format!("{:.2} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.2} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.2} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
@@ -0,0 +1,63 @@
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection, Row};
+use serde_json::Value;
+
+use super::format::{SnapshotExport, TableData};
+use super::schema::{BOOL_COLUMNS, SNAPSHOT_COLUMNS, TABLES};
+
+fn json_value(row: &Row, index: usize, column: &str) -> rusqlite::Result<Value> {
+    Ok(match row.get_ref(index)? {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) if BOOL_COLUMNS.contains(&column) => Value::Bool(i != 0),
+        ValueRef::Integer(i) => i.into(),
+        ValueRef::Real(f) => f.into(),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned().into(),
+        ValueRef::Blob(b) => String::from_utf8_lossy(b).into_owned().into(),
+    })
+}
+
+fn json_row(row: &Row, columns: &[&str]) -> rusqlite::Result<Vec<Value>> {
+    columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| json_value(row, i, c))
+        .collect()
+}
+
+/// Read a snapshot and all of its rows in schema order
+pub fn collect_snapshot(conn: &Connection, snapshot_id: i64) -> Result<SnapshotExport, String> {
+    let snapshot = conn
+        .query_row(
+            "SELECT s.id, s.name, s.project_id, p.name, p.root_path, s.comment, s.files_count,
+                    s.dirs_count, s.files_size_bytes, s.loc_count, s.scanned_at, s.git_revision
+             FROM n002_snapshot s
+             LEFT JOIN n001_project p ON p.id = s.project_id
+             WHERE s.id = ?1",
+            params![snapshot_id],
+            |row| json_row(row, &SNAPSHOT_COLUMNS),
+        )
+        .map_err(|e| format!("Failed to load snapshot {}: {}", snapshot_id, e))?;
+
+    let mut tables = Vec::with_capacity(TABLES.len());
+    for spec in &TABLES {
+        let sql = format!(
+            "SELECT {} FROM {} WHERE snapshot_id = ?1 ORDER BY rowid",
+            spec.columns.join(", "),
+            spec.source
+        );
+        let rows = conn
+            .prepare(&sql)
+            .and_then(|mut stmt| {
+                stmt.query_map(params![snapshot_id], |row| json_row(row, spec.columns))?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .map_err(|e| format!("Failed to read {}: {}", spec.source, e))?;
+        tables.push(TableData { spec, rows });
+    }
+
+    Ok(SnapshotExport {
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        snapshot,
+        tables,
+    })
+}
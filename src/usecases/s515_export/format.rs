@@ -0,0 +1,357 @@
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use super::schema::{TableSpec, SCHEMA_NAME, SCHEMA_VERSION, SNAPSHOT_COLUMNS};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    /// One JSON document, files nested as a directory tree with their metrics
+    JsonTree,
+    /// One JSON document, every table as an array of rows
+    JsonFlat,
+    /// One JSON record per line
+    JsonLines,
+    /// A directory with one CSV file per table
+    Csv,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::JsonTree,
+        ExportFormat::JsonFlat,
+        ExportFormat::JsonLines,
+        ExportFormat::Csv,
+    ];
+
+    /// CLI name
+    pub fn key(self) -> &'static str {
+        match self {
+            ExportFormat::JsonTree => "json",
+            ExportFormat::JsonFlat => "json-flat",
+            ExportFormat::JsonLines => "jsonl",
+            ExportFormat::Csv => "csv",
+        }
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::JsonTree => "JSON (tree)",
+            ExportFormat::JsonFlat => "JSON (flat)",
+            ExportFormat::JsonLines => "JSON Lines",
+            ExportFormat::Csv => "CSV (one file per table)",
+        }
+    }
+
+    /// File extension; None for CSV, which is written to a directory
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            ExportFormat::JsonTree | ExportFormat::JsonFlat => Some("json"),
+            ExportFormat::JsonLines => Some("jsonl"),
+            ExportFormat::Csv => None,
+        }
+    }
+}
+
+/// Rows of one exported table, values ordered like `spec.columns`
+pub struct TableData {
+    pub spec: &'static TableSpec,
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// Everything exported for one snapshot
+pub struct SnapshotExport {
+    pub exported_at: String,
+    /// Ordered like `SNAPSHOT_COLUMNS`
+    pub snapshot: Vec<Value>,
+    pub tables: Vec<TableData>,
+}
+
+impl SnapshotExport {
+    pub fn row_count(&self) -> usize {
+        self.tables.iter().map(|t| t.rows.len()).sum()
+    }
+}
+
+fn record(columns: &[&str], row: &[Value]) -> Map<String, Value> {
+    columns
+        .iter()
+        .zip(row)
+        .map(|(c, v)| (c.to_string(), v.clone()))
+        .collect()
+}
+
+fn header(export: &SnapshotExport, layout: &str) -> Map<String, Value> {
+    let mut map = Map::new();
+    map.insert("schema".into(), SCHEMA_NAME.into());
+    map.insert("schema_version".into(), SCHEMA_VERSION.into());
+    map.insert("layout".into(), layout.into());
+    map.insert("exported_at".into(), export.exported_at.clone().into());
+    map
+}
+
+/// `{schema, schema_version, layout: "flat", exported_at, snapshot, tables: {name: [row]}}`
+pub fn to_json_flat(export: &SnapshotExport) -> Value {
+    let mut doc = header(export, "flat");
+    doc.insert(
+        "snapshot".into(),
+        Value::Object(record(&SNAPSHOT_COLUMNS, &export.snapshot)),
+    );
+    let tables: Map<String, Value> = export
+        .tables
+        .iter()
+        .map(|t| {
+            let rows = t
+                .rows
+                .iter()
+                .map(|r| Value::Object(record(t.spec.columns, r)))
+                .collect();
+            (t.spec.name.to_string(), Value::Array(rows))
+        })
+        .collect();
+    doc.insert("tables".into(), Value::Object(tables));
+    Value::Object(doc)
+}
+
+/// Files as a directory tree: every file node carries its per-file rows
+/// (one-per-file tables as objects, others as arrays, without `file_id`) and `children`
+pub fn to_json_tree(export: &SnapshotExport) -> Value {
+    let mut doc = header(export, "tree");
+    doc.insert(
+        "snapshot".into(),
+        Value::Object(record(&SNAPSHOT_COLUMNS, &export.snapshot)),
+    );
+
+    let Some(files) = export.tables.iter().find(|t| t.spec.name == "files") else {
+        return Value::Object(doc);
+    };
+
+    // file id -> per-file table name -> rows
+    let mut attached: HashMap<i64, Map<String, Value>> = HashMap::new();
+    for table in export.tables.iter().filter(|t| t.spec.per_file()) {
+        for row in &table.rows {
+            let Some(file_id) = row.first().and_then(Value::as_i64) else {
+                continue;
+            };
+            let value = Value::Object(record(&table.spec.columns[1..], &row[1..]));
+            let slot = attached.entry(file_id).or_default();
+            if table.spec.one_per_file {
+                slot.insert(table.spec.name.to_string(), value);
+            } else if let Value::Array(rows) = slot
+                .entry(table.spec.name.to_string())
+                .or_insert_with(|| Value::Array(Vec::new()))
+            {
+                rows.push(value);
+            }
+        }
+    }
+
+    // Nodes keyed by id, children by parent id (rows come in insertion order, parents first)
+    let mut children: HashMap<Option<i64>, Vec<i64>> = HashMap::new();
+    let mut nodes: HashMap<i64, Map<String, Value>> = HashMap::new();
+    for row in &files.rows {
+        let mut node = record(files.spec.columns, row);
+        let Some(id) = node.get("id").and_then(Value::as_i64) else {
+            continue;
+        };
+        // shift_remove keeps the remaining columns in schema order
+        let parent = node.shift_remove("parent_id").and_then(|p| p.as_i64());
+        if let Some(extra) = attached.remove(&id) {
+            node.extend(extra);
+        }
+        children.entry(parent).or_default().push(id);
+        nodes.insert(id, node);
+    }
+
+    fn build(
+        id: i64,
+        nodes: &mut HashMap<i64, Map<String, Value>>,
+        children: &HashMap<Option<i64>, Vec<i64>>,
+    ) -> Value {
+        let mut node = nodes.remove(&id).unwrap_or_default();
+        if let Some(ids) = children.get(&Some(id)) {
+            let kids = ids.iter().map(|c| build(*c, nodes, children)).collect();
+            node.insert("children".into(), Value::Array(kids));
+        }
+        Value::Object(node)
+    }
+    let roots: Vec<Value> = children
+        .get(&None)
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|id| build(id, &mut nodes, &children))
+        .collect();
+
+    for table in export
+        .tables
+        .iter()
+        .filter(|t| t.spec.name != "files" && !t.spec.per_file())
+    {
+        let rows = table
+            .rows
+            .iter()
+            .map(|r| Value::Object(record(table.spec.columns, r)))
+            .collect();
+        doc.insert(table.spec.name.to_string(), Value::Array(rows));
+    }
+    doc.insert("files".into(), Value::Array(roots));
+    Value::Object(doc)
+}
+
+/// A header record, the `snapshot` record, then one record per row, each tagged with `table`
+pub fn write_json_lines(export: &SnapshotExport, out: &mut impl Write) -> io::Result<()> {
+    let mut head = header(export, "lines");
+    head.insert("table".into(), "header".into());
+    writeln!(out, "{}", Value::Object(head))?;
+
+    let mut snapshot = Map::new();
+    snapshot.insert("table".into(), "snapshot".into());
+    snapshot.extend(record(&SNAPSHOT_COLUMNS, &export.snapshot));
+    writeln!(out, "{}", Value::Object(snapshot))?;
+
+    for table in &export.tables {
+        for row in &table.rows {
+            let mut line = Map::new();
+            line.insert("table".into(), table.spec.name.into());
+            line.extend(record(table.spec.columns, row));
+            writeln!(out, "{}", Value::Object(line))?;
+        }
+    }
+    Ok(())
+}
+
+/// RFC 4180 field: quoted when it contains a separator, quote or line break
+fn csv_field(value: &Value) -> String {
+    let text = match value {
+        Value::Null => return String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+fn csv_line(fields: impl Iterator<Item = String>) -> String {
+    let mut line = fields.collect::<Vec<_>>().join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// `(file name, content)` of `snapshot.csv` (with the schema columns first) and one file per table
+pub fn csv_files(export: &SnapshotExport) -> Vec<(String, String)> {
+    let mut snapshot_columns = vec!["schema", "schema_version", "exported_at"];
+    snapshot_columns.extend(SNAPSHOT_COLUMNS);
+    let mut snapshot_values = vec![
+        SCHEMA_NAME.into(),
+        SCHEMA_VERSION.into(),
+        export.exported_at.clone().into(),
+    ];
+    snapshot_values.extend(export.snapshot.iter().cloned());
+    let mut files = vec![(
+        "snapshot.csv".to_string(),
+        csv_line(snapshot_columns.iter().map(|c| c.to_string()))
+            + &csv_line(snapshot_values.iter().map(csv_field)),
+    )];
+
+    for table in &export.tables {
+        let mut content = csv_line(table.spec.columns.iter().map(|c| c.to_string()));
+        for row in &table.rows {
+            content.push_str(&csv_line(row.iter().map(csv_field)));
+        }
+        files.push((format!("{}.csv", table.spec.name), content));
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecases::s515_export::schema::TABLES;
+    use serde_json::json;
+
+    fn sample() -> SnapshotExport {
+        let files = &TABLES[0];
+        let functions = TABLES.iter().find(|t| t.name == "functions").unwrap();
+        let languages = TABLES.iter().find(|t| t.name == "languages").unwrap();
+        let file = |id: i64, parent: Value, name: &str, dir: bool| {
+            let mut row = vec![
+                json!(id),
+                parent,
+                json!(name),
+                json!(name),
+                json!(10),
+                json!(dir),
+            ];
+            row.resize(files.columns.len(), Value::Null);
+            row
+        };
+        SnapshotExport {
+            exported_at: "2026-01-01T00:00:00Z".into(),
+            snapshot: SNAPSHOT_COLUMNS.iter().map(|c| json!(c)).collect(),
+            tables: vec![
+                TableData {
+                    spec: files,
+                    rows: vec![
+                        file(1, Value::Null, "src", true),
+                        file(2, json!(1), "a,\"b\".rs", false),
+                    ],
+                },
+                TableData {
+                    spec: functions,
+                    rows: vec![vec![
+                        json!(2),
+                        json!("f"),
+                        json!(3),
+                        json!(5),
+                        json!(1),
+                        json!(2),
+                        json!(1),
+                        json!(1),
+                    ]],
+                },
+                TableData {
+                    spec: languages,
+                    rows: vec![vec![json!(2), json!("Rust"), json!(40)]],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_tree_lines_and_csv() {
+        let export = sample();
+        let tree = to_json_tree(&export);
+        assert_eq!(tree["schema_version"], json!(SCHEMA_VERSION));
+        let child = &tree["files"][0]["children"][0];
+        assert_eq!(child["name"], json!("a,\"b\".rs"));
+        assert_eq!(child["languages"], json!({"language": "Rust", "loc": 40}));
+        assert_eq!(child["functions"][0]["cyclomatic"], json!(2));
+        assert!(child.get("parent_id").is_none());
+
+        let flat = to_json_flat(&export);
+        assert_eq!(flat["tables"]["files"][1]["parent_id"], json!(1));
+
+        let mut lines = Vec::new();
+        write_json_lines(&export, &mut lines).unwrap();
+        let lines = String::from_utf8(lines).unwrap();
+        assert_eq!(lines.lines().count(), 2 + export.row_count());
+        assert!(lines
+            .lines()
+            .nth(3)
+            .unwrap()
+            .starts_with("{\"table\":\"files\""));
+
+        let csv = csv_files(&export);
+        let (name, content) = &csv[1];
+        assert_eq!(name, "files.csv");
+        assert!(content.contains("2,1,\"a,\"\"b\"\".rs\",\"a,\"\"b\"\".rs\",10,false,"));
+    }
+}
@@ -0,0 +1,107 @@
+pub mod collect;
+pub mod format;
+pub mod schema;
+
+use eframe::egui;
+use rusqlite::Connection;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use format::{
+    csv_files, to_json_flat, to_json_tree, write_json_lines, ExportFormat, SnapshotExport,
+};
+
+/// Write an export to any writer; CSV needs a directory and goes through `export_snapshot`
+pub fn write_export(
+    export: &SnapshotExport,
+    format: ExportFormat,
+    out: &mut impl Write,
+) -> Result<(), String> {
+    let result = match format {
+        ExportFormat::JsonTree => serde_json::to_writer_pretty(&mut *out, &to_json_tree(export))
+            .map_err(|e| e.to_string())
+            .and_then(|_| writeln!(out).map_err(|e| e.to_string())),
+        ExportFormat::JsonFlat => serde_json::to_writer_pretty(&mut *out, &to_json_flat(export))
+            .map_err(|e| e.to_string())
+            .and_then(|_| writeln!(out).map_err(|e| e.to_string())),
+        ExportFormat::JsonLines => write_json_lines(export, out).map_err(|e| e.to_string()),
+        ExportFormat::Csv => return Err("CSV exports are written to a directory".to_string()),
+    };
+    result
+        .and_then(|_| out.flush().map_err(|e| e.to_string()))
+        .map_err(|e| format!("Failed to write export: {}", e))
+}
+
+/// Export a snapshot to `path`: a file for JSON formats, a directory (created if missing) for CSV.
+/// Returns the number of exported rows.
+pub fn export_snapshot(
+    conn: &Connection,
+    snapshot_id: i64,
+    format: ExportFormat,
+    path: &Path,
+) -> Result<usize, String> {
+    let export = collect::collect_snapshot(conn, snapshot_id)?;
+    if format == ExportFormat::Csv {
+        fs::create_dir_all(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        for (name, content) in csv_files(&export) {
+            let file = path.join(name);
+            fs::write(&file, content)
+                .map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+        }
+    } else {
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        write_export(&export, format, &mut BufWriter::new(file))?;
+    }
+    Ok(export.row_count())
+}
+
+/// Snapshot name made safe for a default file name
+pub fn file_stem(snapshot_name: &str) -> String {
+    snapshot_name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Export entries of a snapshot's context menu; returns a status message once an export ran
+pub fn export_menu(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    snapshot_id: i64,
+    snapshot_name: &str,
+) -> Option<String> {
+    ui.label(egui::RichText::new("Export").weak());
+    let format = ExportFormat::ALL
+        .into_iter()
+        .find(|f| ui.button(f.label()).clicked())?;
+    ui.close_menu();
+
+    let stem = file_stem(snapshot_name);
+    let dialog = rfd::FileDialog::new();
+    let path = match format.extension() {
+        Some(ext) => dialog
+            .set_file_name(format!("{}.{}", stem, ext))
+            .add_filter(format.label(), &[ext])
+            .save_file(),
+        None => dialog.set_title("Folder for the CSV files").pick_folder(),
+    }?;
+
+    Some(match export_snapshot(conn, snapshot_id, format, &path) {
+        Ok(rows) => format!(
+            "Exported '{}' ({} rows) to {}",
+            snapshot_name,
+            rows,
+            path.display()
+        ),
+        Err(e) => format!("Export failed: {}", e),
+    })
+}
@@ -0,0 +1,206 @@
+//! Export format version 2, documented in `docs/export-schema.md`.
+//! Column lists are part of the format: append new columns at the end and bump
+//! `SCHEMA_VERSION` when a column is renamed, removed or changes meaning.
+
+pub const SCHEMA_NAME: &str = "navigator.snapshot-export";
+pub const SCHEMA_VERSION: i64 = 2;
+
+/// A snapshot table as it appears in exports
+pub struct TableSpec {
+    /// Name in exports: CSV file name, JSON key, `table` field of JSON Lines records
+    pub name: &'static str,
+    /// Source table in navigator.db (rows are selected by `snapshot_id`)
+    pub source: &'static str,
+    /// Exported columns in order. `id`, `parent_id`, `file_id` and `target_file_id` are ids of the
+    /// exporting database.
+    pub columns: &'static [&'static str],
+    /// At most one row per file (`file_id` is the primary key)
+    pub one_per_file: bool,
+}
+
+impl TableSpec {
+    /// Rows reference `files` by `file_id`
+    pub fn per_file(&self) -> bool {
+        self.columns.first() == Some(&"file_id")
+    }
+}
+
+/// Columns of the `snapshot` record; `project_*` come from n001_project
+pub const SNAPSHOT_COLUMNS: [&str; 12] = [
+    "id",
+    "name",
+    "project_id",
+    "project_name",
+    "project_root_path",
+    "comment",
+    "files_count",
+    "dirs_count",
+    "files_size_bytes",
+    "loc_count",
+    "scanned_at",
+    "git_revision",
+];
+
+/// Stored as 0/1, exported as booleans
+pub const BOOL_COLUMNS: [&str; 4] = ["is_directory", "documented", "has_doc", "in_test"];
+
+/// Exported tables; file contents (n006) are not exported
+pub const TABLES: [TableSpec; 14] = [
+    TableSpec {
+        name: "files",
+        source: "n003_snapshot_file",
+        columns: &[
+            "id",
+            "parent_id",
+            "name",
+            "path",
+            "size_bytes",
+            "is_directory",
+            "file_extension",
+            "crate_layer",
+            "artifact_type",
+            "artifact_id",
+            "artifact_name",
+            "role",
+        ],
+        one_per_file: false,
+    },
+    TableSpec {
+        name: "aggregates",
+        source: "n004_snapshot_aggregate",
+        columns: &[
+            "code",
+            "name",
+            "description",
+            "file_count",
+            "total_size_bytes",
+            "test_fns",
+            "test_loc",
+            "code_loc",
+            "doc_items",
+            "doc_documented",
+            "created_at",
+            "updated_at",
+        ],
+        one_per_file: false,
+    },
+    TableSpec {
+        name: "packages",
+        source: "n005_snapshot_package",
+        columns: &["name", "version", "source", "checksum", "license"],
+        one_per_file: false,
+    },
+    TableSpec {
+        name: "churn",
+        source: "n007_file_churn",
+        columns: &[
+            "file_id",
+            "commits",
+            "authors",
+            "lines_added",
+            "lines_removed",
+            "last_modified",
+            "loc",
+            "window_days",
+            "computed_at",
+        ],
+        one_per_file: true,
+    },
+    TableSpec {
+        name: "ownership",
+        source: "n008_file_ownership",
+        columns: &["file_id", "author_email", "author_name", "lines"],
+        one_per_file: false,
+    },
+    TableSpec {
+        name: "functions",
+        source: "n010_function_metric",
+        columns: &[
+            "file_id",
+            "name",
+            "line",
+            "length",
+            "params",
+            "cyclomatic",
+            "cognitive",
+            "nesting",
+        ],
+        one_per_file: false,
+    },
+    TableSpec {
+        name: "markers",
+        source: "n011_debt_marker",
+        columns: &["file_id", "line", "kind", "text", "author", "authored_at"],
+        one_per_file: false,
+    },
+    TableSpec {
+        name: "safety_sites",
+        source: "n012_safety_site",
+        columns: &["file_id", "kind", "line", "function"],
+        one_per_file: false,
+    },
+    TableSpec {
+        name: "tests",
+        source: "n013_file_tests",
+        columns: &[
+            "file_id",
+            "test_fns",
+            "test_loc",
+            "code_loc",
+            "covers_artifact",
+        ],
+        one_per_file: true,
+    },
+    TableSpec {
+        name: "coverage",
+        source: "n014_file_coverage",
+        columns: &[
+            "file_id",
+            "lines_found",
+            "lines_hit",
+            "branches_found",
+            "branches_hit",
+            "line_hits",
+            "report_path",
+            "imported_at",
+        ],
+        one_per_file: true,
+    },
+    TableSpec {
+        name: "public_items",
+        source: "n015_public_item",
+        columns: &[
+            "file_id",
+            "crate_name",
+            "kind",
+            "path",
+            "signature",
+            "line",
+            "documented",
+        ],
+        one_per_file: false,
+    },
+    TableSpec {
+        name: "module_docs",
+        source: "n016_module_doc",
+        columns: &["file_id", "crate_name", "module_path", "has_doc"],
+        one_per_file: true,
+    },
+    TableSpec {
+        name: "languages",
+        source: "n017_file_language",
+        columns: &["file_id", "language", "loc"],
+        one_per_file: true,
+    },
+    TableSpec {
+        name: "dependencies",
+        source: "n018_module_dependency",
+        columns: &["file_id", "target_file_id", "line", "use_path", "in_test"],
+        one_per_file: false,
+    },
+];
+
+/// Exported table by its export name
+pub fn table(name: &str) -> Option<&'static TableSpec> {
+    TABLES.iter().find(|t| t.name == name)
+}
@@ -0,0 +1,403 @@
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params_from_iter, Connection};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use super::parse::ImportDoc;
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::model::Snapshot;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::usecases::s515_export::schema::{TableSpec, TABLES};
+use crate::usecases::s522_delete_snapshot::clear_snapshot;
+
+/// What to do when the project already has a snapshot with the imported name
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OnConflict {
+    /// Import under a free name: "name (2)", "name (3)", ...
+    #[default]
+    Rename,
+    /// Leave the existing snapshot alone and import nothing
+    Skip,
+    /// Replace the existing snapshot's data, keeping its id
+    Overwrite,
+}
+
+impl OnConflict {
+    pub const ALL: [OnConflict; 3] = [OnConflict::Rename, OnConflict::Skip, OnConflict::Overwrite];
+
+    /// CLI name
+    pub fn key(self) -> &'static str {
+        match self {
+            OnConflict::Rename => "rename",
+            OnConflict::Skip => "skip",
+            OnConflict::Overwrite => "overwrite",
+        }
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            OnConflict::Rename => "Rename",
+            OnConflict::Skip => "Skip",
+            OnConflict::Overwrite => "Overwrite",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportOutcome {
+    Created,
+    /// Imported as a new snapshot because the original name was taken
+    Renamed {
+        from: String,
+    },
+    Overwritten,
+    /// A snapshot with this name exists and nothing was imported
+    Skipped,
+}
+
+pub struct ImportSummary {
+    pub dry_run: bool,
+    pub project_name: String,
+    pub project_id: i64,
+    pub project_created: bool,
+    pub snapshot_id: i64,
+    pub snapshot_name: String,
+    pub outcome: ImportOutcome,
+    /// Imported rows per export table
+    pub rows: Vec<(&'static str, usize)>,
+}
+
+impl ImportSummary {
+    pub fn describe(&self) -> String {
+        let (will, project_created) = if self.dry_run {
+            ("would be", "would be created")
+        } else {
+            ("was", "created")
+        };
+        let project = if self.project_created {
+            format!("Project '{}' {}", self.project_name, project_created)
+        } else {
+            format!(
+                "Project '{}' (ID={}) matched",
+                self.project_name, self.project_id
+            )
+        };
+        let snapshot = match &self.outcome {
+            ImportOutcome::Created => {
+                format!("snapshot '{}' {} imported", self.snapshot_name, will)
+            }
+            ImportOutcome::Renamed { from } => {
+                format!(
+                    "'{}' exists, snapshot {} imported as '{}'",
+                    from, will, self.snapshot_name
+                )
+            }
+            ImportOutcome::Overwritten => {
+                format!(
+                    "snapshot '{}' (ID={}) {} overwritten",
+                    self.snapshot_name, self.snapshot_id, will
+                )
+            }
+            ImportOutcome::Skipped => {
+                format!(
+                    "snapshot '{}' (ID={}) exists, nothing {} imported",
+                    self.snapshot_name, self.snapshot_id, will
+                )
+            }
+        };
+        let mut text = format!("{}; {}", project, snapshot);
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .filter(|(_, n)| *n > 0)
+            .map(|(table, n)| format!("{} {}", n, table))
+            .collect();
+        if !rows.is_empty() {
+            text.push_str(&format!(" ({})", rows.join(", ")));
+        }
+        text
+    }
+}
+
+/// First of "name (2)", "name (3)", ... not in `taken`
+pub fn free_name(name: &str, taken: &[&str]) -> String {
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !taken.contains(&candidate.as_str()))
+        .unwrap_or_default()
+}
+
+fn int(record: &Map<String, Value>, column: &str) -> i64 {
+    record.get(column).and_then(Value::as_i64).unwrap_or(0)
+}
+
+fn sql_value(value: Option<&Value>) -> Result<SqlValue, String> {
+    Ok(match value {
+        None | Some(Value::Null) => SqlValue::Null,
+        Some(Value::Bool(b)) => SqlValue::Integer(*b as i64),
+        Some(Value::Number(n)) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        Some(Value::String(s)) => SqlValue::Text(s.clone()),
+        Some(other) => return Err(format!("Unexpected value {}", other)),
+    })
+}
+
+/// Insert one row of `spec` with `id` dropped, `parent_id`/`file_id` replaced by `link`
+/// and `target_file_id` replaced by `target`
+fn insert_row(
+    conn: &Connection,
+    spec: &TableSpec,
+    snapshot_id: i64,
+    row: &Map<String, Value>,
+    link: Option<i64>,
+    target: Option<i64>,
+) -> Result<i64, String> {
+    let columns: Vec<&str> = spec
+        .columns
+        .iter()
+        .copied()
+        .filter(|c| *c != "id")
+        .collect();
+    let placeholders: Vec<String> = (2..columns.len() + 2).map(|i| format!("?{}", i)).collect();
+    let sql = format!(
+        "INSERT INTO {} (snapshot_id, {}) VALUES (?1, {})",
+        spec.source,
+        columns.join(", "),
+        placeholders.join(", ")
+    );
+
+    let mut values = vec![SqlValue::Integer(snapshot_id)];
+    for column in &columns {
+        values.push(match *column {
+            "parent_id" | "file_id" => link.map_or(SqlValue::Null, SqlValue::Integer),
+            "target_file_id" => target.map_or(SqlValue::Null, SqlValue::Integer),
+            _ => sql_value(row.get(*column))?,
+        });
+    }
+    conn.prepare_cached(&sql)
+        .and_then(|mut stmt| stmt.execute(params_from_iter(values)))
+        .map_err(|e| format!("Failed to import into {}: {}", spec.source, e))?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Insert files parents first, then every other table with `file_id` and `target_file_id` remapped
+fn insert_rows(
+    conn: &Connection,
+    snapshot_id: i64,
+    doc: &ImportDoc,
+) -> Result<Vec<(&'static str, usize)>, String> {
+    let mut file_ids: HashMap<i64, i64> = HashMap::new();
+    let mut pending: Vec<&Map<String, Value>> = doc.rows("files").iter().collect();
+    while !pending.is_empty() {
+        let mut deferred = Vec::new();
+        for row in &pending {
+            let parent = match row.get("parent_id").and_then(Value::as_i64) {
+                None => None,
+                Some(old) => match file_ids.get(&old) {
+                    Some(new) => Some(*new),
+                    None => {
+                        deferred.push(*row);
+                        continue;
+                    }
+                },
+            };
+            let old_id = row
+                .get("id")
+                .and_then(Value::as_i64)
+                .ok_or("File row without id")?;
+            let new_id = insert_row(conn, &TABLES[0], snapshot_id, row, parent, None)?;
+            file_ids.insert(old_id, new_id);
+        }
+        if deferred.len() == pending.len() {
+            return Err(format!(
+                "{} files reference a missing parent directory",
+                deferred.len()
+            ));
+        }
+        pending = deferred;
+    }
+
+    let remap =
+        |spec: &TableSpec, row: &Map<String, Value>, column: &str| -> Result<Option<i64>, String> {
+            if !spec.columns.contains(&column) {
+                return Ok(None);
+            }
+            let old = row.get(column).and_then(Value::as_i64);
+            let new = old.and_then(|old| file_ids.get(&old).copied());
+            Ok(Some(new.ok_or_else(|| {
+                format!("A row of '{}' references an unknown file", spec.name)
+            })?))
+        };
+
+    let mut counts = vec![("files", file_ids.len())];
+    for spec in TABLES.iter().skip(1) {
+        let rows = doc.rows(spec.name);
+        for row in rows {
+            let file_id = remap(spec, row, "file_id")?;
+            let target_file_id = remap(spec, row, "target_file_id")?;
+            insert_row(conn, spec, snapshot_id, row, file_id, target_file_id)?;
+        }
+        counts.push((spec.name, rows.len()));
+    }
+    Ok(counts)
+}
+
+/// Import a parsed export. The project is matched by name and root path or created.
+/// Runs in one transaction; a dry run rolls it back and only reports what would happen.
+pub fn import_snapshot(
+    conn: &Connection,
+    doc: &ImportDoc,
+    on_conflict: OnConflict,
+    dry_run: bool,
+) -> Result<ImportSummary, String> {
+    let project_name = doc
+        .text("project_name")
+        .ok_or("Export has no project name")?;
+    let root_path = doc.text("project_root_path").unwrap_or("");
+    let name = doc.text("name").ok_or("Export has no snapshot name")?;
+    let scanned_at = doc.text("scanned_at").unwrap_or("");
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let project = project_repo::list_all(conn)
+        .map_err(|e| format!("Failed to load projects: {}", e))?
+        .into_iter()
+        .find(|p| p.name == project_name && p.root_path == root_path);
+    let (project_id, project_created) = match project {
+        Some(p) => (p.id, false),
+        None => {
+            let id = project_repo::create(conn, project_name, root_path, None)
+                .map_err(|e| format!("Failed to create project: {}", e))?;
+            (id, true)
+        }
+    };
+
+    let existing = snapshot_repo::list_by_project(conn, project_id)
+        .map_err(|e| format!("Failed to load snapshots: {}", e))?;
+    let conflict = existing.iter().find(|s| s.name == name);
+    let mut summary = ImportSummary {
+        dry_run,
+        project_name: project_name.to_string(),
+        project_id,
+        project_created,
+        snapshot_id: 0,
+        snapshot_name: name.to_string(),
+        outcome: ImportOutcome::Created,
+        rows: Vec::new(),
+    };
+
+    let snapshot = Snapshot::new(
+        conflict.map_or(0, |s| s.id),
+        name.to_string(),
+        project_id,
+        doc.text("comment").map(str::to_string),
+        int(&doc.snapshot, "files_count"),
+        int(&doc.snapshot, "dirs_count"),
+        int(&doc.snapshot, "files_size_bytes"),
+        int(&doc.snapshot, "loc_count"),
+        scanned_at.to_string(),
+        doc.text("git_revision").map(str::to_string),
+    );
+    let create = |name: &str| {
+        snapshot_repo::create(
+            conn,
+            name,
+            project_id,
+            snapshot.comment.as_deref(),
+            snapshot.files_count,
+            snapshot.dirs_count,
+            snapshot.files_size_bytes,
+            snapshot.loc_count,
+            &snapshot.scanned_at,
+        )
+        .map_err(|e| format!("Failed to create snapshot: {}", e))
+    };
+
+    summary.snapshot_id = match (conflict, on_conflict) {
+        (None, _) => create(name)?,
+        (Some(s), OnConflict::Skip) => {
+            summary.snapshot_id = s.id;
+            summary.outcome = ImportOutcome::Skipped;
+            return Ok(summary);
+        }
+        (Some(_), OnConflict::Rename) => {
+            let taken: Vec<&str> = existing.iter().map(|s| s.name.as_str()).collect();
+            summary.snapshot_name = free_name(name, &taken);
+            summary.outcome = ImportOutcome::Renamed {
+                from: name.to_string(),
+            };
+            create(&summary.snapshot_name)?
+        }
+        (Some(s), OnConflict::Overwrite) => {
+            clear_snapshot(conn, s.id)?;
+            snapshot_repo::update(conn, &snapshot)
+                .map_err(|e| format!("Failed to update snapshot: {}", e))?;
+            summary.outcome = ImportOutcome::Overwritten;
+            s.id
+        }
+    };
+    snapshot_repo::set_git_revision(conn, summary.snapshot_id, snapshot.git_revision.as_deref())
+        .map_err(|e| format!("Failed to set git revision: {}", e))?;
+    summary.rows = insert_rows(conn, summary.snapshot_id, doc)?;
+
+    if !dry_run {
+        tx.commit()
+            .map_err(|e| format!("Failed to commit import: {}", e))?;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::db::init_database;
+    use crate::usecases::s515_export::collect::collect_snapshot;
+    use crate::usecases::s515_export::format::to_json_flat;
+    use crate::usecases::s516_import::parse::parse_export;
+
+    #[test]
+    fn renamed_import_remaps_dependency_targets() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_database(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO n001_project (id, name, root_path) VALUES (1, 'demo', '/demo');
+             INSERT INTO n002_snapshot (id, name, project_id, files_count, dirs_count, files_size_bytes,
+                                        loc_count, scanned_at)
+                 VALUES (1, 'base', 1, 2, 0, 0, 0, '');
+             INSERT INTO n003_snapshot_file (id, snapshot_id, name, path, size_bytes, is_directory)
+                 VALUES (10, 1, 'lib.rs', 'lib.rs', 0, 0), (11, 1, 'main.rs', 'main.rs', 0, 0);
+             INSERT INTO n018_module_dependency (snapshot_id, file_id, target_file_id, line, use_path, in_test)
+                 VALUES (1, 11, 10, 3, 'crate::lib', 1);",
+        )
+        .unwrap();
+
+        let export = to_json_flat(&collect_snapshot(&conn, 1).unwrap()).to_string();
+        let doc = parse_export(&export).unwrap();
+        let summary = import_snapshot(&conn, &doc, OnConflict::Rename, false).unwrap();
+        assert_eq!(summary.snapshot_name, "base (2)");
+        assert!(summary.rows.contains(&("dependencies", 1)));
+
+        let (from, to, in_test): (String, String, bool) = conn
+            .query_row(
+                "SELECT f.path, t.path, d.in_test FROM n018_module_dependency d
+                 JOIN n003_snapshot_file f ON f.id = d.file_id
+                 JOIN n003_snapshot_file t ON t.id = d.target_file_id
+                 WHERE d.snapshot_id = ?1 AND f.snapshot_id = ?1 AND t.snapshot_id = ?1",
+                [summary.snapshot_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            (from.as_str(), to.as_str(), in_test),
+            ("main.rs", "lib.rs", true)
+        );
+    }
+}
@@ -0,0 +1,180 @@
+pub mod import;
+pub mod parse;
+
+use eframe::egui;
+use rusqlite::Connection;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use import::{import_snapshot, ImportSummary, OnConflict};
+use parse::{parse_export, ImportDoc};
+
+/// Read and parse a JSON export file
+pub fn read_export(path: &Path) -> Result<ImportDoc, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_export(&text)
+}
+
+#[derive(Default)]
+pub struct ImportState {
+    pub open: bool,
+    pub path: Option<PathBuf>,
+    pub doc: Option<ImportDoc>,
+    pub on_conflict: OnConflict,
+    /// Result of the last dry run or import
+    pub summary: Option<ImportSummary>,
+    pub status: String,
+}
+
+impl ImportState {
+    pub fn open(&mut self) {
+        *self = ImportState {
+            open: true,
+            ..Default::default()
+        };
+    }
+
+    fn pick_file(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Snapshot export", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+        self.summary = None;
+        match read_export(&path) {
+            Ok(doc) => {
+                self.status.clear();
+                self.doc = Some(doc);
+            }
+            Err(e) => {
+                self.status = e;
+                self.doc = None;
+            }
+        }
+        self.path = Some(path);
+    }
+
+    fn run(&mut self, conn: &Connection, dry_run: bool) {
+        let Some(doc) = &self.doc else { return };
+        match import_snapshot(conn, doc, self.on_conflict, dry_run) {
+            Ok(summary) => {
+                self.status.clear();
+                self.summary = Some(summary);
+            }
+            Err(e) => {
+                self.status = e;
+                self.summary = None;
+            }
+        }
+    }
+}
+
+/// Renders the import window; returns the status line once a snapshot was imported
+pub fn ui_import_window(
+    ctx: &egui::Context,
+    conn: &Connection,
+    state: &mut ImportState,
+) -> Option<String> {
+    if !state.open {
+        return None;
+    }
+    let mut open = true;
+    let mut imported = None;
+    egui::Window::new("Import Snapshot")
+        .id(egui::Id::new("s516_import_snapshot"))
+        .open(&mut open)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("File:");
+                match &state.path {
+                    Some(path) => ui.label(path.display().to_string()),
+                    None => ui.label(egui::RichText::new("None").weak()),
+                };
+                if ui.button("Browse...").clicked() {
+                    state.pick_file();
+                }
+            });
+
+            if let Some(doc) = &state.doc {
+                egui::Grid::new("s516_import_doc")
+                    .num_columns(2)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label("Project:");
+                        ui.label(format!(
+                            "{} ({})",
+                            doc.text("project_name").unwrap_or("?"),
+                            doc.text("project_root_path").unwrap_or("")
+                        ));
+                        ui.end_row();
+                        ui.label("Snapshot:");
+                        ui.label(format!(
+                            "{} — scanned {}",
+                            doc.text("name").unwrap_or("?"),
+                            doc.text("scanned_at").unwrap_or("?")
+                        ));
+                        ui.end_row();
+                        ui.label("Files:");
+                        ui.label(doc.rows("files").len().to_string());
+                        ui.end_row();
+                        ui.label("Schema version:");
+                        ui.label(doc.schema_version.to_string());
+                        ui.end_row();
+                    });
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("If the snapshot exists:");
+                egui::ComboBox::from_id_salt("s516_on_conflict")
+                    .selected_text(state.on_conflict.label())
+                    .show_ui(ui, |ui| {
+                        for mode in OnConflict::ALL {
+                            if ui
+                                .selectable_value(&mut state.on_conflict, mode, mode.label())
+                                .changed()
+                            {
+                                state.summary = None;
+                            }
+                        }
+                    });
+            });
+
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(state.doc.is_some(), |ui| {
+                    if ui
+                        .button("Preview")
+                        .on_hover_text("Dry run: nothing is written")
+                        .clicked()
+                    {
+                        state.run(conn, true);
+                    }
+                    if ui.button("Import").clicked() {
+                        state.run(conn, false);
+                        if let Some(summary) = state.summary.as_ref().filter(|s| !s.dry_run) {
+                            imported = Some(summary.describe());
+                        }
+                    }
+                });
+            });
+
+            if let Some(summary) = &state.summary {
+                ui.add_space(6.0);
+                let text = egui::RichText::new(summary.describe());
+                ui.label(if summary.dry_run {
+                    text.italics()
+                } else {
+                    text.strong()
+                });
+            }
+            if !state.status.is_empty() {
+                ui.colored_label(egui::Color32::LIGHT_RED, &state.status);
+            }
+        });
+    if !open {
+        state.open = false;
+    }
+    imported
+}
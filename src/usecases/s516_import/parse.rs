@@ -0,0 +1,248 @@
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use crate::usecases::s515_export::schema::{table, SCHEMA_NAME, SCHEMA_VERSION, TABLES};
+
+/// A parsed JSON export: the snapshot record and rows of every table, keyed by column name
+pub struct ImportDoc {
+    pub schema_version: i64,
+    pub snapshot: Map<String, Value>,
+    /// Rows in the flat layout: files carry `id`/`parent_id`, per-file rows carry `file_id`
+    pub tables: HashMap<String, Vec<Map<String, Value>>>,
+}
+
+impl ImportDoc {
+    pub fn rows(&self, table: &str) -> &[Map<String, Value>] {
+        self.tables.get(table).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn text(&self, column: &str) -> Option<&str> {
+        self.snapshot.get(column).and_then(Value::as_str)
+    }
+}
+
+fn objects(value: &Value, what: &str) -> Result<Vec<Map<String, Value>>, String> {
+    value
+        .as_array()
+        .ok_or_else(|| format!("'{}' must be an array", what))?
+        .iter()
+        .map(|v| {
+            v.as_object()
+                .cloned()
+                .ok_or_else(|| format!("Rows of '{}' must be objects", what))
+        })
+        .collect()
+}
+
+/// Parse a `json` (tree) or `json-flat` export. Newer exports are rejected; unknown tables
+/// and columns of the same version are ignored.
+pub fn parse_export(text: &str) -> Result<ImportDoc, String> {
+    let doc: Value = serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let doc = doc.as_object().ok_or("Export must be a JSON object")?;
+
+    if doc.get("schema").and_then(Value::as_str) != Some(SCHEMA_NAME) {
+        return Err(format!(
+            "Not a snapshot export (expected schema '{}')",
+            SCHEMA_NAME
+        ));
+    }
+    let schema_version = doc
+        .get("schema_version")
+        .and_then(Value::as_i64)
+        .ok_or("Missing schema_version")?;
+    if schema_version > SCHEMA_VERSION {
+        return Err(format!(
+            "Export uses schema version {}, this build reads up to {}",
+            schema_version, SCHEMA_VERSION
+        ));
+    }
+    let snapshot = doc
+        .get("snapshot")
+        .and_then(Value::as_object)
+        .cloned()
+        .ok_or("Missing snapshot record")?;
+
+    let mut tables = HashMap::new();
+    match doc.get("layout").and_then(Value::as_str) {
+        Some("flat") => {
+            let all = doc
+                .get("tables")
+                .and_then(Value::as_object)
+                .ok_or("Missing tables")?;
+            for spec in &TABLES {
+                if let Some(rows) = all.get(spec.name) {
+                    tables.insert(spec.name.to_string(), objects(rows, spec.name)?);
+                }
+            }
+        }
+        Some("tree") => flatten_tree(doc, &mut tables)?,
+        Some(other) => {
+            return Err(format!(
+                "Layout '{}' cannot be imported, use a JSON export",
+                other
+            ))
+        }
+        None => return Err("Missing layout".to_string()),
+    }
+    Ok(ImportDoc {
+        schema_version,
+        snapshot,
+        tables,
+    })
+}
+
+/// Turn nested file nodes back into `files` rows (parents first) and per-file rows
+fn flatten_tree(
+    doc: &Map<String, Value>,
+    tables: &mut HashMap<String, Vec<Map<String, Value>>>,
+) -> Result<(), String> {
+    for spec in TABLES.iter().filter(|t| t.name != "files" && !t.per_file()) {
+        if let Some(rows) = doc.get(spec.name) {
+            tables.insert(spec.name.to_string(), objects(rows, spec.name)?);
+        }
+    }
+
+    let roots = match doc.get("files") {
+        Some(files) => files
+            .as_array()
+            .ok_or("'files' must be an array")?
+            .as_slice(),
+        None => &[],
+    };
+    let mut stack: Vec<(Option<i64>, &Value)> = roots.iter().rev().map(|n| (None, n)).collect();
+    while let Some((parent, node)) = stack.pop() {
+        let node = node.as_object().ok_or("File nodes must be objects")?;
+        let id = node
+            .get("id")
+            .and_then(Value::as_i64)
+            .ok_or("File node without id")?;
+
+        let mut file = Map::new();
+        file.insert("id".into(), id.into());
+        file.insert("parent_id".into(), parent.map_or(Value::Null, Value::from));
+        for (key, value) in node {
+            if key == "id" || key == "children" {
+                continue;
+            }
+            let Some(spec) = table(key).filter(|t| t.per_file()) else {
+                file.insert(key.clone(), value.clone());
+                continue;
+            };
+            let rows = if spec.one_per_file {
+                vec![value.clone()]
+            } else {
+                value.as_array().cloned().unwrap_or_default()
+            };
+            for row in rows {
+                let mut record = Map::new();
+                record.insert("file_id".into(), id.into());
+                record.extend(
+                    row.as_object()
+                        .cloned()
+                        .ok_or_else(|| format!("Rows of '{}' must be objects", key))?,
+                );
+                tables.entry(key.clone()).or_default().push(record);
+            }
+        }
+        tables.entry("files".to_string()).or_default().push(file);
+
+        if let Some(children) = node.get("children").and_then(Value::as_array) {
+            stack.extend(children.iter().rev().map(|c| (Some(id), c)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecases::s515_export::format::{
+        to_json_flat, to_json_tree, SnapshotExport, TableData,
+    };
+    use crate::usecases::s515_export::schema::SNAPSHOT_COLUMNS;
+    use serde_json::json;
+
+    #[test]
+    fn tree_and_flat_exports_parse_to_the_same_rows() {
+        let files = table("files").unwrap();
+        let file = |id: i64, parent: Value, name: &str, dir: bool| {
+            let mut row = vec![
+                json!(id),
+                parent,
+                json!(name),
+                json!(name),
+                json!(1),
+                json!(dir),
+            ];
+            row.resize(files.columns.len(), Value::Null);
+            row
+        };
+        let export = SnapshotExport {
+            exported_at: "2026-01-01T00:00:00Z".into(),
+            snapshot: SNAPSHOT_COLUMNS.iter().map(|c| json!(c)).collect(),
+            tables: vec![
+                TableData {
+                    spec: files,
+                    rows: vec![
+                        file(1, Value::Null, "src", true),
+                        file(2, json!(1), "lib.rs", false),
+                        file(3, json!(1), "main.rs", false),
+                        file(4, Value::Null, "Cargo.toml", false),
+                    ],
+                },
+                TableData {
+                    spec: table("safety_sites").unwrap(),
+                    rows: vec![
+                        vec![json!(2), json!("unsafe_block"), json!(4), Value::Null],
+                        vec![json!(2), json!("unwrap"), json!(9), json!("f")],
+                    ],
+                },
+                TableData {
+                    spec: table("languages").unwrap(),
+                    rows: vec![vec![json!(3), json!("Rust"), json!(7)]],
+                },
+                TableData {
+                    spec: table("dependencies").unwrap(),
+                    rows: vec![vec![
+                        json!(3),
+                        json!(2),
+                        json!(1),
+                        json!("crate::lib"),
+                        json!(false),
+                    ]],
+                },
+                TableData {
+                    spec: table("packages").unwrap(),
+                    rows: vec![vec![
+                        json!("serde"),
+                        json!("1.0.0"),
+                        Value::Null,
+                        Value::Null,
+                        json!("MIT"),
+                    ]],
+                },
+            ],
+        };
+
+        let tree = parse_export(&to_json_tree(&export).to_string()).unwrap();
+        let flat = parse_export(&to_json_flat(&export).to_string()).unwrap();
+        assert_eq!(tree.text("name"), Some("name"));
+        for name in [
+            "files",
+            "safety_sites",
+            "languages",
+            "packages",
+            "dependencies",
+        ] {
+            assert_eq!(tree.rows(name), flat.rows(name), "{}", name);
+        }
+        assert_eq!(tree.rows("files").len(), 4);
+        assert!(tree.rows("functions").is_empty());
+
+        let version = format!("\"schema_version\":{}", SCHEMA_VERSION);
+        let newer = to_json_flat(&export)
+            .to_string()
+            .replace(&version, "\"schema_version\":99");
+        assert!(parse_export(&newer).is_err());
+    }
+}
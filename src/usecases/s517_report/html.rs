@@ -0,0 +1,482 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::treemap::{squarify, Rect};
+use super::{format_size, ReportData};
+use crate::domain::n003_snapshot_file::model::SnapshotFile;
+use crate::domain::n014_file_coverage::model::{FileCoverage, GOOD_RATE, POOR_RATE};
+use crate::domain::n017_file_language::model::{language_rgb, share};
+use crate::shared::language::detect_language;
+
+const LARGEST_FILES: usize = 20;
+const COMPLEX_FILES: usize = 20;
+const TREEMAP_WIDTH: f64 = 1000.0;
+const TREEMAP_HEIGHT: f64 = 560.0;
+
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 24px auto; max-width: 1080px; color: #222; }
+h1 { margin-bottom: 4px; }
+h2 { margin-top: 32px; border-bottom: 1px solid #ddd; padding-bottom: 4px; }
+.meta { color: #666; }
+.cards { display: flex; flex-wrap: wrap; gap: 12px; }
+.card { border: 1px solid #ddd; border-radius: 6px; padding: 8px 14px; min-width: 110px; }
+.card b { display: block; font-size: 1.4em; }
+table { border-collapse: collapse; width: 100%; font-size: 0.92em; }
+th, td { text-align: left; padding: 4px 8px; border-bottom: 1px solid #eee; }
+td.num, th.num { text-align: right; }
+.bar { display: flex; height: 14px; border-radius: 3px; overflow: hidden; background: #eee; }
+.swatch { display: inline-block; width: 10px; height: 10px; border-radius: 2px; margin-right: 6px; }
+.cycle summary { cursor: pointer; margin: 6px 0; }
+.cut { color: #b00; font-weight: bold; }
+#tree details { margin-left: 16px; }
+#tree summary { cursor: pointer; }
+#tree .file { margin-left: 32px; }
+#tree .size { color: #888; margin-left: 8px; }
+svg text { font-size: 11px; fill: #222; pointer-events: none; }
+";
+
+const SCRIPT: &str = "
+function setTree(open) {
+  document.querySelectorAll('#tree details').forEach(function (d) { d.open = open; });
+}
+function setColour(mode) {
+  document.querySelectorAll('#treemap rect[data-' + mode + ']').forEach(function (r) {
+    r.setAttribute('fill', r.getAttribute('data-' + mode));
+  });
+}
+";
+
+/// Escape text for HTML element content and attribute values
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn css_color(language: &str) -> String {
+    let (r, g, b) = language_rgb(language);
+    format!("rgb({},{},{})", r, g, b)
+}
+
+/// Green / yellow / red by line rate like the Coverage tab, grey without data
+fn coverage_css(rate: Option<f64>) -> &'static str {
+    match rate {
+        Some(rate) if rate >= GOOD_RATE => "#6cbf6c",
+        Some(rate) if rate >= POOR_RATE => "#e3cf45",
+        Some(_) => "#e07070",
+        None => "#cccccc",
+    }
+}
+
+fn percent(part: i64, total: i64) -> String {
+    format!("{:.1}%", share(part, total) * 100.0)
+}
+
+/// Children by parent id and the total size of every subtree
+struct FileTree<'a> {
+    children: HashMap<Option<i64>, Vec<&'a SnapshotFile>>,
+    sizes: HashMap<i64, i64>,
+}
+
+impl<'a> FileTree<'a> {
+    fn new(files: &'a [SnapshotFile]) -> Self {
+        let mut children: HashMap<Option<i64>, Vec<&SnapshotFile>> = HashMap::new();
+        for f in files {
+            children.entry(f.parent_id).or_default().push(f);
+        }
+        // Directories first, then by name
+        for list in children.values_mut() {
+            list.sort_by(|a, b| {
+                b.is_directory
+                    .cmp(&a.is_directory)
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+        }
+        let mut tree = FileTree {
+            children,
+            sizes: HashMap::new(),
+        };
+        for root in tree.kids(None).to_vec() {
+            tree.fill_size(root);
+        }
+        tree
+    }
+
+    fn kids(&self, parent: Option<i64>) -> &[&'a SnapshotFile] {
+        self.children.get(&parent).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    fn fill_size(&mut self, file: &'a SnapshotFile) -> i64 {
+        let size = if file.is_directory {
+            self.kids(Some(file.id))
+                .to_vec()
+                .into_iter()
+                .map(|c| self.fill_size(c))
+                .sum()
+        } else {
+            file.size_bytes
+        };
+        self.sizes.insert(file.id, size);
+        size
+    }
+
+    fn size(&self, file: &SnapshotFile) -> i64 {
+        self.sizes.get(&file.id).copied().unwrap_or(0)
+    }
+}
+
+/// Render the self-contained report page: inline CSS, SVG and a few lines of JS
+pub fn render(data: &ReportData) -> String {
+    let s = &data.snapshot;
+    let tree = FileTree::new(&data.files);
+    let mut html = String::new();
+    let title = format!("{} — {}", data.project_name, s.name);
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n<script>{}</script>\n</head>\n<body>\n",
+        escape(&title),
+        STYLE,
+        SCRIPT
+    );
+    let _ = writeln!(html, "<h1>{}</h1>", escape(&title));
+    let _ = write!(html, "<p class=\"meta\">Scanned {}", escape(&s.scanned_at));
+    if let Some(revision) = &s.git_revision {
+        let _ = write!(html, " at <code>{}</code>", escape(revision));
+    }
+    if let Some(comment) = s.comment.as_deref().filter(|c| !c.is_empty()) {
+        let _ = write!(html, " · {}", escape(comment));
+    }
+    let _ = writeln!(
+        html,
+        " · report generated {}</p>",
+        escape(&data.generated_at)
+    );
+
+    summary(&mut html, data);
+    languages(&mut html, data);
+    aggregates(&mut html, data);
+    cycles(&mut html, data);
+    largest(&mut html, data);
+    complex(&mut html, data);
+    treemap(&mut html, &tree, &data.coverage);
+    file_tree(&mut html, &tree);
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn summary(html: &mut String, data: &ReportData) {
+    let s = &data.snapshot;
+    let cards = [
+        ("Files", s.files_count.to_string()),
+        ("Directories", s.dirs_count.to_string()),
+        ("Size", format_size(s.files_size_bytes)),
+        ("Lines of code", s.loc_count.to_string()),
+        ("Languages", data.languages.len().to_string()),
+        ("Functions", data.functions.to_string()),
+        ("Aggregates", data.aggregates.len().to_string()),
+        ("Dependency cycles", data.cycles.len().to_string()),
+    ];
+    html.push_str("<h2>Summary</h2>\n<div class=\"cards\">\n");
+    for (label, value) in cards {
+        let _ = writeln!(
+            html,
+            "<div class=\"card\"><b>{}</b>{}</div>",
+            escape(&value),
+            label
+        );
+    }
+    html.push_str("</div>\n");
+}
+
+fn languages(html: &mut String, data: &ReportData) {
+    if data.languages.is_empty() {
+        return;
+    }
+    let total_loc: i64 = data.languages.iter().map(|l| l.loc).sum();
+    let total_bytes: i64 = data.languages.iter().map(|l| l.bytes).sum();
+
+    html.push_str("<h2>Languages</h2>\n<div class=\"bar\">");
+    for l in data.languages.iter().filter(|l| l.loc > 0) {
+        let _ = write!(
+            html,
+            "<div style=\"width:{:.3}%;background:{}\" title=\"{}: {}\"></div>",
+            share(l.loc, total_loc) * 100.0,
+            css_color(&l.language),
+            escape(&l.language),
+            percent(l.loc, total_loc)
+        );
+    }
+    html.push_str("</div>\n<table>\n<tr><th>Language</th><th class=\"num\">Files</th><th class=\"num\">Size</th><th class=\"num\">LOC</th><th class=\"num\">LOC share</th></tr>\n");
+    for l in &data.languages {
+        let _ = writeln!(
+            html,
+            "<tr><td><span class=\"swatch\" style=\"background:{}\"></span>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            css_color(&l.language),
+            escape(&l.language),
+            l.files,
+            format_size(l.bytes),
+            l.loc,
+            percent(l.loc, total_loc)
+        );
+    }
+    let _ = writeln!(
+        html,
+        "<tr><th>Total</th><th class=\"num\">{}</th><th class=\"num\">{}</th><th class=\"num\">{}</th><th></th></tr>\n</table>",
+        data.languages.iter().map(|l| l.files).sum::<i64>(),
+        format_size(total_bytes),
+        total_loc
+    );
+}
+
+fn aggregates(html: &mut String, data: &ReportData) {
+    if data.aggregates.is_empty() {
+        return;
+    }
+    html.push_str("<h2>Aggregates</h2>\n<table>\n<tr><th>Code</th><th>Name</th><th class=\"num\">Files</th><th class=\"num\">Size</th><th class=\"num\">Test LOC / code LOC</th><th class=\"num\">Doc coverage</th></tr>\n");
+    for a in &data.aggregates {
+        let ratio = a
+            .test_ratio()
+            .map(|r| format!("{:.2}", r))
+            .unwrap_or_else(|| "-".to_string());
+        let docs = a
+            .doc_coverage()
+            .map(|c| format!("{:.1}%", c * 100.0))
+            .unwrap_or_else(|| "-".to_string());
+        let _ = writeln!(
+            html,
+            "<tr><td><code>{}</code></td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            escape(&a.code),
+            escape(&a.name),
+            a.file_count,
+            format_size(a.total_size_bytes),
+            ratio,
+            docs
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn cycles(html: &mut String, data: &ReportData) {
+    if data.cycles.is_empty() {
+        return;
+    }
+    html.push_str("<h2>Dependency cycles</h2>\n<p class=\"meta\">Test code excluded. The marked edge is the cheapest one to cut.</p>\n");
+    for cycle in &data.cycles {
+        let cut = cycle.cut_edge();
+        let _ = writeln!(
+            html,
+            "<details class=\"cycle\"><summary>{} — cut <code>{} → {}</code></summary>\n<table>\n<tr><th>Edge</th><th>Use</th><th>At</th></tr>",
+            escape(&cycle.describe()),
+            escape(&cut.from),
+            escape(&cut.to)
+        );
+        for (i, edge) in cycle.edges.iter().enumerate() {
+            let class = if i == cycle.cut { " class=\"cut\"" } else { "" };
+            for u in &edge.uses {
+                let _ = writeln!(
+                    html,
+                    "<tr><td{}>{} → {}</td><td><code>use {}</code></td><td>{}:{}</td></tr>",
+                    class,
+                    escape(&edge.from),
+                    escape(&edge.to),
+                    escape(&u.use_path),
+                    escape(&u.path),
+                    u.line
+                );
+            }
+        }
+        html.push_str("</table>\n</details>\n");
+    }
+}
+
+fn largest(html: &mut String, data: &ReportData) {
+    let files = data.largest_files(LARGEST_FILES);
+    if files.is_empty() {
+        return;
+    }
+    html.push_str(
+        "<h2>Largest files</h2>\n<table>\n<tr><th>Path</th><th class=\"num\">Size</th></tr>\n",
+    );
+    for f in files {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"num\">{}</td></tr>",
+            escape(&f.path),
+            format_size(f.size_bytes)
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn complex(html: &mut String, data: &ReportData) {
+    if data.complex_files.is_empty() {
+        return;
+    }
+    html.push_str("<h2>Most complex files</h2>\n<table>\n<tr><th>Path</th><th class=\"num\">Functions</th><th class=\"num\">Max cyclomatic</th><th>In function</th><th class=\"num\">Total cognitive</th></tr>\n");
+    for c in data.complex_files.iter().take(COMPLEX_FILES) {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td><code>{}</code></td><td class=\"num\">{}</td></tr>",
+            escape(&c.path),
+            c.functions,
+            c.max_cyclomatic,
+            escape(&c.worst_function),
+            c.total_cognitive
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn treemap(html: &mut String, tree: &FileTree, coverage: &HashMap<i64, FileCoverage>) {
+    let roots = tree.kids(None);
+    if roots.iter().all(|f| tree.size(f) == 0) {
+        return;
+    }
+    html.push_str("<h2>Treemap</h2>\n<p class=\"meta\">Area is file size, colour is language. Hover for details.</p>\n");
+    if !coverage.is_empty() {
+        html.push_str("<p>Colour: <button onclick=\"setColour('language')\">Language</button> <button onclick=\"setColour('coverage')\">Line coverage</button></p>\n");
+    }
+    let _ = writeln!(
+        html,
+        "<svg id=\"treemap\" xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {w} {h}\" width=\"100%\">",
+        w = TREEMAP_WIDTH,
+        h = TREEMAP_HEIGHT
+    );
+    let area = Rect::new(0.0, 0.0, TREEMAP_WIDTH, TREEMAP_HEIGHT);
+    treemap_level(html, tree, coverage, roots, area, 0);
+    html.push_str("</svg>\n");
+}
+
+/// Lay out `files` in `area`; directories get a frame, a label when there is room, and their children inside
+fn treemap_level(
+    html: &mut String,
+    tree: &FileTree,
+    coverage: &HashMap<i64, FileCoverage>,
+    files: &[&SnapshotFile],
+    area: Rect,
+    depth: usize,
+) {
+    let weights: Vec<f64> = files.iter().map(|f| tree.size(f) as f64).collect();
+    for (f, rect) in files.iter().zip(squarify(&weights, area)) {
+        if rect.w * rect.h < 4.0 {
+            continue;
+        }
+        let tooltip = format!("{} — {}", f.path, format_size(tree.size(f)));
+        if f.is_directory {
+            let _ = writeln!(
+                html,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"#f4f4f4\" stroke=\"#999\" stroke-width=\"{}\"><title>{}</title></rect>",
+                rect.x,
+                rect.y,
+                rect.w,
+                rect.h,
+                if depth == 0 { 1.5 } else { 0.5 },
+                escape(&tooltip)
+            );
+            let mut inner = rect.inset(2.0);
+            if rect.w > 80.0 && rect.h > 40.0 {
+                let _ = writeln!(
+                    html,
+                    "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+                    rect.x + 4.0,
+                    rect.y + 12.0,
+                    escape(&f.name)
+                );
+                inner.y += 14.0;
+                inner.h = (inner.h - 14.0).max(0.0);
+            }
+            treemap_level(
+                html,
+                tree,
+                coverage,
+                tree.kids(Some(f.id)),
+                inner,
+                depth + 1,
+            );
+        } else {
+            let language = detect_language(&f.name, None).unwrap_or("Other");
+            let mut title = format!("{} ({})", tooltip, language);
+            let mut colours = format!("data-language=\"{}\"", css_color(language));
+            if !coverage.is_empty() {
+                let file_coverage = coverage.get(&f.id);
+                let line_rate = file_coverage.and_then(FileCoverage::line_rate);
+                for (label, rate) in [
+                    ("lines", line_rate),
+                    (
+                        "branches",
+                        file_coverage.and_then(FileCoverage::branch_rate),
+                    ),
+                ] {
+                    if let Some(rate) = rate {
+                        let _ = write!(title, ", {} {:.1}%", label, rate * 100.0);
+                    }
+                }
+                let _ = write!(colours, " data-coverage=\"{}\"", coverage_css(line_rate));
+            }
+            let _ = writeln!(
+                html,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\" {} stroke=\"#fff\" stroke-width=\"0.5\"><title>{}</title></rect>",
+                rect.x,
+                rect.y,
+                rect.w,
+                rect.h,
+                css_color(language),
+                colours,
+                escape(&title)
+            );
+            if rect.w > 70.0 && rect.h > 16.0 {
+                let _ = writeln!(
+                    html,
+                    "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+                    rect.x + 3.0,
+                    rect.y + 12.0,
+                    escape(&f.name)
+                );
+            }
+        }
+    }
+}
+
+fn file_tree(html: &mut String, tree: &FileTree) {
+    if tree.kids(None).is_empty() {
+        return;
+    }
+    html.push_str("<h2>Files</h2>\n<p><button onclick=\"setTree(true)\">Expand all</button> <button onclick=\"setTree(false)\">Collapse all</button></p>\n<div id=\"tree\">\n");
+    tree_level(html, tree, None, 0);
+    html.push_str("</div>\n");
+}
+
+fn tree_level(html: &mut String, tree: &FileTree, parent: Option<i64>, depth: usize) {
+    for f in tree.kids(parent) {
+        let size = format_size(tree.size(f));
+        if f.is_directory {
+            let _ = writeln!(
+                html,
+                "<details{}><summary>{}/<span class=\"size\">{}</span></summary>",
+                if depth == 0 { " open" } else { "" },
+                escape(&f.name),
+                size
+            );
+            tree_level(html, tree, Some(f.id), depth + 1);
+            html.push_str("</details>\n");
+        } else {
+            let _ = writeln!(
+                html,
+                "<div class=\"file\">{}<span class=\"size\">{}</span></div>",
+                escape(&f.name),
+                size
+            );
+        }
+    }
+}
@@ -0,0 +1,509 @@
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n003_snapshot_file::model::SnapshotFile;
+use crate::domain::n005_snapshot_package::repository as package_repo;
+use crate::domain::n011_debt_marker::repository as marker_repo;
+use crate::domain::n012_safety_site::repository as safety_repo;
+use crate::domain::n017_file_language::repository as language_repo;
+use crate::shared::safety::SiteKind;
+use crate::usecases::s505_compare_snapshots::compare::{compare_snapshots, CompareStatus};
+use crate::usecases::s518_dependencies::cycles::{all_cycles, Cycle};
+use crate::usecases::s518_dependencies::ModuleGraph;
+
+/// Longest list rendered in full; the rest is summarized as "… and N more"
+const MAX_LIST: usize = 15;
+
+/// Changed files and LOC of one aggregate or layer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GroupChange {
+    pub name: String,
+    pub added: usize,
+    pub modified: usize,
+    pub removed: usize,
+    pub loc_before: i64,
+    pub loc_after: i64,
+}
+
+/// One line of a findings list: `path:line` — label: `code`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub path: String,
+    pub line: i64,
+    pub label: String,
+    /// Source text, empty when there is none to show
+    pub code: String,
+}
+
+/// Versions of a package whose version set differs between the snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageChange {
+    pub name: String,
+    pub base: Vec<String>,
+    pub target: Vec<String>,
+}
+
+/// What changed from a base snapshot to a target one, for a pull request comment
+pub struct PrReport {
+    pub base_name: String,
+    pub target_name: String,
+    /// Totals over all changed files
+    pub total: GroupChange,
+    /// Largest LOC change first
+    pub aggregates: Vec<GroupChange>,
+    pub layers: Vec<GroupChange>,
+    pub violations: Vec<Finding>,
+    pub todos: Vec<Finding>,
+    pub unsafe_sites: Vec<Finding>,
+    /// Dependency cycles of production code not present in the base
+    pub cycles: Vec<Cycle>,
+    /// Ordered by name
+    pub packages: Vec<PackageChange>,
+}
+
+/// Items of `target` beyond the number of items with the same key in `base`
+pub fn new_items<T, K: std::hash::Hash + Eq>(
+    base: &[T],
+    target: Vec<T>,
+    key: impl Fn(&T) -> K,
+) -> Vec<T> {
+    let mut left: HashMap<K, usize> = HashMap::new();
+    for item in base {
+        *left.entry(key(item)).or_default() += 1;
+    }
+    target
+        .into_iter()
+        .filter(|item| match left.get_mut(&key(item)) {
+            Some(n) if *n > 0 => {
+                *n -= 1;
+                false
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+fn by_largest_change(groups: BTreeMap<String, GroupChange>) -> Vec<GroupChange> {
+    let mut groups: Vec<GroupChange> = groups.into_values().collect();
+    groups.sort_by_key(|g| std::cmp::Reverse((g.loc_after - g.loc_before).abs()));
+    groups
+}
+
+impl PrReport {
+    pub fn load(conn: &Connection, base_id: i64, target_id: i64) -> Result<Self, String> {
+        let name = |id: i64| {
+            snapshot_repo::get_by_id(conn, id)
+                .map(|s| s.name)
+                .map_err(|e| format!("Failed to load snapshot {}: {}", id, e))
+        };
+        let (base_name, target_name) = (name(base_id)?, name(target_id)?);
+        let base = ModuleGraph::load(conn, base_id)?;
+        let target = ModuleGraph::load(conn, target_id)?;
+        let loc = |id: i64| -> Result<HashMap<i64, i64>, String> {
+            Ok(language_repo::list_by_snapshot(conn, id)
+                .map_err(|e| format!("Failed to load LOC: {}", e))?
+                .into_iter()
+                .map(|l| (l.file_id, l.loc))
+                .collect())
+        };
+        let (base_loc, target_loc) = (loc(base_id)?, loc(target_id)?);
+
+        // Changed files per aggregate and layer
+        let mut total = GroupChange::default();
+        let mut aggregates: BTreeMap<String, GroupChange> = BTreeMap::new();
+        let mut layers: BTreeMap<String, GroupChange> = BTreeMap::new();
+        let entries = compare_snapshots(conn, base_id, target_id)?;
+        // Files that could not be compared are not counted as changes
+        let changed = entries.iter().filter(|e| {
+            !matches!(
+                e.status,
+                CompareStatus::Unchanged | CompareStatus::Unavailable
+            )
+        });
+        for entry in changed {
+            let file: Option<&SnapshotFile> = entry
+                .target_file_id
+                .and_then(|id| target.files.get(&id))
+                .or_else(|| entry.base_file_id.and_then(|id| base.files.get(&id)));
+            let Some(file) = file else { continue };
+            let before = entry
+                .base_file_id
+                .and_then(|id| base_loc.get(&id))
+                .copied()
+                .unwrap_or(0);
+            let after = entry
+                .target_file_id
+                .and_then(|id| target_loc.get(&id))
+                .copied()
+                .unwrap_or(0);
+
+            let aggregate = match (&file.artifact_id, &file.artifact_name) {
+                (Some(id), name) => format!("{}{}", id, name.as_deref().unwrap_or("")),
+                (None, _) => "(outside aggregates)".to_string(),
+            };
+            let layer = file
+                .crate_layer
+                .clone()
+                .unwrap_or_else(|| "(no layer)".to_string());
+            for group in [
+                &mut total,
+                aggregates
+                    .entry(aggregate.clone())
+                    .or_insert_with(|| GroupChange {
+                        name: aggregate,
+                        ..Default::default()
+                    }),
+                layers.entry(layer.clone()).or_insert_with(|| GroupChange {
+                    name: layer,
+                    ..Default::default()
+                }),
+            ] {
+                match entry.status {
+                    CompareStatus::Added => group.added += 1,
+                    CompareStatus::Removed => group.removed += 1,
+                    _ => group.modified += 1,
+                }
+                group.loc_before += before;
+                group.loc_after += after;
+            }
+        }
+
+        // New architecture violations
+        let known: HashSet<(String, String)> = base
+            .violations()
+            .into_iter()
+            .map(|v| (v.path, v.use_path))
+            .collect();
+        let violations = target
+            .violations()
+            .into_iter()
+            .filter(|v| !known.contains(&(v.path.clone(), v.use_path.clone())))
+            .map(|v| Finding {
+                path: v.path,
+                line: v.line,
+                label: format!("{} -> {}", v.rule, v.target_path),
+                code: v.use_path,
+            })
+            .collect();
+
+        // New TODOs: markers with a (path, kind, text) more frequent than in the base
+        let markers = |id: i64, graph: &ModuleGraph| -> Result<Vec<Finding>, String> {
+            Ok(marker_repo::list_by_snapshot(conn, id)
+                .map_err(|e| format!("Failed to load markers: {}", e))?
+                .into_iter()
+                .map(|m| Finding {
+                    path: graph.path(m.file_id).to_string(),
+                    line: m.line,
+                    label: m.kind,
+                    code: m.text,
+                })
+                .collect())
+        };
+        let todos = new_items(
+            &markers(base_id, &base)?,
+            markers(target_id, &target)?,
+            |f| (f.path.clone(), f.label.clone(), f.code.clone()),
+        );
+
+        // New unsafe sites: more sites of a kind in a function than in the base
+        let sites = |id: i64, graph: &ModuleGraph| -> Result<Vec<Finding>, String> {
+            Ok(safety_repo::list_by_snapshot(conn, id)
+                .map_err(|e| format!("Failed to load safety sites: {}", e))?
+                .into_iter()
+                .filter(|s| SiteKind::parse(&s.kind).is_some_and(SiteKind::is_unsafe))
+                .map(|s| Finding {
+                    path: graph.path(s.file_id).to_string(),
+                    line: s.line,
+                    label: s.kind,
+                    code: s.function.unwrap_or_default(),
+                })
+                .collect())
+        };
+        let unsafe_sites = new_items(&sites(base_id, &base)?, sites(target_id, &target)?, |f| {
+            (f.path.clone(), f.label.clone(), f.code.clone())
+        });
+
+        // New dependency cycles, matched by level and members
+        let known: HashSet<String> = all_cycles(&base, true).iter().map(Cycle::key).collect();
+        let cycles = all_cycles(&target, true)
+            .into_iter()
+            .filter(|c| !known.contains(&c.key()))
+            .collect();
+
+        // Lockfile packages whose versions changed
+        let versions = |id: i64| -> Result<BTreeMap<String, Vec<String>>, String> {
+            let mut map: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for p in package_repo::list_by_snapshot(conn, id)
+                .map_err(|e| format!("Failed to load packages: {}", e))?
+            {
+                map.entry(p.name).or_default().push(p.version);
+            }
+            for list in map.values_mut() {
+                list.sort();
+                list.dedup();
+            }
+            Ok(map)
+        };
+        let (base_versions, target_versions) = (versions(base_id)?, versions(target_id)?);
+        let names: std::collections::BTreeSet<&String> =
+            base_versions.keys().chain(target_versions.keys()).collect();
+        let packages = names
+            .into_iter()
+            .filter_map(|name| {
+                let b = base_versions.get(name).cloned().unwrap_or_default();
+                let t = target_versions.get(name).cloned().unwrap_or_default();
+                (b != t).then(|| PackageChange {
+                    name: name.clone(),
+                    base: b,
+                    target: t,
+                })
+            })
+            .collect();
+
+        Ok(PrReport {
+            base_name,
+            target_name,
+            total,
+            aggregates: by_largest_change(aggregates),
+            layers: by_largest_change(layers),
+            violations,
+            todos,
+            unsafe_sites,
+            cycles,
+            packages,
+        })
+    }
+}
+
+/// Inline code span that survives backticks in `text`
+fn code(text: &str) -> String {
+    if text.contains('`') {
+        format!("`` {} ``", text)
+    } else {
+        format!("`{}`", text)
+    }
+}
+
+/// Table cell text: pipes escaped, line breaks flattened
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn signed(n: i64) -> String {
+    if n > 0 {
+        format!("+{}", n)
+    } else {
+        n.to_string()
+    }
+}
+
+fn more(out: &mut String, total: usize) {
+    if total > MAX_LIST {
+        let _ = writeln!(out, "\n… and {} more", total - MAX_LIST);
+    }
+}
+
+/// Heading of a report section, separated from the previous one by a single blank line
+fn section(out: &mut String, title: &str) {
+    let _ = writeln!(out, "\n### {}\n", title);
+}
+
+fn group_table(out: &mut String, title: &str, column: &str, groups: &[GroupChange]) {
+    if groups.is_empty() {
+        return;
+    }
+    section(out, title);
+    let _ = writeln!(
+        out,
+        "| {} | Added | Modified | Removed | LOC | Δ LOC |",
+        column
+    );
+    let _ = writeln!(out, "|---|---:|---:|---:|---:|---:|");
+    for g in groups.iter().take(MAX_LIST) {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} → {} | {} |",
+            cell(&g.name),
+            g.added,
+            g.modified,
+            g.removed,
+            g.loc_before,
+            g.loc_after,
+            signed(g.loc_after - g.loc_before)
+        );
+    }
+    more(out, groups.len());
+}
+
+fn finding_list(out: &mut String, title: &str, findings: &[Finding]) {
+    section(out, &format!("{} ({})", title, findings.len()));
+    if findings.is_empty() {
+        let _ = writeln!(out, "None.");
+        return;
+    }
+    for f in findings.iter().take(MAX_LIST) {
+        let _ = write!(
+            out,
+            "- {} {}",
+            code(&format!("{}:{}", f.path, f.line)),
+            f.label
+        );
+        if !f.code.is_empty() {
+            let _ = write!(out, ": {}", code(&f.code));
+        }
+        out.push('\n');
+    }
+    more(out, findings.len());
+}
+
+/// Markdown for a pull request comment; every list is capped at `MAX_LIST` entries
+pub fn render(report: &PrReport) -> String {
+    let mut out = String::new();
+    let t = &report.total;
+    let _ = writeln!(
+        out,
+        "## Snapshot diff: {} → {}\n",
+        cell(&report.base_name),
+        cell(&report.target_name)
+    );
+    let _ = writeln!(
+        out,
+        "**{}** files changed: {} added, {} modified, {} removed · LOC {} → {} ({})",
+        t.added + t.modified + t.removed,
+        t.added,
+        t.modified,
+        t.removed,
+        t.loc_before,
+        t.loc_after,
+        signed(t.loc_after - t.loc_before)
+    );
+
+    group_table(
+        &mut out,
+        "Changes by aggregate",
+        "Aggregate",
+        &report.aggregates,
+    );
+    group_table(&mut out, "Changes by layer", "Layer", &report.layers);
+    finding_list(&mut out, "New architecture violations", &report.violations);
+    finding_list(&mut out, "New TODOs", &report.todos);
+    finding_list(&mut out, "New unsafe code", &report.unsafe_sites);
+
+    section(
+        &mut out,
+        &format!("New dependency cycles ({})", report.cycles.len()),
+    );
+    if report.cycles.is_empty() {
+        let _ = writeln!(out, "None.");
+    }
+    for cycle in report.cycles.iter().take(MAX_LIST) {
+        let cut = cycle.cut_edge();
+        let _ = writeln!(
+            out,
+            "- {}; cut {}",
+            cycle.describe(),
+            code(&format!("{} → {}", cut.from, cut.to))
+        );
+        for u in &cut.uses {
+            let _ = writeln!(
+                out,
+                "  - {} {}",
+                code(&format!("{}:{}", u.path, u.line)),
+                code(&format!("use {}", u.use_path))
+            );
+        }
+    }
+    more(&mut out, report.cycles.len());
+
+    section(
+        &mut out,
+        &format!("Dependency changes ({})", report.packages.len()),
+    );
+    if report.packages.is_empty() {
+        let _ = writeln!(out, "None.");
+    } else {
+        let _ = writeln!(out, "| Package | Base | Target |");
+        let _ = writeln!(out, "|---|---|---|");
+        let versions = |v: &[String]| {
+            if v.is_empty() {
+                "—".to_string()
+            } else {
+                v.join(", ")
+            }
+        };
+        for p in report.packages.iter().take(MAX_LIST) {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} |",
+                cell(&p.name),
+                versions(&p.base),
+                versions(&p.target)
+            );
+        }
+        more(&mut out, report.packages.len());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_surplus_items() {
+        let base = ["a", "a", "b"];
+        let target = vec!["a", "b", "a", "c", "a", "b"];
+        assert_eq!(new_items(&base, target, |s| *s), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn renders_capped_lists() {
+        let finding = |i: i64| Finding {
+            path: "src/a.rs".into(),
+            line: i,
+            label: "TODO".into(),
+            code: "// TODO `x`".into(),
+        };
+        let report = PrReport {
+            base_name: "v1".into(),
+            target_name: "v2".into(),
+            total: GroupChange {
+                modified: 1,
+                loc_before: 10,
+                loc_after: 4,
+                ..Default::default()
+            },
+            aggregates: vec![GroupChange {
+                name: "n001_a|b".into(),
+                modified: 1,
+                loc_before: 10,
+                loc_after: 4,
+                ..Default::default()
+            }],
+            layers: Vec::new(),
+            violations: Vec::new(),
+            todos: (1..=20).map(finding).collect(),
+            unsafe_sites: Vec::new(),
+            cycles: Vec::new(),
+            packages: vec![PackageChange {
+                name: "serde".into(),
+                base: vec!["1.0.1".into()],
+                target: Vec::new(),
+            }],
+        };
+        let md = render(&report);
+        assert!(md.contains("LOC 10 → 4 (-6)"));
+        assert!(md.contains("| n001_a\\|b | 0 | 1 | 0 | 10 → 4 | -6 |"));
+        assert!(md.contains("### New architecture violations (0)\n\nNone."));
+        assert!(md.contains("- `src/a.rs:15` TODO: `` // TODO `x` ``\n\n… and 5 more"));
+        assert!(!md.contains("src/a.rs:16"));
+        assert!(md.contains(
+            "### New unsafe code (0)\n\nNone.\n\n### New dependency cycles (0)\n\nNone.\n\n### Dependency changes (1)\n\n"
+        ));
+        assert!(!md.contains("\n\n\n"));
+        assert!(md.contains("| serde | 1.0.1 | — |"));
+        assert!(!md.contains("### Changes by layer"));
+    }
+}
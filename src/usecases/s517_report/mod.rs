@@ -0,0 +1,190 @@
+pub mod html;
+pub mod markdown;
+pub mod treemap;
+
+use eframe::egui;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::model::Snapshot;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n003_snapshot_file::model::SnapshotFile;
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n004_snapshot_aggregate::model::SnapshotAggregate;
+use crate::domain::n004_snapshot_aggregate::repository as aggregate_repo;
+use crate::domain::n010_function_metric::repository as function_repo;
+use crate::domain::n014_file_coverage::model::FileCoverage;
+use crate::domain::n014_file_coverage::repository as coverage_repo;
+use crate::domain::n017_file_language::model::LanguageStats;
+use crate::domain::n017_file_language::repository as language_repo;
+use crate::usecases::s515_export::file_stem;
+use crate::usecases::s518_dependencies::cycles::{all_cycles, Cycle};
+use crate::usecases::s518_dependencies::ModuleGraph;
+
+/// Function metrics of one file, for the "most complex files" list
+pub struct ComplexFile {
+    pub path: String,
+    pub functions: usize,
+    pub max_cyclomatic: i64,
+    /// Function with the highest cyclomatic complexity
+    pub worst_function: String,
+    pub total_cognitive: i64,
+}
+
+/// Everything a snapshot report shows
+pub struct ReportData {
+    pub snapshot: Snapshot,
+    pub project_name: String,
+    pub generated_at: String,
+    /// Ordered by path
+    pub files: Vec<SnapshotFile>,
+    pub languages: Vec<LanguageStats>,
+    pub aggregates: Vec<SnapshotAggregate>,
+    pub functions: usize,
+    /// Most complex first
+    pub complex_files: Vec<ComplexFile>,
+    /// Dependency cycles of production code at every level
+    pub cycles: Vec<Cycle>,
+    /// Imported coverage by file id, empty without a coverage report
+    pub coverage: HashMap<i64, FileCoverage>,
+}
+
+impl ReportData {
+    pub fn load(conn: &Connection, snapshot_id: i64) -> Result<Self, String> {
+        let snapshot = snapshot_repo::get_by_id(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load snapshot {}: {}", snapshot_id, e))?;
+        let project_name = project_repo::get_by_id(conn, snapshot.project_id)
+            .map(|p| p.name)
+            .unwrap_or_else(|_| format!("Project {}", snapshot.project_id));
+        let files = file_repo::list_by_snapshot(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load files: {}", e))?;
+        let languages = language_repo::breakdown_by_snapshot(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load languages: {}", e))?;
+        let aggregates = aggregate_repo::list_by_snapshot(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load aggregates: {}", e))?;
+        let metrics = function_repo::list_by_snapshot(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load function metrics: {}", e))?;
+
+        let cycles = all_cycles(&ModuleGraph::load(conn, snapshot_id)?, true);
+        let coverage = coverage_repo::list_by_snapshot(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load coverage: {}", e))?
+            .into_iter()
+            .map(|c| (c.file_id, c))
+            .collect();
+
+        let paths: HashMap<i64, &str> = files.iter().map(|f| (f.id, f.path.as_str())).collect();
+        let mut by_file: HashMap<i64, ComplexFile> = HashMap::new();
+        for m in &metrics {
+            let Some(path) = paths.get(&m.file_id) else {
+                continue;
+            };
+            let entry = by_file.entry(m.file_id).or_insert_with(|| ComplexFile {
+                path: path.to_string(),
+                functions: 0,
+                max_cyclomatic: 0,
+                worst_function: String::new(),
+                total_cognitive: 0,
+            });
+            entry.functions += 1;
+            entry.total_cognitive += m.cognitive;
+            if m.cyclomatic > entry.max_cyclomatic {
+                entry.max_cyclomatic = m.cyclomatic;
+                entry.worst_function = m.name.clone();
+            }
+        }
+        let mut complex_files: Vec<ComplexFile> = by_file.into_values().collect();
+        complex_files.sort_by(|a, b| {
+            (b.max_cyclomatic, b.total_cognitive, &a.path).cmp(&(
+                a.max_cyclomatic,
+                a.total_cognitive,
+                &b.path,
+            ))
+        });
+
+        Ok(ReportData {
+            snapshot,
+            project_name,
+            generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            files,
+            languages,
+            aggregates,
+            functions: metrics.len(),
+            complex_files,
+            cycles,
+            coverage,
+        })
+    }
+
+    /// Largest files (not directories), biggest first
+    pub fn largest_files(&self, limit: usize) -> Vec<&SnapshotFile> {
+        let mut files: Vec<&SnapshotFile> = self.files.iter().filter(|f| !f.is_directory).collect();
+        files.sort_by(|a, b| {
+            b.size_bytes
+                .cmp(&a.size_bytes)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        files.truncate(limit);
+        files
+    }
+}
+
+/// Write a self-contained HTML report of a snapshot to `path`
+pub fn write_html_report(conn: &Connection, snapshot_id: i64, path: &Path) -> Result<(), String> {
+    let data = ReportData::load(conn, snapshot_id)?;
+    fs::write(path, html::render(&data))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Write the Markdown pull request report of `base_id` -> `target_id` to `path`
+pub fn write_markdown_report(
+    conn: &Connection,
+    base_id: i64,
+    target_id: i64,
+    path: &Path,
+) -> Result<(), String> {
+    let report = markdown::PrReport::load(conn, base_id, target_id)?;
+    fs::write(path, markdown::render(&report))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+pub fn format_size(bytes: i64) -> String {
+    const KB: i64 = 1024;
+    const MB: i64 = KB * 1024;
+    const GB: i64 = MB * 1024;
+
+    if bytes >= GB {
+        format!("{:.2} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.2} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.2} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// Report entries of a snapshot's context menu; returns a status message once a report was written
+pub fn report_menu(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    snapshot_id: i64,
+    snapshot_name: &str,
+) -> Option<String> {
+    ui.label(egui::RichText::new("Report").weak());
+    if !ui.button("HTML report...").clicked() {
+        return None;
+    }
+    ui.close_menu();
+
+    let path = rfd::FileDialog::new()
+        .set_file_name(format!("{}.html", file_stem(snapshot_name)))
+        .add_filter("HTML", &["html"])
+        .save_file()?;
+    Some(match write_html_report(conn, snapshot_id, &path) {
+        Ok(()) => format!("Wrote report of '{}' to {}", snapshot_name, path.display()),
+        Err(e) => format!("Report failed: {}", e),
+    })
+}
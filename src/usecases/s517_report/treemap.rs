@@ -0,0 +1,118 @@
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub w: f64,
+    pub h: f64,
+}
+
+impl Rect {
+    pub fn new(x: f64, y: f64, w: f64, h: f64) -> Self {
+        Rect { x, y, w, h }
+    }
+
+    /// Shrunk by `pad` on every side (never below zero size)
+    pub fn inset(&self, pad: f64) -> Rect {
+        let pad_x = pad.min(self.w / 2.0);
+        let pad_y = pad.min(self.h / 2.0);
+        Rect::new(
+            self.x + pad_x,
+            self.y + pad_y,
+            self.w - 2.0 * pad_x,
+            self.h - 2.0 * pad_y,
+        )
+    }
+}
+
+/// Worst aspect ratio of a row of `areas` laid along a side of length `side`
+fn worst(areas: &[f64], side: f64) -> f64 {
+    let sum: f64 = areas.iter().sum();
+    let max = areas.iter().cloned().fold(f64::MIN, f64::max);
+    let min = areas.iter().cloned().fold(f64::MAX, f64::min);
+    let (side2, sum2) = (side * side, sum * sum);
+    (side2 * max / sum2).max(sum2 / (side2 * min))
+}
+
+/// Lay a finished row along the shorter side of `free`; returns the space left
+fn place(row: &[(usize, f64)], free: Rect, out: &mut [Rect]) -> Rect {
+    let sum: f64 = row.iter().map(|(_, a)| a).sum();
+    if free.w >= free.h {
+        let w = sum / free.h;
+        let mut y = free.y;
+        for &(i, area) in row {
+            let h = area / w;
+            out[i] = Rect::new(free.x, y, w, h);
+            y += h;
+        }
+        Rect::new(free.x + w, free.y, free.w - w, free.h)
+    } else {
+        let h = sum / free.w;
+        let mut x = free.x;
+        for &(i, area) in row {
+            let w = area / h;
+            out[i] = Rect::new(x, free.y, w, h);
+            x += w;
+        }
+        Rect::new(free.x, free.y + h, free.w, free.h - h)
+    }
+}
+
+/// Squarified treemap (Bruls et al.): one rectangle per weight, in input order, with areas
+/// proportional to the weights. Non-positive weights get an empty rectangle.
+pub fn squarify(weights: &[f64], area: Rect) -> Vec<Rect> {
+    let mut out = vec![Rect::default(); weights.len()];
+    let total: f64 = weights.iter().filter(|w| **w > 0.0).sum();
+    if total <= 0.0 || area.w <= 0.0 || area.h <= 0.0 {
+        return out;
+    }
+    let scale = area.w * area.h / total;
+    let mut order: Vec<(usize, f64)> = weights
+        .iter()
+        .enumerate()
+        .filter(|(_, w)| **w > 0.0)
+        .map(|(i, w)| (i, w * scale))
+        .collect();
+    order.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut free = area;
+    let mut row: Vec<(usize, f64)> = Vec::new();
+    let mut areas: Vec<f64> = Vec::new();
+    for item in order {
+        let side = free.w.min(free.h);
+        areas.push(item.1);
+        if !row.is_empty() && worst(&areas, side) > worst(&areas[..areas.len() - 1], side) {
+            free = place(&row, free, &mut out);
+            row.clear();
+            areas.clear();
+            areas.push(item.1);
+        }
+        row.push(item);
+    }
+    if !row.is_empty() {
+        place(&row, free, &mut out);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squarify_fills_the_area_proportionally() {
+        let area = Rect::new(10.0, 20.0, 600.0, 400.0);
+        let weights = [6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0, 0.0];
+        let rects = squarify(&weights, area);
+        let total: f64 = weights.iter().sum();
+        for (w, r) in weights.iter().zip(&rects) {
+            let expected = w / total * area.w * area.h;
+            assert!((r.w * r.h - expected).abs() < 1e-6, "{:?} for {}", r, w);
+            if *w > 0.0 {
+                assert!(r.x >= area.x - 1e-9 && r.y >= area.y - 1e-9);
+                assert!(r.x + r.w <= area.x + area.w + 1e-6 && r.y + r.h <= area.y + area.h + 1e-6);
+            }
+        }
+        assert_eq!(rects[7], Rect::default());
+        assert!(squarify(&[0.0], area).iter().all(|r| *r == Rect::default()));
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use super::graph::{GraphLevel, GraphOptions, GroupedGraph, ModuleGraph};
+use super::scc::strongly_connected;
+
+/// A `use` declaration behind a cycle edge
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleUse {
+    pub file_id: i64,
+    pub path: String,
+    pub line: i64,
+    pub use_path: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleEdge {
+    pub from: String,
+    pub to: String,
+    pub uses: Vec<CycleUse>,
+}
+
+/// A strongly connected component of two or more nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle {
+    pub level: GraphLevel,
+    /// Sorted node keys
+    pub nodes: Vec<String>,
+    /// Edges between the nodes, ordered by (from, to)
+    pub edges: Vec<CycleEdge>,
+    /// Index of the proposed edge to cut
+    pub cut: usize,
+}
+
+impl Cycle {
+    /// Identifies the cycle across snapshots
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.level.key(), self.nodes.join(" "))
+    }
+
+    pub fn cut_edge(&self) -> &CycleEdge {
+        &self.edges[self.cut]
+    }
+
+    pub fn use_count(&self) -> usize {
+        self.edges.iter().map(|e| e.uses.len()).sum()
+    }
+
+    /// `3 modules: a.rs, b.rs, c.rs`
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {}: {}",
+            self.nodes.len(),
+            self.level.label().to_lowercase(),
+            self.nodes.join(", ")
+        )
+    }
+}
+
+/// Largest component left among `n` nodes after dropping edge `skip`; 1 when no cycle is left
+fn largest_without(n: usize, edges: &[(usize, usize)], skip: usize) -> usize {
+    let rest: Vec<(usize, usize)> = edges
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != skip)
+        .map(|(_, e)| *e)
+        .collect();
+    strongly_connected(n, &rest)
+        .iter()
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Cycles of the graph for `options` (its `highlight_cycles` is ignored).
+/// The proposed cut is the edge with the fewest `use` declarations among those whose
+/// removal breaks every cycle of the component; when no single edge does that, the
+/// edge that leaves the smallest cycle behind.
+pub fn find_cycles(graph: &ModuleGraph, options: &GraphOptions) -> Vec<Cycle> {
+    let GroupedGraph { nodes, edges: uses } = graph.grouped(options);
+    let keys: Vec<&String> = nodes.keys().collect();
+    let index: HashMap<&String, usize> = keys.iter().enumerate().map(|(i, k)| (*k, i)).collect();
+    let pairs: Vec<(usize, usize)> = uses.keys().map(|(a, b)| (index[a], index[b])).collect();
+
+    let mut cycles = Vec::new();
+    for component in strongly_connected(keys.len(), &pairs)
+        .into_iter()
+        .filter(|c| c.len() > 1)
+    {
+        let local: HashMap<usize, usize> =
+            component.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let mut edges = Vec::new();
+        let mut local_pairs = Vec::new();
+        for ((a, b), deps) in &uses {
+            let (Some(&from), Some(&to)) = (local.get(&index[a]), local.get(&index[b])) else {
+                continue;
+            };
+            local_pairs.push((from, to));
+            edges.push(CycleEdge {
+                from: a.clone(),
+                to: b.clone(),
+                uses: deps
+                    .iter()
+                    .map(|d| CycleUse {
+                        file_id: d.file_id,
+                        path: graph.path(d.file_id).to_string(),
+                        line: d.line,
+                        use_path: d.use_path.clone(),
+                    })
+                    .collect(),
+            });
+        }
+        let cut = (0..edges.len())
+            .min_by_key(|&i| {
+                (
+                    largest_without(component.len(), &local_pairs, i),
+                    edges[i].uses.len(),
+                )
+            })
+            .unwrap_or(0);
+        cycles.push(Cycle {
+            level: options.level,
+            nodes: component.iter().map(|n| keys[*n].clone()).collect(),
+            edges,
+            cut,
+        });
+    }
+    cycles
+}
+
+/// Cycles at module, aggregate and crate level. Files outside aggregates and crates keep
+/// their module node, so a cycle of those is reported at the module level only.
+pub fn all_cycles(graph: &ModuleGraph, exclude_tests: bool) -> Vec<Cycle> {
+    let mut cycles: Vec<Cycle> = Vec::new();
+    for level in GraphLevel::ALL {
+        for cycle in find_cycles(
+            graph,
+            &GraphOptions {
+                level,
+                exclude_tests,
+                ..Default::default()
+            },
+        ) {
+            if !cycles.iter().any(|c| c.nodes == cycle.nodes) {
+                cycles.push(cycle);
+            }
+        }
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::n003_snapshot_file::model::SnapshotFile;
+    use crate::domain::n018_module_dependency::model::ModuleDependency;
+
+    #[test]
+    fn finds_cycles_and_the_cheapest_cut() {
+        let file = |id: i64, path: &str| {
+            let f = SnapshotFile::new(
+                id,
+                1,
+                None,
+                path.into(),
+                path.into(),
+                1,
+                false,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            (id, f)
+        };
+        let dep = |from: i64, to: i64, line: i64| {
+            ModuleDependency::new(0, 1, from, to, line, format!("crate::m{}", to), false)
+        };
+        // a <-> b twice over, b -> c -> a once; d -> a outside any cycle
+        let graph = ModuleGraph {
+            files: [
+                file(1, "a.rs"),
+                file(2, "b.rs"),
+                file(3, "c.rs"),
+                file(4, "d.rs"),
+            ]
+            .into_iter()
+            .collect(),
+            crates: [
+                (1, "app".to_string()),
+                (2, "app".to_string()),
+                (3, "app".to_string()),
+                (4, "app".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+            edges: vec![
+                dep(1, 2, 1),
+                dep(1, 2, 2),
+                dep(2, 1, 3),
+                dep(2, 1, 4),
+                dep(2, 3, 5),
+                dep(3, 1, 6),
+                dep(4, 1, 7),
+            ],
+        };
+
+        let cycles = find_cycles(&graph, &GraphOptions::default());
+        assert_eq!(cycles.len(), 1);
+        let cycle = &cycles[0];
+        assert_eq!(cycle.nodes, ["a.rs", "b.rs", "c.rs"]);
+        assert_eq!(cycle.edges.len(), 4);
+        assert_eq!(cycle.use_count(), 6);
+        // Only cutting a -> b breaks both a -> b -> a and a -> b -> c -> a
+        assert_eq!(
+            (cycle.cut_edge().from.as_str(), cycle.cut_edge().to.as_str()),
+            ("a.rs", "b.rs")
+        );
+        assert_eq!(cycle.cut_edge().uses[1].line, 2);
+        assert_eq!(cycle.describe(), "3 modules: a.rs, b.rs, c.rs");
+
+        // Everything is one crate: no crate-level cycle
+        assert_eq!(all_cycles(&graph, false).len(), 1);
+    }
+}
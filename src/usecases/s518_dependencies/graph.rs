@@ -0,0 +1,285 @@
+use rusqlite::Connection;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::domain::n003_snapshot_file::model::SnapshotFile;
+use crate::domain::n003_snapshot_file::repository as file_repo;
+use crate::domain::n016_module_doc::repository as module_doc_repo;
+use crate::domain::n018_module_dependency::model::ModuleDependency;
+use crate::domain::n018_module_dependency::repository as dependency_repo;
+use crate::shared::architecture::{violated_rule, Placement};
+
+use super::scc::strongly_connected;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GraphLevel {
+    /// One node per module file
+    #[default]
+    Module,
+    /// Module files collapsed into their aggregate (`n001_project`, `s501_create_snapshot`);
+    /// files outside aggregates stay module nodes
+    Aggregate,
+    /// One node per crate
+    Crate,
+}
+
+impl GraphLevel {
+    pub const ALL: [GraphLevel; 3] = [GraphLevel::Module, GraphLevel::Aggregate, GraphLevel::Crate];
+
+    /// CLI name
+    pub fn key(self) -> &'static str {
+        match self {
+            GraphLevel::Module => "module",
+            GraphLevel::Aggregate => "aggregate",
+            GraphLevel::Crate => "crate",
+        }
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GraphLevel::Module => "Modules",
+            GraphLevel::Aggregate => "Aggregates",
+            GraphLevel::Crate => "Crates",
+        }
+    }
+}
+
+/// What part of the dependency graph to show, shared by the Dependencies tab and the exporters
+#[derive(Debug, Clone, Default)]
+pub struct GraphOptions {
+    pub level: GraphLevel,
+    /// Only modules of this crate layer
+    pub layer: Option<String>,
+    /// Drop `use` declarations inside test code
+    pub exclude_tests: bool,
+    /// Mark nodes and edges that are part of a cycle
+    pub highlight_cycles: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    /// File path or aggregate name, unique in the graph
+    pub key: String,
+    pub layer: Option<String>,
+    pub in_cycle: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphEdge {
+    /// Node indices
+    pub from: usize,
+    pub to: usize,
+    /// `use` declarations behind the edge
+    pub count: usize,
+    pub in_cycle: bool,
+}
+
+/// Dependency graph as shown and exported: nodes ordered by key, edges by (from, to)
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// `use` dependencies of a snapshot with the files on both ends
+pub struct ModuleGraph {
+    /// Files by id
+    pub files: HashMap<i64, SnapshotFile>,
+    /// Crate name of every module file
+    pub crates: HashMap<i64, String>,
+    pub edges: Vec<ModuleDependency>,
+}
+
+/// Dependencies collapsed to the nodes of a level
+pub struct GroupedGraph<'a> {
+    /// Node key -> crate layer
+    pub nodes: BTreeMap<String, Option<String>>,
+    /// (from key, to key) -> `use` declarations
+    pub edges: BTreeMap<(String, String), Vec<&'a ModuleDependency>>,
+}
+
+/// A dependency that breaks an architecture rule
+#[derive(Debug, Clone)]
+pub struct Violation {
+    pub path: String,
+    pub line: i64,
+    pub use_path: String,
+    pub target_path: String,
+    pub rule: &'static str,
+}
+
+fn placement(file: &SnapshotFile) -> Placement<'_> {
+    Placement {
+        layer: file.crate_layer.as_deref(),
+        artifact_type: file.artifact_type.as_deref(),
+    }
+}
+
+impl ModuleGraph {
+    pub fn load(conn: &Connection, snapshot_id: i64) -> Result<Self, String> {
+        let files = file_repo::list_by_snapshot(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load files: {}", e))?
+            .into_iter()
+            .map(|f| (f.id, f))
+            .collect();
+        let crates = module_doc_repo::list_by_snapshot(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load modules: {}", e))?
+            .into_iter()
+            .map(|m| (m.file_id, m.crate_name))
+            .collect();
+        let edges = dependency_repo::list_by_snapshot(conn, snapshot_id)
+            .map_err(|e| format!("Failed to load dependencies: {}", e))?;
+        Ok(ModuleGraph {
+            files,
+            crates,
+            edges,
+        })
+    }
+
+    pub fn path(&self, file_id: i64) -> &str {
+        self.files
+            .get(&file_id)
+            .map(|f| f.path.as_str())
+            .unwrap_or("?")
+    }
+
+    /// Crate layers present in the snapshot, sorted
+    pub fn layers(&self) -> Vec<String> {
+        let layers: BTreeSet<&String> = self
+            .files
+            .values()
+            .filter_map(|f| f.crate_layer.as_ref())
+            .collect();
+        layers.into_iter().cloned().collect()
+    }
+
+    /// Node key of a file at `level`
+    fn node_key(&self, file: &SnapshotFile, level: GraphLevel) -> String {
+        if level == GraphLevel::Crate {
+            if let Some(name) = self.crates.get(&file.id) {
+                return name.clone();
+            }
+        }
+        match (level, &file.artifact_id) {
+            (GraphLevel::Aggregate, Some(id)) => {
+                let name = format!("{}{}", id, file.artifact_name.as_deref().unwrap_or(""));
+                match &file.crate_layer {
+                    Some(layer) => format!("{}/{}", layer, name),
+                    None => name,
+                }
+            }
+            _ => file.path.clone(),
+        }
+    }
+
+    /// Nodes and edges for `options` with the dependencies behind every edge;
+    /// dependencies within one node are dropped
+    pub fn grouped(&self, options: &GraphOptions) -> GroupedGraph<'_> {
+        let in_scope =
+            |file: &SnapshotFile| options.layer.is_none() || file.crate_layer == options.layer;
+
+        let mut nodes: BTreeMap<String, Option<String>> = BTreeMap::new();
+        let mut edges: BTreeMap<(String, String), Vec<&ModuleDependency>> = BTreeMap::new();
+        for edge in self
+            .edges
+            .iter()
+            .filter(|e| !(options.exclude_tests && e.in_test))
+        {
+            let (Some(from), Some(to)) = (
+                self.files.get(&edge.file_id),
+                self.files.get(&edge.target_file_id),
+            ) else {
+                continue;
+            };
+            if !in_scope(from) || !in_scope(to) {
+                continue;
+            }
+            let (a, b) = (
+                self.node_key(from, options.level),
+                self.node_key(to, options.level),
+            );
+            nodes
+                .entry(a.clone())
+                .or_insert_with(|| from.crate_layer.clone());
+            nodes
+                .entry(b.clone())
+                .or_insert_with(|| to.crate_layer.clone());
+            if a != b {
+                edges.entry((a, b)).or_default().push(edge);
+            }
+        }
+        GroupedGraph { nodes, edges }
+    }
+
+    /// The graph for `options`
+    pub fn view(&self, options: &GraphOptions) -> DependencyGraph {
+        let GroupedGraph { nodes, edges: uses } = self.grouped(options);
+        let index: HashMap<&String, usize> =
+            nodes.keys().enumerate().map(|(i, k)| (k, i)).collect();
+        let mut graph = DependencyGraph {
+            nodes: nodes
+                .iter()
+                .map(|(key, layer)| GraphNode {
+                    key: key.clone(),
+                    layer: layer.clone(),
+                    in_cycle: false,
+                })
+                .collect(),
+            edges: uses
+                .iter()
+                .map(|((a, b), uses)| GraphEdge {
+                    from: index[a],
+                    to: index[b],
+                    count: uses.len(),
+                    in_cycle: false,
+                })
+                .collect(),
+        };
+
+        if options.highlight_cycles {
+            let pairs: Vec<(usize, usize)> = graph.edges.iter().map(|e| (e.from, e.to)).collect();
+            let mut component = vec![0; graph.nodes.len()];
+            for (c, members) in strongly_connected(graph.nodes.len(), &pairs)
+                .iter()
+                .enumerate()
+            {
+                for &m in members {
+                    component[m] = c;
+                    graph.nodes[m].in_cycle = members.len() > 1;
+                }
+            }
+            for edge in &mut graph.edges {
+                edge.in_cycle = component[edge.from] == component[edge.to];
+            }
+        }
+        graph
+    }
+
+    /// Architecture rule violations of non-test dependencies, ordered by path and line
+    pub fn violations(&self) -> Vec<Violation> {
+        let mut out: Vec<Violation> = self
+            .edges
+            .iter()
+            .filter(|e| !e.in_test)
+            .filter_map(|e| {
+                let (from, to) = (
+                    self.files.get(&e.file_id)?,
+                    self.files.get(&e.target_file_id)?,
+                );
+                let rule = violated_rule(placement(from), placement(to))?;
+                Some(Violation {
+                    path: from.path.clone(),
+                    line: e.line,
+                    use_path: e.use_path.clone(),
+                    target_path: to.path.clone(),
+                    rule,
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+        out
+    }
+}
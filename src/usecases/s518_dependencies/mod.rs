@@ -0,0 +1,356 @@
+pub mod cycles;
+pub mod graph;
+pub mod render;
+pub mod scc;
+
+use eframe::egui;
+use rusqlite::Connection;
+use std::f32::consts::TAU;
+use std::fs;
+use std::path::Path;
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n002_snapshot::ui::picker::{ui_snapshot_picker, SnapshotPickerState};
+use crate::usecases::s515_export::file_stem;
+use cycles::{find_cycles, Cycle};
+use render::GraphFormat;
+
+pub use graph::{DependencyGraph, GraphLevel, GraphOptions, ModuleGraph};
+
+/// Most nodes drawn with their labels; larger graphs show the label of the hovered node only
+const MAX_LABELED_NODES: usize = 30;
+
+#[derive(Default)]
+pub struct DependenciesState {
+    pub snapshot_picker: SnapshotPickerState,
+    pub snapshot_id: Option<i64>,
+    pub snapshot_name: String,
+    pub options: GraphOptions,
+    module_graph: Option<ModuleGraph>,
+    pub graph: DependencyGraph,
+    /// Cycles of the graph as currently filtered
+    pub cycles: Vec<Cycle>,
+    pub layers: Vec<String>,
+    pub status: String,
+}
+
+impl DependenciesState {
+    fn reload(&mut self, conn: &Connection) {
+        let Some(snapshot_id) = self.snapshot_id else {
+            return;
+        };
+        self.status.clear();
+        match ModuleGraph::load(conn, snapshot_id) {
+            Ok(module_graph) => {
+                self.layers = module_graph.layers();
+                if self
+                    .options
+                    .layer
+                    .as_ref()
+                    .is_some_and(|l| !self.layers.contains(l))
+                {
+                    self.options.layer = None;
+                }
+                self.module_graph = Some(module_graph);
+            }
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.module_graph = None;
+            }
+        }
+        self.rebuild();
+    }
+
+    fn rebuild(&mut self) {
+        self.graph = self
+            .module_graph
+            .as_ref()
+            .map(|g| g.view(&self.options))
+            .unwrap_or_default();
+        self.cycles = self
+            .module_graph
+            .as_ref()
+            .map(|g| find_cycles(g, &self.options))
+            .unwrap_or_default();
+    }
+}
+
+/// Write the dependency graph of a snapshot in `format` to `path`
+pub fn write_graph(
+    conn: &Connection,
+    snapshot_id: i64,
+    options: &GraphOptions,
+    format: GraphFormat,
+    path: &Path,
+) -> Result<(), String> {
+    let graph = ModuleGraph::load(conn, snapshot_id)?.view(options);
+    fs::write(path, render::render(&graph, format))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Renders the Dependencies tab.
+/// Returns `(file_id, line)` when a `use` declaration of a cycle is opened.
+pub fn ui_dependencies(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut DependenciesState,
+) -> Option<(i64, Option<usize>)> {
+    let mut opened = None;
+    ui.heading("Dependencies");
+    ui.add_space(12.0);
+
+    if let Some(snapshot_id) = ui_snapshot_picker(ui, conn, &mut state.snapshot_picker) {
+        state.snapshot_id = Some(snapshot_id);
+        if let Ok(snapshot) = snapshot_repo::get_by_id(conn, snapshot_id) {
+            state.snapshot_name = snapshot.name;
+        }
+        state.reload(conn);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Selected Snapshot:");
+        if state.snapshot_id.is_some() {
+            ui.label(egui::RichText::new(&state.snapshot_name).strong());
+        } else {
+            ui.label(egui::RichText::new("None").weak());
+        }
+        if ui.button("Select...").clicked() {
+            state.snapshot_picker.open();
+        }
+        if state.snapshot_id.is_some() && ui.button("Refresh").clicked() {
+            state.reload(conn);
+        }
+    });
+
+    if !state.status.is_empty() {
+        let color = if state.status.starts_with("Error") {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        ui.colored_label(color, &state.status);
+    }
+    let snapshot_id = state.snapshot_id?;
+
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt("dependencies_level")
+            .selected_text(state.options.level.label())
+            .show_ui(ui, |ui| {
+                for level in GraphLevel::ALL {
+                    changed |= ui
+                        .selectable_value(&mut state.options.level, level, level.label())
+                        .changed();
+                }
+            });
+        egui::ComboBox::from_id_salt("dependencies_layer")
+            .selected_text(state.options.layer.as_deref().unwrap_or("All layers"))
+            .show_ui(ui, |ui| {
+                changed |= ui
+                    .selectable_value(&mut state.options.layer, None, "All layers")
+                    .changed();
+                for layer in &state.layers {
+                    changed |= ui
+                        .selectable_value(&mut state.options.layer, Some(layer.clone()), layer)
+                        .changed();
+                }
+            });
+        changed |= ui
+            .checkbox(&mut state.options.exclude_tests, "Exclude tests")
+            .changed();
+        changed |= ui
+            .checkbox(&mut state.options.highlight_cycles, "Highlight cycles")
+            .changed();
+    });
+    if changed {
+        state.rebuild();
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(format!(
+            "{} nodes, {} edges, {} cycles",
+            state.graph.nodes.len(),
+            state.graph.edges.len(),
+            state.cycles.len()
+        ));
+        ui.separator();
+        for format in GraphFormat::ALL {
+            if ui.button(format!("{}...", format.label())).clicked() {
+                let name = format!(
+                    "{}-dependencies.{}",
+                    file_stem(&state.snapshot_name),
+                    format.extension()
+                );
+                if let Some(path) = rfd::FileDialog::new().set_file_name(name).save_file() {
+                    state.status =
+                        match write_graph(conn, snapshot_id, &state.options, format, &path) {
+                            Ok(()) => {
+                                format!("Wrote {} graph to {}", format.label(), path.display())
+                            }
+                            Err(e) => format!("Error: {}", e),
+                        };
+                }
+            }
+        }
+        if ui.button("Copy Mermaid").clicked() {
+            ui.output_mut(|o| o.copied_text = render::to_mermaid(&state.graph));
+            state.status = "Mermaid graph copied to the clipboard".to_string();
+        }
+    });
+
+    if state.graph.nodes.is_empty() {
+        ui.label(
+            egui::RichText::new("No dependencies recorded. Rescan the snapshot to extract them.")
+                .weak(),
+        );
+        return None;
+    }
+
+    ui.add_space(6.0);
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            if !state.cycles.is_empty() {
+                ui.colored_label(
+                    egui::Color32::LIGHT_RED,
+                    format!("Cycles ({})", state.cycles.len()),
+                );
+                for (i, cycle) in state.cycles.iter().enumerate() {
+                    if let Some(open) = ui_cycle(ui, cycle, i) {
+                        opened = Some(open);
+                    }
+                }
+                ui.add_space(8.0);
+            }
+            draw_graph(ui, &state.graph);
+            ui.add_space(8.0);
+            egui::CollapsingHeader::new(format!("Edges ({})", state.graph.edges.len())).show(
+                ui,
+                |ui| {
+                    for edge in &state.graph.edges {
+                        let text = format!(
+                            "{} → {}{}",
+                            state.graph.nodes[edge.from].key,
+                            state.graph.nodes[edge.to].key,
+                            if edge.count > 1 {
+                                format!("  ({} uses)", edge.count)
+                            } else {
+                                String::new()
+                            }
+                        );
+                        if edge.in_cycle {
+                            ui.colored_label(egui::Color32::LIGHT_RED, text);
+                        } else {
+                            ui.label(text);
+                        }
+                    }
+                },
+            );
+        });
+
+    opened
+}
+
+/// One cycle: its edges with the `use` lines behind them, the proposed cut marked
+fn ui_cycle(ui: &mut egui::Ui, cycle: &Cycle, index: usize) -> Option<(i64, Option<usize>)> {
+    let mut opened = None;
+    egui::CollapsingHeader::new(cycle.describe())
+        .id_salt(("dependency_cycle", index))
+        .show(ui, |ui| {
+            let cut = cycle.cut_edge();
+            ui.label(format!(
+                "Cut {} → {} ({} of {} `use` declarations) to break it",
+                cut.from,
+                cut.to,
+                cut.uses.len(),
+                cycle.use_count()
+            ));
+            for (i, edge) in cycle.edges.iter().enumerate() {
+                let title = egui::RichText::new(format!("{} → {}", edge.from, edge.to));
+                ui.label(if i == cycle.cut {
+                    title.strong().color(egui::Color32::YELLOW)
+                } else {
+                    title.strong()
+                });
+                for u in &edge.uses {
+                    ui.horizontal(|ui| {
+                        ui.add_space(16.0);
+                        if ui.link(format!("{}:{}", u.path, u.line)).clicked() {
+                            opened = Some((u.file_id, Some(u.line as usize)));
+                        }
+                        ui.label(
+                            egui::RichText::new(format!("use {}", u.use_path))
+                                .monospace()
+                                .weak(),
+                        );
+                    });
+                }
+            }
+        });
+    opened
+}
+
+/// Nodes on a circle, edges as arrows; cycle members in red
+fn draw_graph(ui: &mut egui::Ui, graph: &DependencyGraph) {
+    let size = ui.available_width().clamp(300.0, 700.0);
+    let (response, painter) =
+        ui.allocate_painter(egui::vec2(ui.available_width(), size), egui::Sense::hover());
+    let center = egui::pos2(response.rect.left() + size / 2.0, response.rect.center().y);
+    let radius = size / 2.0 - 40.0;
+    let n = graph.nodes.len();
+    let positions: Vec<egui::Pos2> = (0..n)
+        .map(|i| center + radius * egui::Vec2::angled(TAU * i as f32 / n as f32 - TAU / 4.0))
+        .collect();
+
+    let hovered = response
+        .hover_pos()
+        .and_then(|pointer| (0..n).find(|i| positions[*i].distance(pointer) < 8.0));
+    let text_color = ui.visuals().text_color();
+    let weak = ui.visuals().weak_text_color();
+
+    for edge in &graph.edges {
+        let (from, to) = (positions[edge.from], positions[edge.to]);
+        let touches_hovered = hovered.is_some_and(|h| h == edge.from || h == edge.to);
+        let color = if edge.in_cycle {
+            egui::Color32::LIGHT_RED
+        } else if touches_hovered {
+            text_color
+        } else {
+            weak.gamma_multiply(0.6)
+        };
+        let direction = (to - from).normalized();
+        painter.arrow(
+            from + direction * 6.0,
+            (to - from) - direction * 12.0,
+            egui::Stroke::new(1.0, color),
+        );
+    }
+
+    for (i, node) in graph.nodes.iter().enumerate() {
+        let color = if node.in_cycle {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        painter.circle_filled(
+            positions[i],
+            if hovered == Some(i) { 7.0 } else { 5.0 },
+            color,
+        );
+        if n <= MAX_LABELED_NODES || hovered == Some(i) {
+            let outward = (positions[i] - center).normalized();
+            let align = if outward.x >= 0.0 {
+                egui::Align2::LEFT_CENTER
+            } else {
+                egui::Align2::RIGHT_CENTER
+            };
+            painter.text(
+                positions[i] + outward * 10.0,
+                align,
+                &node.key,
+                egui::FontId::proportional(11.0),
+                if hovered == Some(i) { text_color } else { weak },
+            );
+        }
+    }
+}
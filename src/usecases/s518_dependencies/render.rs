@@ -0,0 +1,233 @@
+use std::fmt::Write;
+
+use super::graph::DependencyGraph;
+use crate::usecases::s517_report::html::escape;
+
+/// Stroke of nodes and edges on a cycle
+const CYCLE_COLOR: &str = "#d33";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphFormat {
+    /// Graphviz
+    Dot,
+    /// Mermaid flowchart, renders in Markdown on GitHub/GitLab
+    Mermaid,
+    /// GraphML, for yEd, Gephi and other graph tools
+    GraphMl,
+}
+
+impl GraphFormat {
+    pub const ALL: [GraphFormat; 3] =
+        [GraphFormat::Dot, GraphFormat::Mermaid, GraphFormat::GraphMl];
+
+    /// CLI name
+    pub fn key(self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "dot",
+            GraphFormat::Mermaid => "mermaid",
+            GraphFormat::GraphMl => "graphml",
+        }
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "Graphviz DOT",
+            GraphFormat::Mermaid => "Mermaid",
+            GraphFormat::GraphMl => "GraphML",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "dot",
+            GraphFormat::Mermaid => "mmd",
+            GraphFormat::GraphMl => "graphml",
+        }
+    }
+}
+
+pub fn render(graph: &DependencyGraph, format: GraphFormat) -> String {
+    match format {
+        GraphFormat::Dot => to_dot(graph),
+        GraphFormat::Mermaid => to_mermaid(graph),
+        GraphFormat::GraphMl => to_graphml(graph),
+    }
+}
+
+fn dot_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+pub fn to_dot(graph: &DependencyGraph) -> String {
+    let mut out = String::from(
+        "digraph dependencies {\n    rankdir=LR;\n    node [shape=box, fontname=\"Helvetica\"];\n",
+    );
+    for (i, node) in graph.nodes.iter().enumerate() {
+        let _ = write!(out, "    n{} [label={}", i, dot_string(&node.key));
+        if node.in_cycle {
+            let _ = write!(out, ", color=\"{}\", penwidth=2", CYCLE_COLOR);
+        }
+        out.push_str("];\n");
+    }
+    for edge in &graph.edges {
+        let mut attrs = Vec::new();
+        if edge.count > 1 {
+            attrs.push(format!("label=\"{}\"", edge.count));
+        }
+        if edge.in_cycle {
+            attrs.push(format!("color=\"{}\", penwidth=2", CYCLE_COLOR));
+        }
+        let _ = write!(out, "    n{} -> n{}", edge.from, edge.to);
+        if !attrs.is_empty() {
+            let _ = write!(out, " [{}]", attrs.join(", "));
+        }
+        out.push_str(";\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+pub fn to_mermaid(graph: &DependencyGraph) -> String {
+    let mut out = String::from("flowchart LR\n");
+    for (i, node) in graph.nodes.iter().enumerate() {
+        let _ = writeln!(out, "    n{}[\"{}\"]", i, node.key.replace('"', "#quot;"));
+    }
+    for edge in &graph.edges {
+        match edge.count {
+            1 => {
+                let _ = writeln!(out, "    n{} --> n{}", edge.from, edge.to);
+            }
+            count => {
+                let _ = writeln!(out, "    n{} -->|{}| n{}", edge.from, count, edge.to);
+            }
+        }
+    }
+
+    let nodes: Vec<String> = (0..graph.nodes.len())
+        .filter(|i| graph.nodes[*i].in_cycle)
+        .map(|i| format!("n{}", i))
+        .collect();
+    if !nodes.is_empty() {
+        let _ = writeln!(
+            out,
+            "    classDef cycle stroke:{},stroke-width:2px",
+            CYCLE_COLOR
+        );
+        let _ = writeln!(out, "    class {} cycle", nodes.join(","));
+    }
+    // Links are numbered in declaration order
+    let links: Vec<String> = graph
+        .edges
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.in_cycle)
+        .map(|(i, _)| i.to_string())
+        .collect();
+    if !links.is_empty() {
+        let _ = writeln!(
+            out,
+            "    linkStyle {} stroke:{},stroke-width:2px",
+            links.join(","),
+            CYCLE_COLOR
+        );
+    }
+    out
+}
+
+pub fn to_graphml(graph: &DependencyGraph) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n\
+         \x20 <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n\
+         \x20 <key id=\"layer\" for=\"node\" attr.name=\"layer\" attr.type=\"string\"/>\n\
+         \x20 <key id=\"node_cycle\" for=\"node\" attr.name=\"in_cycle\" attr.type=\"boolean\"/>\n\
+         \x20 <key id=\"count\" for=\"edge\" attr.name=\"count\" attr.type=\"int\"/>\n\
+         \x20 <key id=\"edge_cycle\" for=\"edge\" attr.name=\"in_cycle\" attr.type=\"boolean\"/>\n\
+         \x20 <graph id=\"dependencies\" edgedefault=\"directed\">\n",
+    );
+    for (i, node) in graph.nodes.iter().enumerate() {
+        let _ = write!(
+            out,
+            "    <node id=\"n{}\"><data key=\"label\">{}</data>",
+            i,
+            escape(&node.key)
+        );
+        if let Some(layer) = &node.layer {
+            let _ = write!(out, "<data key=\"layer\">{}</data>", escape(layer));
+        }
+        let _ = writeln!(
+            out,
+            "<data key=\"node_cycle\">{}</data></node>",
+            node.in_cycle
+        );
+    }
+    for (i, edge) in graph.edges.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\"><data key=\"count\">{}</data><data key=\"edge_cycle\">{}</data></edge>",
+            i, edge.from, edge.to, edge.count, edge.in_cycle
+        );
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecases::s518_dependencies::graph::{GraphEdge, GraphNode};
+
+    fn sample() -> DependencyGraph {
+        let node = |key: &str, in_cycle| GraphNode {
+            key: key.into(),
+            layer: Some("backend".into()),
+            in_cycle,
+        };
+        let edge = |from, to, count, in_cycle| GraphEdge {
+            from,
+            to,
+            count,
+            in_cycle,
+        };
+        DependencyGraph {
+            nodes: vec![
+                node("n001_a", true),
+                node("n002_\"b\"", true),
+                node("shared/x.rs", false),
+            ],
+            edges: vec![
+                edge(0, 1, 1, true),
+                edge(1, 0, 3, true),
+                edge(1, 2, 1, false),
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_every_format() {
+        let graph = sample();
+
+        let dot = to_dot(&graph);
+        assert!(dot.contains("n1 [label=\"n002_\\\"b\\\"\", color=\"#d33\", penwidth=2];"));
+        assert!(dot.contains("n1 -> n0 [label=\"3\", color=\"#d33\", penwidth=2];"));
+        assert!(dot.contains("n1 -> n2;"));
+
+        let mermaid = to_mermaid(&graph);
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("n1[\"n002_#quot;b#quot;\"]"));
+        assert!(mermaid.contains("n1 -->|3| n0"));
+        assert!(mermaid.contains("class n0,n1 cycle"));
+        assert!(mermaid.contains("linkStyle 0,1 stroke"));
+
+        let graphml = to_graphml(&graph);
+        assert!(graphml.contains(
+            "<data key=\"label\">n002_&quot;b&quot;</data><data key=\"layer\">backend</data>"
+        ));
+        assert!(graphml.contains("<edge id=\"e2\" source=\"n1\" target=\"n2\"><data key=\"count\">1</data><data key=\"edge_cycle\">false</data></edge>"));
+        assert!(graphml.ends_with("</graphml>\n"));
+    }
+}
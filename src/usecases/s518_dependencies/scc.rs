@@ -0,0 +1,80 @@
+/// Strongly connected components (Tarjan) of a directed graph with nodes `0..n`.
+/// Every node is in exactly one component; node ids within a component are sorted,
+/// components are ordered by their smallest node.
+pub fn strongly_connected(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut adjacency = vec![Vec::new(); n];
+    for &(from, to) in edges {
+        adjacency[from].push(to);
+    }
+
+    struct State {
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next: usize,
+        components: Vec<Vec<usize>>,
+    }
+
+    fn visit(v: usize, adjacency: &[Vec<usize>], s: &mut State) {
+        s.index[v] = Some(s.next);
+        s.low[v] = s.next;
+        s.next += 1;
+        s.stack.push(v);
+        s.on_stack[v] = true;
+        for &w in &adjacency[v] {
+            match s.index[w] {
+                None => {
+                    visit(w, adjacency, s);
+                    s.low[v] = s.low[v].min(s.low[w]);
+                }
+                Some(index) if s.on_stack[w] => s.low[v] = s.low[v].min(index),
+                Some(_) => {}
+            }
+        }
+        if Some(s.low[v]) == s.index[v] {
+            let mut component = Vec::new();
+            while let Some(w) = s.stack.pop() {
+                s.on_stack[w] = false;
+                component.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            component.sort_unstable();
+            s.components.push(component);
+        }
+    }
+
+    let mut state = State {
+        index: vec![None; n],
+        low: vec![0; n],
+        on_stack: vec![false; n],
+        stack: Vec::new(),
+        next: 0,
+        components: Vec::new(),
+    };
+    for v in 0..n {
+        if state.index[v].is_none() {
+            visit(v, &adjacency, &mut state);
+        }
+    }
+    state.components.sort_by_key(|c| c[0]);
+    state.components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_components() {
+        // 0 -> 1 -> 2 -> 0, 2 -> 3, 3 -> 4 -> 3, 5 alone
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3)];
+        assert_eq!(
+            strongly_connected(6, &edges),
+            vec![vec![0, 1, 2], vec![3, 4], vec![5]]
+        );
+        assert_eq!(strongly_connected(2, &[(0, 1)]), vec![vec![0], vec![1]]);
+    }
+}
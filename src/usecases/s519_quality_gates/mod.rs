@@ -0,0 +1,234 @@
+use rusqlite::Connection;
+
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n011_debt_marker::repository as marker_repo;
+use crate::domain::n012_safety_site::repository as safety_repo;
+use crate::domain::n017_file_language::repository as language_repo;
+use crate::domain::n019_quality_gate::model::GateRule;
+use crate::domain::n019_quality_gate::repository as gate_repo;
+use crate::domain::n020_gate_result::model::GateResult;
+use crate::domain::n020_gate_result::repository as gate_result_repo;
+use crate::shared::safety::SiteKind;
+use crate::usecases::s513_doc_coverage::rollup::doc_summary;
+use crate::usecases::s517_report::markdown::new_items;
+use crate::usecases::s518_dependencies::cycles::all_cycles;
+use crate::usecases::s518_dependencies::ModuleGraph;
+
+/// Pass when nothing was found; otherwise the count and the first finding
+fn none_found(count: usize, what: &str, first: Option<String>) -> (bool, String) {
+    match (count, first) {
+        (0, _) => (true, format!("no {}", what)),
+        (n, Some(first)) => (false, format!("{} {}, first: {}", n, what, first)),
+        (n, None) => (false, format!("{} {}", n, what)),
+    }
+}
+
+/// Pass when no file is above `max` LOC; `locs` are (path, LOC)
+fn check_file_loc(locs: &[(&str, i64)], max: i64) -> (bool, String) {
+    let over = locs.iter().filter(|(_, loc)| *loc > max).count();
+    match locs.iter().max_by_key(|(_, loc)| *loc) {
+        None => (true, "no source files".to_string()),
+        Some((path, loc)) if over == 0 => (true, format!("largest file {} LOC: {}", loc, path)),
+        Some((path, loc)) => (
+            false,
+            format!(
+                "{} files above {} LOC, largest {} LOC: {}",
+                over, max, loc, path
+            ),
+        ),
+    }
+}
+
+/// Pass when `coverage` (0..1) reaches `min` percent; nothing to document passes
+fn check_doc_coverage(coverage: Option<f64>, min: i64) -> (bool, String) {
+    match coverage {
+        None => (true, "no public items".to_string()),
+        Some(c) => (
+            c * 100.0 + 1e-9 >= min as f64,
+            format!("doc coverage {:.1}%", c * 100.0),
+        ),
+    }
+}
+
+/// TODO/FIXME/... markers beyond those of the project baseline, or the previous snapshot
+fn new_todos(
+    conn: &Connection,
+    snapshot_id: i64,
+    graph: &ModuleGraph,
+) -> Result<(bool, String), String> {
+    let snapshot = snapshot_repo::get_by_id(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load snapshot: {}", e))?;
+    let Some(base) = snapshot_repo::comparison_base(conn, &snapshot)
+        .map_err(|e| format!("Failed to load snapshots: {}", e))?
+    else {
+        return Ok((true, "no earlier snapshot to compare with".to_string()));
+    };
+    let base_graph = ModuleGraph::load(conn, base.id)?;
+    let markers =
+        |id: i64, graph: &ModuleGraph| -> Result<Vec<(String, i64, String, String)>, String> {
+            Ok(marker_repo::list_by_snapshot(conn, id)
+                .map_err(|e| format!("Failed to load markers: {}", e))?
+                .into_iter()
+                .map(|m| (graph.path(m.file_id).to_string(), m.line, m.kind, m.text))
+                .collect())
+        };
+    let added = new_items(
+        &markers(base.id, &base_graph)?,
+        markers(snapshot_id, graph)?,
+        |(path, _, kind, text)| (path.clone(), kind.clone(), text.clone()),
+    );
+    let first = added
+        .first()
+        .map(|(path, line, kind, _)| format!("{} at {}:{}", kind, path, line));
+    let (passed, detail) = none_found(added.len(), "new markers", first);
+    Ok((passed, format!("{} since '{}'", detail, base.name)))
+}
+
+fn check(
+    conn: &Connection,
+    snapshot_id: i64,
+    graph: &ModuleGraph,
+    rule: GateRule,
+    threshold: Option<i64>,
+) -> Result<(bool, String), String> {
+    Ok(match rule {
+        GateRule::MaxFileLoc => {
+            let languages = language_repo::list_by_snapshot(conn, snapshot_id)
+                .map_err(|e| format!("Failed to load LOC: {}", e))?;
+            let locs: Vec<(&str, i64)> = languages
+                .iter()
+                .map(|l| (graph.path(l.file_id), l.loc))
+                .collect();
+            check_file_loc(&locs, threshold.unwrap_or(i64::MAX))
+        }
+        GateRule::NoNewTodos => new_todos(conn, snapshot_id, graph)?,
+        GateRule::MinDocCoverage => check_doc_coverage(
+            doc_summary(conn, snapshot_id)?.coverage(),
+            threshold.unwrap_or(0),
+        ),
+        GateRule::NoUnsafeInDomain => {
+            let sites: Vec<_> = safety_repo::list_by_snapshot(conn, snapshot_id)
+                .map_err(|e| format!("Failed to load safety sites: {}", e))?
+                .into_iter()
+                .filter(|s| SiteKind::parse(&s.kind).is_some_and(SiteKind::is_unsafe))
+                .filter(|s| {
+                    graph
+                        .files
+                        .get(&s.file_id)
+                        .is_some_and(|f| f.artifact_type.as_deref() == Some("domain"))
+                })
+                .collect();
+            let first = sites
+                .first()
+                .map(|s| format!("{} at {}:{}", s.kind, graph.path(s.file_id), s.line));
+            none_found(sites.len(), "unsafe sites in domain modules", first)
+        }
+        GateRule::NoArchitectureViolations => {
+            let violations = graph.violations();
+            let first = violations
+                .first()
+                .map(|v| format!("{}:{} {} -> {}", v.path, v.line, v.rule, v.target_path));
+            none_found(violations.len(), "architecture violations", first)
+        }
+        GateRule::NoDependencyCycles => {
+            let cycles = all_cycles(graph, true);
+            none_found(
+                cycles.len(),
+                "dependency cycles",
+                cycles.first().map(|c| c.describe()),
+            )
+        }
+    })
+}
+
+/// Evaluate the quality gate of the snapshot's project; unknown rules are skipped
+pub fn evaluate(conn: &Connection, snapshot_id: i64) -> Result<Vec<GateResult>, String> {
+    let snapshot = snapshot_repo::get_by_id(conn, snapshot_id)
+        .map_err(|e| format!("Failed to load snapshot: {}", e))?;
+    let gates = gate_repo::list_by_project(conn, snapshot.project_id)
+        .map_err(|e| format!("Failed to load quality gates: {}", e))?;
+    if gates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let graph = ModuleGraph::load(conn, snapshot_id)?;
+    let mut results = Vec::new();
+    for gate in gates {
+        let Some(rule) = GateRule::parse(&gate.rule) else {
+            continue;
+        };
+        let (passed, detail) = check(conn, snapshot_id, &graph, rule, gate.threshold)?;
+        results.push(GateResult::new(
+            0,
+            snapshot_id,
+            gate.rule,
+            gate.threshold,
+            passed,
+            detail,
+        ));
+    }
+    Ok(results)
+}
+
+/// Evaluate the gate and store the results on the snapshot, replacing earlier ones
+pub fn record_gates(conn: &Connection, snapshot_id: i64) -> Result<Vec<GateResult>, String> {
+    let results = evaluate(conn, snapshot_id)?;
+    gate_result_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing gate results: {}", e))?;
+    for result in &results {
+        gate_result_repo::create(conn, result)
+            .map_err(|e| format!("Failed to store gate result: {}", e))?;
+    }
+    Ok(results)
+}
+
+/// `Quality gates passed` or `Quality gates: 2 of 5 failed`; None without a gate
+pub fn gates_summary(results: &[GateResult]) -> Option<String> {
+    let failed = results.iter().filter(|r| !r.passed).count();
+    match (results.len(), failed) {
+        (0, _) => None,
+        (_, 0) => Some("Quality gates passed".to_string()),
+        (total, failed) => Some(format!("Quality gates: {} of {} failed", failed, total)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_thresholds() {
+        let locs = [("src/a.rs", 120), ("src/b.rs", 950), ("src/c.rs", 820)];
+        assert_eq!(
+            check_file_loc(&locs, 1000),
+            (true, "largest file 950 LOC: src/b.rs".to_string())
+        );
+        assert_eq!(
+            check_file_loc(&locs, 800),
+            (
+                false,
+                "2 files above 800 LOC, largest 950 LOC: src/b.rs".to_string()
+            )
+        );
+        assert!(check_file_loc(&[], 800).0);
+
+        assert_eq!(
+            check_doc_coverage(Some(0.6), 60),
+            (true, "doc coverage 60.0%".to_string())
+        );
+        assert!(!check_doc_coverage(Some(0.599), 60).0);
+        assert!(check_doc_coverage(None, 60).0);
+
+        assert_eq!(
+            none_found(0, "dependency cycles", None),
+            (true, "no dependency cycles".to_string())
+        );
+        assert_eq!(
+            none_found(2, "dependency cycles", Some("2 modules: a.rs, b.rs".into())),
+            (
+                false,
+                "2 dependency cycles, first: 2 modules: a.rs, b.rs".to_string()
+            )
+        );
+    }
+}
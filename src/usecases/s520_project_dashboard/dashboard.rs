@@ -0,0 +1,88 @@
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+use crate::domain::n001_project::model::Project;
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::model::Snapshot;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n011_debt_marker::repository as marker_repo;
+use crate::domain::n017_file_language::model::LanguageStats;
+use crate::domain::n017_file_language::repository as language_repo;
+use crate::domain::n020_gate_result::model::GateResult;
+use crate::domain::n020_gate_result::repository as gate_result_repo;
+use crate::usecases::s506_git_churn::hotspots::{rank_files, Hotspot};
+
+/// Snapshots behind the trend sparklines
+const TREND_SNAPSHOTS: usize = 20;
+/// Hotspots listed for the latest snapshot
+const TOP_HOTSPOTS: usize = 5;
+
+/// Everything the dashboard shows about one project
+pub struct Dashboard {
+    pub project: Project,
+    /// Newest first, at most `TREND_SNAPSHOTS`
+    pub snapshots: Vec<Snapshot>,
+    /// Debt markers of each entry of `snapshots`
+    pub markers: Vec<i64>,
+    /// Quality gate results by snapshot id
+    pub gates: HashMap<i64, Vec<GateResult>>,
+    /// Language breakdown of the latest snapshot
+    pub languages: Vec<LanguageStats>,
+    /// Top hotspots of the latest snapshot, empty without computed churn
+    pub hotspots: Vec<Hotspot>,
+}
+
+impl Dashboard {
+    pub fn load(conn: &Connection, project_id: i64) -> Result<Self, String> {
+        let project = project_repo::get_by_id(conn, project_id)
+            .map_err(|e| format!("Failed to load project: {}", e))?;
+        let mut snapshots = snapshot_repo::list_by_project(conn, project_id)
+            .map_err(|e| format!("Failed to load snapshots: {}", e))?;
+        snapshots.truncate(TREND_SNAPSHOTS);
+
+        let mut markers = Vec::new();
+        let mut gates = HashMap::new();
+        for s in &snapshots {
+            let count = marker_repo::list_by_snapshot(conn, s.id)
+                .map_err(|e| format!("Failed to load markers: {}", e))?
+                .len();
+            markers.push(count as i64);
+            let results = gate_result_repo::list_by_snapshot(conn, s.id)
+                .map_err(|e| format!("Failed to load gate results: {}", e))?;
+            gates.insert(s.id, results);
+        }
+
+        let (languages, hotspots) = match snapshots.first() {
+            Some(latest) => {
+                let languages = language_repo::breakdown_by_snapshot(conn, latest.id)
+                    .map_err(|e| format!("Failed to load languages: {}", e))?;
+                let mut hotspots = rank_files(conn, latest.id)?;
+                hotspots.truncate(TOP_HOTSPOTS);
+                (languages, hotspots)
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+
+        Ok(Self {
+            project,
+            snapshots,
+            markers,
+            gates,
+            languages,
+            hotspots,
+        })
+    }
+
+    pub fn latest(&self) -> Option<&Snapshot> {
+        self.snapshots.first()
+    }
+
+    pub fn previous(&self) -> Option<&Snapshot> {
+        self.snapshots.get(1)
+    }
+
+    /// `value` of every snapshot, oldest first
+    pub fn series(&self, value: impl Fn(&Snapshot) -> i64) -> Vec<i64> {
+        self.snapshots.iter().rev().map(value).collect()
+    }
+}
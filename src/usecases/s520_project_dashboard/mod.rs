@@ -0,0 +1,337 @@
+pub mod dashboard;
+pub mod trend;
+
+use eframe::egui;
+use rusqlite::Connection;
+
+use crate::domain::n001_project::ui::picker::{ui_project_picker, ProjectPickerState};
+use crate::domain::n002_snapshot::model::Snapshot;
+use crate::domain::n002_snapshot::ui::baseline::delta_label;
+use crate::domain::n017_file_language::ui::{legend_entry, share_bar};
+use crate::domain::n020_gate_result::ui::{gate_badge, result_line};
+use crate::usecases::s517_report::format_size;
+use crate::usecases::s521_new_scan::new_snapshot_and_scan;
+use dashboard::Dashboard;
+use trend::{change, normalize};
+
+/// Snapshots listed in the recent timeline
+const RECENT_SNAPSHOTS: usize = 8;
+
+/// Label, value and formatter of a latest-snapshot row
+type Stat = (&'static str, fn(&Snapshot) -> i64, fn(i64) -> String);
+/// Label, values and formatter of a trend row
+type Trend = (&'static str, Vec<i64>, fn(i64) -> String);
+
+#[derive(Default)]
+pub struct DashboardState {
+    pub project_picker: ProjectPickerState,
+    pub project_id: Option<i64>,
+    pub dashboard: Option<Dashboard>,
+    pub status: String,
+}
+
+impl DashboardState {
+    /// Show the dashboard of `project_id`
+    pub fn open(&mut self, conn: &Connection, project_id: i64) {
+        self.project_id = Some(project_id);
+        self.reload(conn);
+    }
+
+    /// Create a snapshot of `project_id`, scan it and show the refreshed dashboard
+    pub fn new_scan(&mut self, conn: &Connection, project_id: i64) {
+        self.status = match new_snapshot_and_scan(conn, project_id, None, |_| {}) {
+            Ok(result) => result.describe(),
+            Err(e) => format!("Error: {}", e),
+        };
+        self.open(conn, project_id);
+    }
+
+    fn reload(&mut self, conn: &Connection) {
+        let Some(project_id) = self.project_id else {
+            return;
+        };
+        match Dashboard::load(conn, project_id) {
+            Ok(dashboard) => self.dashboard = Some(dashboard),
+            Err(e) => {
+                self.status = format!("Error: {}", e);
+                self.dashboard = None;
+            }
+        }
+    }
+}
+
+/// What the dashboard asks of the other tabs
+pub enum DashboardAction {
+    /// Open two snapshots in Compare Snapshots
+    Compare { base: i64, target: i64 },
+    /// Open a file in the preview
+    OpenFile(i64),
+}
+
+/// Line chart of `values` (oldest first), without axes
+fn sparkline(ui: &mut egui::Ui, values: &[i64], size: egui::Vec2) {
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let inner = rect.shrink(3.0);
+    let scaled = normalize(values);
+    let step = inner.width() / (scaled.len().max(2) - 1) as f32;
+    let points: Vec<egui::Pos2> = scaled
+        .iter()
+        .enumerate()
+        .map(|(i, y)| {
+            egui::pos2(
+                inner.left() + i as f32 * step,
+                inner.bottom() - y * inner.height(),
+            )
+        })
+        .collect();
+    let color = egui::Color32::LIGHT_BLUE;
+    if points.len() > 1 {
+        painter.add(egui::Shape::line(
+            points.clone(),
+            egui::Stroke::new(1.5, color),
+        ));
+    }
+    if let Some(last) = points.last() {
+        painter.circle_filled(*last, 2.5, color);
+    }
+}
+
+/// Renders the Project Dashboard tab
+pub fn ui_dashboard(
+    ui: &mut egui::Ui,
+    conn: &Connection,
+    state: &mut DashboardState,
+) -> Option<DashboardAction> {
+    ui.heading("Project Dashboard");
+    ui.add_space(12.0);
+
+    if let Some(project_id) = ui_project_picker(ui, conn, &mut state.project_picker) {
+        state.status.clear();
+        state.open(conn, project_id);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Selected Project:");
+        match &state.dashboard {
+            Some(d) => ui.label(egui::RichText::new(&d.project.name).strong()),
+            None => ui.label(egui::RichText::new("None").weak()),
+        };
+        if ui.button("Select...").clicked() {
+            state.project_picker.open();
+        }
+        if state.project_id.is_some() && ui.button("Refresh").clicked() {
+            state.status.clear();
+            state.reload(conn);
+        }
+    });
+
+    let mut action = None;
+    let mut new_scan = false;
+    if let Some(d) = &state.dashboard {
+        ui.label(egui::RichText::new(&d.project.root_path).weak());
+        ui.add_space(8.0);
+
+        // Quick actions
+        ui.horizontal(|ui| {
+            if ui
+                .button("New snapshot + scan")
+                .on_hover_text("Create a snapshot named after the current time, scan the project root and compute churn")
+                .clicked()
+            {
+                new_scan = true;
+            }
+            let previous = d.latest().zip(d.previous());
+            let compare = ui
+                .add_enabled(previous.is_some(), egui::Button::new("Compare with previous"))
+                .on_disabled_hover_text("Needs two snapshots");
+            if compare.clicked() {
+                if let Some((latest, previous)) = previous {
+                    action = Some(DashboardAction::Compare { base: previous.id, target: latest.id });
+                }
+            }
+        });
+    }
+
+    if !state.status.is_empty() {
+        let color = if state.status.starts_with("Error") {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        ui.colored_label(color, &state.status);
+    }
+
+    if let Some(d) = &state.dashboard {
+        ui.add_space(8.0);
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                if let Some(file_id) = render_dashboard(ui, d) {
+                    action = Some(DashboardAction::OpenFile(file_id));
+                }
+            });
+    }
+
+    if new_scan {
+        if let Some(project_id) = state.project_id {
+            state.new_scan(conn, project_id);
+        }
+    }
+    action
+}
+
+/// Every section of a loaded dashboard; returns a hotspot file id when clicked
+fn render_dashboard(ui: &mut egui::Ui, d: &Dashboard) -> Option<i64> {
+    let Some(latest) = d.latest() else {
+        ui.label(
+            egui::RichText::new(
+                "No snapshots yet. Use \"New snapshot + scan\" to fill the dashboard.",
+            )
+            .weak(),
+        );
+        return None;
+    };
+    let previous = d.previous();
+    let count = |n: i64| n.to_string();
+
+    // Latest snapshot
+    ui.strong(format!("Latest snapshot: {}", latest.name));
+    ui.label(egui::RichText::new(format!("Scanned at {}", latest.scanned_at)).weak());
+    ui.add_space(4.0);
+    egui::Grid::new("s520_latest")
+        .num_columns(2)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            let stats: [Stat; 4] = [
+                ("Files", |s| s.files_count, count),
+                ("Dirs", |s| s.dirs_count, count),
+                ("Size", |s| s.files_size_bytes, format_size),
+                ("LOC", |s| s.loc_count, count),
+            ];
+            for (label, value, format) in stats {
+                ui.label(label);
+                ui.horizontal(|ui| {
+                    ui.label(format(value(latest)));
+                    if let Some(previous) = previous {
+                        delta_label(ui, value(latest) - value(previous), format);
+                    }
+                });
+                ui.end_row();
+            }
+            ui.label("Quality gates");
+            let results = d
+                .gates
+                .get(&latest.id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            gate_badge(ui, results, ui.text_style_height(&egui::TextStyle::Body));
+            ui.end_row();
+        });
+    if let Some(results) = d.gates.get(&latest.id) {
+        for result in results.iter().filter(|r| !r.passed) {
+            ui.colored_label(egui::Color32::LIGHT_RED, result_line(result));
+        }
+    }
+
+    if !d.languages.is_empty() {
+        ui.add_space(6.0);
+        share_bar(ui, &d.languages, ui.available_width().min(400.0));
+        ui.horizontal_wrapped(|ui| {
+            for l in &d.languages {
+                legend_entry(ui, &l.language);
+            }
+        });
+    }
+
+    // Trends
+    if d.snapshots.len() > 1 {
+        ui.add_space(12.0);
+        ui.strong(format!("Trends (last {} snapshots)", d.snapshots.len()));
+        ui.add_space(4.0);
+        let markers: Vec<i64> = d.markers.iter().rev().copied().collect();
+        let series: [Trend; 4] = [
+            ("LOC", d.series(|s| s.loc_count), count),
+            ("Files", d.series(|s| s.files_count), count),
+            ("Size", d.series(|s| s.files_size_bytes), format_size),
+            ("Debt markers", markers, count),
+        ];
+        egui::Grid::new("s520_trends")
+            .num_columns(4)
+            .spacing([12.0, 6.0])
+            .show(ui, |ui| {
+                for (label, values, format) in series {
+                    ui.label(label);
+                    sparkline(ui, &values, egui::vec2(160.0, 24.0));
+                    ui.label(format(values.last().copied().unwrap_or_default()));
+                    delta_label(ui, change(&values), format);
+                    ui.end_row();
+                }
+            });
+    }
+
+    // Hotspots
+    let mut opened = None;
+    ui.add_space(12.0);
+    ui.strong("Top hotspots");
+    ui.add_space(4.0);
+    if d.hotspots.is_empty() {
+        ui.label(
+            egui::RichText::new(
+                "No churn computed for the latest snapshot (Churn & Hotspots tab).",
+            )
+            .weak(),
+        );
+    } else {
+        egui::Grid::new("s520_hotspots")
+            .num_columns(4)
+            .spacing([12.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for h in &d.hotspots {
+                    match h.file_id {
+                        Some(file_id) => {
+                            if ui.link(&h.name).clicked() {
+                                opened = Some(file_id);
+                            }
+                        }
+                        None => {
+                            ui.label(&h.name);
+                        }
+                    }
+                    ui.label(format!("score {:.0}", h.score));
+                    ui.label(format!("{} commits", h.commits));
+                    ui.label(format!("{} LOC", h.loc));
+                    ui.end_row();
+                }
+            });
+    }
+
+    // Recent snapshots
+    ui.add_space(12.0);
+    ui.strong("Recent snapshots");
+    ui.add_space(4.0);
+    egui::Grid::new("s520_timeline")
+        .num_columns(4)
+        .spacing([12.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            for s in d.snapshots.iter().take(RECENT_SNAPSHOTS) {
+                ui.label(egui::RichText::new(&s.scanned_at).weak());
+                ui.horizontal(|ui| {
+                    ui.label(&s.name);
+                    if d.project.baseline_snapshot_id == Some(s.id) {
+                        ui.label(egui::RichText::new("★ baseline").color(egui::Color32::GOLD));
+                    }
+                });
+                ui.label(format!("{} LOC", s.loc_count));
+                let results = d.gates.get(&s.id).map(Vec::as_slice).unwrap_or_default();
+                gate_badge(ui, results, ui.text_style_height(&egui::TextStyle::Body));
+                ui.end_row();
+            }
+        });
+
+    opened
+}
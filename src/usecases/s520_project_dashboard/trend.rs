@@ -0,0 +1,37 @@
+/// Values scaled to 0..=1 between their min and max; a flat series sits in the middle
+pub fn normalize(values: &[i64]) -> Vec<f32> {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return Vec::new();
+    };
+    if min == max {
+        return vec![0.5; values.len()];
+    }
+    values
+        .iter()
+        .map(|&v| (v - min) as f32 / (max - min) as f32)
+        .collect()
+}
+
+/// Change from the first to the last value, 0 for fewer than two
+pub fn change(values: &[i64]) -> i64 {
+    match (values.first(), values.last()) {
+        (Some(first), Some(last)) if values.len() > 1 => last - first,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scales_series() {
+        assert_eq!(normalize(&[10, 20, 15, 30]), vec![0.0, 0.5, 0.25, 1.0]);
+        assert_eq!(normalize(&[7, 7, 7]), vec![0.5, 0.5, 0.5]);
+        assert!(normalize(&[]).is_empty());
+
+        assert_eq!(change(&[10, 20, 15, 30]), 20);
+        assert_eq!(change(&[30, 10]), -20);
+        assert_eq!(change(&[5]), 0);
+    }
+}
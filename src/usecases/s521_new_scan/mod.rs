@@ -0,0 +1,157 @@
+use chrono::{Local, Utc};
+use rusqlite::Connection;
+use std::path::Path;
+
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::shared::git;
+use crate::usecases::s501_create_snapshot::{scan_directory, ScanProgress, ScanResult};
+use crate::usecases::s506_git_churn::{compute_churn, DEFAULT_WINDOW_DAYS};
+use crate::usecases::s516_import::import::free_name;
+use crate::usecases::s522_delete_snapshot::delete_snapshot;
+
+/// Outcome of `new_snapshot_and_scan`
+pub struct NewScan {
+    pub snapshot_id: i64,
+    pub snapshot_name: String,
+    pub scan: ScanResult,
+    /// Files with commits in the churn window, None outside a git repository
+    pub churn_files: Option<usize>,
+}
+
+impl NewScan {
+    pub fn describe(&self) -> String {
+        let mut text = format!(
+            "Snapshot '{}' scanned. {}",
+            self.snapshot_name,
+            self.scan.describe()
+        );
+        if let Some(files) = self.churn_files {
+            text.push_str(&format!(
+                ". Churn: {} files changed in {} days",
+                files, DEFAULT_WINDOW_DAYS
+            ));
+        }
+        text
+    }
+}
+
+/// Create a snapshot of the project, scan its root and compute git churn.
+/// The snapshot is named after the current time unless `name` is given; a taken
+/// name gets a " (2)" suffix. When a step fails the new snapshot is removed again with all its rows.
+pub fn new_snapshot_and_scan(
+    conn: &Connection,
+    project_id: i64,
+    name: Option<&str>,
+    progress: impl FnMut(ScanProgress),
+) -> Result<NewScan, String> {
+    let project = project_repo::get_by_id(conn, project_id)
+        .map_err(|e| format!("Failed to load project: {}", e))?;
+    let root = Path::new(&project.root_path);
+    if !root.is_dir() {
+        return Err(format!("Project root not found: {}", project.root_path));
+    }
+
+    let snapshots = snapshot_repo::list_by_project(conn, project_id)
+        .map_err(|e| format!("Failed to load snapshots: {}", e))?;
+    let taken: Vec<&str> = snapshots.iter().map(|s| s.name.as_str()).collect();
+    let stamp = Local::now().format("%Y-%m-%d %H:%M").to_string();
+    let name = name.unwrap_or(&stamp);
+    let snapshot_name = if taken.contains(&name) {
+        free_name(name, &taken)
+    } else {
+        name.to_string()
+    };
+
+    let scanned_at = Utc::now().to_rfc3339();
+    let snapshot_id = snapshot_repo::create(
+        conn,
+        &snapshot_name,
+        project_id,
+        None,
+        0,
+        0,
+        0,
+        0,
+        &scanned_at,
+    )
+    .map_err(|e| format!("Failed to create snapshot: {}", e))?;
+
+    let scanned = scan_directory(conn, snapshot_id, root, progress).and_then(|scan| {
+        let churn_files = if git::is_repository(root) {
+            Some(compute_churn(conn, snapshot_id, DEFAULT_WINDOW_DAYS)?)
+        } else {
+            None
+        };
+        Ok((scan, churn_files))
+    });
+    let (scan, churn_files) = match scanned {
+        Ok(scanned) => scanned,
+        Err(e) => {
+            return Err(match delete_snapshot(conn, snapshot_id) {
+                Ok(()) => e,
+                Err(cleanup) => format!("{} ({})", e, cleanup),
+            })
+        }
+    };
+
+    Ok(NewScan {
+        snapshot_id,
+        snapshot_name,
+        scan,
+        churn_files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::db::init_database;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn setup(name: &str, gitignore: bool) -> (Connection, i64, PathBuf) {
+        let root =
+            std::env::temp_dir().join(format!("navigator-s521-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        if gitignore {
+            fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        }
+        let conn = Connection::open_in_memory().unwrap();
+        init_database(&conn).unwrap();
+        let project_id = project_repo::create(&conn, "demo", root.to_str().unwrap(), None).unwrap();
+        (conn, project_id, root)
+    }
+
+    fn count(conn: &Connection, table: &str, snapshot_id: i64) -> i64 {
+        let sql = format!("SELECT COUNT(*) FROM {} WHERE snapshot_id = ?1", table);
+        conn.query_row(&sql, [snapshot_id], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn taken_name_gets_a_suffix() {
+        let (conn, project_id, root) = setup("names", true);
+        let first = new_snapshot_and_scan(&conn, project_id, Some("nightly"), |_| {}).unwrap();
+        let second = new_snapshot_and_scan(&conn, project_id, Some("nightly"), |_| {}).unwrap();
+        assert_eq!(first.snapshot_name, "nightly");
+        assert_eq!(second.snapshot_name, "nightly (2)");
+        assert_eq!(second.churn_files, None);
+        assert!(count(&conn, "n003_snapshot_file", second.snapshot_id) > 0);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn failed_scan_removes_the_snapshot() {
+        let (conn, project_id, root) = setup("failed", false);
+        let error = new_snapshot_and_scan(&conn, project_id, None, |_| {})
+            .err()
+            .unwrap();
+        assert!(error.contains(".gitignore"), "{}", error);
+        let snapshots = snapshot_repo::list_by_project(&conn, project_id).unwrap();
+        assert!(snapshots.is_empty());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+use rusqlite::Connection;
+
+use crate::domain::n001_project::repository as project_repo;
+use crate::domain::n002_snapshot::repository as snapshot_repo;
+use crate::domain::n006_file_content::repository as content_repo;
+use crate::domain::n020_gate_result::repository as gate_result_repo;
+use crate::usecases::s515_export::schema::TABLES;
+
+/// Remove every row of a snapshot, keeping the snapshot itself. Runs in the caller's transaction.
+pub fn clear_snapshot(conn: &Connection, snapshot_id: i64) -> Result<(), String> {
+    content_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing contents: {}", e))?;
+    gate_result_repo::delete_by_snapshot(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete existing gate results: {}", e))?;
+    // Files last: the other tables reference them
+    for spec in TABLES.iter().rev() {
+        conn.execute(
+            &format!("DELETE FROM {} WHERE snapshot_id = ?1", spec.source),
+            [snapshot_id],
+        )
+        .map_err(|e| format!("Failed to delete existing {}: {}", spec.name, e))?;
+    }
+    Ok(())
+}
+
+/// Delete a snapshot with every row that refers to it in one transaction.
+/// Projects that pinned it as their baseline are left without one.
+pub fn delete_snapshot(conn: &Connection, snapshot_id: i64) -> Result<(), String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    clear_snapshot(conn, snapshot_id)?;
+    project_repo::clear_baseline(conn, snapshot_id)
+        .map_err(|e| format!("Failed to clear baseline: {}", e))?;
+    snapshot_repo::delete(conn, snapshot_id)
+        .map_err(|e| format!("Failed to delete snapshot: {}", e))?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit deletion: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::db::init_database;
+    use crate::usecases::s521_new_scan::new_snapshot_and_scan;
+    use std::fs;
+
+    fn count(conn: &Connection, table: &str, snapshot_id: i64) -> i64 {
+        let sql = format!("SELECT COUNT(*) FROM {} WHERE snapshot_id = ?1", table);
+        conn.query_row(&sql, [snapshot_id], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn deletes_every_row_and_the_baseline() {
+        let root = std::env::temp_dir().join(format!("navigator-s522-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/main.rs"),
+            "// TODO: more\nfn main() { None::<u8>.unwrap(); }\n",
+        )
+        .unwrap();
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        let conn = Connection::open_in_memory().unwrap();
+        init_database(&conn).unwrap();
+        let project_id = project_repo::create(&conn, "demo", root.to_str().unwrap(), None).unwrap();
+
+        let kept = new_snapshot_and_scan(&conn, project_id, Some("kept"), |_| {})
+            .unwrap()
+            .snapshot_id;
+        let id = new_snapshot_and_scan(&conn, project_id, Some("deleted"), |_| {})
+            .unwrap()
+            .snapshot_id;
+        project_repo::set_baseline(&conn, project_id, Some(id)).unwrap();
+        let extra = ["n006_file_content", "n020_gate_result"];
+        let tables: Vec<&str> = TABLES.iter().map(|t| t.source).chain(extra).collect();
+        assert!(count(&conn, "n006_file_content", id) > 0);
+        assert!(count(&conn, "n011_debt_marker", id) > 0);
+
+        delete_snapshot(&conn, id).unwrap();
+        for table in &tables {
+            assert_eq!(count(&conn, table, id), 0, "{}", table);
+        }
+        assert!(count(&conn, "n003_snapshot_file", kept) > 0);
+        assert!(snapshot_repo::get_by_id(&conn, id).is_err());
+        assert_eq!(
+            project_repo::get_by_id(&conn, project_id)
+                .unwrap()
+                .baseline_snapshot_id,
+            None
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
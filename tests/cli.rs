@@ -0,0 +1,86 @@
+//! Headless commands must print nothing but their output on stdout
+
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn navigator(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_navigator"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run navigator")
+}
+
+/// Fresh working directory with a database holding one small snapshot
+fn workspace(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("navigator-cli-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // Any command creates the database with every table
+    navigator(&dir, &["gates", "--snapshot", "1"]);
+    let conn = Connection::open(dir.join("navigator.db")).unwrap();
+    conn.execute_batch(
+        "INSERT INTO n001_project (name, root_path) VALUES ('demo', '/demo');
+         INSERT INTO n002_snapshot (name, project_id, files_count, dirs_count, files_size_bytes, loc_count, scanned_at)
+             VALUES ('s1', 1, 1, 1, 120, 10, '2026-01-01T00:00:00Z');
+         INSERT INTO n003_snapshot_file (snapshot_id, parent_id, name, path, size_bytes, is_directory)
+             VALUES (1, NULL, 'src', 'src', 0, 1);
+         INSERT INTO n003_snapshot_file (snapshot_id, parent_id, name, path, size_bytes, is_directory)
+             VALUES (1, 1, 'main.rs', 'src/main.rs', 120, 0);",
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn export_prints_only_the_export() {
+    let dir = workspace("export");
+
+    let out = navigator(&dir, &["export", "--snapshot", "1"]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&out.stdout).expect("stdout is not JSON");
+    assert!(json.is_object());
+
+    let out = navigator(&dir, &["export", "--snapshot", "1", "--format", "jsonl"]);
+    assert!(out.status.success());
+    let text = String::from_utf8(out.stdout).unwrap();
+    assert!(text.lines().count() > 1);
+    for line in text.lines() {
+        serde_json::from_str::<serde_json::Value>(line).expect("stdout line is not JSON");
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn report_and_graph_print_only_their_output() {
+    let dir = workspace("report");
+
+    let out = navigator(&dir, &["report", "--snapshot", "1"]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(String::from_utf8(out.stdout)
+        .unwrap()
+        .starts_with("<!DOCTYPE html>"));
+
+    let out = navigator(&dir, &["graph", "--snapshot", "1"]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(String::from_utf8(out.stdout)
+        .unwrap()
+        .starts_with("digraph"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}